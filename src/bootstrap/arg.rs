@@ -45,9 +45,12 @@ impl Arg {
   pub fn parse_mode(args: &Vec<String>) -> CmdResult<ModeKind> {
     match args {
       _ if args.contains(&"-ast".into()) => Ok(ModeKind::Ast),
+      _ if args.contains(&"-build".into()) => Ok(ModeKind::Build),
       _ if args.contains(&"-eval".into()) => Ok(ModeKind::Eval),
       _ if args.contains(&"-tokens".into()) => Ok(ModeKind::Tokens),
       _ if args.contains(&"-js".into()) => Ok(ModeKind::Js),
+      _ if args.contains(&"-opt".into()) => Ok(ModeKind::Opt),
+      _ if args.contains(&"-vm".into()) => Ok(ModeKind::Vm),
       _ => Err(format!("mode `{:?}` not exist", args)),
     }
   }
@@ -3,7 +3,10 @@ pub use crate::kind::mode::ModeKind::*;
 #[derive(Clone, Debug, PartialEq)]
 pub enum ModeKind {
   Ast,
+  Build,
   Eval,
   Js,
+  Opt,
   Tokens,
+  Vm,
 }
@@ -1,5 +1,8 @@
+use crate::kind::mode::ModeKind;
 use crate::reader::{Reader, ReaderResult};
 
+use qoeurc::compiler::file;
+
 use std::fs;
 use std::path::Path;
 
@@ -8,16 +11,26 @@ impl Reader {
     let arg = &self.arg.clone().unwrap();
     let path = &arg.path.as_ref().unwrap().to_string();
 
-    match Reader::advance_file(path) {
+    let bytes = Reader::advance_file(path)?;
+
+    // a bytecode file is only ever run by the machine
+    if file::is_bytecode(&bytes) {
+      return match arg.mode.as_ref().unwrap() {
+        ModeKind::Vm => self.add_path(Path::new(path)).loadify(&bytes),
+        _ => Err(format!("bytecode files only run with `-vm`")),
+      };
+    }
+
+    match String::from_utf8(bytes) {
       Err(error) => Err(format!("{}", error)),
-      Ok(file) => self.advance_mode(&file),
+      Ok(file) => self.add_path(Path::new(path)).advance_mode(&file),
     }
   }
 
-  fn advance_file(url: &str) -> ReaderResult<String> {
+  fn advance_file(url: &str) -> ReaderResult<Vec<u8>> {
     let pathname = Path::new(url);
 
-    match fs::read_to_string(pathname) {
+    match fs::read(pathname) {
       Err(error) => Err(format!("{}", error)),
       Ok(file) => Ok(file),
     }
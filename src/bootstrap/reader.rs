@@ -3,10 +3,15 @@ use crate::kind::{mode::ModeKind, optional::OptionalKind};
 
 use qoeurc::analyzer;
 use qoeurc::analyzer::interpreter::Interpreter;
+use qoeurc::compiler;
 use qoeurc::converter;
+use qoeurc::optimizer;
 use qoeurc::tokenizer::{self, token::Token};
 use qoeurc::transformer;
 
+use std::fs;
+use std::path::Path;
+
 pub type ReaderError = String;
 pub type ReaderResult<T> = Result<T, ReaderError>;
 
@@ -31,9 +36,12 @@ impl Reader {
   pub fn advance_mode(&mut self, file: &str) -> ReaderResult<()> {
     match &self.arg.to_owned().unwrap().mode.unwrap() {
       ModeKind::Ast => Ok(self.astify(file)?),
+      ModeKind::Build => Ok(self.buildify(file)?),
       ModeKind::Eval => Ok(self.evalify(file)?),
       ModeKind::Js => Ok(self.transformify(file)?),
+      ModeKind::Opt => Ok(self.optimizify(file)?),
       ModeKind::Tokens => Ok(self.tokenify(file)?),
+      ModeKind::Vm => Ok(self.vmify(file)?),
     }
   }
 
@@ -42,6 +50,11 @@ impl Reader {
     self
   }
 
+  pub fn add_path(&mut self, path: &Path) -> &mut Self {
+    self.interpreter.add_path(path);
+    self
+  }
+
   pub fn astify(&mut self, input: &str) -> ReaderResult<()> {
     match converter::astify(input) {
       Err(error) => Err(format!("{}", error)),
@@ -49,10 +62,41 @@ impl Reader {
     }
   }
 
+  // a file is compiled next to itself, a line is printed as its bytes
+  pub fn buildify(&mut self, input: &str) -> ReaderResult<()> {
+    let bytes = compiler::bytecodify(input, &self.interpreter)?;
+
+    match &self.arg.as_ref().unwrap().path {
+      None => Ok(println!("{:?}", bytes)),
+      Some(path) => {
+        let path = Path::new(&path.to_string()).with_extension("q5c");
+
+        match fs::write(&path, bytes) {
+          Err(error) => Err(format!("{}", error)),
+          Ok(_) => Ok(println!("{}", path.display())),
+        }
+      }
+    }
+  }
+
   pub fn evalify(&mut self, input: &str) -> ReaderResult<()> {
     match analyzer::evalify(input, &mut self.interpreter) {
       Err(error) => Err(format!("{}", error)),
-      Ok(program) => Ok(program.print()),
+      Ok(program) => Ok(self.interpreter.print(program.as_ref())),
+    }
+  }
+
+  pub fn loadify(&mut self, bytes: &[u8]) -> ReaderResult<()> {
+    match compiler::loadify(bytes, &mut self.interpreter) {
+      Err(error) => Err(format!("{}", error)),
+      Ok(program) => Ok(self.interpreter.print(program.as_ref())),
+    }
+  }
+
+  pub fn optimizify(&mut self, input: &str) -> ReaderResult<()> {
+    match optimizer::optimizify(input) {
+      Err(error) => Err(format!("{}", error)),
+      Ok(ast) => Ok(println!("{:?}", ast)),
     }
   }
 
@@ -81,4 +125,11 @@ impl Reader {
 
     Ok(println!("{:?}", tokens))
   }
+
+  pub fn vmify(&mut self, input: &str) -> ReaderResult<()> {
+    match compiler::vmify(input, &mut self.interpreter) {
+      Err(error) => Err(format!("{}", error)),
+      Ok(program) => Ok(self.interpreter.print(program.as_ref())),
+    }
+  }
 }
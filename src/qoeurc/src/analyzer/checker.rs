@@ -0,0 +1,1180 @@
+use crate::analyzer::environment::scope::TScope;
+use crate::tokenizer::kind::*;
+use crate::value::instruction::array::Array;
+use crate::value::instruction::assign::Assign;
+use crate::value::instruction::binary::Binary;
+use crate::value::instruction::block::Block;
+use crate::value::instruction::call::Call;
+use crate::value::instruction::closure::Closure;
+use crate::value::instruction::control::If;
+use crate::value::instruction::delete::Delete;
+use crate::value::instruction::expression::Expression;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::group::Group;
+use crate::value::instruction::hash::Hash;
+use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::implementation::Impl;
+use crate::value::instruction::import::Use;
+use crate::value::instruction::index::Index;
+use crate::value::instruction::instance::Instance;
+use crate::value::instruction::interface::Trait;
+use crate::value::instruction::loop_break::Break;
+use crate::value::instruction::loop_continue::Continue;
+use crate::value::instruction::loop_for_in::LoopForIn;
+use crate::value::instruction::loop_infinite::LoopInfinite;
+use crate::value::instruction::loop_while::LoopWhile;
+use crate::value::instruction::match_value::Match;
+use crate::value::instruction::member::Member;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::native::Native;
+use crate::value::instruction::outcome::Outcome;
+use crate::value::instruction::pattern::Pattern;
+use crate::value::instruction::program::Program;
+use crate::value::instruction::public::Public;
+use crate::value::instruction::range::Range;
+use crate::value::instruction::return_value::Return;
+use crate::value::instruction::spread::Spread;
+use crate::value::instruction::statement::Statement;
+use crate::value::instruction::structure::Struct;
+use crate::value::instruction::try_value::Try;
+use crate::value::instruction::ty::{Ty, TyKind};
+use crate::value::instruction::unary::Unary;
+use crate::value::instruction::val::Val;
+use crate::value::primitive::{
+  bool::Bool, char::Char, float::Float, int::Int, nil::Nil, str::Str,
+};
+use crate::value::Value;
+
+use std::collections::HashMap;
+
+pub type CheckerError = String;
+pub type CheckerResult<T> = Result<T, CheckerError>;
+
+pub type Bindings = HashMap<String, TyKind>;
+
+// an expression whose type cannot be inferred is `None` and is never reported
+pub type Inferred = Option<TyKind>;
+
+#[derive(Clone, Debug)]
+pub struct Checker {
+  functions: HashMap<String, Box<dyn Value>>,
+  imports: Vec<String>,
+  impls: Vec<(String, String)>,
+  loops: Vec<Option<String>>,
+  scope: TScope,
+  structs: HashMap<String, Box<dyn Value>>,
+  traits: HashMap<String, Box<dyn Value>>,
+  variables: Vec<HashMap<String, TyKind>>,
+}
+
+impl Checker {
+  pub fn new(scope: TScope) -> Self {
+    Checker {
+      functions: HashMap::new(),
+      imports: vec![],
+      impls: vec![],
+      loops: vec![],
+      scope,
+      structs: HashMap::new(),
+      traits: HashMap::new(),
+      variables: vec![HashMap::new()],
+    }
+  }
+
+  pub fn check(&mut self, program: &dyn Value) -> CheckerResult<()> {
+    let statements = match program.downcast_ref::<Program>() {
+      Some(program) => program.statements.to_vec(),
+      None => vec![program.boxed()],
+    };
+
+    for statement in &statements {
+      self.declare(statement.as_ref());
+    }
+
+    for statement in &statements {
+      self.check_statement(statement.as_ref())?;
+    }
+
+    Ok(())
+  }
+
+  fn declare(&mut self, statement: &dyn Value) {
+    if let Some(public) = statement.downcast_ref::<Public>() {
+      self.declare(public.node.as_ref());
+    } else if let Some(import) = statement.downcast_ref::<Use>() {
+      match import.glob {
+        true => self.imports.push("*".into()),
+        false => self.imports.append(&mut import.item_names()),
+      }
+    } else if let Some(function) = statement.downcast_ref::<Function>() {
+      self.functions.insert(function.name(), function.boxed());
+    } else if let Some(structure) = statement.downcast_ref::<Struct>() {
+      self.structs.insert(structure.name(), structure.boxed());
+    } else if let Some(interface) = statement.downcast_ref::<Trait>() {
+      self.traits.insert(interface.name(), interface.boxed());
+    }
+  }
+
+  fn check_block(
+    &mut self,
+    statements: &[Box<dyn Value>],
+  ) -> CheckerResult<()> {
+    self.infer_block(statements).map(|_| ())
+  }
+
+  fn check_function(
+    &mut self,
+    function: &Function,
+    this: Option<&TyKind>,
+  ) -> CheckerResult<()> {
+    self.variables.push(HashMap::new());
+
+    for arg in function.args.as_ref().unwrap() {
+      let arg = arg.downcast_ref::<FunctionArg>().unwrap();
+      let kind = match (arg.name().as_str(), this) {
+        ("self", Some(this)) => this.to_owned(),
+        _ => Checker::to_kind(arg.kind.as_ref()),
+      };
+
+      if let Some(default) = &arg.default {
+        self.check_default(function, arg, &kind, default.as_ref())?;
+      }
+
+      match arg.variadic {
+        true => self.bind_arg(arg, &TyKind::ArrayOf(Box::new(kind)))?,
+        false => self.bind_arg(arg, &kind)?,
+      }
+    }
+
+    let loops = std::mem::take(&mut self.loops);
+
+    let checked = match function.block.as_ref() {
+      Some(block) => self.check_statement(block.as_ref()),
+      None => Ok(()),
+    };
+
+    self.loops = loops;
+    self.variables.pop();
+
+    checked
+  }
+
+  // a default sees the parameters before it
+  fn check_default(
+    &mut self,
+    function: &Function,
+    arg: &FunctionArg,
+    expected: &TyKind,
+    default: &dyn Value,
+  ) -> CheckerResult<()> {
+    let generics = function.generic_names();
+
+    match self.infer(default)? {
+      Some(found)
+        if !Checker::unify(
+          expected,
+          &found,
+          &generics,
+          &mut Bindings::new(),
+        ) =>
+      {
+        Err(format!(
+          "mismatched types for the default of `{}`: expected `{}`, found `{}`",
+          arg.name(),
+          expected,
+          found,
+        ))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  fn check_impl(&mut self, implementation: &Impl) -> CheckerResult<()> {
+    let tname = implementation.ty.text();
+
+    // items coming from other modules are only known once evaluated
+    let structure = match self.get_struct(&tname) {
+      Some(structure) => structure,
+      None if self.is_imported(&tname) => return Ok(()),
+      None => {
+        return Err(format!("cannot find type `{}` in this scope", tname))
+      }
+    };
+
+    let structure = structure.downcast_ref::<Struct>().unwrap();
+    let this = TyKind::Named(
+      tname.to_owned(),
+      structure
+        .generic_names()
+        .into_iter()
+        .map(|name| TyKind::Named(name, vec![]))
+        .collect(),
+    );
+
+    for function in &implementation.functions {
+      let method = function.downcast_ref::<Function>().unwrap();
+
+      self.check_function(method, Some(&this))?;
+    }
+
+    let iname = match &implementation.interface {
+      Some(interface) => interface.text(),
+      None => return Ok(()),
+    };
+
+    let interface = match self.get_trait(&iname) {
+      Some(interface) => interface,
+      None if self.is_imported(&iname) => return Ok(()),
+      None => {
+        return Err(format!("cannot find trait `{}` in this scope", iname))
+      }
+    };
+
+    let interface = interface.downcast_ref::<Trait>().unwrap();
+    let key = (iname.to_owned(), tname.to_owned());
+
+    if self.impls.contains(&key) {
+      return Err(format!(
+        "conflicting implementations of trait `{}` for type `{}`",
+        iname, tname
+      ));
+    }
+
+    self.impls.push(key);
+
+    for function in &implementation.functions {
+      let method = function.downcast_ref::<Function>().unwrap();
+
+      match interface.get_function(&method.name()) {
+        Some(signature) => {
+          Checker::check_signature(&iname, &tname, signature, method)?
+        }
+        None => {
+          return Err(format!(
+            "method `{}` is not a member of trait `{}`",
+            method.name(),
+            iname
+          ))
+        }
+      }
+    }
+
+    for signature in &interface.functions {
+      let signature = signature.downcast_ref::<Function>().unwrap();
+      let is_implemented = implementation.functions.iter().any(|function| {
+        function.downcast_ref::<Function>().unwrap().name() == signature.name()
+      });
+
+      if !is_implemented {
+        return Err(format!(
+          "not all trait items implemented, missing `{}` of trait `{}` for `{}`",
+          signature.name(),
+          iname,
+          tname,
+        ));
+      }
+    }
+
+    Ok(())
+  }
+
+  fn check_signature(
+    iname: &str,
+    tname: &str,
+    signature: &Function,
+    method: &Function,
+  ) -> CheckerResult<()> {
+    let expected = signature.args.as_ref().unwrap();
+    let found = method.args.as_ref().unwrap();
+
+    if expected.len() != found.len() {
+      return Err(format!(
+        "method `{}` has {} parameters but the declaration in trait `{}` has {}",
+        method.name(),
+        found.len(),
+        iname,
+        expected.len(),
+      ));
+    }
+
+    let mut kinds = expected
+      .iter()
+      .zip(found.iter())
+      .map(|(lhs, rhs)| {
+        let lhs = lhs.downcast_ref::<FunctionArg>().unwrap();
+        let rhs = rhs.downcast_ref::<FunctionArg>().unwrap();
+
+        (lhs.kind.to_owned(), rhs.kind.to_owned())
+      })
+      .collect::<Vec<(Box<dyn Value>, Box<dyn Value>)>>();
+
+    kinds.push((
+      signature.kind.to_owned().unwrap(),
+      method.kind.to_owned().unwrap(),
+    ));
+
+    let mut bindings = Bindings::new();
+
+    bindings.insert("Self".into(), TyKind::Named(tname.into(), vec![]));
+
+    for (lhs, rhs) in kinds {
+      let expected =
+        Checker::substitute(&Checker::to_kind(lhs.as_ref()), &bindings);
+      let found =
+        Checker::substitute(&Checker::to_kind(rhs.as_ref()), &bindings);
+
+      if expected != found {
+        return Err(format!(
+          "method `{}` has an incompatible type for trait `{}`: expected `{}`, found `{}`",
+          method.name(),
+          iname,
+          expected,
+          found,
+        ));
+      }
+    }
+
+    Ok(())
+  }
+
+  fn check_statement(&mut self, statement: &dyn Value) -> CheckerResult<()> {
+    if let Some(public) = statement.downcast_ref::<Public>() {
+      self.check_statement(public.node.as_ref())
+    } else if statement.is::<Use>() {
+      Ok(())
+    } else if let Some(function) = statement.downcast_ref::<Function>() {
+      self.functions.insert(function.name(), function.boxed());
+      self.check_function(function, None)
+    } else if let Some(implementation) = statement.downcast_ref::<Impl>() {
+      self.check_impl(implementation)
+    } else if let Some(val) = statement.downcast_ref::<Val>() {
+      self.check_val(val)
+    } else if let Some(block) = statement.downcast_ref::<Block>() {
+      self.check_block(&block.statements)
+    } else if let Some(statement) = statement.downcast_ref::<Statement>() {
+      self.check_statement(statement.node.as_ref())
+    } else if let Some(ret) = statement.downcast_ref::<Return>() {
+      match &ret.value {
+        Some(value) => self.infer(value.as_ref()).map(|_| ()),
+        None => Ok(()),
+      }
+    } else if let Some(jump) = statement.downcast_ref::<Break>() {
+      self.check_jump("break", &jump.label)?;
+
+      match &jump.value {
+        Some(value) => self.infer(value.as_ref()).map(|_| ()),
+        None => Ok(()),
+      }
+    } else if let Some(jump) = statement.downcast_ref::<Continue>() {
+      self.check_jump("continue", &jump.label)
+    } else if let Some(lw) = statement.downcast_ref::<LoopWhile>() {
+      self.infer(lw.condition.as_ref())?;
+      self.check_loop(&lw.label, lw.block.as_ref())
+    } else if let Some(lfi) = statement.downcast_ref::<LoopForIn>() {
+      self.check_loop_for_in(lfi)
+    } else if let Some(li) = statement.downcast_ref::<LoopInfinite>() {
+      self.check_loop(&li.label, li.block.as_ref())
+    } else {
+      self.infer(statement).map(|_| ())
+    }
+  }
+
+  // a `break` or a `continue` jumps out of a loop of the current function
+  fn check_jump(
+    &self,
+    keyword: &str,
+    label: &Option<String>,
+  ) -> CheckerResult<()> {
+    match label {
+      _ if self.loops.is_empty() => {
+        Err(format!("`{}` outside of a loop", keyword))
+      }
+      Some(label) if !self.loops.contains(&Some(label.to_owned())) => {
+        Err(format!("use of undeclared label `@{}`", label))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  fn check_loop(
+    &mut self,
+    label: &Option<String>,
+    block: &dyn Value,
+  ) -> CheckerResult<()> {
+    self.loops.push(label.to_owned());
+
+    let checked = self.check_statement(block);
+
+    self.loops.pop();
+
+    checked
+  }
+
+  fn check_loop_for_in(&mut self, lfi: &LoopForIn) -> CheckerResult<()> {
+    let item = match self.infer(lfi.iterable.as_ref())? {
+      _ if lfi.iterable.as_ref().is::<Range>() => TyKind::Int,
+      Some(TyKind::ArrayOf(kind)) => *kind,
+      Some(TyKind::Str) => TyKind::Char,
+      _ => TyKind::Infer,
+    };
+
+    self.variables.push(HashMap::new());
+
+    for variable in &lfi.variables {
+      let kind = match lfi.variables.len() {
+        1 => item.to_owned(),
+        _ => TyKind::Infer,
+      };
+
+      self.set_variable(&variable.text(), kind);
+    }
+
+    let checked = self.check_loop(&lfi.label, lfi.block.as_ref());
+
+    self.variables.pop();
+
+    checked
+  }
+
+  fn check_val(&mut self, val: &Val) -> CheckerResult<()> {
+    let found = self.infer(val.value.as_deref().unwrap())?;
+    let expected = val
+      .kind
+      .as_ref()
+      .map(|kind| Checker::to_kind(kind.as_ref()));
+
+    let kind = match (expected, found) {
+      (Some(expected), Some(found)) => {
+        if !Checker::unify(&expected, &found, &[], &mut Bindings::new()) {
+          return Err(format!(
+            "mismatched types for `{}`: expected `{}`, found `{}`",
+            val.name(),
+            expected,
+            found
+          ));
+        }
+
+        Some(expected)
+      }
+      (expected, found) => expected.or(found),
+    };
+
+    match (&val.pattern, kind) {
+      (Some(pattern), kind) => {
+        self.bind_pattern(pattern, &kind.unwrap_or(TyKind::Infer))
+      }
+      (None, Some(kind)) => {
+        self.set_variable(&val.name.text(), kind);
+        Ok(())
+      }
+      (None, None) => Ok(()),
+    }
+  }
+
+  fn infer(&mut self, expression: &dyn Value) -> CheckerResult<Inferred> {
+    if expression.is::<Int>() {
+      Ok(Some(TyKind::Int))
+    } else if expression.is::<Float>() {
+      Ok(Some(TyKind::Float))
+    } else if expression.is::<Str>() {
+      Ok(Some(TyKind::Str))
+    } else if expression.is::<Bool>() {
+      Ok(Some(TyKind::Bool))
+    } else if expression.is::<Char>() {
+      Ok(Some(TyKind::Char))
+    } else if expression.is::<Nil>() {
+      Ok(Some(TyKind::Nil))
+    } else if expression.is::<Hash>() {
+      Ok(Some(TyKind::Hash))
+    } else if let Some(array) = expression.downcast_ref::<Array>() {
+      self.infer_array(array)
+    } else if let Some(assign) = expression.downcast_ref::<Assign>() {
+      self.infer_assign(assign)
+    } else if let Some(binary) = expression.downcast_ref::<Binary>() {
+      self.infer_binary(binary)
+    } else if let Some(call) = expression.downcast_ref::<Call>() {
+      self.infer_call(call)
+    } else if let Some(closure) = expression.downcast_ref::<Closure>() {
+      self.infer_closure(closure)
+    } else if let Some(expression) = expression.downcast_ref::<Expression>() {
+      self.infer(expression.node.as_ref())
+    } else if let Some(group) = expression.downcast_ref::<Group>() {
+      self.infer(group.expression.as_ref())
+    } else if let Some(identifier) = expression.downcast_ref::<Identifier>() {
+      match self.get_variable(&identifier.name) {
+        None if self.get_function(&identifier.name).is_some() => {
+          Ok(Some(TyKind::Fn))
+        }
+        kind => Ok(kind),
+      }
+    } else if let Some(delete) = expression.downcast_ref::<Delete>() {
+      self.infer(delete.target.as_ref())
+    } else if let Some(control) = expression.downcast_ref::<If>() {
+      self.infer_if(control)
+    } else if let Some(index) = expression.downcast_ref::<Index>() {
+      self.infer_index(index)
+    } else if let Some(instance) = expression.downcast_ref::<Instance>() {
+      self.infer_instance(instance)
+    } else if let Some(li) = expression.downcast_ref::<LoopInfinite>() {
+      self.check_loop(&li.label, li.block.as_ref()).map(|_| None)
+    } else if let Some(matching) = expression.downcast_ref::<Match>() {
+      self.infer_match(matching)
+    } else if let Some(member) = expression.downcast_ref::<Member>() {
+      self.infer_member(member)
+    } else if let Some(outcome) = expression.downcast_ref::<Outcome>() {
+      let kind = self.infer(outcome.value.as_ref())?.unwrap_or(TyKind::Infer);
+
+      match outcome.is_ok {
+        true => Ok(Some(Checker::result(kind, TyKind::Infer))),
+        false => Ok(Some(Checker::result(TyKind::Infer, kind))),
+      }
+    } else if let Some(range) = expression.downcast_ref::<Range>() {
+      for bound in
+        vec![Some(&range.start), Some(&range.end), range.step.as_ref()]
+          .into_iter()
+          .flatten()
+      {
+        match self.infer(bound.as_ref())? {
+          Some(TyKind::Int) | Some(TyKind::Infer) | None => (),
+          Some(kind) => {
+            return Err(format!("range bounds must be `int`, found `{}`", kind))
+          }
+        }
+      }
+
+      Ok(None)
+    } else if let Some(propagation) = expression.downcast_ref::<Try>() {
+      self.infer_try(propagation)
+    } else if let Some(unary) = expression.downcast_ref::<Unary>() {
+      match (unary.operand.kind, self.infer(unary.rhs.as_ref())?) {
+        (TokenKind::Symbol(Bang), _) => Ok(Some(TyKind::Bool)),
+        (_, kind) => Ok(kind),
+      }
+    } else {
+      Ok(None)
+    }
+  }
+
+  fn infer_array(&mut self, array: &Array) -> CheckerResult<Inferred> {
+    let mut kinds = vec![];
+
+    // a spread array brings its item type
+    for data in &array.data {
+      match data.downcast_ref::<Spread>() {
+        Some(spread) => match self.infer(spread.value.as_ref())? {
+          Some(TyKind::ArrayOf(kind)) => kinds.push(Some(*kind)),
+          _ => kinds.push(None),
+        },
+        None => kinds.push(self.infer(data.as_ref())?),
+      }
+    }
+
+    match kinds.first() {
+      None => Ok(Some(TyKind::Array)),
+      Some(Some(first)) if kinds.iter().all(|k| k.as_ref() == Some(first)) => {
+        Ok(Some(TyKind::ArrayOf(Box::new(first.to_owned()))))
+      }
+      Some(_) => Ok(Some(TyKind::Array)),
+    }
+  }
+
+  fn infer_assign(&mut self, assign: &Assign) -> CheckerResult<Inferred> {
+    let expected = self.infer(assign.target.as_ref())?;
+    let found = self.infer(assign.value.as_ref())?;
+
+    match (expected, found) {
+      (Some(expected), Some(found))
+        if !Checker::unify(&expected, &found, &[], &mut Bindings::new()) =>
+      {
+        Err(format!(
+          "mismatched types for `{}`: expected `{}`, found `{}`",
+          assign.target, expected, found,
+        ))
+      }
+      (_, found) => Ok(found),
+    }
+  }
+
+  fn infer_binary(&mut self, binary: &Binary) -> CheckerResult<Inferred> {
+    let lhs = self.infer(binary.lhs.as_ref())?;
+    let rhs = self.infer(binary.rhs.as_ref())?;
+
+    if binary.operator.kind == TokenKind::Operator(QuestionQuestion) {
+      return Checker::infer_default(lhs, rhs);
+    }
+
+    let kind = match (lhs, rhs) {
+      (Some(lhs), Some(rhs)) if lhs == rhs => lhs,
+      _ => return Ok(None),
+    };
+
+    match (binary.operator.kind, kind) {
+      (TokenKind::Operator(Equal), _)
+      | (TokenKind::Operator(NotEqual), _)
+      | (TokenKind::Operator(LessThan), _)
+      | (TokenKind::Operator(LessThanOrEqual), _)
+      | (TokenKind::Operator(GreaterThan), _)
+      | (TokenKind::Operator(GreaterThanOrEqual), _) => Ok(Some(TyKind::Bool)),
+      (_, TyKind::Int) => Ok(Some(TyKind::Int)),
+      (_, TyKind::Float) => Ok(Some(TyKind::Float)),
+      (TokenKind::Operator(Plus), TyKind::Str) => Ok(Some(TyKind::Str)),
+      _ => Ok(None),
+    }
+  }
+
+  // `a ?? b` has the type of `a` once unwrapped, `b` must fit in it
+  fn infer_default(lhs: Inferred, rhs: Inferred) -> CheckerResult<Inferred> {
+    match (lhs, rhs) {
+      (Some(TyKind::Optional(kind)), Some(rhs)) => {
+        let expected = TyKind::Optional(kind.to_owned());
+
+        if !Checker::unify(&expected, &rhs, &[], &mut Bindings::new()) {
+          return Err(format!(
+            "mismatched types in `??`: expected `{}`, found `{}`",
+            kind, rhs
+          ));
+        }
+
+        match rhs.is_optional() {
+          true => Ok(Some(expected)),
+          false => Ok(Some(*kind)),
+        }
+      }
+      (Some(TyKind::Optional(kind)), None) => Ok(Some(*kind)),
+      (Some(TyKind::Nil), rhs) => Ok(rhs),
+      (lhs, _) => Ok(lhs),
+    }
+  }
+
+  // generic parameters are bound by unifying the declared parameters with
+  // the arguments, the return type is then resolved from these bindings
+  fn infer_call(&mut self, call: &Call) -> CheckerResult<Inferred> {
+    let mut kinds = vec![];
+
+    for arg in call.args.as_ref().unwrap() {
+      match arg.downcast_ref::<NamedArg>() {
+        Some(named) => kinds.push((
+          Some(named.name.to_owned()),
+          self.infer(named.value.as_ref())?,
+        )),
+        None => kinds.push((None, self.infer(arg.as_ref())?)),
+      }
+    }
+
+    let name = match call.callee.downcast_ref::<Identifier>() {
+      Some(identifier) => identifier.name.to_owned(),
+      None => return Ok(None),
+    };
+
+    if self.get_variable(&name).is_some() {
+      return Ok(None);
+    }
+
+    let function = match self.get_function(&name) {
+      Some(function) => function,
+      None => return Ok(None),
+    };
+
+    let function = function.downcast_ref::<Function>().unwrap();
+    let params = function.args.as_ref().unwrap();
+    let assigned = FunctionArg::assign(&name, params, kinds)?;
+
+    let generics = function.generic_names();
+    let mut bindings = Bindings::new();
+
+    // every argument a variadic parameter collects has its item type
+    for (param, kinds) in params.iter().zip(assigned) {
+      let param = param.downcast_ref::<FunctionArg>().unwrap();
+      let expected = Checker::to_kind(param.kind.as_ref());
+
+      for found in kinds.into_iter().flatten() {
+        if !Checker::unify(&expected, &found, &generics, &mut bindings) {
+          return Err(format!(
+            "mismatched types in call to `{}`: expected `{}`, found `{}`",
+            name,
+            Checker::substitute(&expected, &bindings),
+            found,
+          ));
+        }
+      }
+    }
+
+    let kind = Checker::to_kind(function.kind.as_deref().unwrap());
+
+    Ok(Checker::resolve(&kind, &generics, &bindings))
+  }
+
+  // a block is worth its last expression
+  fn infer_block(
+    &mut self,
+    statements: &[Box<dyn Value>],
+  ) -> CheckerResult<Inferred> {
+    self.variables.push(HashMap::new());
+
+    for statement in statements {
+      self.declare(statement.as_ref());
+    }
+
+    let mut inferred = Ok(None);
+
+    for (x, statement) in statements.iter().enumerate() {
+      inferred = match statement.downcast_ref::<Expression>() {
+        Some(_) if x == statements.len() - 1 => self.infer(statement.as_ref()),
+        _ => self.check_statement(statement.as_ref()).map(|_| None),
+      };
+
+      if inferred.is_err() {
+        break;
+      }
+    }
+
+    self.variables.pop();
+
+    inferred
+  }
+
+  fn infer_closure(&mut self, closure: &Closure) -> CheckerResult<Inferred> {
+    self.variables.push(HashMap::new());
+
+    for arg in closure.args.as_ref().unwrap() {
+      let arg = arg.downcast_ref::<FunctionArg>().unwrap();
+
+      self.bind_arg(arg, &Checker::to_kind(arg.kind.as_ref()))?;
+    }
+
+    let loops = std::mem::take(&mut self.loops);
+    let checked = self.check_statement(closure.block.as_deref().unwrap());
+
+    self.loops = loops;
+    self.variables.pop();
+    checked?;
+
+    Ok(Some(TyKind::Fn))
+  }
+
+  // both branches must agree for the `if` to have a type, without `else` it
+  // has none
+  fn infer_if(&mut self, control: &If) -> CheckerResult<Inferred> {
+    self.infer(control.condition.as_ref())?;
+
+    let mut infer_branch =
+      |branch: &dyn Value| match branch.downcast_ref::<Block>() {
+        Some(block) => self.infer_block(&block.statements),
+        None => self.infer(branch),
+      };
+
+    let consequence = infer_branch(control.consequence.as_ref())?;
+    let alternative = match &control.alternative {
+      Some(alternative) => infer_branch(alternative.as_ref())?,
+      None => None,
+    };
+
+    match (consequence, alternative) {
+      (Some(lhs), Some(rhs)) if lhs == rhs => Ok(Some(lhs)),
+      _ => Ok(None),
+    }
+  }
+
+  // a range slices an array or a string into one of the same type
+  fn infer_index(&mut self, index: &Index) -> CheckerResult<Inferred> {
+    let key = self.infer(index.rhs.as_ref())?;
+    let data = self.infer(index.lhs.as_ref())?;
+
+    if index.rhs.as_ref().is::<Range>() {
+      return match data {
+        Some(TyKind::ArrayOf(kind)) => Ok(Some(TyKind::ArrayOf(kind))),
+        Some(TyKind::Str) => Ok(Some(TyKind::Str)),
+        _ => Ok(None),
+      };
+    }
+
+    match (data, key) {
+      (Some(TyKind::ArrayOf(_)), Some(key))
+      | (Some(TyKind::Str), Some(key))
+        if key != TyKind::Int && key != TyKind::Infer =>
+      {
+        Err(format!("the index must be `int`, found `{}`", key))
+      }
+      (Some(TyKind::ArrayOf(kind)), _) => Ok(Some(*kind)),
+      (Some(TyKind::Str), _) => Ok(Some(TyKind::Char)),
+      _ => Ok(None),
+    }
+  }
+
+  fn infer_instance(&mut self, instance: &Instance) -> CheckerResult<Inferred> {
+    let sname = instance.name();
+
+    let structure = match self.get_struct(&sname) {
+      Some(structure) => structure,
+      None => return Ok(None),
+    };
+
+    let structure = structure.downcast_ref::<Struct>().unwrap();
+    let generics = structure.generic_names();
+    let mut bindings = Bindings::new();
+
+    for (name, value) in &instance.fields {
+      let found = match self.infer(value.as_ref())? {
+        Some(found) => found,
+        None => continue,
+      };
+
+      let expected = match Checker::get_field(structure, name) {
+        Some(expected) => expected,
+        None => continue,
+      };
+
+      if !Checker::unify(&expected, &found, &generics, &mut bindings) {
+        return Err(format!(
+          "mismatched types for field `{}` of `{}`: expected `{}`, found `{}`",
+          name,
+          sname,
+          Checker::substitute(&expected, &bindings),
+          found,
+        ));
+      }
+    }
+
+    let kind = TyKind::Named(
+      sname,
+      generics
+        .iter()
+        .map(|name| TyKind::Named(name.to_owned(), vec![]))
+        .collect(),
+    );
+
+    Ok(Checker::resolve(&kind, &generics, &bindings))
+  }
+
+  // `a?.b` is optional when `a` is
+  // every arm must agree for the `match` to have a type
+  fn infer_match(&mut self, matching: &Match) -> CheckerResult<Inferred> {
+    let subject = self
+      .infer(matching.subject.as_ref())?
+      .unwrap_or(TyKind::Infer);
+    let mut kinds = vec![];
+
+    for (pattern, body) in &matching.arms {
+      self.variables.push(HashMap::new());
+      let bound = self.bind_pattern(pattern, &subject);
+
+      let inferred = bound.and_then(|_| match body.downcast_ref::<Block>() {
+        Some(block) => self.infer_block(&block.statements),
+        None => self.infer(body.as_ref()),
+      });
+
+      self.variables.pop();
+      kinds.push(inferred?);
+    }
+
+    match kinds.first() {
+      Some(Some(kind)) if kinds.iter().all(|k| k.as_ref() == Some(kind)) => {
+        Ok(Some(kind.to_owned()))
+      }
+      _ => Ok(None),
+    }
+  }
+
+  // `?` unwraps a `Result<T, E>` or a `T?` to `T`
+  fn infer_try(&mut self, propagation: &Try) -> CheckerResult<Inferred> {
+    match self.infer(propagation.value.as_ref())? {
+      Some(TyKind::Named(name, args)) if name == "Result" && args.len() == 2 => {
+        match &args[0] {
+          TyKind::Infer => Ok(None),
+          kind => Ok(Some(kind.to_owned())),
+        }
+      }
+      Some(TyKind::Optional(kind)) => Ok(Some(*kind)),
+      Some(TyKind::Infer) | Some(TyKind::Nil) | None => Ok(None),
+      Some(kind) => Err(format!(
+        "the `?` operator can only be applied to `Result` or optional values, found `{}`",
+        kind
+      )),
+    }
+  }
+
+  fn infer_member(&mut self, member: &Member) -> CheckerResult<Inferred> {
+    let (object, is_optional) = match self.infer(member.object.as_ref())? {
+      Some(TyKind::Optional(kind)) if member.optional => (Some(*kind), true),
+      Some(TyKind::Optional(kind)) => {
+        return Err(format!(
+          "cannot access `{}` on a value of type `{}?`, use `?.` instead",
+          member.name(),
+          kind
+        ))
+      }
+      Some(TyKind::Nil) if member.optional => return Ok(Some(TyKind::Nil)),
+      kind => (kind, false),
+    };
+
+    let (sname, args) = match object {
+      Some(TyKind::Named(sname, args)) => (sname, args),
+      _ => return Ok(None),
+    };
+
+    let structure = match self.get_struct(&sname) {
+      Some(structure) => structure,
+      None => return Ok(None),
+    };
+
+    let structure = structure.downcast_ref::<Struct>().unwrap();
+    let bindings = structure
+      .generic_names()
+      .into_iter()
+      .zip(args)
+      .collect::<Bindings>();
+
+    Ok(
+      Checker::get_field(structure, &member.name())
+        .map(|kind| Checker::substitute(&kind, &bindings))
+        .map(|kind| match is_optional && !kind.is_optional() {
+          true => TyKind::Optional(Box::new(kind)),
+          false => kind,
+        }),
+    )
+  }
+
+  // the types of the variables a pattern binds out of a value of type `kind`
+  fn bind_pattern(
+    &mut self,
+    pattern: &Pattern,
+    kind: &TyKind,
+  ) -> CheckerResult<()> {
+    match (pattern, kind) {
+      (Pattern::Binding(name), kind) => {
+        self.set_variable(name, kind.to_owned());
+        Ok(())
+      }
+      (Pattern::Outcome(is_ok, pattern), TyKind::Named(name, args))
+        if name == "Result" && args.len() == 2 =>
+      {
+        let kind = match is_ok {
+          true => &args[0],
+          false => &args[1],
+        };
+
+        self.bind_pattern(pattern, kind)
+      }
+      (Pattern::Array(patterns), TyKind::ArrayOf(item)) => {
+        for pattern in patterns {
+          match pattern {
+            Pattern::Rest(Some(name)) => {
+              self.set_variable(name, kind.to_owned())
+            }
+            pattern => self.bind_pattern(pattern, item)?,
+          }
+        }
+
+        Ok(())
+      }
+      (Pattern::Struct(sname, fields), _)
+      | (Pattern::Hash(fields), TyKind::Named(sname, _)) => {
+        let structure = match self.get_struct(sname) {
+          Some(structure) => structure,
+          None => return self.bind_unknown(pattern),
+        };
+
+        let structure = structure.downcast_ref::<Struct>().unwrap();
+
+        for (key, pattern) in fields {
+          match Checker::get_field(structure, key) {
+            Some(kind) => self.bind_pattern(pattern, &kind)?,
+            None => {
+              return Err(format!("no field `{}` on type `{}`", key, sname))
+            }
+          }
+        }
+
+        Ok(())
+      }
+      (pattern, _) => self.bind_unknown(pattern),
+    }
+  }
+
+  fn bind_arg(
+    &mut self,
+    arg: &FunctionArg,
+    kind: &TyKind,
+  ) -> CheckerResult<()> {
+    match &arg.pattern {
+      Some(pattern) => self.bind_pattern(pattern, kind),
+      None => {
+        self.set_variable(&arg.name(), kind.to_owned());
+        Ok(())
+      }
+    }
+  }
+
+  fn bind_unknown(&mut self, pattern: &Pattern) -> CheckerResult<()> {
+    for name in pattern.names() {
+      self.set_variable(&name, TyKind::Infer);
+    }
+
+    Ok(())
+  }
+
+  fn result(value: TyKind, error: TyKind) -> TyKind {
+    TyKind::Named("Result".into(), vec![value, error])
+  }
+
+  fn get_field(structure: &Struct, name: &str) -> Option<TyKind> {
+    structure
+      .fields
+      .iter()
+      .filter_map(|field| field.downcast_ref::<FunctionArg>())
+      .find(|field| field.name() == name)
+      .map(|field| Checker::to_kind(field.kind.as_ref()))
+  }
+
+  // natives are checked against their signature
+  fn get_function(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.functions.get(name) {
+      Some(function) => Some(function.to_owned()),
+      None => self.scope.borrow().get_function(name).map(|function| {
+        match function.downcast_ref::<Native>() {
+          Some(native) => native.signature.to_owned(),
+          None => function,
+        }
+      }),
+    }
+  }
+
+  fn get_struct(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.structs.get(name) {
+      Some(structure) => Some(structure.to_owned()),
+      None => self.scope.borrow().get_struct(name),
+    }
+  }
+
+  fn get_trait(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.traits.get(name) {
+      Some(interface) => Some(interface.to_owned()),
+      None => self.scope.borrow().get_trait(name),
+    }
+  }
+
+  fn is_imported(&self, name: &str) -> bool {
+    self
+      .imports
+      .iter()
+      .any(|import| import == name || import == "*")
+  }
+
+  fn get_variable(&self, name: &str) -> Inferred {
+    self
+      .variables
+      .iter()
+      .rev()
+      .find_map(|variables| variables.get(name))
+      .map(|kind| kind.to_owned())
+  }
+
+  fn set_variable(&mut self, name: &str, kind: TyKind) {
+    self.variables.last_mut().unwrap().insert(name.into(), kind);
+  }
+
+  // the type stays unknown while one of its generic parameters is unbound
+  fn resolve(
+    kind: &TyKind,
+    generics: &[String],
+    bindings: &Bindings,
+  ) -> Inferred {
+    let kind = Checker::substitute(kind, bindings);
+
+    match Checker::is_bound(&kind, generics) {
+      true => Some(kind),
+      false => None,
+    }
+  }
+
+  fn is_bound(kind: &TyKind, generics: &[String]) -> bool {
+    match kind {
+      TyKind::ArrayOf(kind) | TyKind::Optional(kind) => {
+        Checker::is_bound(kind, generics)
+      }
+      TyKind::Named(name, args) => {
+        !generics.contains(name)
+          && args.iter().all(|arg| Checker::is_bound(arg, generics))
+      }
+      _ => true,
+    }
+  }
+
+  pub fn substitute(kind: &TyKind, bindings: &Bindings) -> TyKind {
+    match kind {
+      TyKind::ArrayOf(kind) => {
+        TyKind::ArrayOf(Box::new(Checker::substitute(kind, bindings)))
+      }
+      TyKind::Optional(kind) => {
+        TyKind::Optional(Box::new(Checker::substitute(kind, bindings)))
+      }
+      TyKind::Named(name, args) if args.is_empty() => {
+        match bindings.get(name) {
+          Some(kind) => kind.to_owned(),
+          None => TyKind::Named(name.to_owned(), vec![]),
+        }
+      }
+      TyKind::Named(name, args) => TyKind::Named(
+        name.to_owned(),
+        args
+          .iter()
+          .map(|arg| Checker::substitute(arg, bindings))
+          .collect(),
+      ),
+      kind => kind.to_owned(),
+    }
+  }
+
+  pub fn to_kind(ty: &dyn Value) -> TyKind {
+    match ty.downcast_ref::<Ty>() {
+      Some(ty) => ty.kind(),
+      None => TyKind::Named(ty.text(), vec![]),
+    }
+  }
+
+  pub fn unify(
+    expected: &TyKind,
+    found: &TyKind,
+    generics: &[String],
+    bindings: &mut Bindings,
+  ) -> bool {
+    match (expected, found) {
+      (TyKind::Infer, _) | (_, TyKind::Infer) => true,
+      (TyKind::Named(name, args), _)
+        if args.is_empty() && generics.contains(name) =>
+      {
+        match bindings.get(name).map(|kind| kind.to_owned()) {
+          Some(bound) => Checker::unify(&bound, found, &[], bindings),
+          None => {
+            bindings.insert(name.to_owned(), found.to_owned());
+            true
+          }
+        }
+      }
+      (TyKind::Optional(_), TyKind::Nil) => true,
+      (TyKind::Optional(lhs), TyKind::Optional(rhs)) => {
+        Checker::unify(lhs, rhs, generics, bindings)
+      }
+      (TyKind::Optional(lhs), rhs) => {
+        Checker::unify(lhs, rhs, generics, bindings)
+      }
+      (TyKind::Array, TyKind::Array) | (TyKind::Array, TyKind::ArrayOf(_)) => {
+        true
+      }
+      (TyKind::ArrayOf(_), TyKind::Array) => true,
+      (TyKind::ArrayOf(lhs), TyKind::ArrayOf(rhs)) => {
+        Checker::unify(lhs, rhs, generics, bindings)
+      }
+      (TyKind::Named(lhs, lhs_args), TyKind::Named(rhs, rhs_args)) => {
+        lhs == rhs
+          && lhs_args.len() == rhs_args.len()
+          && lhs_args
+            .iter()
+            .zip(rhs_args.iter())
+            .all(|(lhs, rhs)| Checker::unify(lhs, rhs, generics, bindings))
+      }
+      (lhs, rhs) => lhs == rhs,
+    }
+  }
+}
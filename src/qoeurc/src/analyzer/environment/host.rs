@@ -0,0 +1,243 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub type THost = Rc<RefCell<dyn Host>>;
+
+// everything a script can observe of the world outside the interpreter goes
+// through the host, so that it can be replaced where there is no terminal
+pub trait Host: fmt::Debug {
+  fn stdout(&mut self, text: &str);
+  fn stderr(&mut self, text: &str);
+  // a line without its line break, `None` at the end of the input
+  fn stdin(&mut self) -> Option<String>;
+  // seconds since the unix epoch
+  fn now(&mut self) -> f64;
+  // a number in `[0, 1)`
+  fn random(&mut self) -> f64;
+}
+
+// the process itself, what the bootstrap uses
+#[derive(Clone, Debug, Default)]
+pub struct StdHost {
+  random: Random,
+}
+
+impl Host for StdHost {
+  fn stdout(&mut self, text: &str) {
+    print!("{}", text);
+    io::stdout().flush().ok();
+  }
+
+  fn stderr(&mut self, text: &str) {
+    eprint!("{}", text);
+  }
+
+  fn stdin(&mut self) -> Option<String> {
+    let mut line = String::new();
+
+    match io::stdin().lock().read_line(&mut line) {
+      Ok(0) | Err(_) => None,
+      Ok(_) => Some(line.trim_end_matches(&['\r', '\n'][..]).to_owned()),
+    }
+  }
+
+  fn now(&mut self) -> f64 {
+    StdHost::clock()
+  }
+
+  // seeded on first use, the clock is not there on every target
+  fn random(&mut self) -> f64 {
+    if self.random.is_unseeded() {
+      self.random = Random::new(StdHost::clock().to_bits());
+    }
+
+    self.random.float()
+  }
+}
+
+impl StdHost {
+  pub fn new() -> Self {
+    StdHost::default()
+  }
+
+  fn clock() -> f64 {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_secs_f64())
+      .unwrap_or_default()
+  }
+}
+
+// keeps what a script does for tests to look at, its input is given upfront
+// and its clock and randomness are fixed
+#[derive(Clone, Debug, Default)]
+pub struct CaptureHost {
+  pub stdout: String,
+  pub stderr: String,
+  pub stdin: VecDeque<String>,
+  pub clock: f64,
+  random: Random,
+}
+
+impl Host for CaptureHost {
+  fn stdout(&mut self, text: &str) {
+    self.stdout.push_str(text);
+  }
+
+  fn stderr(&mut self, text: &str) {
+    self.stderr.push_str(text);
+  }
+
+  fn stdin(&mut self) -> Option<String> {
+    self.stdin.pop_front()
+  }
+
+  fn now(&mut self) -> f64 {
+    self.clock
+  }
+
+  fn random(&mut self) -> f64 {
+    self.random.float()
+  }
+}
+
+impl CaptureHost {
+  pub fn new() -> Self {
+    CaptureHost::default()
+  }
+
+  pub fn add_clock(mut self, clock: f64) -> Self {
+    self.clock = clock;
+    self
+  }
+
+  pub fn add_seed(mut self, seed: u64) -> Self {
+    self.random = Random::new(seed);
+    self
+  }
+
+  pub fn add_stdin(mut self, lines: &[&str]) -> Self {
+    self.stdin = lines.iter().map(|line| line.to_string()).collect();
+    self
+  }
+
+  pub fn shared(self) -> Rc<RefCell<CaptureHost>> {
+    Rc::new(RefCell::new(self))
+  }
+
+  pub fn stdout_lines(&self) -> Vec<&str> {
+    self.stdout.lines().collect()
+  }
+
+  pub fn stderr_lines(&self) -> Vec<&str> {
+    self.stderr.lines().collect()
+  }
+}
+
+// writes into buffers shared with its clones, the lab keeps one and reads
+// back what was printed once the evaluation is over
+#[derive(Clone, Debug, Default)]
+pub struct BufferHost {
+  stdout: Rc<RefCell<String>>,
+  stderr: Rc<RefCell<String>>,
+  stdin: Rc<RefCell<String>>,
+  clock: Rc<RefCell<f64>>,
+  random: Random,
+}
+
+impl Host for BufferHost {
+  fn stdout(&mut self, text: &str) {
+    self.stdout.borrow_mut().push_str(text);
+  }
+
+  fn stderr(&mut self, text: &str) {
+    self.stderr.borrow_mut().push_str(text);
+  }
+
+  fn stdin(&mut self) -> Option<String> {
+    let mut stdin = self.stdin.borrow_mut();
+
+    if stdin.is_empty() {
+      return None;
+    }
+
+    let end = stdin.find('\n').map_or(stdin.len(), |index| index + 1);
+    let line = stdin.drain(..end).collect::<String>();
+
+    Some(line.trim_end_matches(&['\r', '\n'][..]).to_owned())
+  }
+
+  fn now(&mut self) -> f64 {
+    *self.clock.borrow()
+  }
+
+  fn random(&mut self) -> f64 {
+    self.random.float()
+  }
+}
+
+impl BufferHost {
+  pub fn new(seed: u64) -> Self {
+    BufferHost {
+      random: Random::new(seed),
+      ..BufferHost::default()
+    }
+  }
+
+  pub fn into_host(&self) -> THost {
+    Rc::new(RefCell::new(self.to_owned()))
+  }
+
+  // the time the host reports, set by the embedder which knows the clock
+  pub fn set_clock(&self, clock: f64) {
+    *self.clock.borrow_mut() = clock;
+  }
+
+  pub fn push_stdin(&self, text: &str) {
+    self.stdin.borrow_mut().push_str(text);
+  }
+
+  pub fn take_stdout(&self) -> String {
+    self.stdout.borrow_mut().split_off(0)
+  }
+
+  pub fn take_stderr(&self) -> String {
+    self.stderr.borrow_mut().split_off(0)
+  }
+}
+
+// xorshift64*, enough for scripts and the same on every target
+#[derive(Clone, Debug, Default)]
+pub struct Random {
+  state: u64,
+}
+
+impl Random {
+  pub fn new(seed: u64) -> Self {
+    Random {
+      state: seed ^ 0x9e37_79b9_7f4a_7c15,
+    }
+  }
+
+  pub fn is_unseeded(&self) -> bool {
+    self.state == 0
+  }
+
+  pub fn float(&mut self) -> f64 {
+    if self.state == 0 {
+      self.state = 0x9e37_79b9_7f4a_7c15;
+    }
+
+    self.state ^= self.state >> 12;
+    self.state ^= self.state << 25;
+    self.state ^= self.state >> 27;
+
+    let bits = self.state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11;
+
+    bits as f64 / (1u64 << 53) as f64
+  }
+}
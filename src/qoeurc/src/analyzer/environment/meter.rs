@@ -0,0 +1,141 @@
+use crate::analyzer::interpreter::ValueResult;
+use crate::value::instruction::array::Array;
+use crate::value::instruction::hash::{Hash, HashKey};
+use crate::value::instruction::instance::Instance;
+use crate::value::primitive::str::Str;
+use crate::value::Value;
+
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+pub type TMeter = Rc<RefCell<Meter>>;
+
+// deep enough for most recursions, shallow enough for the rust stack of a
+// debug build
+pub const DEFAULT_DEPTH: usize = 256;
+
+// what a script may spend, `None` is no limit at all
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+  pub fuel: Option<u64>,
+  pub depth: Option<usize>,
+  pub memory: Option<usize>,
+}
+
+impl Default for Limits {
+  fn default() -> Self {
+    Limits {
+      fuel: None,
+      depth: Some(DEFAULT_DEPTH),
+      memory: None,
+    }
+  }
+}
+
+impl Limits {
+  pub fn new() -> Self {
+    Limits::default()
+  }
+
+  pub fn unlimited() -> Self {
+    Limits {
+      fuel: None,
+      depth: None,
+      memory: None,
+    }
+  }
+
+  // the number of steps, a block is one and each of its statements one more
+  pub fn add_fuel(mut self, fuel: u64) -> Self {
+    self.fuel = Some(fuel);
+    self
+  }
+
+  // the number of calls that can be nested
+  pub fn add_depth(mut self, depth: usize) -> Self {
+    self.depth = Some(depth);
+    self
+  }
+
+  // the bytes of the strings and collections built, what is freed is not
+  // given back so it bounds the whole run
+  pub fn add_memory(mut self, memory: usize) -> Self {
+    self.memory = Some(memory);
+    self
+  }
+}
+
+// what a script has spent so far, shared by the nested interpreters
+#[derive(Clone, Debug, Default)]
+pub struct Meter {
+  pub limits: Limits,
+  pub steps: u64,
+  pub depth: usize,
+  pub memory: usize,
+}
+
+impl Meter {
+  pub fn new(limits: Limits) -> Self {
+    Meter {
+      limits,
+      ..Meter::default()
+    }
+  }
+
+  pub fn step(&mut self) -> ValueResult<()> {
+    self.steps += 1;
+
+    match self.limits.fuel {
+      Some(fuel) if self.steps > fuel => {
+        Err(format!("out of fuel after {} steps", fuel))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  pub fn enter(&mut self) -> ValueResult<()> {
+    match self.limits.depth {
+      Some(depth) if self.depth >= depth => {
+        Err(format!("maximum call depth of {} exceeded", depth))
+      }
+      _ => {
+        self.depth += 1;
+        Ok(())
+      }
+    }
+  }
+
+  pub fn leave(&mut self) {
+    self.depth = self.depth.saturating_sub(1);
+  }
+
+  pub fn allocate(&mut self, value: &dyn Value) -> ValueResult<()> {
+    self.memory += Meter::footprint(value);
+
+    match self.limits.memory {
+      Some(memory) if self.memory > memory => {
+        Err(format!("memory limit of {} bytes exceeded", memory))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  // an approximation of the heap a value owns, its items are counted when
+  // they are built themselves
+  pub fn footprint(value: &dyn Value) -> usize {
+    let item = mem::size_of::<Box<dyn Value>>();
+
+    if let Some(text) = value.downcast_ref::<Str>() {
+      text.value.len()
+    } else if let Some(array) = value.downcast_ref::<Array>() {
+      array.data.len() * item
+    } else if let Some(hash) = value.downcast_ref::<Hash>() {
+      hash.entries().len() * (item + mem::size_of::<HashKey>())
+    } else if let Some(instance) = value.downcast_ref::<Instance>() {
+      instance.fields.len() * (item + mem::size_of::<String>())
+    } else {
+      item
+    }
+  }
+}
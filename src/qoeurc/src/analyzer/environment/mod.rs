@@ -1 +1,6 @@
+pub mod host;
+pub mod meter;
+pub mod registry;
 pub mod scope;
+pub mod signal;
+pub mod slots;
@@ -0,0 +1,62 @@
+use crate::analyzer::environment::scope::{ScopeResult, TScope};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub type TRegistry = Rc<RefCell<Registry>>;
+
+#[derive(Clone, Debug)]
+pub struct Exports {
+  pub names: Vec<String>,
+  pub scope: TScope,
+}
+
+impl Exports {
+  pub fn new(names: Vec<String>, scope: TScope) -> Self {
+    Exports { names, scope }
+  }
+}
+
+// modules evaluated so far, keyed by their file (or their name for inline
+// modules), and the chain of modules currently being loaded
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+  cache: HashMap<String, Exports>,
+  loading: Vec<(String, String)>,
+}
+
+impl Registry {
+  pub fn new() -> Self {
+    Registry::default()
+  }
+
+  pub fn enter(&mut self, key: &str, name: &str) -> ScopeResult<()> {
+    if let Some(x) = self.loading.iter().position(|(k, _)| k == key) {
+      let chain = self.loading[x..]
+        .iter()
+        .map(|(_, name)| name.to_owned())
+        .chain(vec![name.to_owned()])
+        .collect::<Vec<String>>()
+        .join(" -> ");
+
+      return Err(format!("cyclic import of module `{}`: {}", name, chain));
+    }
+
+    self.loading.push((key.into(), name.into()));
+
+    Ok(())
+  }
+
+  pub fn get(&self, key: &str) -> Option<Exports> {
+    self.cache.get(key).map(|exports| exports.to_owned())
+  }
+
+  pub fn insert(&mut self, key: &str, exports: Exports) {
+    self.cache.insert(key.into(), exports);
+  }
+
+  pub fn leave(&mut self) {
+    self.loading.pop();
+  }
+}
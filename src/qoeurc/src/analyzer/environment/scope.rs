@@ -1,6 +1,9 @@
+use crate::analyzer::environment::slots::{Slot, Slots};
 use crate::value::instruction::function::Function;
-use crate::value::instruction::function_arg::FunctionArg;
 use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::interface::Trait;
+use crate::value::instruction::native::Native;
+use crate::value::instruction::structure::Struct;
 use crate::value::instruction::val::Val;
 use crate::value::Value;
 
@@ -16,15 +19,19 @@ pub type TScope = Rc<RefCell<Scope>>;
 pub struct Scope {
   functions: HashMap<String, Box<dyn Value>>,
   outer: Option<TScope>,
-  variables: HashMap<String, Box<dyn Value>>,
+  structs: HashMap<String, Box<dyn Value>>,
+  traits: HashMap<String, Box<dyn Value>>,
+  variables: Slots,
 }
 
 impl Scope {
   pub fn new() -> Self {
     Scope {
-      variables: HashMap::new(),
+      variables: Slots::new(),
       outer: None,
       functions: HashMap::new(),
+      structs: HashMap::new(),
+      traits: HashMap::new(),
     }
   }
 
@@ -32,7 +39,9 @@ impl Scope {
     Scope {
       functions: HashMap::new(),
       outer: Some(outer),
-      variables: HashMap::new(),
+      structs: HashMap::new(),
+      traits: HashMap::new(),
+      variables: Slots::new(),
     }
   }
 
@@ -54,6 +63,34 @@ impl Scope {
     }
   }
 
+  // a function known by the value it evaluates to, like the ones of the
+  // bytecode machine
+  pub fn add_callable(
+    &mut self,
+    name: &str,
+    function: Box<dyn Value>,
+  ) -> ScopeResult<()> {
+    match self.functions.get(name) {
+      Some(_) => Err(String::from("function already exist")),
+      None => {
+        self.set_function(name, function);
+        Ok(())
+      }
+    }
+  }
+
+  pub fn add_native(&mut self, native: &dyn Value) -> ScopeResult<()> {
+    let nname = native.downcast_ref::<Native>().unwrap().name();
+
+    match self.functions.get(&nname) {
+      Some(_) => Err(String::from("function already exist")),
+      None => {
+        self.set_function(&nname, native.boxed());
+        Ok(())
+      }
+    }
+  }
+
   pub fn add_variable(
     &mut self,
     statement: &Box<dyn Value>,
@@ -62,42 +99,171 @@ impl Scope {
     let vname = &val.name.as_ref().downcast_ref::<Identifier>().unwrap().name;
     let vvalue = val.value.as_ref().unwrap().boxed();
 
-    match self.variables.get(vname) {
-      Some(_) => Err(format!("variable already exist")),
-      None => Ok(self.set_variable(vname, vvalue)),
+    self.add_binding(vname, vvalue)
+  }
+
+  pub fn add_binding(
+    &mut self,
+    name: &str,
+    value: Box<dyn Value>,
+  ) -> ScopeResult<()> {
+    match self.variables.contains(name) {
+      true => Err(String::from("variable already exist")),
+      false => {
+        self.set_variable(name, value);
+        Ok(())
+      }
+    }
+  }
+
+  pub fn add_struct(&mut self, statement: &dyn Value) -> ScopeResult<()> {
+    let structure = statement.downcast_ref::<Struct>().unwrap();
+    let sname = structure.name();
+
+    match self.structs.get(&sname) {
+      Some(_) => Err(String::from("struct already exist")),
+      None => {
+        self.set_struct(&sname, structure.boxed());
+        Ok(())
+      }
     }
   }
 
-  pub fn get_function(&self, name: &str) -> Option<&Box<dyn Value>> {
-    self.functions.get(name)
+  pub fn add_trait(&mut self, statement: &dyn Value) -> ScopeResult<()> {
+    let interface = statement.downcast_ref::<Trait>().unwrap();
+    let tname = interface.name();
+
+    match self.traits.get(&tname) {
+      Some(_) => Err(String::from("trait already exist")),
+      None => {
+        self.set_trait(&tname, interface.boxed());
+        Ok(())
+      }
+    }
   }
 
-  pub fn get_variable(&self, name: &str) -> Option<&Box<dyn Value>> {
-    self.variables.get(name)
+  pub fn bind(&mut self, name: &str, value: Box<dyn Value>) {
+    self.set_variable(name, value);
   }
 
-  fn set_function(&mut self, name: &str, function: Box<dyn Value>) {
-    self.functions.insert(name.into(), function);
+  // replaces the value of `name` in the scope that defines it
+  pub fn assign(
+    &mut self,
+    name: &str,
+    value: Box<dyn Value>,
+  ) -> ScopeResult<()> {
+    if self.variables.contains(name) {
+      self.set_variable(name, value);
+      return Ok(());
+    }
+
+    match &self.outer {
+      Some(outer) => outer.borrow_mut().assign(name, value),
+      None => Err(format!("cannot find value `{}` in this scope", name)),
+    }
   }
 
-  fn set_variable(&mut self, name: &str, value: Box<dyn Value>) {
-    self.variables.insert(name.into(), value);
+  pub fn outer(&self) -> Option<TScope> {
+    self.outer.to_owned()
+  }
+
+  pub fn get_function(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.functions.get(name) {
+      Some(function) => Some(function.to_owned()),
+      None => self.outer.as_ref()?.borrow().get_function(name),
+    }
   }
 
-  pub fn extend_scope(
-    outer: TScope,
-    lhs: Vec<Box<dyn Value>>,
-    rhs: Vec<Box<dyn Value>>,
-  ) -> Scope {
-    let mut scope = Scope::new_with_outer(outer);
+  pub fn get_struct(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.structs.get(name) {
+      Some(structure) => Some(structure.to_owned()),
+      None => self.outer.as_ref()?.borrow().get_struct(name),
+    }
+  }
+
+  pub fn get_trait(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.traits.get(name) {
+      Some(interface) => Some(interface.to_owned()),
+      None => self.outer.as_ref()?.borrow().get_trait(name),
+    }
+  }
+
+  pub fn get_variable(&self, name: &str) -> Option<Box<dyn Value>> {
+    match self.variables.get(name) {
+      Some(variable) => Some(variable.boxed()),
+      None => self.outer.as_ref()?.borrow().get_variable(name),
+    }
+  }
+
+  // the variable the resolver found for `name`, none when the scopes met at
+  // run time are not the ones it saw and the name has to be looked up
+  pub fn get_slot(&self, slot: Slot, name: &str) -> Option<Box<dyn Value>> {
+    match slot.depth {
+      0 => self
+        .variables
+        .get_at(slot.index, name)
+        .map(|value| value.boxed()),
+      depth => self
+        .outer
+        .as_ref()?
+        .borrow()
+        .get_slot(Slot::new(depth - 1, slot.index), name),
+    }
+  }
+
+  pub fn count_variables(&self) -> usize {
+    self.variables.len()
+  }
+
+  // brings every item called `name` defined at the top of `module` in scope
+  pub fn import(&mut self, name: &str, module: &Scope) -> ScopeResult<()> {
+    let items = vec![
+      (&module.functions, &mut self.functions),
+      (&module.structs, &mut self.structs),
+      (&module.traits, &mut self.traits),
+    ];
+
+    let mut found = false;
 
-    for (x, lhs_arg) in lhs.iter().enumerate() {
-      let arg = &lhs_arg.as_ref().downcast_ref::<FunctionArg>().unwrap();
-      let name = &arg.name.as_ref().downcast_ref::<Identifier>().unwrap().name;
+    if let Some(variable) = module.variables.get(name) {
+      if self.variables.contains(name) {
+        return Err(format!("the name `{}` is defined multiple times", name));
+      }
 
-      scope.set_variable(name, rhs[x].to_owned());
+      self.variables.insert(name, variable.boxed());
+      found = true;
     }
 
-    scope
+    for (from, to) in items {
+      if let Some(item) = from.get(name) {
+        if to.contains_key(name) {
+          return Err(format!("the name `{}` is defined multiple times", name));
+        }
+
+        to.insert(name.into(), item.to_owned());
+        found = true;
+      }
+    }
+
+    match found {
+      true => Ok(()),
+      false => Err(format!("cannot find `{}` in module", name)),
+    }
+  }
+
+  fn set_function(&mut self, name: &str, function: Box<dyn Value>) {
+    self.functions.insert(name.into(), function);
+  }
+
+  fn set_struct(&mut self, name: &str, structure: Box<dyn Value>) {
+    self.structs.insert(name.into(), structure);
+  }
+
+  fn set_trait(&mut self, name: &str, interface: Box<dyn Value>) {
+    self.traits.insert(name.into(), interface);
+  }
+
+  fn set_variable(&mut self, name: &str, value: Box<dyn Value>) {
+    self.variables.insert(name, value);
   }
 }
@@ -0,0 +1,35 @@
+use crate::value::Value;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub type TSignal = Rc<RefCell<Option<Signal>>>;
+
+// raised by `break`, `continue` and `return`, the statements around them stop
+// until a loop or a call consumes it
+#[derive(Clone, Debug)]
+pub enum Signal {
+  Break(Option<String>, Option<Box<dyn Value>>),
+  Continue(Option<String>),
+  Return(Box<dyn Value>),
+}
+
+impl Signal {
+  pub fn keyword(&self) -> &str {
+    match self {
+      Signal::Break(..) => "break",
+      Signal::Continue(_) => "continue",
+      Signal::Return(_) => "return",
+    }
+  }
+
+  // whether the loop labelled `label` is the one a jump leaves
+  pub fn is_aimed_at(&self, label: &Option<String>) -> bool {
+    let target = match self {
+      Signal::Break(target, _) | Signal::Continue(target) => target,
+      Signal::Return(_) => return false,
+    };
+
+    target.is_none() || target == label
+  }
+}
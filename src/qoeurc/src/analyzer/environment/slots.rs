@@ -0,0 +1,86 @@
+use crate::value::Value;
+
+use std::collections::HashMap;
+
+// where the resolver found a variable, `depth` scopes up from the one it is
+// used in and at `index` in the order that scope binds its variables
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Slot {
+  pub depth: usize,
+  pub index: usize,
+}
+
+impl Slot {
+  pub fn new(depth: usize, index: usize) -> Self {
+    Slot { depth, index }
+  }
+}
+
+// the variables of a scope in the order they are bound, `names` holds the
+// position of each name in `values`
+#[derive(Clone, Debug, Default)]
+pub struct Slots {
+  names: HashMap<String, usize>,
+  values: Vec<(String, Box<dyn Value>)>,
+}
+
+// two scopes hold the same variables when they bind the same names to the
+// same values, whatever the order
+impl PartialEq for Slots {
+  fn eq(&self, rhs: &Slots) -> bool {
+    self.len() == rhs.len()
+      && self.iter().all(|(name, value)| {
+        rhs.names.get(name).map(|index| &rhs.values[*index].1) == Some(value)
+      })
+  }
+}
+
+impl Slots {
+  pub fn new() -> Self {
+    Slots::default()
+  }
+
+  pub fn contains(&self, name: &str) -> bool {
+    self.names.contains_key(name)
+  }
+
+  pub fn get(&self, name: &str) -> Option<&dyn Value> {
+    self
+      .names
+      .get(name)
+      .map(|index| self.values[*index].1.as_ref())
+  }
+
+  // a slot only holds when the variable bound there has the name it was
+  // resolved with
+  pub fn get_at(&self, index: usize, name: &str) -> Option<&dyn Value> {
+    self
+      .values
+      .get(index)
+      .filter(|(known, _)| known == name)
+      .map(|(_, value)| value.as_ref())
+  }
+
+  // a name that is already there keeps its slot and gets the new value
+  pub fn insert(&mut self, name: &str, value: Box<dyn Value>) {
+    match self.names.get(name) {
+      Some(index) => self.values[*index].1 = value,
+      None => {
+        self.names.insert(name.into(), self.values.len());
+        self.values.push((name.into(), value));
+      }
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&String, &Box<dyn Value>)> {
+    self.values.iter().map(|(name, value)| (name, value))
+  }
+
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+}
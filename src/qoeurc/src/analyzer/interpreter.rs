@@ -1,28 +1,165 @@
+use crate::analyzer::checker::Checker;
+use crate::analyzer::environment::host::{StdHost, THost};
+use crate::analyzer::environment::meter::{Limits, Meter, TMeter};
+use crate::analyzer::environment::registry::{Exports, Registry, TRegistry};
 use crate::analyzer::environment::scope::{Scope, TScope};
+use crate::analyzer::environment::signal::{Signal, TSignal};
+use crate::analyzer::prelude::PRELUDE;
+use crate::analyzer::resolver::Resolver;
+use crate::analyzer::stdlib;
+use crate::converter::parser::Parser;
+use crate::value::convert::IntoValue;
+use crate::value::instruction::native::{HostFunction, Native};
+use crate::value::instruction::public::Public;
+use crate::value::instruction::Program;
 use crate::value::Value;
+use crate::void;
 
 use std::cell::{Ref, RefCell, RefMut};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 pub type InterpreterError = String;
 pub type ValueResult<T> = Result<T, InterpreterError>;
 
+pub const EXTENSIONS: [&str; 2] = ["q", "q5"];
+
 #[derive(Clone, Debug)]
 pub struct Interpreter {
+  pub host: THost,
+  pub meter: TMeter,
+  pub path: Option<PathBuf>,
+  pub registry: TRegistry,
   pub scope: TScope,
+  pub signal: TSignal,
 }
 
 impl Interpreter {
+  // the standard library lives in a scope of its own, outside the one of the
+  // program, so that a program can define functions with the same names
   pub fn new() -> Self {
-    Interpreter {
-      scope: Rc::new(RefCell::new(Scope::new())),
+    let mut natives = Scope::new();
+
+    for native in stdlib::natives() {
+      natives.add_native(&native).unwrap();
     }
+
+    let mut interpreter = Interpreter {
+      host: Rc::new(RefCell::new(StdHost::new())),
+      meter: Rc::new(RefCell::new(Meter::new(Limits::default()))),
+      path: None,
+      registry: Rc::new(RefCell::new(Registry::new())),
+      scope: Rc::new(RefCell::new(Scope::new_with_outer(Rc::new(
+        RefCell::new(natives),
+      )))),
+      signal: Rc::new(RefCell::new(None)),
+    };
+
+    let prelude = Parser::new(PRELUDE).parse().unwrap();
+
+    interpreter.eval(&prelude).unwrap();
+    interpreter
   }
 
   pub fn new_with_outer(outer: Scope) -> Self {
     Interpreter {
+      host: Rc::new(RefCell::new(StdHost::new())),
+      meter: Rc::new(RefCell::new(Meter::new(Limits::default()))),
+      path: None,
+      registry: Rc::new(RefCell::new(Registry::new())),
       scope: Rc::new(RefCell::new(outer)),
+      signal: Rc::new(RefCell::new(None)),
+    }
+  }
+
+  // a nested interpreter running in `scope`, the host, the limits, modules
+  // and control-flow signals stay shared with self
+  pub fn new_with_scope(&self, scope: Scope) -> Self {
+    Interpreter {
+      host: self.host.to_owned(),
+      meter: self.meter.to_owned(),
+      path: self.path.to_owned(),
+      registry: self.registry.to_owned(),
+      scope: Rc::new(RefCell::new(scope)),
+      signal: self.signal.to_owned(),
+    }
+  }
+
+  // the entry file is marked as loading so that importing it back from one
+  // of its modules is reported as a cycle
+  pub fn add_path(&mut self, path: &Path) -> &mut Self {
+    let name = Interpreter::module_name(path);
+    let key = Interpreter::module_key(path);
+
+    self.registry.borrow_mut().enter(&key, &name).ok();
+    self.path = Some(path.to_owned());
+    self
+  }
+
+  // a value of the host that scripts see as a variable, like the standard
+  // library it can be hidden by the script
+  pub fn add_constant<T: IntoValue>(
+    &mut self,
+    name: &str,
+    value: T,
+  ) -> ValueResult<&mut Self> {
+    self
+      .root()
+      .borrow_mut()
+      .add_binding(name, value.into_value())?;
+    Ok(self)
+  }
+
+  // a rust function of the host that scripts can call, `signature` is the
+  // head of a qoeur function: `ƒ add(a: int, b: int) -> int`
+  pub fn add_function<Args, F>(
+    &mut self,
+    signature: &str,
+    function: F,
+  ) -> ValueResult<&mut Self>
+  where
+    F: HostFunction<Args> + 'static,
+  {
+    let mut native = Native::try_new(signature, |_, _| Ok(void!()), "")?;
+    let name = native.name();
+
+    if native.params().len() != F::ARITY {
+      return Err(format!(
+        "the signature of `{}` has {} parameters but the function takes {}",
+        name,
+        native.params().len(),
+        F::ARITY,
+      ));
     }
+
+    native.function = Rc::new(move |_, args| function.call(&name, args));
+
+    self.root().borrow_mut().add_native(&native)?;
+    Ok(self)
+  }
+
+  // where the output of the scripts goes and where their input, time and
+  // randomness come from, the process itself by default
+  pub fn add_host(&mut self, host: THost) -> &mut Self {
+    self.host = host;
+    self
+  }
+
+  // the limits apply to what is spent from now on
+  pub fn add_limits(&mut self, limits: Limits) -> &mut Self {
+    self.meter = Rc::new(RefCell::new(Meter::new(limits)));
+    self
+  }
+
+  pub fn add_meter(&mut self, meter: TMeter) -> &mut Self {
+    self.meter = meter;
+    self
+  }
+
+  pub fn add_registry(&mut self, registry: TRegistry) -> &mut Self {
+    self.registry = registry;
+    self
   }
 
   pub fn eval(
@@ -32,6 +169,82 @@ impl Interpreter {
     Ok(program.to_owned().eval(self)?)
   }
 
+  // evaluates the module at `path` once, the following imports share the
+  // exports of the first one
+  pub fn import(&mut self, path: &[String]) -> ValueResult<Exports> {
+    let name = path.join("::");
+
+    if let Some(exports) = self.registry.borrow().get(&name) {
+      return Ok(exports);
+    }
+
+    let file = self.resolve(path)?;
+    let key = Interpreter::module_key(&file);
+
+    if let Some(exports) = self.registry.borrow().get(&key) {
+      return Ok(exports);
+    }
+
+    self.registry.borrow_mut().enter(&key, &name)?;
+
+    let exports = self.load(&file);
+
+    self.registry.borrow_mut().leave();
+
+    let exports =
+      exports.map_err(|error| format!("in module `{}`: {}", name, error))?;
+
+    self.registry.borrow_mut().insert(&key, exports.to_owned());
+
+    Ok(exports)
+  }
+
+  pub fn allocate(&self, value: &dyn Value) -> ValueResult<()> {
+    self.meter.borrow_mut().allocate(value)
+  }
+
+  pub fn is_unwinding(&self) -> bool {
+    self.signal.borrow().is_some()
+  }
+
+  // writes the text of `value` on a line of the output of the host
+  pub fn print(&self, value: &dyn Value) {
+    self
+      .host
+      .borrow_mut()
+      .stdout(&format!("{}\n", value.text()));
+  }
+
+  pub fn raise(&self, signal: Signal) {
+    *self.signal.borrow_mut() = Some(signal);
+  }
+
+  pub fn signal(&self) -> Option<Signal> {
+    self.signal.borrow().to_owned()
+  }
+
+  pub fn step(&self) -> ValueResult<()> {
+    self.meter.borrow_mut().step()
+  }
+
+  pub fn take_signal(&self) -> Option<Signal> {
+    self.signal.borrow_mut().take()
+  }
+
+  // the outermost scope, the one of the standard library
+  fn root(&self) -> TScope {
+    let mut scope = self.scope.to_owned();
+
+    loop {
+      let outer = scope.borrow().outer();
+
+      match outer {
+        Some(outer) => scope = outer,
+        None => return scope,
+      }
+    }
+  }
+
   pub fn scope(&self) -> Ref<'_, Scope> {
     self.scope.borrow()
   }
@@ -39,4 +252,68 @@ impl Interpreter {
   pub fn scope_mut(&self) -> RefMut<'_, Scope> {
     self.scope.borrow_mut()
   }
+
+  fn load(&self, file: &Path) -> ValueResult<Exports> {
+    let input =
+      fs::read_to_string(file).map_err(|error| format!("{}", error))?;
+    let mut program = Parser::new(&input).parse()?;
+    let mut interpreter = Interpreter::new();
+
+    interpreter
+      .add_host(self.host.to_owned())
+      .add_meter(self.meter.to_owned())
+      .add_registry(self.registry.to_owned())
+      .path = Some(file.to_owned());
+
+    Resolver::new(interpreter.scope.to_owned()).resolve(&mut program)?;
+    Checker::new(interpreter.scope.to_owned()).check(program.as_ref())?;
+    interpreter.eval(&program)?;
+
+    let statements = &program.downcast_ref::<Program>().unwrap().statements;
+
+    Ok(Exports::new(
+      Public::exports(statements),
+      interpreter.scope.to_owned(),
+    ))
+  }
+
+  fn module_key(path: &Path) -> String {
+    match fs::canonicalize(path) {
+      Ok(path) => path.display().to_string(),
+      Err(_) => path.display().to_string(),
+    }
+  }
+
+  fn module_name(path: &Path) -> String {
+    match path.file_stem() {
+      Some(stem) => stem.to_string_lossy().to_string(),
+      None => path.display().to_string(),
+    }
+  }
+
+  // `a::b` is looked up as `a/b.q` next to the current file, `super` goes
+  // one directory up
+  fn resolve(&self, path: &[String]) -> ValueResult<PathBuf> {
+    let mut file = match self.path.as_ref().and_then(|path| path.parent()) {
+      Some(dir) => dir.to_owned(),
+      None => PathBuf::from("."),
+    };
+
+    for segment in path {
+      match segment.as_str() {
+        "super" => file.push(".."),
+        _ => file.push(segment),
+      }
+    }
+
+    EXTENSIONS
+      .iter()
+      .map(|extension| file.with_extension(extension))
+      .find(|file| file.is_file())
+      .ok_or(format!(
+        "file not found for module `{}`, expected `{}`",
+        path.join("::"),
+        file.with_extension(EXTENSIONS[0]).display(),
+      ))
+  }
 }
@@ -1,19 +1,36 @@
+pub mod checker;
 pub mod environment;
 pub mod interpreter;
+pub mod prelude;
+pub mod resolver;
+pub mod stdlib;
 
 #[cfg(test)]
 mod tests;
 
+use crate::analyzer::checker::Checker;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::resolver::Resolver;
+use crate::converter::lowering::raise;
 use crate::converter::parser::Parser;
+use crate::optimizer::optimize;
 use crate::value::Value;
 
 pub fn evalify(
   input: &str,
   interpreter: &mut Interpreter,
 ) -> ValueResult<Box<dyn Value>> {
-  let mut parser = Parser::new(input);
-  let program = &parser.parse()?;
+  let ast = Parser::new(input).parse_ast()?;
+  // the passes below still run on values and not on the tree
+  let program = &mut raise(&ast);
+
+  Resolver::new(interpreter.scope.to_owned()).check(program.as_ref())?;
+  Checker::new(interpreter.scope.to_owned()).check(program.as_ref())?;
+
+  // errors are reported on the program as written, its optimized form runs
+  let program = &mut optimize(ast);
+
+  Resolver::new(interpreter.scope.to_owned()).resolve(program)?;
 
   interpreter.eval(program)
 }
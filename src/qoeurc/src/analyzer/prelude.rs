@@ -0,0 +1,10 @@
+// traits known by every interpreter, binary operators on struct values are
+// dispatched to them and `Display` is used to print them
+pub const PRELUDE: &str = "
+trait Add { ƒ add (self, rhs: Self) -> Self; }
+trait Sub { ƒ sub (self, rhs: Self) -> Self; }
+trait Mul { ƒ mul (self, rhs: Self) -> Self; }
+trait Div { ƒ div (self, rhs: Self) -> Self; }
+trait Eq { ƒ eq (self, rhs: Self) -> bool; }
+trait Display { ƒ fmt (self) -> str; }
+";
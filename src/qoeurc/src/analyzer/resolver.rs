@@ -0,0 +1,532 @@
+use crate::analyzer::environment::scope::TScope;
+use crate::analyzer::environment::slots::Slot;
+use crate::value::instruction::array::Array;
+use crate::value::instruction::assign::Assign;
+use crate::value::instruction::binary::Binary;
+use crate::value::instruction::block::Block;
+use crate::value::instruction::call::Call;
+use crate::value::instruction::closure::Closure;
+use crate::value::instruction::control::If;
+use crate::value::instruction::delete::Delete;
+use crate::value::instruction::expression::Expression;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::group::Group;
+use crate::value::instruction::hash::Hash;
+use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::import::Use;
+use crate::value::instruction::index::Index;
+use crate::value::instruction::instance::Instance;
+use crate::value::instruction::interface::Trait;
+use crate::value::instruction::loop_break::Break;
+use crate::value::instruction::loop_for_in::LoopForIn;
+use crate::value::instruction::loop_infinite::LoopInfinite;
+use crate::value::instruction::loop_while::LoopWhile;
+use crate::value::instruction::match_value::Match;
+use crate::value::instruction::member::Member;
+use crate::value::instruction::module::Module;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::native::Native;
+use crate::value::instruction::outcome::Outcome;
+use crate::value::instruction::program::Program;
+use crate::value::instruction::public::Public;
+use crate::value::instruction::range::Range;
+use crate::value::instruction::return_value::Return;
+use crate::value::instruction::spread::Spread;
+use crate::value::instruction::statement::Statement;
+use crate::value::instruction::structure::Struct;
+use crate::value::instruction::try_value::Try;
+use crate::value::instruction::unary::Unary;
+use crate::value::instruction::val::Val;
+use crate::value::Value;
+
+use std::collections::HashSet;
+
+pub type ResolverError = String;
+pub type ResolverResult<T> = Result<T, ResolverError>;
+
+// the variables a scope binds, in the order it binds them, the first one
+// takes the slot `base`
+#[derive(Clone, Debug, Default)]
+struct Names {
+  base: usize,
+  names: Vec<String>,
+}
+
+impl Names {
+  fn position(&self, name: &str) -> Option<usize> {
+    self
+      .names
+      .iter()
+      .position(|known| known == name)
+      .map(|position| self.base + position)
+  }
+
+  fn push(&mut self, name: &str) {
+    if self.position(name).is_none() {
+      self.names.push(name.into());
+    }
+  }
+}
+
+// the names declared unconditionally by the statements of a scope, a second
+// declaration of one of them always fails when the scope runs
+#[derive(Debug, Default)]
+struct Declared {
+  functions: HashSet<String>,
+  variables: HashSet<String>,
+}
+
+// binds each variable to the scope that defines it and its slot there,
+// mirroring the scopes the interpreter opens: calls, loop iterations and
+// match arms, and reports names used but never defined
+#[derive(Clone, Debug)]
+pub struct Resolver {
+  functions: HashSet<String>,
+  glob: bool,
+  items: HashSet<String>,
+  scope: TScope,
+  scopes: Vec<Names>,
+}
+
+impl Resolver {
+  pub fn new(scope: TScope) -> Self {
+    Resolver {
+      functions: HashSet::new(),
+      glob: false,
+      items: HashSet::new(),
+      scope,
+      scopes: vec![],
+    }
+  }
+
+  pub fn resolve(
+    &mut self,
+    program: &mut Box<dyn Value>,
+  ) -> ResolverResult<()> {
+    self.collect(program);
+
+    let base = self.scope.borrow().count_variables();
+
+    self.scopes.push(Names {
+      base,
+      names: vec![],
+    });
+
+    self.declare_all(Resolver::children(program))?;
+
+    for statement in Resolver::children(program) {
+      self.resolve_node(statement)?;
+    }
+
+    self.scopes.pop();
+
+    Ok(())
+  }
+
+  // reports the errors without binding anything, for the bytecode machine
+  // whose scopes are not the ones of the interpreter
+  pub fn check(&mut self, program: &dyn Value) -> ResolverResult<()> {
+    self.resolve(&mut program.boxed())
+  }
+
+  // the functions and other items declared anywhere in the program, those
+  // are found by name wherever they are used
+  fn collect(&mut self, node: &mut Box<dyn Value>) {
+    if let Some(import) = node.downcast_ref::<Use>() {
+      match import.glob {
+        true => self.glob = true,
+        false => self.items.extend(import.item_names()),
+      }
+    } else if let Some(function) = node.downcast_ref::<Function>() {
+      self.functions.insert(function.name());
+    } else if let Some(structure) = node.downcast_ref::<Struct>() {
+      self.items.insert(structure.name());
+    } else if let Some(interface) = node.downcast_ref::<Trait>() {
+      self.items.insert(interface.name());
+    } else if let Some(module) = node.downcast_ref::<Module>() {
+      self.items.insert(module.name());
+    }
+
+    for child in Resolver::children(node) {
+      self.collect(child);
+    }
+  }
+
+  fn declare_all(
+    &mut self,
+    statements: Vec<&mut Box<dyn Value>>,
+  ) -> ResolverResult<()> {
+    let mut declared = Declared::default();
+
+    for statement in statements {
+      self.declare(statement.as_ref(), false, &mut declared)?;
+    }
+
+    Ok(())
+  }
+
+  // the variables the statements of a scope bind, `if` and blocks do not
+  // open a scope so their statements bind in the one around them
+  fn declare(
+    &mut self,
+    statement: &dyn Value,
+    conditional: bool,
+    declared: &mut Declared,
+  ) -> ResolverResult<()> {
+    if let Some(public) = statement.downcast_ref::<Public>() {
+      self.declare(public.node.as_ref(), conditional, declared)
+    } else if let Some(statement) = statement.downcast_ref::<Statement>() {
+      self.declare(statement.node.as_ref(), conditional, declared)
+    } else if let Some(expression) = statement.downcast_ref::<Expression>() {
+      self.declare(expression.node.as_ref(), conditional, declared)
+    } else if let Some(block) = statement.downcast_ref::<Block>() {
+      for statement in &block.statements {
+        self.declare(statement.as_ref(), conditional, declared)?;
+      }
+
+      Ok(())
+    } else if let Some(control) = statement.downcast_ref::<If>() {
+      self.declare(control.consequence.as_ref(), true, declared)?;
+
+      match &control.alternative {
+        Some(alternative) => self.declare(alternative.as_ref(), true, declared),
+        None => Ok(()),
+      }
+    } else if let Some(val) = statement.downcast_ref::<Val>() {
+      let names = match &val.pattern {
+        Some(pattern) => pattern.names(),
+        None => vec![val.name.text()],
+      };
+
+      for name in names {
+        if !conditional && !declared.variables.insert(name.to_owned()) {
+          return Err(format!("the name `{}` is defined multiple times", name));
+        }
+
+        self.bind(&name);
+      }
+
+      Ok(())
+    } else if let Some(function) = statement.downcast_ref::<Function>() {
+      let name = function.name();
+
+      match conditional || declared.functions.insert(name.to_owned()) {
+        true => Ok(()),
+        false => Err(format!("the name `{}` is defined multiple times", name)),
+      }
+    } else {
+      Ok(())
+    }
+  }
+
+  fn bind(&mut self, name: &str) {
+    self.scopes.last_mut().unwrap().push(name);
+  }
+
+  // runs `nodes` in a new scope that first binds `names`
+  fn enter(
+    &mut self,
+    names: Vec<String>,
+    mut nodes: Vec<&mut Box<dyn Value>>,
+  ) -> ResolverResult<()> {
+    self.scopes.push(Names::default());
+
+    for name in names {
+      self.bind(&name);
+    }
+
+    let statements = nodes
+      .iter_mut()
+      .flat_map(|node| Resolver::statements(node))
+      .collect::<Vec<&mut Box<dyn Value>>>();
+
+    let resolved = self.declare_all(statements).and_then(|_| {
+      nodes
+        .into_iter()
+        .try_for_each(|node| self.resolve_node(node))
+    });
+
+    self.scopes.pop();
+
+    resolved
+  }
+
+  fn resolve_node(&mut self, node: &mut Box<dyn Value>) -> ResolverResult<()> {
+    if node.is::<Identifier>() {
+      self.resolve_identifier(node.downcast_mut::<Identifier>().unwrap())
+    } else if node.is::<Function>() {
+      let function = node.downcast_mut::<Function>().unwrap();
+
+      self.resolve_function(&mut function.args, &mut function.block)
+    } else if node.is::<Closure>() {
+      let closure = node.downcast_mut::<Closure>().unwrap();
+
+      self.resolve_function(&mut closure.args, &mut closure.block)
+    } else if node.is::<LoopForIn>() {
+      let lfi = node.downcast_mut::<LoopForIn>().unwrap();
+
+      self.resolve_node(&mut lfi.iterable)?;
+      self.enter(lfi.names(), vec![&mut lfi.block])
+    } else if node.is::<LoopInfinite>() {
+      let li = node.downcast_mut::<LoopInfinite>().unwrap();
+
+      self.enter(vec![], vec![&mut li.block])
+    } else if node.is::<LoopWhile>() {
+      let lw = node.downcast_mut::<LoopWhile>().unwrap();
+
+      self.resolve_node(&mut lw.condition)?;
+      self.enter(vec![], vec![&mut lw.block])
+    } else if node.is::<Match>() {
+      let matching = node.downcast_mut::<Match>().unwrap();
+
+      self.resolve_node(&mut matching.subject)?;
+
+      for (pattern, body) in matching.arms.iter_mut() {
+        self.enter(pattern.names(), vec![body])?;
+      }
+
+      Ok(())
+    } else {
+      for child in Resolver::children(node) {
+        self.resolve_node(child)?;
+      }
+
+      Ok(())
+    }
+  }
+
+  // a call binds its parameters in order, their defaults see the ones
+  // before them
+  fn resolve_function(
+    &mut self,
+    args: &mut Option<Vec<Box<dyn Value>>>,
+    block: &mut Option<Box<dyn Value>>,
+  ) -> ResolverResult<()> {
+    let mut names = vec![];
+    let mut nodes = vec![];
+
+    for arg in args.iter_mut().flatten() {
+      let arg = arg.downcast_mut::<FunctionArg>().unwrap();
+
+      match &arg.pattern {
+        Some(pattern) => names.append(&mut pattern.names()),
+        None => names.push(arg.name.text()),
+      }
+
+      if let Some(default) = arg.default.as_mut() {
+        nodes.push(default);
+      }
+    }
+
+    nodes.extend(block.as_mut());
+
+    self.enter(names, nodes)
+  }
+
+  // a function that is not a native is found before any variable with its
+  // name, those and names brought by a glob import keep being looked up
+  fn resolve_identifier(
+    &mut self,
+    identifier: &mut Identifier,
+  ) -> ResolverResult<()> {
+    let name = identifier.name.to_owned();
+    let function = self.scope.borrow().get_function(&name);
+
+    if self.glob
+      || self.functions.contains(&name)
+      || function.iter().any(|function| !function.is::<Native>())
+    {
+      return Ok(());
+    }
+
+    if let Some(slot) = self.lookup(&name) {
+      identifier.add_slot(slot);
+      return Ok(());
+    }
+
+    match self.is_known(&name) {
+      true => Ok(()),
+      false => Err(format!("cannot find value `{}` in this scope", name)),
+    }
+  }
+
+  fn lookup(&self, name: &str) -> Option<Slot> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .enumerate()
+      .find_map(|(depth, names)| {
+        names.position(name).map(|index| Slot::new(depth, index))
+      })
+  }
+
+  // defined before the program runs, by the standard library, the host or
+  // an earlier line of the repl
+  fn is_known(&self, name: &str) -> bool {
+    let scope = self.scope.borrow();
+
+    self.items.contains(name)
+      || scope.get_variable(name).is_some()
+      || scope.get_function(name).is_some()
+      || scope.get_struct(name).is_some()
+      || scope.get_trait(name).is_some()
+  }
+
+  // the statements a node runs in the scope it is given, a block runs its
+  // own and anything else runs itself
+  fn statements(node: &mut Box<dyn Value>) -> Vec<&mut Box<dyn Value>> {
+    match node.is::<Block>() {
+      true => node
+        .downcast_mut::<Block>()
+        .unwrap()
+        .statements
+        .iter_mut()
+        .collect(),
+      false => vec![node],
+    }
+  }
+
+  // the nodes under `node` that are evaluated, implementations, structs,
+  // traits and modules are left out as they run in scopes of their own
+  fn children(node: &mut Box<dyn Value>) -> Vec<&mut Box<dyn Value>> {
+    if node.is::<Program>() {
+      let program = node.downcast_mut::<Program>().unwrap();
+
+      program.statements.iter_mut().collect()
+    } else if node.is::<Block>() {
+      let block = node.downcast_mut::<Block>().unwrap();
+
+      block.statements.iter_mut().collect()
+    } else if node.is::<Statement>() {
+      vec![&mut node.downcast_mut::<Statement>().unwrap().node]
+    } else if node.is::<Expression>() {
+      vec![&mut node.downcast_mut::<Expression>().unwrap().node]
+    } else if node.is::<Group>() {
+      vec![&mut node.downcast_mut::<Group>().unwrap().expression]
+    } else if node.is::<Public>() {
+      vec![&mut node.downcast_mut::<Public>().unwrap().node]
+    } else if node.is::<Array>() {
+      node
+        .downcast_mut::<Array>()
+        .unwrap()
+        .data
+        .iter_mut()
+        .collect()
+    } else if node.is::<Hash>() {
+      node.downcast_mut::<Hash>().unwrap().values_mut().collect()
+    } else if node.is::<Spread>() {
+      vec![&mut node.downcast_mut::<Spread>().unwrap().value]
+    } else if node.is::<Assign>() {
+      let assign = node.downcast_mut::<Assign>().unwrap();
+
+      vec![&mut assign.target, &mut assign.value]
+    } else if node.is::<Binary>() {
+      let binary = node.downcast_mut::<Binary>().unwrap();
+
+      vec![&mut binary.lhs, &mut binary.rhs]
+    } else if node.is::<Unary>() {
+      vec![&mut node.downcast_mut::<Unary>().unwrap().rhs]
+    } else if node.is::<Index>() {
+      let index = node.downcast_mut::<Index>().unwrap();
+
+      vec![&mut index.lhs, &mut index.rhs]
+    } else if node.is::<Member>() {
+      vec![&mut node.downcast_mut::<Member>().unwrap().object]
+    } else if node.is::<Call>() {
+      let call = node.downcast_mut::<Call>().unwrap();
+      let mut children = vec![&mut call.callee];
+
+      children.extend(call.args.iter_mut().flatten());
+      children
+    } else if node.is::<NamedArg>() {
+      vec![&mut node.downcast_mut::<NamedArg>().unwrap().value]
+    } else if node.is::<If>() {
+      let control = node.downcast_mut::<If>().unwrap();
+      let mut children = vec![&mut control.condition, &mut control.consequence];
+
+      children.extend(control.alternative.as_mut());
+      children
+    } else if node.is::<Val>() {
+      node
+        .downcast_mut::<Val>()
+        .unwrap()
+        .value
+        .iter_mut()
+        .collect()
+    } else if node.is::<Function>() {
+      let function = node.downcast_mut::<Function>().unwrap();
+      let mut children = function
+        .args
+        .iter_mut()
+        .flatten()
+        .collect::<Vec<&mut Box<dyn Value>>>();
+
+      children.extend(function.block.as_mut());
+      children
+    } else if node.is::<Closure>() {
+      let closure = node.downcast_mut::<Closure>().unwrap();
+      let mut children = closure
+        .args
+        .iter_mut()
+        .flatten()
+        .collect::<Vec<&mut Box<dyn Value>>>();
+
+      children.extend(closure.block.as_mut());
+      children
+    } else if node.is::<FunctionArg>() {
+      let arg = node.downcast_mut::<FunctionArg>().unwrap();
+
+      arg.default.iter_mut().collect()
+    } else if node.is::<LoopForIn>() {
+      let lfi = node.downcast_mut::<LoopForIn>().unwrap();
+
+      vec![&mut lfi.iterable, &mut lfi.block]
+    } else if node.is::<LoopInfinite>() {
+      vec![&mut node.downcast_mut::<LoopInfinite>().unwrap().block]
+    } else if node.is::<LoopWhile>() {
+      let lw = node.downcast_mut::<LoopWhile>().unwrap();
+
+      vec![&mut lw.condition, &mut lw.block]
+    } else if node.is::<Match>() {
+      let matching = node.downcast_mut::<Match>().unwrap();
+      let mut children = vec![&mut matching.subject];
+
+      children.extend(matching.arms.iter_mut().map(|(_, body)| body));
+      children
+    } else if node.is::<Return>() {
+      node
+        .downcast_mut::<Return>()
+        .unwrap()
+        .value
+        .iter_mut()
+        .collect()
+    } else if node.is::<Break>() {
+      node
+        .downcast_mut::<Break>()
+        .unwrap()
+        .value
+        .iter_mut()
+        .collect()
+    } else if node.is::<Try>() {
+      vec![&mut node.downcast_mut::<Try>().unwrap().value]
+    } else if node.is::<Outcome>() {
+      vec![&mut node.downcast_mut::<Outcome>().unwrap().value]
+    } else if node.is::<Delete>() {
+      vec![&mut node.downcast_mut::<Delete>().unwrap().target]
+    } else if node.is::<Range>() {
+      let range = node.downcast_mut::<Range>().unwrap();
+      let mut children = vec![&mut range.start, &mut range.end];
+
+      children.extend(range.step.as_mut());
+      children
+    } else if node.is::<Instance>() {
+      let instance = node.downcast_mut::<Instance>().unwrap();
+
+      instance.fields.iter_mut().map(|(_, value)| value).collect()
+    } else {
+      vec![]
+    }
+  }
+}
@@ -0,0 +1,230 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::stdlib::{expect_array, expect_hash, mismatch};
+use crate::value::instruction::array::Array;
+use crate::value::instruction::call::Call;
+use crate::value::instruction::hash::HashKey;
+use crate::value::instruction::native::Native;
+use crate::value::instruction::IKind;
+use crate::value::primitive::{bool::Bool, int::Int};
+use crate::value::{Value, Values};
+
+pub fn natives() -> Vec<Native> {
+  vec![
+    Native::new(
+      "ƒ len(value: _) -> int",
+      len,
+      "function len(value) { \
+        if (typeof value === \"string\") return [...value].length; \
+        if (Array.isArray(value)) return value.length; \
+        return Object.keys(value).length; \
+      }",
+    ),
+    Native::new(
+      "ƒ push<T>(items: [T], item: T) -> [T]",
+      push,
+      "function push(items, item) { return [...items, item]; }",
+    ),
+    Native::new(
+      "ƒ reverse<T>(items: [T]) -> [T]",
+      reverse,
+      "function reverse(items) { return [...items].reverse(); }",
+    ),
+    Native::new(
+      "ƒ sort<T>(items: [T]) -> [T]",
+      sort,
+      "function sort(items) { \
+        return [...items].sort((a, b) => (a < b ? -1 : a > b ? 1 : 0)); \
+      }",
+    ),
+    Native::new(
+      "ƒ contains(value: _, item: _) -> bool",
+      contains,
+      "function contains(value, item) { \
+        if (typeof value === \"string\") return value.includes(item); \
+        if (Array.isArray(value)) { \
+          var found = JSON.stringify(item); \
+          return value.some((x) => JSON.stringify(x) === found); \
+        } \
+        return Object.prototype.hasOwnProperty.call(value, item); \
+      }",
+    ),
+    Native::new(
+      "ƒ map(items: [], f: fn) -> []",
+      map,
+      "function map(items, f) { return items.map((item) => f(item)); }",
+    ),
+    Native::new(
+      "ƒ filter<T>(items: [T], f: fn) -> [T]",
+      filter,
+      "function filter(items, f) { return items.filter((item) => f(item)); }",
+    ),
+    Native::new(
+      "ƒ fold(items: [], init: _, f: fn) -> _",
+      fold,
+      "function fold(items, init, f) { \
+        return items.reduce((acc, item) => f(acc, item), init); \
+      }",
+    ),
+    Native::new(
+      "ƒ keys(entries: hash) -> []",
+      keys,
+      "function keys(entries) { return Object.keys(entries); }",
+    ),
+    Native::new(
+      "ƒ values(entries: hash) -> []",
+      values,
+      "function values(entries) { return Object.values(entries); }",
+    ),
+  ]
+}
+
+fn len(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let len = match args[0].ikind() {
+    IKind::Str(text) => text.chars().count(),
+    IKind::Array(items) => items.len(),
+    IKind::Hash(entries) => entries.len(),
+    _ => {
+      return mismatch("len", "a `str`, an array or a hash", args[0].as_ref())
+    }
+  };
+
+  Ok(Int::new(len as i64).boxed())
+}
+
+fn push(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let mut items = expect_array("push", args[0].as_ref())?;
+
+  items.push(args[1].to_owned());
+
+  Ok(Array::new(items).boxed())
+}
+
+fn reverse(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let mut items = expect_array("reverse", args[0].as_ref())?;
+
+  items.reverse();
+
+  Ok(Array::new(items).boxed())
+}
+
+// only values of the same kind can be sorted
+fn sort(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let mut items = expect_array("sort", args[0].as_ref())?;
+
+  for pair in items.windows(2) {
+    if pair[0].partial_cmp(&pair[1]).is_none() {
+      return Err(format!("cannot compare `{}` with `{}`", pair[0], pair[1]));
+    }
+  }
+
+  items.sort_by(|lhs, rhs| lhs.partial_cmp(rhs).unwrap());
+
+  Ok(Array::new(items).boxed())
+}
+
+// a substring of a string, an item of an array or a key of a hash
+fn contains(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let (value, item) = (&args[0], &args[1]);
+
+  let found = match (value.ikind(), item.ikind()) {
+    (IKind::Str(text), IKind::Str(part)) => text.contains(&part),
+    (IKind::Str(text), IKind::Char(part)) => text.contains(part),
+    (IKind::Array(items), _) => items.contains(item),
+    (IKind::Hash(entries), _) => {
+      entries.contains_key(&HashKey::new(item.as_ref())?)
+    }
+    _ => {
+      return mismatch(
+        "contains",
+        "a `str`, an array or a hash",
+        value.as_ref(),
+      )
+    }
+  };
+
+  Ok(Bool::new(found).boxed())
+}
+
+fn map(
+  interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let items = expect_array("map", args[0].as_ref())?
+    .into_iter()
+    .map(|item| Call::call_function(interpreter, args[1].as_ref(), vec![item]))
+    .collect::<ValueResult<Values>>()?;
+
+  Ok(Array::new(items).boxed())
+}
+
+fn filter(
+  interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let mut items = vec![];
+
+  for item in expect_array("filter", args[0].as_ref())? {
+    if Call::call_function(
+      interpreter,
+      args[1].as_ref(),
+      vec![item.to_owned()],
+    )?
+    .is_truthy()
+    {
+      items.push(item);
+    }
+  }
+
+  Ok(Array::new(items).boxed())
+}
+
+fn fold(
+  interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let mut acc = args[1].to_owned();
+
+  for item in expect_array("fold", args[0].as_ref())? {
+    acc = Call::call_function(interpreter, args[2].as_ref(), vec![acc, item])?;
+  }
+
+  Ok(acc)
+}
+
+fn keys(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let entries = expect_hash("keys", args[0].as_ref())?;
+
+  Ok(
+    Array::new(entries.iter().map(|(key, _)| key.to_value()).collect()).boxed(),
+  )
+}
+
+fn values(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let entries = expect_hash("values", args[0].as_ref())?;
+
+  Ok(
+    Array::new(entries.iter().map(|(_, value)| value.to_owned()).collect())
+      .boxed(),
+  )
+}
@@ -0,0 +1,118 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::stdlib::show;
+use crate::value::instruction::native::Native;
+use crate::value::instruction::IKind;
+use crate::value::primitive::{float::Float, int::Int, str::Str};
+use crate::value::{Value, Values};
+use crate::void;
+
+pub fn natives() -> Vec<Native> {
+  vec![
+    Native::new(
+      "ƒ to_str(value: _) -> str",
+      to_str,
+      "function to_str(value) { \
+        return typeof value === \"string\" ? value : JSON.stringify(value); \
+      }",
+    ),
+    Native::new(
+      "ƒ to_int(value: _) -> int?",
+      to_int,
+      "function to_int(value) { \
+        if (typeof value === \"string\") { \
+          var digits = /^\\s*[+-]?\\d+\\s*$/.test(value); \
+          return digits ? parseInt(value, 10) : null; \
+        } \
+        return typeof value === \"number\" ? Math.trunc(value) : null; \
+      }",
+    ),
+    Native::new(
+      "ƒ to_float(value: _) -> float?",
+      to_float,
+      "function to_float(value) { \
+        var number = typeof value === \"string\" ? Number(value) : value; \
+        return typeof number === \"number\" && !Number.isNaN(number) \
+          ? number \
+          : null; \
+      }",
+    ),
+    Native::new(
+      "ƒ type_of(value: _) -> str",
+      type_of,
+      "function type_of(value) { \
+        if (value === null) return \"nil\"; \
+        if (Array.isArray(value)) return \"array\"; \
+        switch (typeof value) { \
+          case \"boolean\": return \"bool\"; \
+          case \"function\": return \"fn\"; \
+          case \"number\": \
+            return Number.isInteger(value) ? \"int\" : \"float\"; \
+          case \"string\": return \"str\"; \
+        } \
+        if (value.constructor !== Object) return value.constructor.name; \
+        return \"ok\" in value && \"value\" in value ? \"result\" : \"hash\"; \
+      }",
+    ),
+  ]
+}
+
+fn to_str(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Str::new(&show(args[0].as_ref())).boxed())
+}
+
+// `nil` when the value has no integer reading, floats are truncated
+fn to_int(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  match args[0].ikind() {
+    IKind::Int(value) => Ok(Int::new(value).boxed()),
+    IKind::Float(value) => Ok(Int::new(value.trunc() as i64).boxed()),
+    IKind::Str(value) => match value.trim().parse::<i64>() {
+      Ok(value) => Ok(Int::new(value).boxed()),
+      Err(_) => Ok(void!()),
+    },
+    _ => Ok(void!()),
+  }
+}
+
+fn to_float(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  match args[0].ikind() {
+    IKind::Int(value) => Ok(Float::new(value as f64).boxed()),
+    IKind::Float(value) => Ok(Float::new(value).boxed()),
+    IKind::Str(value) => match value.trim().parse::<f64>() {
+      Ok(value) => Ok(Float::new(value).boxed()),
+      Err(_) => Ok(void!()),
+    },
+    _ => Ok(void!()),
+  }
+}
+
+fn type_of(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let name = match args[0].ikind() {
+    IKind::Array(_) => "array".into(),
+    IKind::Bool(_) => "bool".into(),
+    IKind::Char(_) => "char".into(),
+    IKind::Closure | IKind::Function(..) | IKind::Native => "fn".into(),
+    IKind::Float(_) => "float".into(),
+    IKind::Hash(_) => "hash".into(),
+    IKind::Instance(name, _) => name,
+    IKind::Int(_) => "int".into(),
+    IKind::Nil => "nil".into(),
+    IKind::Outcome(..) => "result".into(),
+    IKind::Range => "range".into(),
+    IKind::Str(_) => "str".into(),
+    _ => return Err(format!("`{}` is not a value", args[0])),
+  };
+
+  Ok(Str::new(name.as_str()).boxed())
+}
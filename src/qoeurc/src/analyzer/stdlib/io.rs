@@ -0,0 +1,102 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::stdlib::{expect_array, expect_str, show};
+use crate::value::instruction::native::Native;
+use crate::value::primitive::{float::Float, str::Str};
+use crate::value::{Value, Values};
+use crate::void;
+
+pub fn natives() -> Vec<Native> {
+  vec![
+    Native::new(
+      "ƒ print(..values: _) -> nil",
+      print,
+      "function print(...values) { \
+        console.log(values.map((value) => typeof value === \"string\" \
+          ? value \
+          : value === null ? \"nil\" : JSON.stringify(value)).join(\" \")); \
+        return null; \
+      }",
+    ),
+    Native::new(
+      "ƒ eprint(..values: _) -> nil",
+      eprint,
+      "function eprint(...values) { \
+        console.error(values.map((value) => typeof value === \"string\" \
+          ? value \
+          : value === null ? \"nil\" : JSON.stringify(value)).join(\" \")); \
+        return null; \
+      }",
+    ),
+    Native::new(
+      r#"ƒ input(prompt: str = "") -> str?"#,
+      input,
+      "function input(prompt = \"\") { \
+        var fs = require(\"fs\"), byte = Buffer.alloc(1), bytes = []; \
+        process.stdout.write(prompt); \
+        while (fs.readSync(0, byte, 0, 1) === 1 && byte[0] !== 10) { \
+          bytes.push(byte[0]); \
+        } \
+        var line = Buffer.from(bytes).toString().replace(/\\r$/, \"\"); \
+        return bytes.length === 0 && line === \"\" ? null : line; \
+      }",
+    ),
+    Native::new(
+      "ƒ now() -> float",
+      now,
+      "function now() { return Date.now() / 1000; }",
+    ),
+  ]
+}
+
+fn line(function: &str, values: &dyn Value) -> ValueResult<String> {
+  Ok(
+    expect_array(function, values)?
+      .iter()
+      .map(|value| show(value.as_ref()))
+      .collect::<Vec<String>>()
+      .join(" "),
+  )
+}
+
+fn print(
+  interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let line = line("print", args[0].as_ref())?;
+
+  interpreter.host.borrow_mut().stdout(&format!("{}\n", line));
+  Ok(void!())
+}
+
+fn eprint(
+  interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let line = line("eprint", args[0].as_ref())?;
+
+  interpreter.host.borrow_mut().stderr(&format!("{}\n", line));
+  Ok(void!())
+}
+
+// `nil` once the input is over
+fn input(
+  interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let prompt = expect_str("input", args[0].as_ref())?;
+  let mut host = interpreter.host.borrow_mut();
+
+  host.stdout(&prompt);
+
+  match host.stdin() {
+    Some(line) => Ok(Str::new(&line).boxed()),
+    None => Ok(void!()),
+  }
+}
+
+fn now(
+  interpreter: &mut Interpreter,
+  _args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Float::new(interpreter.host.borrow_mut().now()).boxed())
+}
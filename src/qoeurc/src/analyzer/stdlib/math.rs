@@ -0,0 +1,135 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::stdlib::{expect_float, mismatch};
+use crate::value::instruction::native::Native;
+use crate::value::instruction::IKind;
+use crate::value::primitive::{float::Float, int::Int};
+use crate::value::{Value, Values};
+
+pub fn natives() -> Vec<Native> {
+  vec![
+    Native::new(
+      "ƒ abs<T>(value: T) -> T",
+      abs,
+      "function abs(value) { return Math.abs(value); }",
+    ),
+    Native::new(
+      "ƒ min<T>(lhs: T, rhs: T) -> T",
+      min,
+      "function min(lhs, rhs) { return rhs < lhs ? rhs : lhs; }",
+    ),
+    Native::new(
+      "ƒ max<T>(lhs: T, rhs: T) -> T",
+      max,
+      "function max(lhs, rhs) { return rhs > lhs ? rhs : lhs; }",
+    ),
+    Native::new(
+      "ƒ sqrt(value: float) -> float",
+      sqrt,
+      "function sqrt(value) { return Math.sqrt(value); }",
+    ),
+    Native::new(
+      "ƒ pow(base: float, exponent: float) -> float",
+      pow,
+      "function pow(base, exponent) { return Math.pow(base, exponent); }",
+    ),
+    Native::new(
+      "ƒ floor(value: float) -> int",
+      floor,
+      "function floor(value) { return Math.floor(value); }",
+    ),
+    Native::new(
+      "ƒ ceil(value: float) -> int",
+      ceil,
+      "function ceil(value) { return Math.ceil(value); }",
+    ),
+    Native::new(
+      "ƒ round(value: float) -> int",
+      round,
+      "function round(value) { return Math.round(value); }",
+    ),
+    Native::new(
+      "ƒ random() -> float",
+      random,
+      "function random() { return Math.random(); }",
+    ),
+  ]
+}
+
+fn abs(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  match args[0].ikind() {
+    IKind::Int(value) => Ok(Int::new(value.abs()).boxed()),
+    IKind::Float(value) => Ok(Float::new(value.abs()).boxed()),
+    _ => mismatch("abs", "an `int` or a `float`", args[0].as_ref()),
+  }
+}
+
+fn min(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  match args[0].partial_cmp(&args[1]) {
+    Some(ordering) if ordering.is_gt() => Ok(args[1].to_owned()),
+    Some(_) => Ok(args[0].to_owned()),
+    None => Err(format!("cannot compare `{}` with `{}`", args[0], args[1])),
+  }
+}
+
+fn max(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  match args[0].partial_cmp(&args[1]) {
+    Some(ordering) if ordering.is_lt() => Ok(args[1].to_owned()),
+    Some(_) => Ok(args[0].to_owned()),
+    None => Err(format!("cannot compare `{}` with `{}`", args[0], args[1])),
+  }
+}
+
+fn sqrt(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Float::new(expect_float("sqrt", args[0].as_ref())?.sqrt()).boxed())
+}
+
+fn pow(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let base = expect_float("pow", args[0].as_ref())?;
+  let exponent = expect_float("pow", args[1].as_ref())?;
+
+  Ok(Float::new(base.powf(exponent)).boxed())
+}
+
+fn floor(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Int::new(expect_float("floor", args[0].as_ref())?.floor() as i64).boxed())
+}
+
+fn ceil(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Int::new(expect_float("ceil", args[0].as_ref())?.ceil() as i64).boxed())
+}
+
+fn round(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Int::new(expect_float("round", args[0].as_ref())?.round() as i64).boxed())
+}
+
+// a float in `[0, 1)` from the host
+fn random(
+  interpreter: &mut Interpreter,
+  _args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  Ok(Float::new(interpreter.host.borrow_mut().random()).boxed())
+}
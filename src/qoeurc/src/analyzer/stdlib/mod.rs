@@ -0,0 +1,75 @@
+pub mod collection;
+pub mod convert;
+pub mod io;
+pub mod math;
+pub mod string;
+
+use crate::analyzer::interpreter::ValueResult;
+use crate::value::instruction::hash::Entries;
+use crate::value::instruction::native::Native;
+use crate::value::instruction::IKind;
+use crate::value::{Value, Values};
+
+// the functions every interpreter knows without importing them
+pub fn natives() -> Vec<Native> {
+  [
+    io::natives(),
+    string::natives(),
+    collection::natives(),
+    math::natives(),
+    convert::natives(),
+  ]
+  .concat()
+}
+
+// how a value is printed, strings and chars without their quotes
+pub fn show(value: &dyn Value) -> String {
+  match value.ikind() {
+    IKind::Str(value) => value,
+    IKind::Char(value) => value.to_string(),
+    _ => value.text(),
+  }
+}
+
+// the checker cannot see through `_` parameters, so natives check the kind of
+// what they are given themselves
+fn mismatch<T>(
+  function: &str,
+  expected: &str,
+  found: &dyn Value,
+) -> ValueResult<T> {
+  Err(format!(
+    "`{}` expects {}, found `{}`",
+    function,
+    expected,
+    found.text()
+  ))
+}
+
+fn expect_array(function: &str, value: &dyn Value) -> ValueResult<Values> {
+  match value.ikind() {
+    IKind::Array(items) => Ok(items),
+    _ => mismatch(function, "an array", value),
+  }
+}
+
+fn expect_float(function: &str, value: &dyn Value) -> ValueResult<f64> {
+  match value.ikind() {
+    IKind::Float(value) => Ok(value),
+    _ => mismatch(function, "a `float`", value),
+  }
+}
+
+fn expect_hash(function: &str, value: &dyn Value) -> ValueResult<Entries> {
+  match value.ikind() {
+    IKind::Hash(entries) => Ok(entries),
+    _ => mismatch(function, "a hash", value),
+  }
+}
+
+fn expect_str(function: &str, value: &dyn Value) -> ValueResult<String> {
+  match value.ikind() {
+    IKind::Str(value) => Ok(value),
+    _ => mismatch(function, "a `str`", value),
+  }
+}
@@ -0,0 +1,156 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::stdlib::{expect_array, expect_str};
+use crate::value::instruction::array::Array;
+use crate::value::instruction::native::Native;
+use crate::value::primitive::{bool::Bool, char::Char, str::Str};
+use crate::value::{Value, Values};
+
+pub fn natives() -> Vec<Native> {
+  vec![
+    Native::new(
+      "ƒ upper(text: str) -> str",
+      upper,
+      "function upper(text) { return text.toUpperCase(); }",
+    ),
+    Native::new(
+      "ƒ lower(text: str) -> str",
+      lower,
+      "function lower(text) { return text.toLowerCase(); }",
+    ),
+    Native::new(
+      "ƒ trim(text: str) -> str",
+      trim,
+      "function trim(text) { return text.trim(); }",
+    ),
+    Native::new(
+      "ƒ split(text: str, separator: str) -> [str]",
+      split,
+      "function split(text, separator) { return text.split(separator); }",
+    ),
+    Native::new(
+      r#"ƒ join(items: [str], separator: str = "") -> str"#,
+      join,
+      "function join(items, separator = \"\") { \
+        return items.join(separator); \
+      }",
+    ),
+    Native::new(
+      "ƒ replace(text: str, from: str, to: str) -> str",
+      replace,
+      "function replace(text, from, to) { return text.split(from).join(to); }",
+    ),
+    Native::new(
+      "ƒ chars(text: str) -> [char]",
+      chars,
+      "function chars(text) { return [...text]; }",
+    ),
+    Native::new(
+      "ƒ starts_with(text: str, prefix: str) -> bool",
+      starts_with,
+      "function starts_with(text, prefix) { return text.startsWith(prefix); }",
+    ),
+    Native::new(
+      "ƒ ends_with(text: str, suffix: str) -> bool",
+      ends_with,
+      "function ends_with(text, suffix) { return text.endsWith(suffix); }",
+    ),
+  ]
+}
+
+fn upper(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("upper", args[0].as_ref())?;
+
+  Ok(Str::new(&text.to_uppercase()).boxed())
+}
+
+fn lower(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("lower", args[0].as_ref())?;
+
+  Ok(Str::new(&text.to_lowercase()).boxed())
+}
+
+fn trim(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("trim", args[0].as_ref())?;
+
+  Ok(Str::new(text.trim()).boxed())
+}
+
+fn split(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("split", args[0].as_ref())?;
+  let separator = expect_str("split", args[1].as_ref())?;
+
+  Ok(
+    Array::new(
+      text
+        .split(separator.as_str())
+        .map(|part| Str::new(part).boxed())
+        .collect(),
+    )
+    .boxed(),
+  )
+}
+
+fn join(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let separator = expect_str("join", args[1].as_ref())?;
+  let items = expect_array("join", args[0].as_ref())?
+    .iter()
+    .map(|item| expect_str("join", item.as_ref()))
+    .collect::<ValueResult<Vec<String>>>()?;
+
+  Ok(Str::new(&items.join(&separator)).boxed())
+}
+
+fn replace(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("replace", args[0].as_ref())?;
+  let from = expect_str("replace", args[1].as_ref())?;
+  let to = expect_str("replace", args[2].as_ref())?;
+
+  Ok(Str::new(&text.replace(&from, &to)).boxed())
+}
+
+fn chars(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("chars", args[0].as_ref())?;
+
+  Ok(Array::new(text.chars().map(|c| Char::new(c).boxed()).collect()).boxed())
+}
+
+fn starts_with(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("starts_with", args[0].as_ref())?;
+  let prefix = expect_str("starts_with", args[1].as_ref())?;
+
+  Ok(Bool::new(text.starts_with(&prefix)).boxed())
+}
+
+fn ends_with(
+  _interpreter: &mut Interpreter,
+  args: Values,
+) -> ValueResult<Box<dyn Value>> {
+  let text = expect_str("ends_with", args[0].as_ref())?;
+  let suffix = expect_str("ends_with", args[1].as_ref())?;
+
+  Ok(Bool::new(text.ends_with(&suffix)).boxed())
+}
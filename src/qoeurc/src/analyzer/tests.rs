@@ -3,7 +3,1211 @@ use super::*;
 extern crate qutonium;
 use qutonium::prelude::*;
 
+use crate::compiler::vmify;
+use crate::testing::suite;
+
+// every program of the suites also runs on the bytecode machine, which has
+// to give the same value or error as the interpreter
+fn eval(input: &str) -> Result<String, String> {
+  let value = evalify(input, &mut Interpreter::new()).map(|value| value.text());
+  let compiled =
+    vmify(input, &mut Interpreter::new()).map(|value| value.text());
+
+  assert_eq!(compiled, value, "the machine disagrees on `{}`", input);
+  value
+}
+
 #[test]
 fn from_test() {
-  suite!("qoeurc::analyzer", { "test empty input" || { Ok(()) } });
+  suite!("qoeurc::analyzer", {
+    "test empty input" || { must!(true; be.truthy) }
+
+    "test integers that do not fit are errors" || {
+      must!(
+        eval("9223372036854775807 + 1;");
+        eq Err("`9223372036854775807 + 1` overflows".into())
+      )?;
+      must!(
+        eval("val a := -9223372036854775807 - 1; -a;");
+        eq Err("`-` overflows on `-9223372036854775808`".into())
+      )?;
+      must!(eval("val a := 0; 1 / a;"); eq Err("cannot divide `1` by zero".into()))
+    }
+  });
+}
+
+#[test]
+fn stdlib_test() {
+  suite!("qoeurc::analyzer::stdlib", {
+    "test natives are in every interpreter" || {
+      must!(eval("[len([1, 2]), upper(\"a\")];"); eq Ok("[2, \"A\"]".into()))
+    }
+
+    "test natives take named and default arguments" || {
+      must!(eval("join([\"a\", \"b\"]);"); eq Ok("\"ab\"".into()))?;
+      must!(
+        eval("join([\"a\", \"b\"], separator: \"-\");");
+        eq Ok("\"a-b\"".into())
+      )
+    }
+
+    "test natives are checked against their signature" || {
+      must!(eval("upper(1);").is_err(); be.truthy)?;
+      must!(eval("push([1], \"a\");").is_err(); be.truthy)
+    }
+
+    "test programs can shadow natives" || {
+      let input = "ƒ len(a: int) -> int { return a; }
+        val upper := 1;
+        [len(2), upper];";
+
+      must!(eval(input); eq Ok("[2, 1]".into()))
+    }
+
+    "test natives call back into the program" || {
+      let input = "map([1, 2], |x: int| { x * 10 });";
+
+      must!(eval(input); eq Ok("[10, 20]".into()))
+    }
+  });
+}
+
+#[test]
+fn host_test() {
+  use crate::value::convert::{FromValue, IntoValue};
+
+  use std::cell::Cell;
+  use std::collections::HashMap;
+  use std::rc::Rc;
+
+  suite!("qoeurc::analyzer::host", {
+    "test host functions are called with rust values" || {
+      let mut interpreter = Interpreter::new();
+
+      interpreter
+        .add_function("ƒ add(a: int, b: int) -> int", |a: i64, b: i64| {
+          Ok(a + b)
+        })
+        .unwrap();
+
+      let value = evalify("add(1, b: 2);", &mut interpreter).unwrap();
+
+      must!(i64::from_value(value.as_ref()); eq Ok(3))
+    }
+
+    "test host functions keep their state" || {
+      let mut interpreter = Interpreter::new();
+      let calls = Rc::new(Cell::new(0));
+      let counter = calls.to_owned();
+
+      interpreter
+        .add_function("ƒ tick() -> nil", move || {
+          counter.set(counter.get() + 1);
+          Ok(())
+        })
+        .unwrap();
+
+      evalify("tick(); tick();", &mut interpreter).unwrap();
+
+      must!(calls.get(); eq 2)
+    }
+
+    "test host constants" || {
+      let mut interpreter = Interpreter::new();
+      let mut limits = HashMap::new();
+
+      limits.insert("max".to_string(), 10i64);
+
+      interpreter
+        .add_constant("limits", limits)
+        .unwrap()
+        .add_constant("names", vec!["a", "b"])
+        .unwrap();
+
+      let value = evalify("[limits, names];", &mut interpreter).unwrap();
+
+      must!(value.text(); eq "[{ max: 10 }, [\"a\", \"b\"]]".to_string())
+    }
+
+    "test argument type errors are runtime errors" || {
+      let mut interpreter = Interpreter::new();
+
+      interpreter
+        .add_function("ƒ twice(value: _) -> int", |value: i64| Ok(value * 2))
+        .unwrap();
+
+      must!(
+        evalify("twice(\"a\");", &mut interpreter);
+        eq Err(
+          "mismatched types for the argument 1 of `twice`: expected `int`, \
+           found `\"a\"`"
+            .into()
+        )
+      )
+    }
+
+    "test signatures must match the function" || {
+      let mut interpreter = Interpreter::new();
+      let added = interpreter.add_function("ƒ f(a: int) -> int", || Ok(1));
+
+      must!(added.is_err(); be.truthy)
+    }
+
+    "test conversions" || {
+      let mut scores = HashMap::new();
+
+      scores.insert("a".to_string(), vec![1.5]);
+
+      let value = scores.to_owned().into_value();
+
+      must!(HashMap::<String, Vec<f64>>::from_value(value.as_ref()); eq Ok(scores))?;
+      let floats = vec![1.5].into_value();
+
+      must!(Vec::<i64>::from_value(floats.as_ref()).is_err(); be.truthy)?;
+      must!(Option::<i64>::from_value(None::<i64>.into_value().as_ref()); eq Ok(None))
+    }
+  });
+}
+
+#[test]
+fn io_test() {
+  use crate::analyzer::environment::host::{BufferHost, CaptureHost};
+
+  suite!("qoeurc::analyzer::io", {
+    "test output goes through the host" || {
+      let host = CaptureHost::new().shared();
+      let mut interpreter = Interpreter::new();
+
+      interpreter.add_host(host.to_owned());
+      evalify("print(\"a\", 1); eprint([2]);", &mut interpreter).unwrap();
+
+      let host = host.borrow();
+
+      must!(host.stdout_lines(); eq vec!["a 1"])?;
+      must!(host.stderr_lines(); eq vec!["[2]"])
+    }
+
+    "test functions called by natives share the host" || {
+      let host = CaptureHost::new().shared();
+      let mut interpreter = Interpreter::new();
+
+      interpreter.add_host(host.to_owned());
+      evalify("map([1, 2], |x: int| { print(x); x });", &mut interpreter)
+        .unwrap();
+
+      let host = host.borrow();
+
+      must!(host.stdout_lines(); eq vec!["1", "2"])
+    }
+
+    "test input reads lines until the end" || {
+      let host = CaptureHost::new().add_stdin(&["Ada"]).shared();
+      let mut interpreter = Interpreter::new();
+
+      interpreter.add_host(host.to_owned());
+
+      let value =
+        evalify("[input(\"name: \"), input()];", &mut interpreter).unwrap();
+
+      let host = host.borrow();
+
+      must!(value.text(); eq "[\"Ada\", nil]".to_string())?;
+      must!(host.stdout.as_str(); eq "name: ")
+    }
+
+    "test clock and randomness come from the host" || {
+      let seeded = |seed| {
+        let host = CaptureHost::new().add_clock(12.5).add_seed(seed).shared();
+        let mut interpreter = Interpreter::new();
+
+        interpreter.add_host(host);
+        evalify("[now(), random(), random()];", &mut interpreter)
+          .unwrap()
+          .text()
+      };
+
+      must!(seeded(1).starts_with("[12.5, "); be.truthy)?;
+      must!(seeded(1); eq seeded(1))?;
+      must!(seeded(1) != seeded(2); be.truthy)
+    }
+
+    "test buffers are read back after the evaluation" || {
+      let host = BufferHost::new(0);
+      let mut interpreter = Interpreter::new();
+
+      host.push_stdin("b\n");
+      interpreter.add_host(host.into_host());
+      evalify("print(input());", &mut interpreter).unwrap();
+
+      must!(host.take_stdout(); eq "b\n".to_string())?;
+      must!(host.take_stdout(); eq String::new())
+    }
+  });
+}
+
+#[test]
+fn limits_test() {
+  use crate::analyzer::environment::meter::Limits;
+
+  fn eval(input: &str, limits: Limits) -> Result<String, String> {
+    let mut interpreter = Interpreter::new();
+
+    interpreter.add_limits(limits);
+    evalify(input, &mut interpreter).map(|value| value.text())
+  }
+
+  suite!("qoeurc::analyzer::limits", {
+    "test endless loops run out of fuel" || {
+      must!(
+        eval("loop {}", Limits::new().add_fuel(1000));
+        eq Err("out of fuel after 1000 steps".into())
+      )
+    }
+
+    "test recursion is bounded" || {
+      let input = "ƒ f(n: int) -> int { return f(n + 1); } f(0);";
+
+      must!(
+        eval(input, Limits::new().add_depth(32));
+        eq Err("maximum call depth of 32 exceeded".into())
+      )?;
+      must!(
+        eval(input, Limits::new());
+        eq Err("maximum call depth of 256 exceeded".into())
+      )
+    }
+
+    "test the depth is given back when calls return" || {
+      let input = "ƒ f() -> int { return 1; } for 0..100 |i| { f(); } f();";
+
+      must!(eval(input, Limits::new().add_depth(1)); eq Ok("1".into()))
+    }
+
+    "test allocations are capped" || {
+      let input = "ƒ grow(s: str, n: int) -> str { \
+                     if n == 0 { return s; } \
+                     return grow(s + s, n - 1); \
+                   } \
+                   grow(\"ab\", 20);";
+
+      must!(
+        eval(input, Limits::new().add_memory(1024));
+        eq Err("memory limit of 1024 bytes exceeded".into())
+      )
+    }
+
+    "test programs within their limits are untouched" || {
+      let limits = Limits::new().add_fuel(100).add_memory(1024);
+
+      must!(eval("map([1, 2], |x: int| { x });", limits).is_ok(); be.truthy)?;
+      must!(eval("upper(\"a\");", limits); eq Ok("\"A\"".into()))
+    }
+  });
+}
+
+#[test]
+fn resolver_test() {
+  use crate::analyzer::environment::scope::Scope;
+  use crate::analyzer::environment::slots::Slot;
+  use crate::value::primitive::int::Int;
+
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  suite!("qoeurc::analyzer::resolver", {
+    "test variables are found in the scope that defines them" || {
+      let input = "val a := 1;
+        ƒ f(b: int) -> int {
+          val a := 10;
+          val g := |c: int| { a + b + c };
+          return g(100);
+        }
+        [f(1000), a];";
+
+      must!(eval(input); eq Ok("[1110, 1]".into()))?;
+      must!(
+        eval("val a := [0]; if a[0] == 0 { val b := 2; a[0] = b; } a[0];");
+        eq Ok("2".into())
+      )
+    }
+
+    "test loops and match arms bind in a scope of their own" || {
+      let input = "val a := [0];
+        for 1..=3 |i| {
+          val n := match i { 2 => i * 10, x => x };
+          a[0] = a[0] + n;
+        }
+        a[0];";
+
+      must!(eval(input); eq Ok("24".into()))
+    }
+
+    "test names defined later are seen by functions" || {
+      let input = "ƒ f() -> int { return g() + n; } ƒ g() -> int { 1; }
+        val n := 2;
+        f();";
+
+      must!(eval(input); eq Ok("3".into()))
+    }
+
+    "test undefined names are reported before running" || {
+      must!(
+        eval("print(1); ƒ f() -> int { return b; }");
+        eq Err("cannot find value `b` in this scope".into())
+      )?;
+      must!(
+        eval("for 0..2 |i| { i; } i;");
+        eq Err("cannot find value `i` in this scope".into())
+      )
+    }
+
+    "test duplicate names are reported before running" || {
+      must!(
+        eval("print(1); val a := 1; val a := 2;");
+        eq Err("the name `a` is defined multiple times".into())
+      )?;
+      must!(
+        eval("val a := 1; if a == 1 { val b := 1; } else { val b := 2; } a;");
+        eq Ok("1".into())
+      )
+    }
+
+    "test names from earlier runs are known" || {
+      let mut interpreter = Interpreter::new();
+
+      evalify("val a := 1;", &mut interpreter).unwrap();
+
+      must!(
+        evalify("val b := a + 1; b;", &mut interpreter).map(|b| b.text());
+        eq Ok("2".into())
+      )
+    }
+
+    "test a slot only holds for the name it was resolved with" || {
+      let outer = Rc::new(RefCell::new(Scope::new()));
+      let scope = Scope::new_with_outer(outer.to_owned());
+
+      outer.borrow_mut().bind("a", Int::new(1).boxed());
+
+      must!(scope.get_slot(Slot::new(1, 0), "a").is_some(); be.truthy)?;
+      must!(scope.get_slot(Slot::new(1, 0), "b").is_none(); be.truthy)?;
+      must!(scope.get_slot(Slot::new(2, 0), "a").is_none(); be.truthy)
+    }
+  });
+}
+
+#[test]
+fn for_test() {
+  suite!("qoeurc::analyzer::for", {
+    "test arrays yield their elements" || {
+      let input = "val a := [0]; for [1, 2, 3] |x| { a[0] = a[0] + x; } a[0];";
+
+      must!(eval(input); eq Ok("6".into()))
+    }
+
+    "test hashes yield their entries in order" || {
+      let input = "val s := [\"\"];
+        for { \"a\": 1, \"b\": 2 } |k, v| { s[0] = s[0] + k; }
+        s[0];";
+
+      must!(eval(input); eq Ok("\"ab\"".into()))
+    }
+
+    "test strings yield their chars" || {
+      let input = "val a := [[]]; for \"ab\" |c| { a[0] = [..a[0], c]; } a[0];";
+
+      must!(eval(input); eq Ok("[a, b]".into()))
+    }
+
+    "test ranges yield their integers" || {
+      let sum = |range: &str| {
+        eval(&format!(
+          "val n := [0]; for {} |i| {{ n[0] = n[0] + i; }} n[0];",
+          range
+        ))
+      };
+
+      must!(sum("0..4"); eq Ok("6".into()))?;
+      must!(sum("1..=3"); eq Ok("6".into()))?;
+      must!(sum("0..10 step 3"); eq Ok("18".into()))
+    }
+
+    "test ranges are walked as they go" || {
+      let input = "val n := [0];
+        for 0..9223372036854775807 |i| { if i == 2 { break; } n[0] = i; }
+        for 9223372036854775806..=9223372036854775807 |i| { n[0] = n[0] + 1; }
+        n[0];";
+
+      must!(eval(input); eq Ok("3".into()))
+    }
+
+    "test a loop is worth nil" || {
+      must!(eval("for 0..3 |i| { };"); eq Ok("nil".into()))?;
+      must!(eval("for 0..3 |i| { break; };"); eq Ok("nil".into()))
+    }
+
+    "test what cannot be iterated" || {
+      must!(eval("for 1 |i| { };"); eq Err("`1` is not iterable".into()))?;
+      must!(
+        eval("for [1, 2] |a, b| { };");
+        eq Err("cannot bind `1` to 2 loop variables".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn while_test() {
+  suite!("qoeurc::analyzer::while", {
+    "test a loop runs while its condition holds" || {
+      let input = "val n := [0]; while n[0] < 5 { n[0] = n[0] + 1; } n[0];";
+
+      must!(eval(input); eq Ok("5".into()))
+    }
+
+    "test a loop is worth nil" || {
+      must!(eval("val x := while false { }; x;"); eq Ok("nil".into()))?;
+      must!(eval("while true { break; }"); eq Ok("nil".into()))
+    }
+
+    "test a break cannot give a value" || {
+      must!(
+        eval("while true { break 5; }");
+        eq Err("`break` with value from a `while` loop".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn trait_test() {
+  suite!("qoeurc::analyzer::trait", {
+    "test methods of an implementation are called on instances" || {
+      let input = "trait Shape { ƒ area(self) -> int; }
+        struct Square { side: int }
+        impl Shape for Square { ƒ area(self) -> int { self.side * self.side } }
+        Square { side: 3 }.area();";
+
+      must!(eval(input); eq Ok("9".into()))
+    }
+
+    "test operators on structs go through their trait" || {
+      let input = "struct Point { x: int, y: int }
+        impl Add for Point {
+          ƒ add(self, rhs: Self) -> Self {
+            Point { x: self.x + rhs.x, y: self.y + rhs.y }
+          }
+        }
+        impl Eq for Point { ƒ eq(self, rhs: Self) -> bool { self.x == rhs.x } }
+        val p := Point { x: 1, y: 2 } + Point { x: 3, y: 4 };
+        [p.x, p.y, p == Point { x: 4, y: 0 }, p != Point { x: 4, y: 0 }];";
+
+      must!(eval(input); eq Ok("[4, 6, true, false]".into()))?;
+      must!(
+        eval("struct P { x: int } P { x: 1 } + P { x: 2 };");
+        eq Err("cannot use `+` on `P`, the trait `Add` is not implemented".into())
+      )
+    }
+
+    "test display is used to print a struct" || {
+      let input = "struct Point { x: int, y: int }
+        impl Display for Point {
+          ƒ fmt(self) -> str { \"(\" + to_str(self.x) + \")\" }
+        }
+        [to_str(Point { x: 1, y: 2 }), Point { x: 1, y: 2 }];";
+
+      must!(eval(input); eq Ok("[\"(1)\", (1)]".into()))
+    }
+
+    "test implementations must match their trait" || {
+      let check = |methods: &str| {
+        eval(&format!(
+          "trait Shape {{ ƒ area(self) -> int; }}
+          struct Square {{ side: int }}
+          impl Shape for Square {{ {} }}",
+          methods
+        ))
+      };
+
+      must!(
+        check("");
+        eq Err("not all trait items implemented, missing `area` of trait `Shape` for `Square`".into())
+      )?;
+      must!(
+        check("ƒ area(self) -> int { 1 } ƒ side(self) -> int { 1 }");
+        eq Err("method `side` is not a member of trait `Shape`".into())
+      )?;
+      must!(
+        check("ƒ area(self, n: int) -> int { n }");
+        eq Err("method `area` has 2 parameters but the declaration in trait `Shape` has 1".into())
+      )?;
+      must!(
+        check("ƒ area(self) -> str { \"a\" }");
+        eq Err("method `area` has an incompatible type for trait `Shape`: expected `int`, found `str`".into())
+      )
+    }
+
+    "test traits must exist and be implemented once" || {
+      must!(
+        eval("struct S { } impl Shape for S { }");
+        eq Err("cannot find trait `Shape` in this scope".into())
+      )?;
+      must!(
+        eval("struct S { } impl Eq for S { ƒ eq(self, rhs: Self) -> bool { true } }
+          impl Eq for S { ƒ eq(self, rhs: Self) -> bool { false } }");
+        eq Err("conflicting implementations of trait `Eq` for type `S`".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn generic_test() {
+  suite!("qoeurc::analyzer::generic", {
+    "test a generic function takes any type" || {
+      let input = "ƒ first<T>(xs: [T]) -> T { return xs[0]; }
+        val a: int = first([1, 2]);
+        val b: str = first([\"b\"]);
+        [a, b];";
+
+      must!(eval(input); eq Ok("[1, \"b\"]".into()))
+    }
+
+    "test a generic struct holds any type" || {
+      let input = "struct Wrap<T> { value: T }
+        ƒ unwrap<T>(w: Wrap<T>) -> T { return w.value; }
+        val w: Wrap<str> = Wrap { value: \"x\" };
+        unwrap(w);";
+
+      must!(eval(input); eq Ok("\"x\"".into()))
+    }
+
+    "test type parameters are inferred at the call" || {
+      must!(
+        eval("ƒ id<T>(x: T) -> T { return x; } val s: str = id(1);");
+        eq Err("mismatched types for `s`: expected `str`, found `int`".into())
+      )?;
+      must!(
+        eval("ƒ pair<T>(a: T, b: T) -> [T] { return [a, b]; } pair(1, \"a\");");
+        eq Err("mismatched types in call to `pair`: expected `int`, found `str`".into())
+      )?;
+      must!(
+        eval("struct Wrap<T> { value: T } val w: Wrap<int> = Wrap { value: \"x\" };");
+        eq Err("mismatched types for `w`: expected `Wrap<int>`, found `Wrap<str>`".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn module_test() {
+  use std::fs;
+  use std::path::PathBuf;
+
+  // modules are files next to the one being run
+  fn dir() -> PathBuf {
+    std::env::temp_dir().join("qoeurc-module-test")
+  }
+
+  fn run(input: &str) -> Result<String, String> {
+    let mut interpreter = Interpreter::new();
+
+    interpreter.add_path(&dir().join("main.q"));
+    evalify(input, &mut interpreter).map(|value| value.text())
+  }
+
+  let files = [
+    ("a.q", "use b::f; pub ƒ g() -> int { return f() + 1; }"),
+    ("b.q", "use a::g; pub ƒ f() -> int { return 1; }"),
+    ("c.q", "pub ƒ f() -> int { return 41; } pub val n := 1;"),
+  ];
+
+  fs::create_dir_all(dir()).unwrap();
+
+  for (name, input) in files.iter() {
+    fs::write(dir().join(name), input).unwrap();
+  }
+
+  suite!("qoeurc::analyzer::module", {
+    "test public items of an inline module are imported" || {
+      let input = "mod m {
+          pub ƒ g() -> int { return 1; }
+          pub struct P { x: int }
+          pub val n := 3;
+        }";
+
+      must!(eval(&format!("{} use m::g; g();", input)); eq Ok("1".into()))?;
+      must!(
+        eval(&format!("{} use m::*; [g(), P {{ x: 2 }}.x, n];", input));
+        eq Ok("[1, 2, 3]".into())
+      )
+    }
+
+    "test items of a module are not in scope without use" || {
+      must!(
+        eval("mod m { pub ƒ g() -> int { return 1; } } g();");
+        eq Err("cannot find value `g` in this scope".into())
+      )
+    }
+
+    "test private and unknown items cannot be imported" || {
+      let input = "mod m { pub ƒ g() -> int { return 1; } ƒ h() -> int { return 2; } }";
+
+      must!(
+        eval(&format!("{} use m::h;", input));
+        eq Err("`h` is private in module `m`".into())
+      )?;
+      must!(
+        eval(&format!("{} use m::k;", input));
+        eq Err("unresolved import `m::k`".into())
+      )
+    }
+
+    "test modules are loaded from files" || {
+      must!(run("use c::f; f() + 1;"); eq Ok("42".into()))?;
+      must!(run("use c::{f, n}; f() + n;"); eq Ok("42".into()))?;
+      must!(
+        run("use nowhere::f;");
+        eq Err(format!(
+          "file not found for module `nowhere`, expected `{}`",
+          dir().join("nowhere.q").display()
+        ))
+      )
+    }
+
+    "test cyclic imports are refused" || {
+      must!(
+        run("use a::g; g();");
+        eq Err("in module `a`: in module `b`: cyclic import of module `a`: a -> b -> a".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn closure_test() {
+  suite!("qoeurc::analyzer::closure", {
+    "test closures capture their environment" || {
+      must!(
+        eval("val n := 10; val add := |x: int| { x + n }; add(1);");
+        eq Ok("11".into())
+      )?;
+      must!(
+        eval("val c := [0]; val f := || { c[0] }; c[0] = 5; f();");
+        eq Ok("5".into())
+      )
+    }
+
+    "test closures keep their state between calls" || {
+      let input = "ƒ counter() -> int {
+          val c := [0];
+          val inc := || { c[0] = c[0] + 1; c[0] };
+          inc();
+          inc();
+          return inc();
+        }
+        counter();";
+
+      must!(eval(input); eq Ok("3".into()))
+    }
+
+    "test closures are values" || {
+      let input = "ƒ make(k: int) -> int { return |x: int| { x * k }; }
+        val fs := [make(3), |x: int| x + 1];
+        [fs[0](4), fs[1](4), map([1, 2], fs[0])];";
+
+      must!(eval(input); eq Ok("[12, 5, [3, 6]]".into()))
+    }
+
+    "test closures are called like functions" || {
+      must!(
+        eval("val f := |x: int| { x }; f(1, 2);");
+        eq Err("function `closure` takes 1 arguments but 2 were supplied".into())
+      )?;
+      must!(eval("val a := 1; a();"); eq Err("`1` is not callable".into()))
+    }
+  });
+}
+
+#[test]
+fn break_test() {
+  suite!("qoeurc::analyzer::break", {
+    "test break and continue stop the innermost loop" || {
+      let input = "val a := [0];
+        for 0..10 |i| {
+          if i == 2 { continue; }
+          if i == 5 { break; }
+          a[0] = a[0] + i;
+        }
+        a[0];";
+
+      must!(eval(input); eq Ok("8".into()))
+    }
+
+    "test labels stop an outer loop" || {
+      let input = "val a := [0];
+        @outer for 0..3 |i| {
+          for 0..3 |j| {
+            if j == 1 { continue @outer; }
+            if i == 2 { break @outer; }
+            a[0] = a[0] + 1;
+          }
+        }
+        a[0];";
+
+      must!(eval(input); eq Ok("2".into()))?;
+      must!(eval("@outer loop { loop { break @outer 7; } };"); eq Ok("7".into()))
+    }
+
+    "test a loop is worth the value of its break" || {
+      let input = "val n := [0];
+        val x := loop {
+          n[0] = n[0] + 1;
+          if n[0] == 4 { break n[0] * 10; }
+        };
+        x;";
+
+      must!(eval(input); eq Ok("40".into()))?;
+      must!(eval("val x := loop { break; }; x;"); eq Ok("nil".into()))
+    }
+
+    "test break and continue must be in a loop" || {
+      must!(eval("break;"); eq Err("`break` outside of a loop".into()))?;
+      must!(eval("continue;"); eq Err("`continue` outside of a loop".into()))?;
+      must!(
+        eval("for 0..3 |i| { val g := || { break; }; }");
+        eq Err("`break` outside of a loop".into())
+      )?;
+      must!(
+        eval("for 0..3 |i| { break @nope; }");
+        eq Err("use of undeclared label `@nope`".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn return_test() {
+  suite!("qoeurc::analyzer::return", {
+    "test a return leaves the loops it is in" || {
+      let input = "ƒ f() -> int {
+          for 0..10 |i| { if i == 3 { return i; } }
+          return -1;
+        }
+        ƒ g() -> int { loop { while true { return 5; } } }
+        [f(), g()];";
+
+      must!(eval(input); eq Ok("[3, 5]".into()))
+    }
+
+    "test a return leaves the blocks it is in" || {
+      let input = "ƒ f(x: int) -> str {
+          if x > 0 {
+            if x > 10 { return \"big\"; }
+            return \"small\";
+          }
+          return \"neg\";
+        }
+        ƒ g() -> int { match 2 { 2 => { return 9; }, _ => 0 }; return 1; }
+        [f(20), f(2), f(-1), g()];";
+
+      must!(eval(input); eq Ok("[\"big\", \"small\", \"neg\", 9]".into()))
+    }
+
+    "test a return in a closure only leaves the closure" || {
+      let input = "ƒ f() -> int { val g := || { return 1; }; g(); return 2; } f();";
+
+      must!(eval(input); eq Ok("2".into()))
+    }
+
+    "test a return without a value gives nil" || {
+      must!(eval("ƒ f() -> int { return; } f();"); eq Ok("nil".into()))
+    }
+  });
+}
+
+#[test]
+fn if_test() {
+  suite!("qoeurc::analyzer::if", {
+    "test else if chains take the first branch that holds" || {
+      let input = "ƒ sign(x: int) -> str {
+          if x > 0 { \"pos\" } else if x < 0 { \"neg\" } else { \"zero\" }
+        }
+        [sign(3), sign(-2), sign(0)];";
+
+      must!(eval(input); eq Ok("[\"pos\", \"neg\", \"zero\"]".into()))
+    }
+
+    "test an if is an expression everywhere" || {
+      must!(eval("val a := if true { 1 } else { 2 }; a;"); eq Ok("1".into()))?;
+      must!(
+        eval("[if false { 1 } else { 2 }, 3 + if true { 4 } else { 5 }];");
+        eq Ok("[2, 7]".into())
+      )?;
+      must!(
+        eval("ƒ f(c: bool) -> int { val a: int = if c { 1 } else { 2 }; return a; } f(false);");
+        eq Ok("2".into())
+      )
+    }
+
+    "test an if without else is worth nil when it does not hold" || {
+      must!(eval("val a := if false { 1 }; a;"); eq Ok("nil".into()))
+    }
+
+    "test the type of an if is the one of its branches" || {
+      must!(
+        eval("ƒ f(c: bool) -> str { val a: str = if c { 1 } else { 2 }; return a; }");
+        eq Err("mismatched types for `a`: expected `str`, found `int`".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn nil_test() {
+  suite!("qoeurc::analyzer::nil", {
+    "test nil is a value" || {
+      must!(eval("nil;"); eq Ok("nil".into()))?;
+      must!(
+        eval("[nil == nil, nil == 0, nil != 1];");
+        eq Ok("[true, false, true]".into())
+      )?;
+      must!(eval("ƒ f() { } f();"); eq Ok("nil".into()))
+    }
+
+    "test optional values fall back with ??" || {
+      must!(eval("val a: int? = nil; a ?? 4;"); eq Ok("4".into()))?;
+      must!(eval("val a: int? = 3; a ?? 0;"); eq Ok("3".into()))?;
+      must!(
+        eval("ƒ f() -> int? { return nil; } f() ?? 5;");
+        eq Ok("5".into())
+      )
+    }
+
+    "test optional members are read with ?." || {
+      let input = "struct P { x: int }
+        val p: P? = nil;
+        val q: P? = P { x: 2 };
+        [p?.x, q?.x];";
+
+      must!(eval(input); eq Ok("[nil, 2]".into()))?;
+      must!(
+        eval("struct P { x: int } ƒ f(p: P?) -> int { return p.x; }");
+        eq Err("cannot access `x` on a value of type `P?`, use `?.` instead".into())
+      )
+    }
+
+    "test only optional types hold nil" || {
+      must!(
+        eval("val a: int = nil;");
+        eq Err("mismatched types for `a`: expected `int`, found `nil`".into())
+      )?;
+      must!(
+        eval("val a: str? = 1;");
+        eq Err("mismatched types for `a`: expected `str?`, found `int`".into())
+      )?;
+      must!(eval("nil + 1;"); eq Err("cannot use `+` on `nil`".into()))
+    }
+  });
+}
+
+#[test]
+fn result_test() {
+  suite!("qoeurc::analyzer::result", {
+    "test ? gives back the first error" || {
+      let input = "ƒ half(n: int) -> Result<int, str> {
+          if n / 2 * 2 != n { return err(\"odd\"); }
+          return ok(n / 2);
+        }
+        ƒ quarter(n: int) -> Result<int, str> {
+          val h := half(n)?;
+          return half(h);
+        }
+        [quarter(8), quarter(6), quarter(3)];";
+
+      must!(eval(input); eq Ok("[ok(2), err(\"odd\"), err(\"odd\")]".into()))
+    }
+
+    "test ? gives back nil from an optional" || {
+      let input = "ƒ first(xs: [int]) -> int? {
+          if len(xs) == 0 { return nil; }
+          return xs[0];
+        }
+        ƒ next(xs: [int]) -> int? { val a := first(xs)?; return a + 1; }
+        [next([1]), next([])];";
+
+      must!(eval(input); eq Ok("[2, nil]".into()))
+    }
+
+    "test ? unwraps to the type of the value" || {
+      must!(
+        eval("ƒ f() -> int { val a := 3?; return a; }");
+        eq Err("the `?` operator can only be applied to `Result` or optional values, found `int`".into())
+      )?;
+      must!(
+        eval("ƒ half(n: int) -> Result<int, str> { return ok(n / 2); }
+          val a: str = half(2)?;");
+        eq Err("mismatched types for `a`: expected `str`, found `int`".into())
+      )
+    }
+
+    "test match takes the first arm that matches" || {
+      must!(
+        eval("match ok(3) { ok(v) => v + 1, err(e) => 0 };");
+        eq Ok("4".into())
+      )?;
+      must!(
+        eval("match err(\"bad\") { ok(v) => v, err(e) => e };");
+        eq Ok("\"bad\"".into())
+      )?;
+      must!(
+        eval("match [1, 2] { [a, b] => a + b, _ => 0 };");
+        eq Ok("3".into())
+      )?;
+      must!(
+        eval("match 5 { 1 => \"one\" };");
+        eq Err("no arm of `match` matches `5`".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn pattern_test() {
+  suite!("qoeurc::analyzer::pattern", {
+    "test val bindings take arrays apart" || {
+      must!(eval("val [a, b] := [1, 2]; a + b;"); eq Ok("3".into()))?;
+      must!(eval("val [a, ..rest] := [1, 2, 3]; rest;"); eq Ok("[2, 3]".into()))?;
+      must!(eval("val [a, _] := [1, 2]; a;"); eq Ok("1".into()))?;
+      must!(
+        eval("val [[a, b], c] := [[1, 2], 3]; a + b + c;");
+        eq Ok("6".into())
+      )
+    }
+
+    "test tuple patterns take arrays of their length apart" || {
+      must!(eval("val (a, b) := [1, 2]; a + b;"); eq Ok("3".into()))?;
+      must!(
+        eval("match [1, 2, 3] { (a, b) => a + b, (a, b, c) => a + b + c };");
+        eq Ok("6".into())
+      )?;
+      must!(
+        eval("val (a, b) := [1, 2, 3];");
+        eq Err("the pattern `(a, b)` does not match `[1, 2, 3]`".into())
+      )
+    }
+
+    "test val bindings take hashes and structs apart" || {
+      must!(
+        eval("val { x, y } := { \"x\": 1, \"y\": 2 }; x * y;");
+        eq Ok("2".into())
+      )?;
+      must!(
+        eval("struct P { x: int, y: int } val P { x, y } := P { x: 3, y: 4 }; x + y;");
+        eq Ok("7".into())
+      )
+    }
+
+    "test parameters take their arguments apart" || {
+      let input = "ƒ mul([a, b]: [int]) -> int { return a * b; }
+        ƒ sub({ x, y }: hash) -> int { return x - y; }
+        [mul([3, 4]), sub({ \"x\": 5, \"y\": 2 })];";
+
+      must!(eval(input); eq Ok("[12, 3]".into()))?;
+      must!(
+        eval("ƒ f([a, b]: [int]) -> int { return a * b; } f([3]);");
+        eq Err("the argument `[3]` does not match the parameter `[a, b]`".into())
+      )
+    }
+
+    "test values that do not match are refused" || {
+      must!(
+        eval("val [a, b] := [1];");
+        eq Err("the pattern `[a, b]` does not match `[1]`".into())
+      )?;
+      must!(
+        eval("val [a, b] := 3;");
+        eq Err("the pattern `[a, b]` does not match `3`".into())
+      )?;
+      must!(
+        eval("val { z } := { \"x\": 1 };");
+        eq Err("the pattern `{ z }` does not match `{ x: 1 }`".into())
+      )?;
+      must!(
+        eval("val [a, a] := [1, 2];");
+        eq Err("the name `a` is defined multiple times".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn param_test() {
+  suite!("qoeurc::analyzer::param", {
+    "test missing arguments take their default" || {
+      let input = "ƒ greet(name: str, greeting: str = \"hi\") -> str {
+          return greeting + \" \" + name;
+        }
+        [greet(\"x\"), greet(\"x\", \"yo\")];";
+
+      must!(eval(input); eq Ok("[\"hi x\", \"yo x\"]".into()))?;
+      must!(
+        eval("ƒ f(a: int, b: int = a + 1) -> int { return a + b; } f(1);");
+        eq Ok("3".into())
+      )
+    }
+
+    "test named arguments go to their parameter" || {
+      let input = "ƒ greet(name: str, greeting: str = \"hi\") -> str {
+          return greeting + \" \" + name;
+        }
+        [greet(name: \"y\"), greet(greeting: \"hey\", name: \"z\")];";
+
+      must!(eval(input); eq Ok("[\"hi y\", \"hey z\"]".into()))
+    }
+
+    "test a variadic parameter collects the remaining arguments" || {
+      must!(
+        eval("ƒ f(..xs: int) -> [int] { return xs; } [f(), f(1), f(1, 2)];");
+        eq Ok("[[], [1], [1, 2]]".into())
+      )?;
+      must!(
+        eval("ƒ f(a: int, ..xs: int) -> [int] { return [a, ..xs]; } f(1, 2, 3);");
+        eq Ok("[1, 2, 3]".into())
+      )
+    }
+
+    "test arguments that do not fit are refused" || {
+      let f = "ƒ f(a: int, b: int) -> int { return a; }";
+
+      must!(
+        eval(&format!("{} f(1, c: 2);", f));
+        eq Err("function `f` has no parameter named `c`".into())
+      )?;
+      must!(
+        eval(&format!("{} f(1, a: 2);", f));
+        eq Err("the argument `a` of function `f` is supplied more than once".into())
+      )?;
+      must!(
+        eval(&format!("{} f(1, 2, 3);", f));
+        eq Err("function `f` takes 2 arguments but 3 were supplied".into())
+      )?;
+      must!(
+        eval(&format!("{} f(1);", f));
+        eq Err("function `f` is missing the argument `b`".into())
+      )?;
+      must!(
+        eval(&format!("{} f(a: 1, 2);", f));
+        eq Err("the positional argument `2` follows the named argument `a: 1`".into())
+      )
+    }
+
+    "test parameters are checked where they are declared" || {
+      must!(
+        eval("ƒ f(..a: int, b: int) -> int { return b; }");
+        eq Err("the variadic parameter `a` must be the last one".into())
+      )?;
+      must!(
+        eval("ƒ f(..a: int = 1) -> int { return 1; }");
+        eq Err("the variadic parameter `a` cannot have a default".into())
+      )?;
+      must!(
+        eval("ƒ f(a: int = \"s\") -> int { return a; }");
+        eq Err(
+          "mismatched types for the default of `a`: expected `int`, found `str`"
+            .into()
+        )
+      )?;
+      must!(
+        eval("ƒ f(..xs: int) -> int { return 1; } f(1, \"s\");");
+        eq Err("mismatched types in call to `f`: expected `int`, found `str`".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn index_test() {
+  suite!("qoeurc::analyzer::index", {
+    "test index assignment writes arrays and hashes" || {
+      must!(eval("val a := [1, 2, 3]; a[1] = 5; a;"); eq Ok("[1, 5, 3]".into()))?;
+      must!(
+        eval("val h := { \"k\": 1 }; h[\"k\"] = 2; h[\"n\"] = 3; h;");
+        eq Ok("{ k: 2, n: 3 }".into())
+      )?;
+      must!(
+        eval("val b := [1]; val a := b; a[0] = 9; b;");
+        eq Ok("[1]".into())
+      )
+    }
+
+    "test delete removes elements and entries" || {
+      must!(eval("val a := [1, 2, 3]; delete a[0]; a;"); eq Ok("[2, 3]".into()))?;
+      must!(
+        eval("val h := { \"k\": 1, \"j\": 2 }; delete h[\"k\"]; h;");
+        eq Ok("{ j: 2 }".into())
+      )
+    }
+
+    "test spread copies arrays and hashes into new ones" || {
+      must!(eval("val a := [1, 2]; [..a, 3];"); eq Ok("[1, 2, 3]".into()))?;
+      must!(
+        eval("val h := { \"a\": 1 }; { ..h, \"k\": 2 };");
+        eq Ok("{ a: 1, k: 2 }".into())
+      )?;
+      must!(
+        eval("val h := { \"a\": 1 }; { ..h, \"a\": 2 };");
+        eq Ok("{ a: 2 }".into())
+      )
+    }
+
+    "test ranges slice arrays and strings" || {
+      must!(
+        eval("val a := [1, 2, 3, 4]; [a[1..3], a[1..=2], a[0..0]];");
+        eq Ok("[[2, 3], [2, 3], []]".into())
+      )?;
+      must!(
+        eval("val s := \"héllo\"; [s[0..2], s[1..=3]];");
+        eq Ok("[\"hé\", \"éll\"]".into())
+      )
+    }
+
+    "test out of bounds indexes and slices are errors" || {
+      must!(
+        eval("val a := [1, 2, 3]; a[1..5];");
+        eq Err("range `1..5` out of bounds for length 3".into())
+      )?;
+      must!(
+        eval("val a := [1, 2]; a[-1..1];");
+        eq Err("range `-1..1` out of bounds for length 2".into())
+      )?;
+      must!(
+        eval("val s := \"abc\"; s[0..9];");
+        eq Err("range `0..9` out of bounds for length 3".into())
+      )?;
+      must!(
+        eval("val a := [1, 2, 3]; a[5] = 1;");
+        eq Err("index out of bounds: the length is 3 but the index is 5".into())
+      )?;
+      must!(
+        eval("val a := [1, 2, 3]; delete a[9];");
+        eq Err("index out of bounds: the length is 3 but the index is 9".into())
+      )
+    }
+
+    "test writes that cannot happen are refused" || {
+      must!(
+        eval("val a := [1, 2, 3]; a[\"x\"] = 1;");
+        eq Err("the index must be `int`, found `str`".into())
+      )?;
+      must!(
+        eval("val h := { \"k\": 1 }; delete h[\"z\"];");
+        eq Err("no entry found for key `z`".into())
+      )?;
+      must!(
+        eval("val x := 1; [..x];");
+        eq Err("cannot spread `x` in an array".into())
+      )
+    }
+  });
 }
@@ -0,0 +1,252 @@
+use crate::tokenizer::token::Token;
+use crate::value::instruction::hash::HashKey;
+use crate::value::instruction::pattern::Pattern;
+use crate::value::{Value, Values};
+
+use std::rc::Rc;
+
+// where a variable lives: a slot of the running function, a cell captured by
+// its closure or the scope of the program, looked up by name
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Place {
+  Local(usize),
+  Upvalue(usize),
+  Global(usize),
+}
+
+// where a closure takes the cells it captures from when it is created
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Capture {
+  Local(usize),
+  Upvalue(usize),
+}
+
+// how the value on top of the stack is taken apart into variables
+#[derive(Clone, Debug)]
+pub enum Target {
+  Val(Option<Pattern>),
+  Param(usize),
+  Loop(Vec<String>),
+  Arm(Pattern),
+  Function,
+}
+
+#[derive(Clone, Debug)]
+pub struct Binder {
+  pub target: Target,
+  pub places: Vec<(String, Place)>,
+}
+
+impl Binder {
+  pub fn new(target: Target, places: Vec<(String, Place)>) -> Self {
+    Binder { target, places }
+  }
+
+  pub fn place(&self, name: &str) -> Option<Place> {
+    self
+      .places
+      .iter()
+      .find(|(variable, _)| variable == name)
+      .map(|(_, place)| *place)
+  }
+}
+
+// the ops a statement compiled to, with its code, the innermost one covering
+// an op tells where it comes from
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+  pub source: String,
+}
+
+impl Span {
+  pub fn new(start: usize, end: usize, source: &str) -> Self {
+    Span {
+      start,
+      end,
+      source: source.into(),
+    }
+  }
+}
+
+// the operands are indices into the tables of the chunk, jumps are absolute
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+  Constant(usize),
+  Nil,
+  Pop,
+  Dup,
+  Get(Place),
+  Set(Place),
+  // a fresh empty cell for a function declared later in the block
+  Reserve(usize),
+  Bind(usize),
+  // the `val` node holding the value on top, what a declaration is worth
+  Declared(usize),
+  // the argument of a parameter, its default follows when it has none
+  Arg(usize, usize),
+  Closure(usize),
+  Binary(usize),
+  Unary(usize),
+  Jump(usize),
+  JumpIfFalse(usize),
+  JumpIfNotNil(usize),
+  Call(usize),
+  Invoke(usize, usize, bool),
+  Named(usize),
+  // the source of the spread value, shown when it is not an array
+  Spread(usize),
+  Array(usize),
+  // the keys of the entries on the stack, a spread hash has none
+  Hash(usize),
+  Outcome(bool),
+  Try,
+  Member(usize, bool),
+  Index,
+  SetIndex,
+  Remove,
+  Range(bool, bool),
+  Match(usize, usize),
+  NoMatch,
+  Enter,
+  Leave,
+  Iterate,
+  Next(usize),
+  Drop,
+  Break(usize),
+  Continue(usize),
+  // a node left to the tree-walker, with the locals it can see
+  Eval(usize, usize),
+  Fail(usize),
+  Return,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Chunk {
+  pub ops: Vec<Op>,
+  pub constants: Values,
+  pub names: Vec<String>,
+  pub nodes: Values,
+  pub tokens: Vec<Token>,
+  pub binders: Vec<Binder>,
+  pub keys: Vec<Vec<Option<HashKey>>>,
+  pub bridges: Vec<Vec<(String, Place)>>,
+  pub protos: Vec<Rc<Proto>>,
+  pub spans: Vec<Span>,
+}
+
+impl Chunk {
+  pub fn new() -> Self {
+    Chunk::default()
+  }
+
+  pub fn emit(&mut self, op: Op) -> usize {
+    self.ops.push(op);
+    self.ops.len() - 1
+  }
+
+  // points the jump at `at` to the next instruction
+  pub fn patch(&mut self, at: usize) {
+    let target = self.ops.len();
+
+    self.ops[at] = match self.ops[at] {
+      Op::Arg(param, _) => Op::Arg(param, target),
+      Op::Jump(_) => Op::Jump(target),
+      Op::JumpIfFalse(_) => Op::JumpIfFalse(target),
+      Op::JumpIfNotNil(_) => Op::JumpIfNotNil(target),
+      Op::Match(binder, _) => Op::Match(binder, target),
+      Op::Next(_) => Op::Next(target),
+      op => op,
+    };
+  }
+
+  pub fn add_constant(&mut self, value: Box<dyn Value>) -> usize {
+    self.constants.push(value);
+    self.constants.len() - 1
+  }
+
+  pub fn add_name(&mut self, name: &str) -> usize {
+    match self.names.iter().position(|known| known == name) {
+      Some(index) => index,
+      None => {
+        self.names.push(name.into());
+        self.names.len() - 1
+      }
+    }
+  }
+
+  pub fn add_node(&mut self, node: Box<dyn Value>) -> usize {
+    self.nodes.push(node);
+    self.nodes.len() - 1
+  }
+
+  pub fn add_token(&mut self, token: Token) -> usize {
+    self.tokens.push(token);
+    self.tokens.len() - 1
+  }
+
+  pub fn add_binder(&mut self, binder: Binder) -> usize {
+    self.binders.push(binder);
+    self.binders.len() - 1
+  }
+
+  pub fn add_keys(&mut self, keys: Vec<Option<HashKey>>) -> usize {
+    self.keys.push(keys);
+    self.keys.len() - 1
+  }
+
+  pub fn add_bridge(&mut self, bridge: Vec<(String, Place)>) -> usize {
+    self.bridges.push(bridge);
+    self.bridges.len() - 1
+  }
+
+  pub fn add_proto(&mut self, proto: Proto) -> usize {
+    self.protos.push(Rc::new(proto));
+    self.protos.len() - 1
+  }
+
+  // opens a span at the next instruction, `end_span` closes it
+  pub fn add_span(&mut self, source: &str) -> usize {
+    self
+      .spans
+      .push(Span::new(self.ops.len(), self.ops.len(), source));
+    self.spans.len() - 1
+  }
+
+  pub fn end_span(&mut self, at: usize) {
+    self.spans[at].end = self.ops.len();
+  }
+
+  pub fn span(&self, at: usize) -> Option<&Span> {
+    self
+      .spans
+      .iter()
+      .rev()
+      .find(|span| span.start <= at && at < span.end)
+  }
+}
+
+// a compiled function, the program is one without parameters
+#[derive(Clone, Debug)]
+pub struct Proto {
+  pub name: String,
+  pub node: Box<dyn Value>,
+  pub params: Values,
+  pub slots: Vec<String>,
+  pub captures: Vec<(String, Capture)>,
+  pub chunk: Chunk,
+}
+
+impl Proto {
+  pub fn new(name: &str, node: Box<dyn Value>, params: Values) -> Self {
+    Proto {
+      name: name.into(),
+      node,
+      params,
+      slots: vec![],
+      captures: vec![],
+      chunk: Chunk::new(),
+    }
+  }
+}
@@ -0,0 +1,865 @@
+use crate::analyzer::interpreter::ValueResult;
+use crate::compiler::bytecode::{
+  Binder, Capture, Chunk, Op, Place, Proto, Target,
+};
+use crate::tokenizer::kind::*;
+use crate::value::instruction::assign::Assign;
+use crate::value::instruction::closure::Closure;
+use crate::value::instruction::comment::Comment;
+use crate::value::instruction::delete::Delete;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::group::Group;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::shebang::Shebang;
+use crate::value::instruction::spread::Spread;
+use crate::value::instruction::{
+  Array, Binary, Block, Break, Call, Continue, Expression, Function, Hash,
+  Identifier, If, Index, LoopForIn, LoopInfinite, LoopWhile, Match, Member,
+  Outcome, Program, Range, Return, Statement, Try, Unary, Val,
+};
+use crate::value::primitive::{
+  bool::Bool, char::Char, float::Float, int::Int, nil::Nil, str::Str,
+};
+use crate::value::{Value, Values};
+use crate::void;
+
+#[derive(Debug)]
+struct Loop {
+  label: Option<String>,
+  // `while` and `for` loops cannot break with a value, `loop` can
+  keyword: Option<&'static str>,
+  start: usize,
+  breaks: Vec<usize>,
+}
+
+// a function being compiled, the program has no scope of its own: what it
+// declares at its top lives in the scope of the interpreter
+#[derive(Debug)]
+struct State {
+  proto: Proto,
+  scopes: Vec<Vec<(String, usize)>>,
+  functions: Vec<Vec<String>>,
+  upvalues: Vec<String>,
+  loops: Vec<Loop>,
+}
+
+impl State {
+  fn new(proto: Proto, is_global: bool) -> Self {
+    let scopes = match is_global {
+      true => vec![],
+      false => vec![vec![]],
+    };
+
+    State {
+      functions: scopes.iter().map(|_| vec![]).collect(),
+      loops: vec![],
+      proto,
+      scopes,
+      upvalues: vec![],
+    }
+  }
+
+  fn local(&self, name: &str) -> Option<usize> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .flat_map(|scope| scope.iter().rev())
+      .find(|(local, _)| local == name)
+      .map(|(_, slot)| *slot)
+  }
+
+  fn upvalue(&self, name: &str) -> Option<usize> {
+    self.upvalues.iter().position(|upvalue| upvalue == name)
+  }
+}
+
+// turns the tree of a program into bytecode. it is not a whole backend:
+// structs, impls, traits, modules, `use` and `pub` have no instructions, they
+// are kept whole and run by the tree-walker through `Op::Eval`
+#[derive(Debug)]
+pub struct Compiler {
+  states: Vec<State>,
+}
+
+impl Compiler {
+  pub fn compile(program: &dyn Value) -> ValueResult<Proto> {
+    let statements = match program.downcast_ref::<Program>() {
+      Some(program) => program.statements.to_vec(),
+      None => vec![program.boxed()],
+    };
+
+    let proto = Proto::new("program", program.boxed(), vec![]);
+    let mut compiler = Compiler {
+      states: vec![State::new(proto, true)],
+    };
+
+    compiler.compile_statements(&statements)?;
+    compiler.emit(Op::Return);
+
+    Ok(compiler.states.pop().unwrap().proto)
+  }
+
+  fn compile_node(&mut self, node: &dyn Value) -> ValueResult<()> {
+    if let Some(statement) = node.downcast_ref::<Statement>() {
+      return self.compile_node(statement.node.as_ref());
+    } else if let Some(expression) = node.downcast_ref::<Expression>() {
+      return self.compile_node(expression.node.as_ref());
+    } else if let Some(group) = node.downcast_ref::<Group>() {
+      return self.compile_node(group.expression.as_ref());
+    }
+
+    if node.is::<Bool>()
+      || node.is::<Char>()
+      || node.is::<Float>()
+      || node.is::<Int>()
+      || node.is::<Nil>()
+      || node.is::<Str>()
+      || node.is::<Comment>()
+      || node.is::<Shebang>()
+    {
+      let constant = self.chunk().add_constant(node.boxed());
+
+      self.emit(Op::Constant(constant));
+      return Ok(());
+    }
+
+    if let Some(identifier) = node.downcast_ref::<Identifier>() {
+      let place = self.resolve(&identifier.name);
+
+      self.emit(Op::Get(place));
+      Ok(())
+    } else if let Some(val) = node.downcast_ref::<Val>() {
+      self.compile_val(val)
+    } else if let Some(function) = node.downcast_ref::<Function>() {
+      self.compile_declaration(function)
+    } else if let Some(closure) = node.downcast_ref::<Closure>() {
+      let proto = self.compile_function(
+        "closure",
+        closure.boxed(),
+        closure.args.to_owned().unwrap_or_default(),
+        &closure.block,
+      )?;
+
+      self.emit(Op::Closure(proto));
+      Ok(())
+    } else if let Some(call) = node.downcast_ref::<Call>() {
+      self.compile_call(call)
+    } else if let Some(binary) = node.downcast_ref::<Binary>() {
+      self.compile_binary(binary)
+    } else if let Some(unary) = node.downcast_ref::<Unary>() {
+      self.compile_node(unary.rhs.as_ref())?;
+
+      let token = self.chunk().add_token(*unary.operand.to_owned());
+
+      self.emit(Op::Unary(token));
+      Ok(())
+    } else if let Some(control) = node.downcast_ref::<If>() {
+      self.compile_if(control)
+    } else if let Some(block) = node.downcast_ref::<Block>() {
+      self.compile_statements(&block.statements)
+    } else if let Some(looping) = node.downcast_ref::<LoopInfinite>() {
+      self.compile_loop(looping)
+    } else if let Some(looping) = node.downcast_ref::<LoopWhile>() {
+      self.compile_while(looping)
+    } else if let Some(looping) = node.downcast_ref::<LoopForIn>() {
+      self.compile_for(looping)
+    } else if let Some(jump) = node.downcast_ref::<Break>() {
+      self.compile_break(jump)
+    } else if let Some(jump) = node.downcast_ref::<Continue>() {
+      self.compile_continue(jump)
+    } else if let Some(jump) = node.downcast_ref::<Return>() {
+      self.compile_option(&jump.value)?;
+      self.emit(Op::Return);
+      Ok(())
+    } else if let Some(attempt) = node.downcast_ref::<Try>() {
+      self.compile_node(attempt.value.as_ref())?;
+      self.emit(Op::Try);
+      Ok(())
+    } else if let Some(matching) = node.downcast_ref::<Match>() {
+      self.compile_match(matching)
+    } else if let Some(array) = node.downcast_ref::<Array>() {
+      self.compile_array(&array.data)
+    } else if let Some(hash) = node.downcast_ref::<Hash>() {
+      self.compile_hash(hash)
+    } else if let Some(outcome) = node.downcast_ref::<Outcome>() {
+      self.compile_node(outcome.value.as_ref())?;
+      self.emit(Op::Outcome(outcome.is_ok));
+      Ok(())
+    } else if let Some(member) = node.downcast_ref::<Member>() {
+      self.compile_node(member.object.as_ref())?;
+
+      let name = self.chunk().add_name(&member.name());
+
+      self.emit(Op::Member(name, member.optional));
+      Ok(())
+    } else if let Some(index) = node.downcast_ref::<Index>() {
+      self.compile_node(index.lhs.as_ref())?;
+      self.compile_node(index.rhs.as_ref())?;
+      self.emit(Op::Index);
+      Ok(())
+    } else if let Some(assign) = node.downcast_ref::<Assign>() {
+      self.compile_assign(assign)
+    } else if let Some(delete) = node.downcast_ref::<Delete>() {
+      self.compile_delete(delete)
+    } else if let Some(range) = node.downcast_ref::<Range>() {
+      self.compile_node(range.start.as_ref())?;
+      self.compile_node(range.end.as_ref())?;
+
+      if let Some(step) = &range.step {
+        self.compile_node(step.as_ref())?;
+      }
+
+      self.emit(Op::Range(range.inclusive, range.step.is_some()));
+      Ok(())
+    } else {
+      self.compile_fallback(node.boxed())
+    }
+  }
+
+  fn compile_option(
+    &mut self,
+    node: &Option<Box<dyn Value>>,
+  ) -> ValueResult<()> {
+    match node {
+      Some(node) => self.compile_node(node.as_ref()),
+      None => {
+        self.emit(Op::Nil);
+        Ok(())
+      }
+    }
+  }
+
+  // the value of the last statement, functions declared in a local block
+  // get their cell upfront so that they can call each other
+  fn compile_statements(&mut self, statements: &Values) -> ValueResult<()> {
+    if !self.is_global() {
+      for statement in statements {
+        if let Some(function) = Compiler::declaration(statement.as_ref()) {
+          let name = function.name();
+
+          if !self.is_declared_here(&name) {
+            if let Place::Local(slot) = self.declare(&name) {
+              self.emit(Op::Reserve(slot));
+            }
+          }
+        }
+      }
+    }
+
+    if statements.is_empty() {
+      self.emit(Op::Nil);
+    }
+
+    for (index, statement) in statements.iter().enumerate() {
+      let span = self.chunk().add_span(&statement.text());
+
+      self.compile_node(statement.as_ref())?;
+      self.chunk().end_span(span);
+
+      if index + 1 < statements.len() {
+        self.emit(Op::Pop);
+      }
+    }
+
+    Ok(())
+  }
+
+  fn compile_val(&mut self, val: &Val) -> ValueResult<()> {
+    self.compile_node(val.value.as_deref().unwrap())?;
+
+    let names = match &val.pattern {
+      Some(pattern) => pattern.names(),
+      None => vec![val.name.text()],
+    };
+
+    if names.iter().any(|name| self.is_declared_here(name)) {
+      return self.fail("variable already exist");
+    }
+
+    let places = names
+      .iter()
+      .map(|name| (name.to_owned(), self.declare(name)))
+      .collect();
+
+    let binder = Binder::new(Target::Val(val.pattern.to_owned()), places);
+    let binder = self.chunk().add_binder(binder);
+    // the value is put back in when the declaration runs
+    let node = val.to_owned().add_value(void!()).boxed();
+    let node = self.chunk().add_node(node);
+
+    self.emit(Op::Dup);
+    self.emit(Op::Bind(binder));
+    self.emit(Op::Declared(node));
+    Ok(())
+  }
+
+  fn compile_declaration(&mut self, function: &Function) -> ValueResult<()> {
+    let name = function.name();
+    let proto = self.compile_function(
+      &name,
+      function.boxed(),
+      function.args.to_owned().unwrap_or_default(),
+      &function.block,
+    )?;
+
+    if self.is_global() {
+      let place = self.declare(&name);
+      let binder = Binder::new(Target::Function, vec![(name, place)]);
+      let binder = self.chunk().add_binder(binder);
+
+      self.emit(Op::Closure(proto));
+      self.emit(Op::Dup);
+      self.emit(Op::Bind(binder));
+      return Ok(());
+    }
+
+    let state = self.state();
+
+    if state.functions.last().unwrap().contains(&name) {
+      return self.fail("function already exist");
+    }
+
+    state.functions.last_mut().unwrap().push(name.to_owned());
+
+    let place = self.resolve(&name);
+
+    self.emit(Op::Closure(proto));
+    self.emit(Op::Dup);
+    self.emit(Op::Set(place));
+    Ok(())
+  }
+
+  // the parameters are bound in order, a default sees the ones before it
+  fn compile_function(
+    &mut self,
+    name: &str,
+    node: Box<dyn Value>,
+    params: Values,
+    block: &Option<Box<dyn Value>>,
+  ) -> ValueResult<usize> {
+    let proto = Proto::new(name, node, params.to_owned());
+
+    self.states.push(State::new(proto, false));
+
+    for (index, param) in params.iter().enumerate() {
+      let param = param.as_ref().downcast_ref::<FunctionArg>().unwrap();
+      let jump = self.emit(Op::Arg(index, 0));
+
+      self.compile_option(&param.default)?;
+      self.chunk().patch(jump);
+
+      let names = match &param.pattern {
+        Some(pattern) => pattern.names(),
+        None => vec![param.name()],
+      };
+
+      let places = names
+        .iter()
+        .map(|name| (name.to_owned(), self.declare(name)))
+        .collect();
+
+      let binder = Binder::new(Target::Param(index), places);
+      let binder = self.chunk().add_binder(binder);
+
+      self.emit(Op::Bind(binder));
+    }
+
+    match block {
+      Some(block) => self.compile_node(block.as_ref())?,
+      None => {
+        self.emit(Op::Nil);
+      }
+    }
+
+    self.emit(Op::Return);
+
+    let state = self.states.pop().unwrap();
+    let mut proto = state.proto;
+
+    proto.captures = state
+      .upvalues
+      .iter()
+      .map(|name| (name.to_owned(), self.capture(name)))
+      .collect();
+
+    Ok(self.chunk().add_proto(proto))
+  }
+
+  // `obj.method(args)` calls a method when `obj` is a struct value, the
+  // arguments are evaluated before the callee in both cases
+  fn compile_call(&mut self, call: &Call) -> ValueResult<()> {
+    let args = call.args.to_owned().unwrap_or_default();
+
+    for arg in &args {
+      match arg.as_ref().downcast_ref::<NamedArg>() {
+        Some(named) => {
+          self.compile_node(named.value.as_ref())?;
+
+          let name = self.chunk().add_name(&named.name);
+
+          self.emit(Op::Named(name));
+        }
+        None => self.compile_node(arg.as_ref())?,
+      }
+    }
+
+    match call.callee.as_ref().downcast_ref::<Member>() {
+      Some(member) => {
+        self.compile_node(member.object.as_ref())?;
+
+        let name = self.chunk().add_name(&member.name());
+
+        self.emit(Op::Invoke(name, args.len(), member.optional));
+      }
+      None => {
+        self.compile_node(call.callee.as_ref())?;
+        self.emit(Op::Call(args.len()));
+      }
+    }
+
+    Ok(())
+  }
+
+  fn compile_binary(&mut self, binary: &Binary) -> ValueResult<()> {
+    self.compile_node(binary.lhs.as_ref())?;
+
+    if binary.operator.kind == TokenKind::Operator(QuestionQuestion) {
+      let jump = self.emit(Op::JumpIfNotNil(0));
+
+      self.compile_node(binary.rhs.as_ref())?;
+      self.chunk().patch(jump);
+      return Ok(());
+    }
+
+    self.compile_node(binary.rhs.as_ref())?;
+
+    let token = self.chunk().add_token(*binary.operator.to_owned());
+
+    self.emit(Op::Binary(token));
+    Ok(())
+  }
+
+  fn compile_if(&mut self, control: &If) -> ValueResult<()> {
+    self.compile_node(control.condition.as_ref())?;
+
+    let otherwise = self.emit(Op::JumpIfFalse(0));
+
+    self.compile_node(control.consequence.as_ref())?;
+
+    let end = self.emit(Op::Jump(0));
+
+    self.chunk().patch(otherwise);
+    self.compile_option(&control.alternative)?;
+    self.chunk().patch(end);
+    Ok(())
+  }
+
+  fn compile_loop(&mut self, looping: &LoopInfinite) -> ValueResult<()> {
+    self.emit(Op::Enter);
+
+    let start = self.enter_loop(&looping.label, None);
+
+    self.compile_body(looping.block.as_ref(), None)?;
+    self.emit(Op::Jump(start));
+    self.leave_loop();
+    Ok(())
+  }
+
+  fn compile_while(&mut self, looping: &LoopWhile) -> ValueResult<()> {
+    self.emit(Op::Enter);
+
+    let start = self.enter_loop(&looping.label, Some("while"));
+
+    self.compile_node(looping.condition.as_ref())?;
+
+    let done = self.emit(Op::JumpIfFalse(0));
+
+    self.compile_body(looping.block.as_ref(), None)?;
+    self.emit(Op::Jump(start));
+    self.chunk().patch(done);
+    self.emit(Op::Leave);
+    self.emit(Op::Nil);
+    self.leave_loop();
+    Ok(())
+  }
+
+  // the iterator is kept by the machine below the mark of the loop, a
+  // `break` lands on the instruction dropping it
+  fn compile_for(&mut self, looping: &LoopForIn) -> ValueResult<()> {
+    self.compile_node(looping.iterable.as_ref())?;
+    self.emit(Op::Iterate);
+    self.emit(Op::Enter);
+
+    let start = self.enter_loop(&looping.label, Some("for"));
+    let done = self.emit(Op::Next(0));
+
+    self.compile_body(looping.block.as_ref(), Some(looping.names()))?;
+    self.emit(Op::Jump(start));
+    self.chunk().patch(done);
+    self.emit(Op::Leave);
+    self.emit(Op::Nil);
+    self.leave_loop();
+    self.emit(Op::Drop);
+    Ok(())
+  }
+
+  // every iteration runs in a scope of its own, holding the loop variables
+  fn compile_body(
+    &mut self,
+    block: &dyn Value,
+    variables: Option<Vec<String>>,
+  ) -> ValueResult<()> {
+    self.begin_scope();
+
+    if let Some(variables) = variables {
+      let places = variables
+        .iter()
+        .map(|name| (name.to_owned(), self.declare(name)))
+        .collect();
+
+      let binder = Binder::new(Target::Loop(variables), places);
+      let binder = self.chunk().add_binder(binder);
+
+      self.emit(Op::Bind(binder));
+    }
+
+    self.compile_node(block)?;
+    self.emit(Op::Pop);
+    self.end_scope();
+    Ok(())
+  }
+
+  fn compile_break(&mut self, jump: &Break) -> ValueResult<()> {
+    let target = match self.find_loop(&jump.label) {
+      Some(target) => target,
+      None => {
+        self.compile_option(&jump.value)?;
+        return self.fail("`break` outside of a loop");
+      }
+    };
+
+    let depth = self.state().loops.len() - 1 - target;
+    let looping = &self.state().loops[target];
+
+    match (looping.keyword, &jump.value) {
+      (Some(keyword), Some(value)) => {
+        let message = format!("`break` with value from a `{}` loop", keyword);
+
+        self.compile_node(value.as_ref())?;
+        return self.fail(&message);
+      }
+      (Some(_), None) => {
+        self.emit(Op::Nil);
+      }
+      (None, value) => self.compile_option(value)?,
+    }
+
+    self.emit(Op::Break(depth));
+
+    let exit = self.emit(Op::Jump(0));
+
+    self.state().loops[target].breaks.push(exit);
+    Ok(())
+  }
+
+  fn compile_continue(&mut self, jump: &Continue) -> ValueResult<()> {
+    let target = match self.find_loop(&jump.label) {
+      Some(target) => target,
+      None => return self.fail("`continue` outside of a loop"),
+    };
+
+    let depth = self.state().loops.len() - 1 - target;
+    let start = self.state().loops[target].start;
+
+    self.emit(Op::Continue(depth));
+    self.emit(Op::Jump(start));
+    Ok(())
+  }
+
+  // each arm tests the subject left on the stack, the one that matches takes
+  // it and binds its variables in a scope of its own
+  fn compile_match(&mut self, matching: &Match) -> ValueResult<()> {
+    self.compile_node(matching.subject.as_ref())?;
+
+    let mut ends = vec![];
+
+    for (pattern, body) in &matching.arms {
+      self.begin_scope();
+
+      let places = pattern
+        .names()
+        .iter()
+        .map(|name| (name.to_owned(), self.declare(name)))
+        .collect();
+
+      let binder = Binder::new(Target::Arm(pattern.to_owned()), places);
+      let binder = self.chunk().add_binder(binder);
+      let next = self.emit(Op::Match(binder, 0));
+
+      self.compile_node(body.as_ref())?;
+      self.end_scope();
+
+      ends.push(self.emit(Op::Jump(0)));
+      self.chunk().patch(next);
+    }
+
+    self.emit(Op::NoMatch);
+
+    for end in ends {
+      self.chunk().patch(end);
+    }
+
+    Ok(())
+  }
+
+  fn compile_array(&mut self, data: &Values) -> ValueResult<()> {
+    for item in data {
+      match item.as_ref().downcast_ref::<Spread>() {
+        Some(spread) => {
+          self.compile_node(spread.value.as_ref())?;
+
+          let source = self.chunk().add_name(&spread.value.text());
+
+          self.emit(Op::Spread(source));
+        }
+        None => self.compile_node(item.as_ref())?,
+      }
+    }
+
+    self.emit(Op::Array(data.len()));
+    Ok(())
+  }
+
+  fn compile_hash(&mut self, hash: &Hash) -> ValueResult<()> {
+    let mut keys = vec![];
+
+    for (key, value) in hash.items() {
+      match value.as_ref().downcast_ref::<Spread>() {
+        Some(spread) if key.is_none() => {
+          self.compile_node(spread.value.as_ref())?
+        }
+        _ => self.compile_node(value.as_ref())?,
+      }
+
+      keys.push(key);
+    }
+
+    let keys = self.chunk().add_keys(keys);
+
+    self.emit(Op::Hash(keys));
+    Ok(())
+  }
+
+  fn compile_assign(&mut self, assign: &Assign) -> ValueResult<()> {
+    if !assign.target.as_ref().is::<Index>() {
+      return self.fail(&format!("cannot assign to `{}`", assign.target));
+    }
+
+    self.compile_node(assign.value.as_ref())?;
+    self.emit(Op::Dup);
+    self.compile_store(assign.target.as_ref())
+  }
+
+  // the removed value stays on the stack, the rest is stored back
+  fn compile_delete(&mut self, delete: &Delete) -> ValueResult<()> {
+    let index = match delete.target.as_ref().downcast_ref::<Index>() {
+      Some(index) => index,
+      None => return self.fail(&format!("cannot delete `{}`", delete.target)),
+    };
+
+    self.compile_node(index.lhs.as_ref())?;
+    self.compile_node(index.rhs.as_ref())?;
+    self.emit(Op::Remove);
+    self.compile_store(index.lhs.as_ref())
+  }
+
+  // stores the value on top in `target`, see `Assign::store`
+  fn compile_store(&mut self, target: &dyn Value) -> ValueResult<()> {
+    if let Some(identifier) = target.downcast_ref::<Identifier>() {
+      let place = self.resolve(&identifier.name);
+
+      self.emit(Op::Set(place));
+      return Ok(());
+    }
+
+    match target.downcast_ref::<Index>() {
+      Some(index) => {
+        self.compile_node(index.lhs.as_ref())?;
+        self.compile_node(index.rhs.as_ref())?;
+        self.emit(Op::SetIndex);
+        self.compile_store(index.lhs.as_ref())
+      }
+      None => self.fail(&format!("cannot assign to `{}`", target)),
+    }
+  }
+
+  // the locals in sight are lent to the tree-walker by name
+  fn compile_fallback(&mut self, node: Box<dyn Value>) -> ValueResult<()> {
+    let mut names = vec![];
+
+    for state in &self.states {
+      let locals = state.scopes.iter().flat_map(|scope| scope.iter());
+
+      for name in locals.map(|(name, _)| name).chain(&state.upvalues) {
+        if !names.contains(name) {
+          names.push(name.to_owned());
+        }
+      }
+    }
+
+    let bridge = names
+      .into_iter()
+      .map(|name| (name.to_owned(), self.resolve(&name)))
+      .filter(|(_, place)| !matches!(place, Place::Global(_)))
+      .collect();
+
+    let node = self.chunk().add_node(node);
+    let bridge = self.chunk().add_bridge(bridge);
+
+    self.emit(Op::Eval(node, bridge));
+    Ok(())
+  }
+
+  fn fail(&mut self, message: &str) -> ValueResult<()> {
+    let message = self.chunk().add_name(message);
+
+    self.emit(Op::Fail(message));
+    Ok(())
+  }
+
+  fn enter_loop(
+    &mut self,
+    label: &Option<String>,
+    keyword: Option<&'static str>,
+  ) -> usize {
+    let start = self.chunk().ops.len();
+
+    self.state().loops.push(Loop {
+      breaks: vec![],
+      keyword,
+      label: label.to_owned(),
+      start,
+    });
+
+    start
+  }
+
+  fn leave_loop(&mut self) {
+    let looping = self.state().loops.pop().unwrap();
+
+    for exit in looping.breaks {
+      self.chunk().patch(exit);
+    }
+  }
+
+  // a jump without label is aimed at the innermost loop
+  fn find_loop(&mut self, label: &Option<String>) -> Option<usize> {
+    self
+      .state()
+      .loops
+      .iter()
+      .rposition(|looping| label.is_none() || &looping.label == label)
+  }
+
+  fn begin_scope(&mut self) {
+    let state = self.state();
+
+    state.scopes.push(vec![]);
+    state.functions.push(vec![]);
+  }
+
+  fn end_scope(&mut self) {
+    let state = self.state();
+
+    state.scopes.pop();
+    state.functions.pop();
+  }
+
+  fn declare(&mut self, name: &str) -> Place {
+    if self.is_global() {
+      return Place::Global(self.chunk().add_name(name));
+    }
+
+    let state = self.state();
+    let slot = state.proto.slots.len();
+
+    state.proto.slots.push(name.into());
+    state.scopes.last_mut().unwrap().push((name.into(), slot));
+
+    Place::Local(slot)
+  }
+
+  fn is_declared_here(&mut self, name: &str) -> bool {
+    match self.state().scopes.last() {
+      Some(scope) => scope.iter().any(|(local, _)| local == name),
+      None => false,
+    }
+  }
+
+  fn is_global(&mut self) -> bool {
+    self.states.len() == 1 && self.state().scopes.is_empty()
+  }
+
+  fn resolve(&mut self, name: &str) -> Place {
+    let level = self.states.len() - 1;
+
+    match self.resolve_at(level, name) {
+      Some(place) => place,
+      None => Place::Global(self.chunk().add_name(name)),
+    }
+  }
+
+  fn resolve_at(&mut self, level: usize, name: &str) -> Option<Place> {
+    if let Some(slot) = self.states[level].local(name) {
+      return Some(Place::Local(slot));
+    }
+
+    if let Some(upvalue) = self.states[level].upvalue(name) {
+      return Some(Place::Upvalue(upvalue));
+    }
+
+    if level == 0 {
+      return None;
+    }
+
+    match self.resolve_at(level - 1, name)? {
+      Place::Global(_) => None,
+      _ => {
+        self.states[level].upvalues.push(name.into());
+
+        Some(Place::Upvalue(self.states[level].upvalues.len() - 1))
+      }
+    }
+  }
+
+  // where the function just compiled takes `name` from in the current one
+  fn capture(&mut self, name: &str) -> Capture {
+    match self.resolve(name) {
+      Place::Local(slot) => Capture::Local(slot),
+      Place::Upvalue(upvalue) => Capture::Upvalue(upvalue),
+      Place::Global(_) => unreachable!("globals are not captured"),
+    }
+  }
+
+  fn declaration(statement: &dyn Value) -> Option<&Function> {
+    let node = match statement.downcast_ref::<Statement>() {
+      Some(statement) => statement.node.as_ref(),
+      None => statement,
+    };
+
+    node.downcast_ref::<Function>()
+  }
+
+  fn emit(&mut self, op: Op) -> usize {
+    self.chunk().emit(op)
+  }
+
+  fn chunk(&mut self) -> &mut Chunk {
+    &mut self.state().proto.chunk
+  }
+
+  fn state(&mut self) -> &mut State {
+    self.states.last_mut().unwrap()
+  }
+}
@@ -0,0 +1,1621 @@
+use crate::analyzer::interpreter::ValueResult;
+use crate::compiler::bytecode::{
+  Binder, Capture, Chunk, Op, Place, Proto, Span, Target,
+};
+use crate::compiler::source::Source;
+use crate::reporter::location;
+use crate::tokenizer::kind::{CommentKind, TokenKind};
+use crate::tokenizer::token::Token;
+use crate::tokenizer::tokenify;
+use crate::value::instruction::assign::Assign;
+use crate::value::instruction::closure::Closure;
+use crate::value::instruction::comment::Comment;
+use crate::value::instruction::delete::Delete;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::group::Group;
+use crate::value::instruction::hash::HashKey;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::pattern::Pattern;
+use crate::value::instruction::spread::Spread;
+use crate::value::instruction::ty::{Ty, TyKind};
+use crate::value::instruction::{
+  Array, Binary, Block, Break, Call, Continue, Expression, Function, Hash,
+  Identifier, If, Impl, Index, Instance, LoopForIn, LoopInfinite, LoopWhile,
+  Match, Member, Module, Outcome, Program, Public, Range, Return, Statement,
+  Struct, Trait, Try, Unary, Use, Val,
+};
+use crate::value::primitive::{
+  bool::Bool, char::Char, float::Float, int::Int, nil::Nil, str::Str,
+};
+use crate::value::{Value, Values};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// a bytecode file is the magic, the version and the function table, the
+// program comes last and every function before the ones that create it.
+// the integers are little-endian, the nodes the machine hands over to the
+// tree-walker are kept as their tree, the ones that are only shown come back
+// as `Source`
+pub const MAGIC: &[u8; 4] = b"\0q5c";
+pub const VERSION: u16 = 2;
+
+pub fn is_bytecode(bytes: &[u8]) -> bool {
+  bytes.starts_with(MAGIC)
+}
+
+pub fn write(program: &Proto) -> Vec<u8> {
+  let mut table = vec![];
+
+  Encoder::table(program, &mut table);
+
+  let mut encoder = Encoder::new();
+  let mut indices = HashMap::new();
+
+  encoder.bytes(MAGIC);
+  encoder.u16(VERSION);
+  encoder.usize(table.len());
+
+  for (index, proto) in table.iter().enumerate() {
+    encoder.proto(proto, index + 1 == table.len(), &indices);
+    indices.insert(*proto as *const Proto, index);
+  }
+
+  encoder.bytes
+}
+
+pub fn read(bytes: &[u8]) -> ValueResult<Proto> {
+  let mut decoder = Decoder::new(bytes);
+
+  if decoder.take(MAGIC.len())? != MAGIC {
+    return Err(String::from("not a bytecode file"));
+  }
+
+  let version = decoder.u16()?;
+
+  if version != VERSION {
+    return Err(format!("bytecode version {} is not supported", version));
+  }
+
+  let mut table: Vec<Rc<Proto>> = vec![];
+
+  for index in (0..decoder.len()?).rev() {
+    let proto = decoder.proto(index == 0, &table)?;
+
+    table.push(Rc::new(proto));
+  }
+
+  if decoder.at != bytes.len() {
+    return Err(String::from("bytecode file has trailing bytes"));
+  }
+
+  match table.pop() {
+    Some(program) => Ok((*program).to_owned()),
+    None => Err(String::from("bytecode file has no program")),
+  }
+}
+
+struct Encoder {
+  bytes: Vec<u8>,
+}
+
+impl Encoder {
+  fn new() -> Self {
+    Encoder { bytes: vec![] }
+  }
+
+  // the functions a proto creates come before it
+  fn table<'a>(proto: &'a Proto, table: &mut Vec<&'a Proto>) {
+    for child in &proto.chunk.protos {
+      Encoder::table(child, table);
+    }
+
+    table.push(proto);
+  }
+
+  fn bytes(&mut self, bytes: &[u8]) {
+    self.bytes.extend_from_slice(bytes);
+  }
+
+  fn u8(&mut self, value: u8) {
+    self.bytes.push(value);
+  }
+
+  fn u16(&mut self, value: u16) {
+    self.bytes(&value.to_le_bytes());
+  }
+
+  fn usize(&mut self, value: usize) {
+    self.bytes(&(value as u32).to_le_bytes());
+  }
+
+  fn bool(&mut self, value: bool) {
+    self.u8(value as u8);
+  }
+
+  fn str(&mut self, value: &str) {
+    self.usize(value.len());
+    self.bytes(value.as_bytes());
+  }
+
+  fn strs(&mut self, values: &[String]) {
+    self.usize(values.len());
+
+    for value in values {
+      self.str(value);
+    }
+  }
+
+  fn proto(
+    &mut self,
+    proto: &Proto,
+    program: bool,
+    indices: &HashMap<*const Proto, usize>,
+  ) {
+    self.str(&proto.name);
+
+    // the code of the program is not kept, a function keeps it to be shown
+    if !program {
+      self.str(&proto.node.text());
+    }
+
+    self.usize(proto.params.len());
+
+    for param in &proto.params {
+      self.param(param.as_ref().downcast_ref::<FunctionArg>().unwrap());
+    }
+
+    self.strs(&proto.slots);
+    self.usize(proto.captures.len());
+
+    for (name, capture) in &proto.captures {
+      self.str(name);
+
+      match capture {
+        Capture::Local(slot) => self.pair(0, *slot),
+        Capture::Upvalue(index) => self.pair(1, *index),
+      }
+    }
+
+    self.chunk(&proto.chunk, indices);
+  }
+
+  fn chunk(&mut self, chunk: &Chunk, indices: &HashMap<*const Proto, usize>) {
+    self.usize(chunk.ops.len());
+
+    for op in &chunk.ops {
+      self.op(op);
+    }
+
+    self.values(&chunk.constants);
+    self.strs(&chunk.names);
+    self.usize(chunk.nodes.len());
+
+    for node in &chunk.nodes {
+      self.node(node.as_ref());
+    }
+
+    self.usize(chunk.tokens.len());
+
+    for token in &chunk.tokens {
+      self.str(&token.literal);
+    }
+
+    self.usize(chunk.binders.len());
+
+    for binder in &chunk.binders {
+      self.target(&binder.target);
+      self.places(&binder.places);
+    }
+
+    self.usize(chunk.keys.len());
+
+    for keys in &chunk.keys {
+      self.usize(keys.len());
+
+      for key in keys {
+        self.bool(key.is_some());
+
+        if let Some(key) = key {
+          self.key(key);
+        }
+      }
+    }
+
+    self.usize(chunk.bridges.len());
+
+    for bridge in &chunk.bridges {
+      self.places(bridge);
+    }
+
+    self.usize(chunk.protos.len());
+
+    for proto in &chunk.protos {
+      self.usize(indices[&Rc::as_ptr(proto)]);
+    }
+
+    self.usize(chunk.spans.len());
+
+    for span in &chunk.spans {
+      self.usize(span.start);
+      self.usize(span.end);
+      self.str(&span.source);
+    }
+  }
+
+  // the default is compiled with the body, only whether there is one is kept
+  fn param(&mut self, param: &FunctionArg) {
+    self.str(&param.name.text());
+    self.str(&param.kind.text());
+    self.bool(param.pattern.is_some());
+
+    if let Some(pattern) = &param.pattern {
+      self.pattern(pattern);
+    }
+
+    self.bool(param.default.is_some());
+
+    if let Some(default) = &param.default {
+      self.str(&default.text());
+    }
+
+    self.bool(param.variadic);
+  }
+
+  fn key(&mut self, key: &HashKey) {
+    match key {
+      HashKey::Bool(value) => {
+        self.u8(0);
+        self.bool(*value);
+      }
+      HashKey::Char(value) => {
+        self.u8(1);
+        self.bytes(&(*value as u32).to_le_bytes());
+      }
+      HashKey::Int(value) => {
+        self.u8(2);
+        self.bytes(&value.to_le_bytes());
+      }
+      HashKey::Str(value) => {
+        self.u8(3);
+        self.str(value);
+      }
+      HashKey::Tuple(keys) => {
+        self.u8(4);
+        self.usize(keys.len());
+
+        for key in keys {
+          self.key(key);
+        }
+      }
+      HashKey::Value(value) => {
+        self.u8(5);
+        self.node(value.as_ref());
+      }
+    }
+  }
+
+  fn pair(&mut self, tag: u8, operand: usize) {
+    self.u8(tag);
+    self.usize(operand);
+  }
+
+  fn op(&mut self, op: &Op) {
+    match *op {
+      Op::Constant(constant) => self.pair(0, constant),
+      Op::Nil => self.u8(1),
+      Op::Pop => self.u8(2),
+      Op::Dup => self.u8(3),
+      Op::Get(place) => {
+        self.u8(4);
+        self.place(place);
+      }
+      Op::Set(place) => {
+        self.u8(5);
+        self.place(place);
+      }
+      Op::Reserve(slot) => self.pair(6, slot),
+      Op::Bind(binder) => self.pair(7, binder),
+      Op::Declared(node) => self.pair(8, node),
+      Op::Arg(param, jump) => {
+        self.pair(9, param);
+        self.usize(jump);
+      }
+      Op::Closure(proto) => self.pair(10, proto),
+      Op::Binary(token) => self.pair(11, token),
+      Op::Unary(token) => self.pair(12, token),
+      Op::Jump(jump) => self.pair(13, jump),
+      Op::JumpIfFalse(jump) => self.pair(14, jump),
+      Op::JumpIfNotNil(jump) => self.pair(15, jump),
+      Op::Call(argc) => self.pair(16, argc),
+      Op::Invoke(name, argc, optional) => {
+        self.pair(17, name);
+        self.usize(argc);
+        self.bool(optional);
+      }
+      Op::Named(name) => self.pair(18, name),
+      Op::Spread(source) => self.pair(19, source),
+      Op::Array(len) => self.pair(20, len),
+      Op::Hash(keys) => self.pair(21, keys),
+      Op::Outcome(ok) => {
+        self.u8(22);
+        self.bool(ok);
+      }
+      Op::Try => self.u8(23),
+      Op::Member(name, optional) => {
+        self.pair(24, name);
+        self.bool(optional);
+      }
+      Op::Index => self.u8(25),
+      Op::SetIndex => self.u8(26),
+      Op::Remove => self.u8(27),
+      Op::Range(inclusive, stepped) => {
+        self.u8(28);
+        self.bool(inclusive);
+        self.bool(stepped);
+      }
+      Op::Match(binder, next) => {
+        self.pair(29, binder);
+        self.usize(next);
+      }
+      Op::NoMatch => self.u8(30),
+      Op::Enter => self.u8(31),
+      Op::Leave => self.u8(32),
+      Op::Iterate => self.u8(33),
+      Op::Next(done) => self.pair(34, done),
+      Op::Drop => self.u8(35),
+      Op::Break(depth) => self.pair(36, depth),
+      Op::Continue(depth) => self.pair(37, depth),
+      Op::Eval(node, bridge) => {
+        self.pair(38, node);
+        self.usize(bridge);
+      }
+      Op::Fail(message) => self.pair(39, message),
+      Op::Return => self.u8(40),
+    }
+  }
+
+  fn place(&mut self, place: Place) {
+    match place {
+      Place::Local(slot) => self.pair(0, slot),
+      Place::Upvalue(index) => self.pair(1, index),
+      Place::Global(name) => self.pair(2, name),
+    }
+  }
+
+  fn places(&mut self, places: &[(String, Place)]) {
+    self.usize(places.len());
+
+    for (name, place) in places {
+      self.str(name);
+      self.place(*place);
+    }
+  }
+
+  fn value(&mut self, value: &dyn Value) {
+    if value.is::<Nil>() {
+      self.u8(0);
+    } else if let Some(bool) = value.downcast_ref::<Bool>() {
+      self.u8(1);
+      self.bool(bool.value);
+    } else if let Some(int) = value.downcast_ref::<Int>() {
+      self.u8(2);
+      self.bytes(&int.value.to_le_bytes());
+    } else if let Some(float) = value.downcast_ref::<Float>() {
+      self.u8(3);
+      self.bytes(&float.value.to_bits().to_le_bytes());
+    } else if let Some(char) = value.downcast_ref::<Char>() {
+      self.u8(4);
+      self.bytes(&(char.value as u32).to_le_bytes());
+    } else if let Some(str) = value.downcast_ref::<Str>() {
+      self.u8(5);
+      self.str(&str.value);
+    } else {
+      self.u8(6);
+      self.str(&value.text());
+    }
+  }
+
+  fn values(&mut self, values: &Values) {
+    self.usize(values.len());
+
+    for value in values {
+      self.value(value.as_ref());
+    }
+  }
+
+  // a node left to the tree-walker, what is not one of the nodes a program
+  // is made of is kept as a constant
+  fn node(&mut self, node: &dyn Value) {
+    if let Some(array) = node.downcast_ref::<Array>() {
+      self.u8(1);
+      self.nodes(&array.data);
+    } else if let Some(assign) = node.downcast_ref::<Assign>() {
+      self.u8(2);
+      self.node(assign.target.as_ref());
+      self.node(assign.value.as_ref());
+    } else if let Some(binary) = node.downcast_ref::<Binary>() {
+      self.u8(3);
+      self.node(binary.lhs.as_ref());
+      self.str(&binary.operator.literal);
+      self.node(binary.rhs.as_ref());
+    } else if let Some(block) = node.downcast_ref::<Block>() {
+      self.u8(4);
+      self.usize(block.spans.len());
+
+      for span in &block.spans {
+        self.usize(span.start);
+        self.usize(span.end);
+      }
+
+      self.nodes(&block.statements);
+    } else if let Some(call) = node.downcast_ref::<Call>() {
+      self.u8(5);
+      self.node(call.callee.as_ref());
+      self.list(&call.args);
+    } else if let Some(closure) = node.downcast_ref::<Closure>() {
+      self.u8(6);
+      self.list(&closure.args);
+      self.option(&closure.kind);
+      self.option(&closure.block);
+    } else if let Some(comment) = node.downcast_ref::<Comment>() {
+      self.u8(7);
+      self.str(&comment.value);
+    } else if let Some(delete) = node.downcast_ref::<Delete>() {
+      self.u8(8);
+      self.node(delete.target.as_ref());
+    } else if let Some(expression) = node.downcast_ref::<Expression>() {
+      self.u8(9);
+      self.node(expression.node.as_ref());
+    } else if let Some(function) = node.downcast_ref::<Function>() {
+      self.u8(10);
+      self.node(function.name.as_ref());
+      self.nodes(&function.generics);
+      self.list(&function.args);
+      self.option(&function.kind);
+      self.option(&function.block);
+    } else if let Some(arg) = node.downcast_ref::<FunctionArg>() {
+      self.u8(11);
+      self.node(arg.name.as_ref());
+      self.node(arg.kind.as_ref());
+      self.bool(arg.pattern.is_some());
+
+      if let Some(pattern) = &arg.pattern {
+        self.pattern(pattern);
+      }
+
+      self.option(&arg.default);
+      self.bool(arg.variadic);
+    } else if let Some(group) = node.downcast_ref::<Group>() {
+      self.u8(12);
+      self.node(group.expression.as_ref());
+    } else if let Some(hash) = node.downcast_ref::<Hash>() {
+      let items = hash.items();
+
+      self.u8(13);
+      self.usize(items.len());
+
+      for (key, value) in &items {
+        self.bool(key.is_some());
+
+        if let Some(key) = key {
+          self.key(key);
+        }
+
+        self.node(value.as_ref());
+      }
+    } else if let Some(identifier) = node.downcast_ref::<Identifier>() {
+      self.u8(14);
+      self.str(&identifier.name);
+    } else if let Some(control) = node.downcast_ref::<If>() {
+      self.u8(15);
+      self.node(control.condition.as_ref());
+      self.node(control.consequence.as_ref());
+      self.option(&control.alternative);
+    } else if let Some(implementation) = node.downcast_ref::<Impl>() {
+      self.u8(16);
+      self.node(implementation.ty.as_ref());
+      self.option(&implementation.interface);
+      self.nodes(&implementation.functions);
+    } else if let Some(index) = node.downcast_ref::<Index>() {
+      self.u8(17);
+      self.node(index.lhs.as_ref());
+      self.node(index.rhs.as_ref());
+    } else if let Some(instance) = node.downcast_ref::<Instance>() {
+      self.u8(18);
+      self.node(instance.name.as_ref());
+      self.usize(instance.fields.len());
+
+      for (name, value) in &instance.fields {
+        self.str(name);
+        self.node(value.as_ref());
+      }
+    } else if let Some(looping) = node.downcast_ref::<LoopForIn>() {
+      self.u8(19);
+      self.label(&looping.label);
+      self.nodes(&looping.variables);
+      self.node(looping.iterable.as_ref());
+      self.node(looping.block.as_ref());
+    } else if let Some(looping) = node.downcast_ref::<LoopInfinite>() {
+      self.u8(20);
+      self.label(&looping.label);
+      self.node(looping.block.as_ref());
+    } else if let Some(looping) = node.downcast_ref::<LoopWhile>() {
+      self.u8(21);
+      self.label(&looping.label);
+      self.node(looping.condition.as_ref());
+      self.node(looping.block.as_ref());
+    } else if let Some(matching) = node.downcast_ref::<Match>() {
+      self.u8(22);
+      self.node(matching.subject.as_ref());
+      self.usize(matching.arms.len());
+
+      for (pattern, body) in &matching.arms {
+        self.pattern(pattern);
+        self.node(body.as_ref());
+      }
+    } else if let Some(member) = node.downcast_ref::<Member>() {
+      self.u8(23);
+      self.node(member.object.as_ref());
+      self.bool(member.optional);
+      self.node(member.property.as_ref());
+    } else if let Some(module) = node.downcast_ref::<Module>() {
+      self.u8(24);
+      self.node(module.name.as_ref());
+      self.option(&module.block);
+    } else if let Some(arg) = node.downcast_ref::<NamedArg>() {
+      self.u8(25);
+      self.str(&arg.name);
+      self.node(arg.value.as_ref());
+    } else if let Some(outcome) = node.downcast_ref::<Outcome>() {
+      self.u8(26);
+      self.bool(outcome.is_ok);
+      self.node(outcome.value.as_ref());
+    } else if let Some(public) = node.downcast_ref::<Public>() {
+      self.u8(27);
+      self.node(public.node.as_ref());
+    } else if let Some(range) = node.downcast_ref::<Range>() {
+      self.u8(28);
+      self.node(range.start.as_ref());
+      self.node(range.end.as_ref());
+      self.bool(range.inclusive);
+      self.option(&range.step);
+    } else if let Some(jump) = node.downcast_ref::<Return>() {
+      self.u8(29);
+      self.option(&jump.value);
+    } else if let Some(spread) = node.downcast_ref::<Spread>() {
+      self.u8(30);
+      self.node(spread.value.as_ref());
+    } else if let Some(statement) = node.downcast_ref::<Statement>() {
+      self.u8(31);
+      self.node(statement.node.as_ref());
+    } else if let Some(structure) = node.downcast_ref::<Struct>() {
+      self.u8(32);
+      self.node(structure.name.as_ref());
+      self.nodes(&structure.generics);
+      self.nodes(&structure.fields);
+    } else if let Some(interface) = node.downcast_ref::<Trait>() {
+      self.u8(33);
+      self.node(interface.name.as_ref());
+      self.nodes(&interface.functions);
+    } else if let Some(attempt) = node.downcast_ref::<Try>() {
+      self.u8(34);
+      self.node(attempt.value.as_ref());
+    } else if let Some(ty) = node.downcast_ref::<Ty>() {
+      self.u8(35);
+      self.ty(&ty.kind());
+    } else if let Some(unary) = node.downcast_ref::<Unary>() {
+      self.u8(36);
+      self.str(&unary.operand.literal);
+      self.node(unary.rhs.as_ref());
+    } else if let Some(import) = node.downcast_ref::<Use>() {
+      self.u8(37);
+      self.nodes(&import.path);
+      self.nodes(&import.items);
+      self.bool(import.glob);
+    } else if let Some(val) = node.downcast_ref::<Val>() {
+      self.u8(38);
+      self.bool(val.immutable);
+      self.option(&val.kind);
+      self.node(val.name.as_ref());
+      self.bool(val.pattern.is_some());
+
+      if let Some(pattern) = &val.pattern {
+        self.pattern(pattern);
+      }
+
+      self.option(&val.value);
+    } else if let Some(jump) = node.downcast_ref::<Break>() {
+      self.u8(39);
+      self.label(&jump.label);
+      self.option(&jump.value);
+    } else if let Some(jump) = node.downcast_ref::<Continue>() {
+      self.u8(40);
+      self.label(&jump.label);
+    } else {
+      self.u8(0);
+      self.value(node);
+    }
+  }
+
+  fn nodes(&mut self, nodes: &[Box<dyn Value>]) {
+    self.usize(nodes.len());
+
+    for node in nodes {
+      self.node(node.as_ref());
+    }
+  }
+
+  fn option(&mut self, node: &Option<Box<dyn Value>>) {
+    self.bool(node.is_some());
+
+    if let Some(node) = node {
+      self.node(node.as_ref());
+    }
+  }
+
+  fn list(&mut self, nodes: &Option<Vec<Box<dyn Value>>>) {
+    self.bool(nodes.is_some());
+
+    if let Some(nodes) = nodes {
+      self.nodes(nodes);
+    }
+  }
+
+  fn label(&mut self, label: &Option<String>) {
+    self.bool(label.is_some());
+
+    if let Some(label) = label {
+      self.str(label);
+    }
+  }
+
+  fn ty(&mut self, kind: &TyKind) {
+    match kind {
+      TyKind::Array => self.u8(0),
+      TyKind::ArrayOf(kind) => {
+        self.u8(1);
+        self.ty(kind);
+      }
+      TyKind::Bool => self.u8(2),
+      TyKind::Char => self.u8(3),
+      TyKind::Float => self.u8(4),
+      TyKind::Fn => self.u8(5),
+      TyKind::Hash => self.u8(6),
+      TyKind::Infer => self.u8(7),
+      TyKind::Int => self.u8(8),
+      TyKind::Named(name, args) => {
+        self.u8(9);
+        self.str(name);
+        self.usize(args.len());
+
+        for arg in args {
+          self.ty(arg);
+        }
+      }
+      TyKind::Nil => self.u8(10),
+      TyKind::Optional(kind) => {
+        self.u8(11);
+        self.ty(kind);
+      }
+      TyKind::Str => self.u8(12),
+    }
+  }
+
+  fn target(&mut self, target: &Target) {
+    match target {
+      Target::Val(pattern) => {
+        self.u8(0);
+        self.bool(pattern.is_some());
+
+        if let Some(pattern) = pattern {
+          self.pattern(pattern);
+        }
+      }
+      Target::Param(param) => self.pair(1, *param),
+      Target::Loop(variables) => {
+        self.u8(2);
+        self.strs(variables);
+      }
+      Target::Arm(pattern) => {
+        self.u8(3);
+        self.pattern(pattern);
+      }
+      Target::Function => self.u8(4),
+    }
+  }
+
+  fn pattern(&mut self, pattern: &Pattern) {
+    match pattern {
+      Pattern::Array(patterns) => {
+        self.u8(0);
+        self.patterns(patterns);
+      }
+      Pattern::Binding(name) => {
+        self.u8(1);
+        self.str(name);
+      }
+      Pattern::Hash(fields) => {
+        self.u8(2);
+        self.fields(fields);
+      }
+      Pattern::Literal(value) => {
+        self.u8(3);
+        self.node(value.as_ref());
+      }
+      Pattern::Outcome(ok, pattern) => {
+        self.u8(4);
+        self.bool(*ok);
+        self.pattern(pattern);
+      }
+      Pattern::Rest(name) => {
+        self.u8(5);
+        self.bool(name.is_some());
+
+        if let Some(name) = name {
+          self.str(name);
+        }
+      }
+      Pattern::Struct(name, fields) => {
+        self.u8(6);
+        self.str(name);
+        self.fields(fields);
+      }
+      Pattern::Tuple(patterns) => {
+        self.u8(7);
+        self.patterns(patterns);
+      }
+      Pattern::Wildcard => self.u8(8),
+    }
+  }
+
+  fn patterns(&mut self, patterns: &[Pattern]) {
+    self.usize(patterns.len());
+
+    for pattern in patterns {
+      self.pattern(pattern);
+    }
+  }
+
+  fn fields(&mut self, fields: &[(String, Pattern)]) {
+    self.usize(fields.len());
+
+    for (name, pattern) in fields {
+      self.str(name);
+      self.pattern(pattern);
+    }
+  }
+}
+
+// how deep the stack and the iterators of a frame are before an op, and where
+// the loops it is in start
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Depth {
+  stack: usize,
+  marks: Vec<(usize, usize)>,
+  iters: usize,
+}
+
+impl Depth {
+  fn unbalanced() -> String {
+    String::from("bytecode file has an unbalanced stack")
+  }
+
+  fn shift(&mut self, pops: usize, pushes: usize) -> ValueResult<()> {
+    match self.stack.checked_sub(pops) {
+      Some(stack) => {
+        self.stack = stack + pushes;
+        Ok(())
+      }
+      None => Err(Depth::unbalanced()),
+    }
+  }
+
+  // see `Frame::unwind`
+  fn unwind(&mut self, loops: usize) -> ValueResult<()> {
+    if loops >= self.marks.len() {
+      return Err(Depth::unbalanced());
+    }
+
+    self.marks.truncate(self.marks.len() - loops);
+
+    let (stack, iters) = self.marks[self.marks.len() - 1];
+
+    match stack <= self.stack && iters <= self.iters {
+      true => {
+        self.stack = stack;
+        self.iters = iters;
+        Ok(())
+      }
+      false => Err(Depth::unbalanced()),
+    }
+  }
+}
+
+struct Decoder<'a> {
+  bytes: &'a [u8],
+  at: usize,
+}
+
+impl<'a> Decoder<'a> {
+  fn new(bytes: &'a [u8]) -> Self {
+    Decoder { bytes, at: 0 }
+  }
+
+  fn take(&mut self, len: usize) -> ValueResult<&'a [u8]> {
+    match self.bytes.get(self.at..self.at + len) {
+      Some(bytes) => {
+        self.at += len;
+        Ok(bytes)
+      }
+      None => Err(String::from("bytecode file is truncated")),
+    }
+  }
+
+  fn array<const N: usize>(&mut self) -> ValueResult<[u8; N]> {
+    let mut array = [0; N];
+
+    array.copy_from_slice(self.take(N)?);
+    Ok(array)
+  }
+
+  fn u8(&mut self) -> ValueResult<u8> {
+    Ok(self.take(1)?[0])
+  }
+
+  fn u16(&mut self) -> ValueResult<u16> {
+    Ok(u16::from_le_bytes(self.array()?))
+  }
+
+  fn usize(&mut self) -> ValueResult<usize> {
+    Ok(u32::from_le_bytes(self.array()?) as usize)
+  }
+
+  // every item takes a byte at least, a count past the end of the file is
+  // refused before anything is made for it
+  fn len(&mut self) -> ValueResult<usize> {
+    let len = self.usize()?;
+
+    match len <= self.bytes.len() - self.at {
+      true => Ok(len),
+      false => Err(String::from("bytecode file is truncated")),
+    }
+  }
+
+  fn bool(&mut self) -> ValueResult<bool> {
+    Ok(self.u8()? != 0)
+  }
+
+  fn str(&mut self) -> ValueResult<String> {
+    let len = self.len()?;
+
+    match String::from_utf8(self.take(len)?.to_vec()) {
+      Ok(value) => Ok(value),
+      Err(_) => Err(String::from("bytecode file has a malformed string")),
+    }
+  }
+
+  fn strs(&mut self) -> ValueResult<Vec<String>> {
+    (0..self.len()?).map(|_| self.str()).collect()
+  }
+
+  fn unknown<T>(&self, what: &str, tag: u8) -> ValueResult<T> {
+    Err(format!("bytecode file has an unknown {} `{}`", what, tag))
+  }
+
+  fn proto(
+    &mut self,
+    program: bool,
+    table: &[Rc<Proto>],
+  ) -> ValueResult<Proto> {
+    let name = self.str()?;
+
+    let node = match program {
+      true => Program::default().boxed(),
+      false => Source::new(&self.str()?).boxed(),
+    };
+
+    let params = (0..self.len()?)
+      .map(|_| self.param())
+      .collect::<ValueResult<Values>>()?;
+
+    let mut proto = Proto::new(&name, node, params);
+
+    proto.slots = self.strs()?;
+
+    for _ in 0..self.len()? {
+      let name = self.str()?;
+      let capture = match self.u8()? {
+        0 => Capture::Local(self.usize()?),
+        1 => Capture::Upvalue(self.usize()?),
+        tag => return self.unknown("capture", tag),
+      };
+
+      proto.captures.push((name, capture));
+    }
+
+    proto.chunk = self.chunk(table)?;
+    Decoder::check(&proto)?;
+
+    Ok(proto)
+  }
+
+  // the machine trusts the operands to be in their tables and the jumps to
+  // land in the code, a file that breaks this is refused when it is read
+  fn check(proto: &Proto) -> ValueResult<()> {
+    let chunk = &proto.chunk;
+    let within = |index: usize, len: usize| match index < len {
+      true => Ok(()),
+      false => Err(String::from("bytecode file has an operand out of range")),
+    };
+    let jump = |target: usize| match target < chunk.ops.len() {
+      true => Ok(()),
+      false => Err(String::from("bytecode file has a jump out of its code")),
+    };
+    let place = |place: Place| match place {
+      Place::Local(slot) => within(slot, proto.slots.len()),
+      Place::Upvalue(upvalue) => within(upvalue, proto.captures.len()),
+      Place::Global(name) => within(name, chunk.names.len()),
+    };
+
+    for op in &chunk.ops {
+      match *op {
+        Op::Constant(constant) => within(constant, chunk.constants.len()),
+        Op::Get(target) | Op::Set(target) => place(target),
+        Op::Reserve(slot) => within(slot, proto.slots.len()),
+        Op::Bind(binder) => within(binder, chunk.binders.len()),
+        Op::Declared(node) => within(node, chunk.nodes.len()),
+        Op::Arg(param, target) => {
+          within(param, proto.params.len()).and(jump(target))
+        }
+        Op::Closure(child) => within(child, chunk.protos.len()),
+        Op::Binary(token) | Op::Unary(token) => {
+          within(token, chunk.tokens.len())
+        }
+        Op::Jump(target)
+        | Op::JumpIfFalse(target)
+        | Op::JumpIfNotNil(target)
+        | Op::Next(target) => jump(target),
+        Op::Invoke(name, _, _)
+        | Op::Named(name)
+        | Op::Spread(name)
+        | Op::Member(name, _)
+        | Op::Fail(name) => within(name, chunk.names.len()),
+        Op::Hash(keys) => within(keys, chunk.keys.len()),
+        Op::Match(binder, next) => {
+          within(binder, chunk.binders.len()).and(jump(next))
+        }
+        Op::Eval(node, bridge) => within(node, chunk.nodes.len())
+          .and(within(bridge, chunk.bridges.len())),
+        _ => Ok(()),
+      }?;
+    }
+
+    for binder in &chunk.binders {
+      if let Target::Param(param) = binder.target {
+        within(param, proto.params.len())?;
+      }
+    }
+
+    for (_, target) in chunk.binders.iter().flat_map(|binder| &binder.places) {
+      place(*target)?;
+    }
+
+    for (_, target) in chunk.bridges.iter().flatten() {
+      place(*target)?;
+    }
+
+    // the cells of a closure are taken from the function creating it
+    for (_, capture) in chunk.protos.iter().flat_map(|child| &child.captures) {
+      match *capture {
+        Capture::Local(slot) => within(slot, proto.slots.len()),
+        Capture::Upvalue(upvalue) => within(upvalue, proto.captures.len()),
+      }?;
+    }
+
+    for binder in &chunk.binders {
+      let is_named =
+        matches!(binder.target, Target::Val(None) | Target::Function);
+
+      if is_named && binder.places.is_empty() {
+        return Err(String::from("bytecode file has a malformed binding"));
+      }
+    }
+
+    // the tree-walker gets the cells of locals, globals are in its scope
+    for (_, target) in chunk.bridges.iter().flatten() {
+      if let Place::Global(_) = target {
+        return Err(String::from("bytecode file has a malformed binding"));
+      }
+    }
+
+    match chunk.ops.last() {
+      Some(Op::Return) => Decoder::balance(chunk),
+      _ => Err(String::from(
+        "bytecode file has code that runs past its end",
+      )),
+    }
+  }
+
+  // every path through the code is followed with how deep the stack, the
+  // loops and the iterators are, an op never takes more than there is and
+  // the paths meeting on an op agree on it
+  fn balance(chunk: &Chunk) -> ValueResult<()> {
+    let mut seen: Vec<Option<Depth>> = vec![None; chunk.ops.len()];
+    let mut paths = vec![(0, Depth::default())];
+
+    while let Some((pc, depth)) = paths.pop() {
+      match &seen[pc] {
+        Some(known) if *known == depth => continue,
+        Some(_) => return Err(Depth::unbalanced()),
+        None => seen[pc] = Some(depth.clone()),
+      }
+
+      let mut next = depth;
+      let mut jump = None;
+
+      match chunk.ops[pc] {
+        Op::Constant(_)
+        | Op::Nil
+        | Op::Get(_)
+        | Op::Closure(_)
+        | Op::Eval(_, _) => next.shift(0, 1)?,
+        Op::Pop | Op::Set(_) | Op::Bind(_) => next.shift(1, 0)?,
+        Op::Dup => next.shift(1, 2)?,
+        Op::Reserve(_) => {}
+        Op::Declared(_)
+        | Op::Unary(_)
+        | Op::Named(_)
+        | Op::Spread(_)
+        | Op::Outcome(_)
+        | Op::Try
+        | Op::Member(_, _) => next.shift(1, 1)?,
+        Op::Binary(_) | Op::Index => next.shift(2, 1)?,
+        Op::SetIndex => next.shift(3, 1)?,
+        Op::Remove => next.shift(2, 2)?,
+        Op::Range(_, stepped) => next.shift(2 + stepped as usize, 1)?,
+        Op::Call(len) | Op::Invoke(_, len, _) => next.shift(len + 1, 1)?,
+        Op::Array(len) => next.shift(len, 1)?,
+        Op::Hash(keys) => next.shift(chunk.keys[keys].len(), 1)?,
+        Op::Arg(_, target) => {
+          let mut taken = next.clone();
+
+          taken.shift(0, 1)?;
+          jump = Some((target, taken));
+        }
+        Op::Jump(target) => {
+          paths.push((target, next));
+          continue;
+        }
+        Op::JumpIfFalse(target) => {
+          next.shift(1, 0)?;
+          jump = Some((target, next.clone()));
+        }
+        Op::JumpIfNotNil(target) | Op::Match(_, target) => {
+          next.shift(1, 1)?;
+          jump = Some((target, next.clone()));
+          next.shift(1, 0)?;
+        }
+        Op::Enter => next.marks.push((next.stack, next.iters)),
+        Op::Leave => {
+          next.marks.pop().ok_or_else(Depth::unbalanced)?;
+        }
+        Op::Iterate => {
+          next.shift(1, 0)?;
+          next.iters += 1;
+        }
+        Op::Next(done) => {
+          if next.iters == 0 {
+            return Err(Depth::unbalanced());
+          }
+
+          jump = Some((done, next.clone()));
+          next.shift(0, 1)?;
+        }
+        Op::Drop => match next.iters.checked_sub(1) {
+          Some(iters) => next.iters = iters,
+          None => return Err(Depth::unbalanced()),
+        },
+        Op::Break(loops) => {
+          next.shift(1, 0)?;
+          next.unwind(loops)?;
+          next.marks.pop();
+          next.shift(0, 1)?;
+        }
+        Op::Continue(loops) => next.unwind(loops)?,
+        Op::NoMatch | Op::Return => {
+          next.shift(1, 0)?;
+          continue;
+        }
+        Op::Fail(_) => continue,
+      }
+
+      if let Some(jump) = jump {
+        paths.push(jump);
+      }
+
+      // only a `return` can end the code, checked before
+      paths.push((pc + 1, next));
+    }
+
+    Ok(())
+  }
+
+  fn chunk(&mut self, table: &[Rc<Proto>]) -> ValueResult<Chunk> {
+    let mut chunk = Chunk::new();
+
+    for _ in 0..self.len()? {
+      let op = self.op()?;
+
+      chunk.ops.push(op);
+    }
+
+    chunk.constants = self.values()?;
+    chunk.names = self.strs()?;
+
+    for _ in 0..self.len()? {
+      let node = self.node()?;
+
+      chunk.nodes.push(node);
+    }
+
+    for _ in 0..self.len()? {
+      let token = Decoder::token(&self.str()?)?;
+
+      chunk.tokens.push(token);
+    }
+
+    for _ in 0..self.len()? {
+      let target = self.target()?;
+      let places = self.places()?;
+
+      chunk.binders.push(Binder::new(target, places));
+    }
+
+    for _ in 0..self.len()? {
+      let mut keys = vec![];
+
+      for _ in 0..self.len()? {
+        match self.bool()? {
+          true => keys.push(Some(self.key()?)),
+          false => keys.push(None),
+        }
+      }
+
+      chunk.keys.push(keys);
+    }
+
+    for _ in 0..self.len()? {
+      let bridge = self.places()?;
+
+      chunk.bridges.push(bridge);
+    }
+
+    for _ in 0..self.len()? {
+      match table.get(self.usize()?) {
+        Some(proto) => chunk.protos.push(proto.to_owned()),
+        None => {
+          return Err(String::from("bytecode file has an unknown function"))
+        }
+      }
+    }
+
+    for _ in 0..self.len()? {
+      let start = self.usize()?;
+      let end = self.usize()?;
+      let source = self.str()?;
+
+      chunk.spans.push(Span::new(start, end, &source));
+    }
+
+    Ok(chunk)
+  }
+
+  fn param(&mut self) -> ValueResult<Box<dyn Value>> {
+    let name = Identifier::new(&self.str()?).boxed();
+    let kind = Source::new(&self.str()?).boxed();
+    let mut param = FunctionArg::new(name, kind);
+
+    if self.bool()? {
+      param.add_pattern(self.pattern()?);
+    }
+
+    if self.bool()? {
+      param.add_default(Source::new(&self.str()?).boxed());
+    }
+
+    Ok(param.add_variadic(self.bool()?).boxed())
+  }
+
+  fn key(&mut self) -> ValueResult<HashKey> {
+    match self.u8()? {
+      0 => Ok(HashKey::Bool(self.bool()?)),
+      1 => match std::char::from_u32(u32::from_le_bytes(self.array()?)) {
+        Some(char) => Ok(HashKey::Char(char)),
+        None => Err(String::from("bytecode file has a malformed char")),
+      },
+      2 => Ok(HashKey::Int(i64::from_le_bytes(self.array()?))),
+      3 => Ok(HashKey::Str(self.str()?)),
+      4 => {
+        let keys = (0..self.len()?)
+          .map(|_| self.key())
+          .collect::<ValueResult<Vec<HashKey>>>()?;
+
+        Ok(HashKey::Tuple(keys))
+      }
+      5 => Ok(HashKey::Value(self.node()?)),
+      tag => self.unknown("key", tag),
+    }
+  }
+
+  fn op(&mut self) -> ValueResult<Op> {
+    let op = match self.u8()? {
+      0 => Op::Constant(self.usize()?),
+      1 => Op::Nil,
+      2 => Op::Pop,
+      3 => Op::Dup,
+      4 => Op::Get(self.place()?),
+      5 => Op::Set(self.place()?),
+      6 => Op::Reserve(self.usize()?),
+      7 => Op::Bind(self.usize()?),
+      8 => Op::Declared(self.usize()?),
+      9 => Op::Arg(self.usize()?, self.usize()?),
+      10 => Op::Closure(self.usize()?),
+      11 => Op::Binary(self.usize()?),
+      12 => Op::Unary(self.usize()?),
+      13 => Op::Jump(self.usize()?),
+      14 => Op::JumpIfFalse(self.usize()?),
+      15 => Op::JumpIfNotNil(self.usize()?),
+      16 => Op::Call(self.usize()?),
+      17 => Op::Invoke(self.usize()?, self.usize()?, self.bool()?),
+      18 => Op::Named(self.usize()?),
+      19 => Op::Spread(self.usize()?),
+      20 => Op::Array(self.usize()?),
+      21 => Op::Hash(self.usize()?),
+      22 => Op::Outcome(self.bool()?),
+      23 => Op::Try,
+      24 => Op::Member(self.usize()?, self.bool()?),
+      25 => Op::Index,
+      26 => Op::SetIndex,
+      27 => Op::Remove,
+      28 => Op::Range(self.bool()?, self.bool()?),
+      29 => Op::Match(self.usize()?, self.usize()?),
+      30 => Op::NoMatch,
+      31 => Op::Enter,
+      32 => Op::Leave,
+      33 => Op::Iterate,
+      34 => Op::Next(self.usize()?),
+      35 => Op::Drop,
+      36 => Op::Break(self.usize()?),
+      37 => Op::Continue(self.usize()?),
+      38 => Op::Eval(self.usize()?, self.usize()?),
+      39 => Op::Fail(self.usize()?),
+      40 => Op::Return,
+      tag => return self.unknown("instruction", tag),
+    };
+
+    Ok(op)
+  }
+
+  fn place(&mut self) -> ValueResult<Place> {
+    match self.u8()? {
+      0 => Ok(Place::Local(self.usize()?)),
+      1 => Ok(Place::Upvalue(self.usize()?)),
+      2 => Ok(Place::Global(self.usize()?)),
+      tag => self.unknown("place", tag),
+    }
+  }
+
+  fn places(&mut self) -> ValueResult<Vec<(String, Place)>> {
+    (0..self.len()?)
+      .map(|_| Ok((self.str()?, self.place()?)))
+      .collect()
+  }
+
+  fn value(&mut self) -> ValueResult<Box<dyn Value>> {
+    match self.u8()? {
+      0 => Ok(Nil::new().boxed()),
+      1 => Ok(Bool::new(self.bool()?).boxed()),
+      2 => Ok(Int::new(i64::from_le_bytes(self.array()?)).boxed()),
+      3 => {
+        let bits = u64::from_le_bytes(self.array()?);
+
+        Ok(Float::new(f64::from_bits(bits)).boxed())
+      }
+      4 => match std::char::from_u32(u32::from_le_bytes(self.array()?)) {
+        Some(char) => Ok(Char::new(char).boxed()),
+        None => Err(String::from("bytecode file has a malformed char")),
+      },
+      5 => Ok(Str::new(&self.str()?).boxed()),
+      6 => Ok(Source::new(&self.str()?).boxed()),
+      tag => self.unknown("constant", tag),
+    }
+  }
+
+  fn values(&mut self) -> ValueResult<Values> {
+    (0..self.len()?).map(|_| self.value()).collect()
+  }
+
+  fn target(&mut self) -> ValueResult<Target> {
+    match self.u8()? {
+      0 => match self.bool()? {
+        true => Ok(Target::Val(Some(self.pattern()?))),
+        false => Ok(Target::Val(None)),
+      },
+      1 => Ok(Target::Param(self.usize()?)),
+      2 => Ok(Target::Loop(self.strs()?)),
+      3 => Ok(Target::Arm(self.pattern()?)),
+      4 => Ok(Target::Function),
+      tag => self.unknown("binding", tag),
+    }
+  }
+
+  fn pattern(&mut self) -> ValueResult<Pattern> {
+    match self.u8()? {
+      0 => Ok(Pattern::Array(self.patterns()?)),
+      1 => Ok(Pattern::Binding(self.str()?)),
+      2 => Ok(Pattern::Hash(self.fields()?)),
+      3 => Ok(Pattern::Literal(self.node()?)),
+      4 => Ok(Pattern::Outcome(self.bool()?, Box::new(self.pattern()?))),
+      5 => match self.bool()? {
+        true => Ok(Pattern::Rest(Some(self.str()?))),
+        false => Ok(Pattern::Rest(None)),
+      },
+      6 => Ok(Pattern::Struct(self.str()?, self.fields()?)),
+      7 => Ok(Pattern::Tuple(self.patterns()?)),
+      8 => Ok(Pattern::Wildcard),
+      tag => self.unknown("pattern", tag),
+    }
+  }
+
+  fn patterns(&mut self) -> ValueResult<Vec<Pattern>> {
+    (0..self.len()?).map(|_| self.pattern()).collect()
+  }
+
+  fn fields(&mut self) -> ValueResult<Vec<(String, Pattern)>> {
+    (0..self.len()?)
+      .map(|_| Ok((self.str()?, self.pattern()?)))
+      .collect()
+  }
+
+  fn node(&mut self) -> ValueResult<Box<dyn Value>> {
+    let node = match self.u8()? {
+      0 => return self.value(),
+      1 => Array::new(self.nodes()?).boxed(),
+      2 => Assign::new(self.node()?, self.node()?).boxed(),
+      3 => {
+        let lhs = self.node()?;
+        let operator = Decoder::token(&self.str()?)?;
+
+        Binary::new(lhs, Box::new(operator), self.node()?).boxed()
+      }
+      4 => {
+        let spans = (0..self.len()?)
+          .map(|_| Ok(location::Span::new(self.usize()?, self.usize()?)))
+          .collect::<ValueResult<Vec<location::Span>>>()?;
+        let mut block = Block::new(self.nodes()?);
+
+        block.spans = spans;
+        block.boxed()
+      }
+      5 => Call {
+        callee: self.node()?,
+        args: self.list()?,
+      }
+      .boxed(),
+      6 => Closure {
+        args: self.list()?,
+        kind: self.option()?,
+        block: self.option()?,
+        identity: None,
+        scope: None,
+      }
+      .boxed(),
+      7 => Comment::new(TokenKind::Comment(CommentKind::Line), &self.str()?)
+        .boxed(),
+      8 => Delete::new(self.node()?).boxed(),
+      9 => Expression::new(self.node()?).boxed(),
+      10 => Function {
+        name: self.node()?,
+        generics: self.nodes()?,
+        args: self.list()?,
+        kind: self.option()?,
+        block: self.option()?,
+        scope: None,
+      }
+      .boxed(),
+      11 => {
+        let mut arg = FunctionArg::new(self.node()?, self.node()?);
+
+        if self.bool()? {
+          arg.add_pattern(self.pattern()?);
+        }
+
+        if let Some(default) = self.option()? {
+          arg.add_default(default);
+        }
+
+        arg.add_variadic(self.bool()?).boxed()
+      }
+      12 => Group::new(self.node()?).boxed(),
+      13 => {
+        let mut hash = Hash::default();
+
+        for _ in 0..self.len()? {
+          match self.bool()? {
+            true => hash.add_item((self.key()?, self.node()?)),
+            false => hash.add_spread(self.node()?),
+          };
+        }
+
+        hash.boxed()
+      }
+      14 => Identifier::new(&self.str()?).boxed(),
+      15 => If::new(self.node()?, self.node()?, self.option()?).boxed(),
+      16 => Impl::new(self.node()?, self.option()?, self.nodes()?).boxed(),
+      17 => Index::new(self.node()?, self.node()?).boxed(),
+      18 => {
+        let name = self.node()?;
+        let fields = (0..self.len()?)
+          .map(|_| Ok((self.str()?, self.node()?)))
+          .collect::<ValueResult<Vec<(String, Box<dyn Value>)>>>()?;
+
+        Instance::new(name, fields).boxed()
+      }
+      19 => {
+        let label = self.label()?;
+        let variables = self.nodes()?;
+        let iterable = self.node()?;
+        let mut looping = LoopForIn::new(self.node()?, iterable, variables);
+
+        looping.label = label;
+        looping.boxed()
+      }
+      20 => {
+        let label = self.label()?;
+        let mut looping = LoopInfinite::new(self.node()?);
+
+        looping.label = label;
+        looping.boxed()
+      }
+      21 => {
+        let label = self.label()?;
+        let condition = self.node()?;
+        let mut looping = LoopWhile::new(self.node()?, condition);
+
+        looping.label = label;
+        looping.boxed()
+      }
+      22 => {
+        let subject = self.node()?;
+        let arms = (0..self.len()?)
+          .map(|_| Ok((self.pattern()?, self.node()?)))
+          .collect::<ValueResult<Vec<(Pattern, Box<dyn Value>)>>>()?;
+
+        Match::new(subject, arms).boxed()
+      }
+      23 => {
+        let object = self.node()?;
+        let optional = self.bool()?;
+        let mut member = Member::new(object, self.node()?);
+
+        member.optional = optional;
+        member.boxed()
+      }
+      24 => Module::new(self.node()?, self.option()?).boxed(),
+      25 => NamedArg::new(&self.str()?, self.node()?).boxed(),
+      26 => Outcome::new(self.bool()?, self.node()?).boxed(),
+      27 => Public::new(self.node()?).boxed(),
+      28 => {
+        let mut range = Range::new(self.node()?, self.node()?, self.bool()?);
+
+        if let Some(step) = self.option()? {
+          range.add_step(step);
+        }
+
+        range.boxed()
+      }
+      29 => Return::new(self.option()?).boxed(),
+      30 => Spread::new(self.node()?).boxed(),
+      31 => Statement::new(self.node()?).boxed(),
+      32 => {
+        let name = self.node()?;
+        let generics = self.nodes()?;
+
+        Struct::new(name, self.nodes()?)
+          .add_generics(generics)
+          .boxed()
+      }
+      33 => Trait::new(self.node()?, self.nodes()?).boxed(),
+      34 => Try::new(self.node()?).boxed(),
+      35 => Ty::new(self.ty()?).boxed(),
+      36 => {
+        let operand = Decoder::token(&self.str()?)?;
+
+        Unary::new(Box::new(operand), self.node()?).boxed()
+      }
+      37 => {
+        let mut import = Use::new(self.nodes()?, self.nodes()?);
+
+        import.glob = self.bool()?;
+        import.boxed()
+      }
+      38 => {
+        let immutable = self.bool()?;
+        let kind = self.option()?;
+        let name = self.node()?;
+        let pattern = match self.bool()? {
+          true => Some(self.pattern()?),
+          false => None,
+        };
+        let mut val = Val::new(immutable, kind, name, self.option()?);
+
+        val.pattern = pattern;
+        val.boxed()
+      }
+      39 => Break::new(self.label()?, self.option()?).boxed(),
+      40 => Continue::new(self.label()?).boxed(),
+      tag => return self.unknown("node", tag),
+    };
+
+    Ok(node)
+  }
+
+  fn nodes(&mut self) -> ValueResult<Values> {
+    (0..self.len()?).map(|_| self.node()).collect()
+  }
+
+  fn option(&mut self) -> ValueResult<Option<Box<dyn Value>>> {
+    match self.bool()? {
+      true => Ok(Some(self.node()?)),
+      false => Ok(None),
+    }
+  }
+
+  fn list(&mut self) -> ValueResult<Option<Values>> {
+    match self.bool()? {
+      true => Ok(Some(self.nodes()?)),
+      false => Ok(None),
+    }
+  }
+
+  fn label(&mut self) -> ValueResult<Option<String>> {
+    match self.bool()? {
+      true => Ok(Some(self.str()?)),
+      false => Ok(None),
+    }
+  }
+
+  fn ty(&mut self) -> ValueResult<TyKind> {
+    match self.u8()? {
+      0 => Ok(TyKind::Array),
+      1 => Ok(TyKind::ArrayOf(Box::new(self.ty()?))),
+      2 => Ok(TyKind::Bool),
+      3 => Ok(TyKind::Char),
+      4 => Ok(TyKind::Float),
+      5 => Ok(TyKind::Fn),
+      6 => Ok(TyKind::Hash),
+      7 => Ok(TyKind::Infer),
+      8 => Ok(TyKind::Int),
+      9 => {
+        let name = self.str()?;
+        let args = (0..self.len()?)
+          .map(|_| self.ty())
+          .collect::<ValueResult<Vec<TyKind>>>()?;
+
+        Ok(TyKind::Named(name, args))
+      }
+      10 => Ok(TyKind::Nil),
+      11 => Ok(TyKind::Optional(Box::new(self.ty()?))),
+      12 => Ok(TyKind::Str),
+      tag => self.unknown("type", tag),
+    }
+  }
+
+  fn token(literal: &str) -> ValueResult<Token> {
+    match tokenify(literal).next() {
+      Some(token) => Ok(token),
+      None => Err(String::from("bytecode file has a malformed operator")),
+    }
+  }
+}
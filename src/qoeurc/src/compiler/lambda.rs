@@ -0,0 +1,79 @@
+use crate::analyzer::environment::scope::TScope;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::compiler::bytecode::Proto;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+// a variable of the machine, shared with the closures capturing it, empty
+// while a function declared later in its block is not there yet
+pub type Slot = Rc<RefCell<Option<Box<dyn Value>>>>;
+
+// a compiled function or closure with the cells it captured, it reads the
+// same as the code it comes from
+#[derive(Clone, Debug)]
+pub struct Lambda {
+  pub globals: TScope,
+  // given when the lambda is made, as the one of a `Closure`
+  pub identity: Rc<()>,
+  pub proto: Rc<Proto>,
+  pub upvalues: Vec<Slot>,
+}
+
+impl fmt::Display for Lambda {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Lambda {
+  fn eval(
+    &mut self,
+    _interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Closure
+  }
+
+  fn text(&self) -> String {
+    self.proto.node.text()
+  }
+}
+
+impl Lambda {
+  pub fn new(globals: TScope, proto: Rc<Proto>, upvalues: Vec<Slot>) -> Self {
+    Lambda {
+      globals,
+      identity: Rc::new(()),
+      proto,
+      upvalues,
+    }
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn name(&self) -> String {
+    self.proto.name.to_owned()
+  }
+
+  // copies of the lambda made by one run of its code
+  pub fn is_same(&self, rhs: &Lambda) -> bool {
+    Rc::ptr_eq(&self.identity, &rhs.identity)
+  }
+
+  pub fn address(&self) -> usize {
+    Rc::as_ptr(&self.proto) as usize
+  }
+}
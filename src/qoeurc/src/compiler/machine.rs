@@ -0,0 +1,759 @@
+use crate::analyzer::environment::scope::Scope;
+use crate::analyzer::environment::signal::Signal;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::compiler::bytecode::{
+  Binder, Capture, Chunk, Op, Place, Proto, Target,
+};
+use crate::compiler::lambda::{Lambda, Slot};
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::hash::Entries;
+use crate::value::instruction::loop_for_in::Items;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::spread::Spread;
+use crate::value::instruction::{
+  Array, Binary, Call, Hash, IKind, Identifier, Index, Instance, LoopForIn,
+  Member, Outcome, Range, Unary, Val,
+};
+use crate::value::{Value, Values};
+use crate::void;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// where a loop starts on the stack and among the iterators, a jump out of it
+// drops what is above
+#[derive(Clone, Copy, Debug)]
+struct Mark {
+  stack: usize,
+  iters: usize,
+}
+
+// a running function: its variables, its operands, its loops and where it is
+// at
+struct Frame {
+  proto: Rc<Proto>,
+  pc: usize,
+  upvalues: Vec<Slot>,
+  slots: Vec<Slot>,
+  args: Vec<Option<Box<dyn Value>>>,
+  stack: Values,
+  marks: Vec<Mark>,
+  iters: Vec<Items>,
+}
+
+impl Frame {
+  fn new(
+    proto: Rc<Proto>,
+    upvalues: Vec<Slot>,
+    args: Vec<Option<Box<dyn Value>>>,
+  ) -> Self {
+    let slots = proto.slots.iter().map(|_| Machine::slot(None)).collect();
+
+    Frame {
+      args,
+      iters: vec![],
+      marks: vec![],
+      pc: 0,
+      proto,
+      slots,
+      stack: vec![],
+      upvalues,
+    }
+  }
+
+  fn push(&mut self, value: Box<dyn Value>) {
+    self.stack.push(value);
+  }
+
+  // a file is checked when it is read, an op taking more than the stack
+  // holds is still an error rather than a crash
+  fn pop(&mut self) -> ValueResult<Box<dyn Value>> {
+    self.stack.pop().ok_or_else(Frame::unbalanced)
+  }
+
+  fn pop_many(&mut self, len: usize) -> ValueResult<Values> {
+    match self.stack.len().checked_sub(len) {
+      Some(at) => Ok(self.stack.split_off(at)),
+      None => Err(Frame::unbalanced()),
+    }
+  }
+
+  fn peek(&self) -> ValueResult<&dyn Value> {
+    match self.stack.last() {
+      Some(value) => Ok(value.as_ref()),
+      None => Err(Frame::unbalanced()),
+    }
+  }
+
+  fn unbalanced() -> String {
+    String::from("the stack of a frame is unbalanced")
+  }
+
+  // leaves the `depth` innermost loops and drops what the next one holds
+  fn unwind(&mut self, depth: usize) -> ValueResult<Mark> {
+    if depth >= self.marks.len() {
+      return Err(String::from("a jump leaves a loop it is not in"));
+    }
+
+    self.marks.truncate(self.marks.len() - depth);
+
+    let mark = self.marks[self.marks.len() - 1];
+
+    self.stack.truncate(mark.stack);
+    self.iters.truncate(mark.iters);
+    Ok(mark)
+  }
+
+  fn cell(&self, place: Place) -> Option<&Slot> {
+    match place {
+      Place::Local(slot) => Some(&self.slots[slot]),
+      Place::Upvalue(upvalue) => Some(&self.upvalues[upvalue]),
+      Place::Global(_) => None,
+    }
+  }
+
+  fn name(&self, place: Place) -> String {
+    match place {
+      Place::Local(slot) => self.proto.slots[slot].to_owned(),
+      Place::Upvalue(upvalue) => self.proto.captures[upvalue].0.to_owned(),
+      Place::Global(name) => self.proto.chunk.names[name].to_owned(),
+    }
+  }
+}
+
+// what the tree-walker gave back for a node the compiler left to it
+enum Resume {
+  Value(Box<dyn Value>),
+  Return(Box<dyn Value>),
+}
+
+// why a frame stopped running: it called a compiled function, whose frame
+// runs in the interpreter next to it, or it returned
+enum Exit {
+  Call(Box<Interpreter>, Frame),
+  Return(Box<dyn Value>),
+}
+
+pub struct Machine;
+
+impl Machine {
+  // runs a compiled program in the scope of `interpreter`
+  pub fn run(
+    interpreter: &mut Interpreter,
+    proto: Proto,
+  ) -> ValueResult<Box<dyn Value>> {
+    Machine::execute(interpreter, Frame::new(Rc::new(proto), vec![], vec![]))
+  }
+
+  // a compiled function called by the tree-walker or by a native
+  pub fn call(
+    interpreter: &mut Interpreter,
+    lambda: &Lambda,
+    args_expected: Values,
+  ) -> ValueResult<Box<dyn Value>> {
+    let (mut interpreter_with_outer, frame) =
+      Machine::enter(interpreter, lambda, args_expected)?;
+    let value = Machine::execute(&mut interpreter_with_outer, frame);
+
+    interpreter.meter.borrow_mut().leave();
+    value
+  }
+
+  // the arguments are assigned to the parameters as `Call::call_function`
+  // does, the body runs in the scope the function was made in
+  fn enter(
+    interpreter: &mut Interpreter,
+    lambda: &Lambda,
+    args_expected: Values,
+  ) -> ValueResult<(Interpreter, Frame)> {
+    let proto = &lambda.proto;
+    let assigned = FunctionArg::assign(
+      &lambda.name(),
+      &proto.params,
+      Call::label_args(args_expected),
+    )?;
+
+    let args = proto
+      .params
+      .iter()
+      .zip(assigned)
+      .map(|(param, mut args)| {
+        let param = param.as_ref().downcast_ref::<FunctionArg>().unwrap();
+
+        match (param.variadic, args.is_empty()) {
+          (true, _) => Some(Array::new(args).boxed()),
+          (false, true) => None,
+          (false, false) => Some(args.remove(0)),
+        }
+      })
+      .collect();
+
+    let mut interpreter_with_outer = interpreter.new_with_scope(Scope::new());
+
+    interpreter_with_outer.scope = lambda.globals.to_owned();
+    interpreter.meter.borrow_mut().enter()?;
+
+    let frame = Frame::new(proto.to_owned(), lambda.upvalues.to_owned(), args);
+
+    Ok((interpreter_with_outer, frame))
+  }
+
+  // the frames of the calls between compiled functions are kept here rather
+  // than on the stack of rust, each one with the interpreter it runs in
+  fn execute(
+    interpreter: &mut Interpreter,
+    frame: Frame,
+  ) -> ValueResult<Box<dyn Value>> {
+    let mut frames = vec![frame];
+    let mut interpreters = vec![];
+
+    loop {
+      let frame = frames.last_mut().unwrap();
+      let exit = match interpreters.last_mut() {
+        Some(interpreter) => Machine::resume(interpreter, frame),
+        None => Machine::resume(interpreter, frame),
+      };
+
+      match exit {
+        Ok(Exit::Call(interpreter, frame)) => {
+          interpreters.push(*interpreter);
+          frames.push(frame);
+        }
+        Ok(Exit::Return(value)) => {
+          frames.pop();
+
+          if interpreters.pop().is_none() {
+            return Ok(value);
+          }
+
+          interpreter.meter.borrow_mut().leave();
+          frames.last_mut().unwrap().push(value);
+        }
+        Err(error) => {
+          for _ in interpreters {
+            interpreter.meter.borrow_mut().leave();
+          }
+
+          return Err(error);
+        }
+      }
+    }
+  }
+
+  // runs a frame until it calls a compiled function or returns
+  fn resume(
+    interpreter: &mut Interpreter,
+    frame: &mut Frame,
+  ) -> ValueResult<Exit> {
+    let proto = frame.proto.to_owned();
+    let chunk = &proto.chunk;
+    let mut pc = frame.pc;
+
+    loop {
+      interpreter.step()?;
+
+      let op = match chunk.ops.get(pc) {
+        Some(op) => *op,
+        None => return Err(String::from("a frame runs past its code")),
+      };
+
+      pc += 1;
+
+      match op {
+        Op::Constant(constant) => {
+          frame.push(chunk.constants[constant].to_owned())
+        }
+        Op::Nil => frame.push(void!()),
+        Op::Pop => {
+          frame.pop()?;
+        }
+        Op::Dup => {
+          let value = frame.peek()?.boxed();
+
+          frame.push(value);
+        }
+        Op::Get(place) => {
+          let value = Machine::get(interpreter, frame, place)?;
+
+          frame.push(value);
+        }
+        Op::Set(place) => {
+          let value = frame.pop()?;
+
+          Machine::set(interpreter, frame, place, value)?;
+        }
+        Op::Reserve(slot) => frame.slots[slot] = Machine::slot(None),
+        Op::Bind(binder) => {
+          let value = frame.pop()?;
+
+          Machine::bind(interpreter, frame, &chunk.binders[binder], value)?;
+        }
+        Op::Declared(node) => {
+          let value = frame.pop()?;
+          let mut node = chunk.nodes[node].to_owned();
+
+          if let Some(val) = node.downcast_mut::<Val>() {
+            val.add_value(value);
+          }
+
+          frame.push(node);
+        }
+        Op::Arg(param, jump) => {
+          if let Some(arg) = frame.args[param].take() {
+            frame.push(arg);
+            pc = jump;
+          }
+        }
+        Op::Closure(proto) => {
+          let proto = chunk.protos[proto].to_owned();
+          let upvalues = proto
+            .captures
+            .iter()
+            .map(|(_, capture)| match capture {
+              Capture::Local(slot) => frame.slots[*slot].to_owned(),
+              Capture::Upvalue(upvalue) => frame.upvalues[*upvalue].to_owned(),
+            })
+            .collect();
+
+          let lambda =
+            Lambda::new(interpreter.scope.to_owned(), proto, upvalues);
+
+          frame.push(lambda.boxed());
+        }
+        Op::Binary(token) => {
+          let rhs = frame.pop()?;
+          let lhs = frame.pop()?;
+          let value =
+            Binary::operate(interpreter, lhs, &chunk.tokens[token], rhs)?;
+
+          frame.push(value);
+        }
+        Op::Unary(token) => {
+          let rhs = frame.pop()?;
+
+          frame.push(Unary::operate(&chunk.tokens[token], rhs.as_ref())?);
+        }
+        Op::Jump(target) => pc = target,
+        Op::JumpIfFalse(target) => {
+          if !frame.pop()?.is_truthy() {
+            pc = target;
+          }
+        }
+        Op::JumpIfNotNil(target) => match frame.peek()?.ikind() {
+          IKind::Nil => {
+            frame.pop()?;
+          }
+          _ => pc = target,
+        },
+        Op::Call(len) => {
+          let callee = frame.pop()?;
+          let args = frame.pop_many(len)?;
+
+          if let Some(lambda) = callee.as_ref().downcast_ref::<Lambda>() {
+            let (interpreter, callee) =
+              Machine::enter(interpreter, lambda, args)?;
+
+            frame.pc = pc;
+            return Ok(Exit::Call(Box::new(interpreter), callee));
+          }
+
+          frame.push(Call::call_function(interpreter, callee.as_ref(), args)?);
+        }
+        Op::Invoke(name, len, optional) => {
+          let object = frame.pop()?;
+          let args = frame.pop_many(len)?;
+          let name = &chunk.names[name];
+
+          let (callee, args) =
+            match Machine::method(object, name, args, optional)? {
+              Some(method) => method,
+              None => {
+                frame.push(void!());
+                continue;
+              }
+            };
+
+          if let Some(lambda) = callee.as_ref().downcast_ref::<Lambda>() {
+            let (interpreter, callee) =
+              Machine::enter(interpreter, lambda, args)?;
+
+            frame.pc = pc;
+            return Ok(Exit::Call(Box::new(interpreter), callee));
+          }
+
+          frame.push(Call::call_function(interpreter, callee.as_ref(), args)?);
+        }
+        Op::Named(name) => {
+          let value = frame.pop()?;
+
+          frame.push(NamedArg::new(&chunk.names[name], value).boxed());
+        }
+        Op::Spread(source) => {
+          let value = frame.pop()?;
+
+          match value.ikind() {
+            IKind::Array(_) => frame.push(Spread::new(value).boxed()),
+            _ => {
+              return Err(format!(
+                "cannot spread `{}` in an array",
+                chunk.names[source]
+              ))
+            }
+          }
+        }
+        Op::Array(len) => {
+          let mut data = vec![];
+
+          for item in frame.pop_many(len)? {
+            match item.as_ref().downcast_ref::<Spread>() {
+              Some(spread) => match spread.value.ikind() {
+                IKind::Array(items) => data.extend(items),
+                _ => return Err(String::from("only arrays are spread")),
+              },
+              None => data.push(item),
+            }
+          }
+
+          let value = Array::new(data).boxed();
+
+          interpreter.allocate(value.as_ref())?;
+          frame.push(value);
+        }
+        Op::Hash(keys) => {
+          let keys = &chunk.keys[keys];
+          let mut data = Entries::new();
+
+          // a spread entry for a key that is already there replaces its value
+          for (key, value) in keys.iter().zip(frame.pop_many(keys.len())?) {
+            if let Some(key) = key {
+              data.insert(key.to_owned(), value);
+              continue;
+            }
+
+            match value.ikind() {
+              IKind::Hash(entries) => {
+                for (key, value) in entries {
+                  data.insert(key, value);
+                }
+              }
+              _ => {
+                return Err(format!(
+                  "cannot spread `{}` in a hash",
+                  value.text()
+                ))
+              }
+            }
+          }
+
+          let value = Hash::new(data).boxed();
+
+          interpreter.allocate(value.as_ref())?;
+          frame.push(value);
+        }
+        Op::Outcome(is_ok) => {
+          let value = frame.pop()?;
+
+          frame.push(Outcome::new(is_ok, value).boxed());
+        }
+        Op::Try => {
+          let value = frame.pop()?;
+
+          match value.ikind() {
+            IKind::Outcome(true, value) => frame.push(value),
+            IKind::Outcome(false, _) | IKind::Nil => {
+              return Ok(Exit::Return(value))
+            }
+            _ => frame.push(value),
+          }
+        }
+        Op::Member(name, optional) => {
+          let object = frame.pop()?;
+
+          frame.push(Member::get(
+            object.as_ref(),
+            &chunk.names[name],
+            optional,
+          )?);
+        }
+        Op::Index => {
+          let key = frame.pop()?;
+          let data = frame.pop()?;
+
+          frame.push(Index::get(data.as_ref(), key.as_ref())?);
+        }
+        Op::SetIndex => {
+          let key = frame.pop()?;
+          let data = frame.pop()?;
+          let value = frame.pop()?;
+
+          frame.push(Index::set(data.as_ref(), key.as_ref(), value)?);
+        }
+        Op::Remove => {
+          let key = frame.pop()?;
+          let data = frame.pop()?;
+          let (data, value) = Index::remove(data.as_ref(), key.as_ref())?;
+
+          frame.push(value);
+          frame.push(data);
+        }
+        Op::Range(inclusive, stepped) => {
+          let step = match stepped {
+            true => Some(frame.pop()?),
+            false => None,
+          };
+
+          let end = frame.pop()?;
+          let start = frame.pop()?;
+          let mut range =
+            Range::new(Range::bound(start)?, Range::bound(end)?, inclusive);
+
+          if let Some(step) = step {
+            let step = Range::bound(step)?;
+
+            if let IKind::Int(0) = step.ikind() {
+              return Err(String::from("range step cannot be zero"));
+            }
+
+            range.add_step(step);
+          }
+
+          frame.push(range.boxed());
+        }
+        Op::Match(binder, next) => {
+          let binder = &chunk.binders[binder];
+          let matched = match &binder.target {
+            Target::Arm(pattern) => pattern.matches(frame.peek()?),
+            _ => None,
+          };
+
+          match matched {
+            Some(bindings) => {
+              frame.pop()?;
+              Machine::define(interpreter, frame, binder, bindings)?;
+            }
+            None => pc = next,
+          }
+        }
+        Op::NoMatch => {
+          let subject = frame.pop()?;
+
+          return Err(format!(
+            "no arm of `match` matches `{}`",
+            subject.text()
+          ));
+        }
+        Op::Enter => frame.marks.push(Mark {
+          iters: frame.iters.len(),
+          stack: frame.stack.len(),
+        }),
+        Op::Leave => {
+          frame.marks.pop();
+        }
+        Op::Iterate => {
+          let iterable = frame.pop()?;
+
+          frame.iters.push(LoopForIn::items(iterable.as_ref())?);
+        }
+        Op::Next(done) => match frame.iters.last_mut() {
+          Some(iter) => match iter.next() {
+            Some(item) => frame.push(item),
+            None => pc = done,
+          },
+          None => return Err(String::from("a loop has nothing to iterate")),
+        },
+        Op::Drop => {
+          frame.iters.pop();
+        }
+        Op::Break(depth) => {
+          let value = frame.pop()?;
+
+          frame.unwind(depth)?;
+          frame.marks.pop();
+          frame.push(value);
+        }
+        Op::Continue(depth) => {
+          frame.unwind(depth)?;
+        }
+        Op::Eval(node, bridge) => {
+          match Machine::eval(interpreter, frame, chunk, node, bridge)? {
+            Resume::Value(value) => frame.push(value),
+            Resume::Return(value) => return Ok(Exit::Return(value)),
+          }
+        }
+        Op::Fail(message) => return Err(chunk.names[message].to_owned()),
+        Op::Return => return Ok(Exit::Return(frame.pop()?)),
+      }
+    }
+  }
+
+  fn get(
+    interpreter: &mut Interpreter,
+    frame: &Frame,
+    place: Place,
+  ) -> ValueResult<Box<dyn Value>> {
+    match frame.cell(place) {
+      Some(cell) => cell.borrow().to_owned().ok_or(format!(
+        "error eval unknown identifier expression: {}",
+        frame.name(place)
+      )),
+      None => Identifier::new(&frame.name(place)).eval(interpreter),
+    }
+  }
+
+  fn set(
+    interpreter: &mut Interpreter,
+    frame: &Frame,
+    place: Place,
+    value: Box<dyn Value>,
+  ) -> ValueResult<()> {
+    match frame.cell(place) {
+      Some(cell) => {
+        *cell.borrow_mut() = Some(value);
+        Ok(())
+      }
+      None => interpreter.scope_mut().assign(&frame.name(place), value),
+    }
+  }
+
+  fn bind(
+    interpreter: &mut Interpreter,
+    frame: &mut Frame,
+    binder: &Binder,
+    value: Box<dyn Value>,
+  ) -> ValueResult<()> {
+    let bindings = match &binder.target {
+      Target::Val(Some(pattern)) => {
+        pattern.matches(value.as_ref()).ok_or(format!(
+          "the pattern `{}` does not match `{}`",
+          pattern,
+          value.text(),
+        ))?
+      }
+      Target::Val(None) | Target::Function => match binder.places.first() {
+        Some((name, _)) => vec![(name.to_owned(), value)],
+        None => return Err(String::from("a binding has no name")),
+      },
+      Target::Param(param) => frame.proto.params[*param]
+        .as_ref()
+        .downcast_ref::<FunctionArg>()
+        .unwrap()
+        .bind(value)?,
+      Target::Loop(variables) => LoopForIn::bindings(variables, value)?,
+      Target::Arm(pattern) => {
+        pattern.matches(value.as_ref()).unwrap_or_default()
+      }
+    };
+
+    Machine::define(interpreter, frame, binder, bindings)
+  }
+
+  // a local gets a fresh cell, the closures made before keep the old one
+  fn define(
+    interpreter: &mut Interpreter,
+    frame: &mut Frame,
+    binder: &Binder,
+    bindings: Vec<(String, Box<dyn Value>)>,
+  ) -> ValueResult<()> {
+    for (name, value) in bindings {
+      match (binder.place(&name), &binder.target) {
+        (Some(Place::Local(slot)), _) => {
+          frame.slots[slot] = Machine::slot(Some(value))
+        }
+        (Some(Place::Global(_)), Target::Function) => {
+          interpreter.scope_mut().add_callable(&name, value)?
+        }
+        (Some(Place::Global(_)), _) => {
+          interpreter.scope_mut().add_binding(&name, value)?
+        }
+        _ => {}
+      }
+    }
+
+    Ok(())
+  }
+
+  // see `Call::eval`, struct values have methods, which get the value as
+  // their first argument, nothing is called on `nil` with `?.`
+  fn method(
+    object: Box<dyn Value>,
+    name: &str,
+    args_expected: Values,
+    optional: bool,
+  ) -> ValueResult<Option<(Box<dyn Value>, Values)>> {
+    if optional && object.ikind() == IKind::Nil {
+      return Ok(None);
+    }
+
+    if let Some(instance) = object.as_ref().downcast_ref::<Instance>() {
+      let method = instance.get_method(name).ok_or(format!(
+        "no method named `{}` found for `{}`",
+        name,
+        instance.name(),
+      ))?;
+
+      let mut args = vec![object.to_owned()];
+
+      args.extend(args_expected);
+
+      return Ok(Some((method, args)));
+    }
+
+    let callee = Member::get(object.as_ref(), name, optional)?;
+
+    Ok(Some((callee, args_expected)))
+  }
+
+  // the locals in sight are bound in a scope of their own for the
+  // tree-walker and read back once it is done with the node
+  fn eval(
+    interpreter: &mut Interpreter,
+    frame: &Frame,
+    chunk: &Chunk,
+    node: usize,
+    bridge: usize,
+  ) -> ValueResult<Resume> {
+    let mut node = chunk.nodes[node].to_owned();
+    let bridge = &chunk.bridges[bridge];
+
+    let value = match bridge.is_empty() {
+      true => node.eval(interpreter)?,
+      false => {
+        let mut scope = Scope::new_with_outer(interpreter.scope.to_owned());
+        let mut bound = vec![];
+
+        for (name, place) in bridge {
+          let cell = match frame.cell(*place) {
+            Some(cell) => cell,
+            None => return Err(format!("`{}` is not a local", name)),
+          };
+
+          if let Some(value) = cell.borrow().to_owned() {
+            scope.bind(name, value);
+            bound.push((name, cell));
+          }
+        }
+
+        let mut nested = interpreter.new_with_scope(scope);
+        let value = node.eval(&mut nested)?;
+
+        for (name, cell) in bound {
+          if let Some(value) = nested.scope().get_variable(name) {
+            *cell.borrow_mut() = Some(value);
+          }
+        }
+
+        value
+      }
+    };
+
+    match interpreter.take_signal() {
+      None => Ok(Resume::Value(value)),
+      Some(Signal::Return(value)) => Ok(Resume::Return(value)),
+      Some(signal) => Err(format!("`{}` outside of a loop", signal.keyword())),
+    }
+  }
+
+  fn slot(value: Option<Box<dyn Value>>) -> Slot {
+    Rc::new(RefCell::new(value))
+  }
+}
@@ -0,0 +1,59 @@
+pub mod bytecode;
+pub mod codegen;
+pub mod file;
+pub mod lambda;
+pub mod machine;
+pub mod source;
+
+#[cfg(test)]
+mod tests;
+
+use crate::analyzer::checker::Checker;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::resolver::Resolver;
+use crate::compiler::bytecode::Proto;
+use crate::compiler::codegen::Compiler;
+use crate::compiler::machine::Machine;
+use crate::converter::lowering::raise;
+use crate::converter::parser::Parser;
+use crate::optimizer::optimize;
+use crate::value::Value;
+
+pub fn compilify(input: &str, interpreter: &Interpreter) -> ValueResult<Proto> {
+  let ast = Parser::new(input).parse_ast()?;
+  let program = &raise(&ast);
+
+  Resolver::new(interpreter.scope.to_owned()).check(program.as_ref())?;
+  Checker::new(interpreter.scope.to_owned()).check(program.as_ref())?;
+
+  Compiler::compile(optimize(ast).as_ref())
+}
+
+// the same as `evalify`, on the bytecode machine
+pub fn vmify(
+  input: &str,
+  interpreter: &mut Interpreter,
+) -> ValueResult<Box<dyn Value>> {
+  let proto = compilify(input, interpreter)?;
+
+  Machine::run(interpreter, proto)
+}
+
+// the program written as a bytecode file, `loadify` runs it back
+pub fn bytecodify(
+  input: &str,
+  interpreter: &Interpreter,
+) -> ValueResult<Vec<u8>> {
+  let proto = compilify(input, interpreter)?;
+
+  Ok(file::write(&proto))
+}
+
+pub fn loadify(
+  bytes: &[u8],
+  interpreter: &mut Interpreter,
+) -> ValueResult<Box<dyn Value>> {
+  let proto = file::read(bytes)?;
+
+  Machine::run(interpreter, proto)
+}
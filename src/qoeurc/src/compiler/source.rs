@@ -0,0 +1,49 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+
+use std::fmt;
+
+// a node loaded from a bytecode file that is only ever shown, such as the
+// code of a function or a loop, it reads as the source it was written from
+#[derive(Clone, Debug)]
+pub struct Source {
+  pub code: String,
+}
+
+impl fmt::Display for Source {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Source {
+  fn eval(
+    &mut self,
+    _interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::NOOP
+  }
+
+  fn text(&self) -> String {
+    self.code.to_owned()
+  }
+}
+
+impl Source {
+  pub fn new(code: &str) -> Self {
+    Source { code: code.into() }
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
@@ -0,0 +1,356 @@
+use super::*;
+
+extern crate qutonium;
+use qutonium::prelude::*;
+
+use crate::analyzer::evalify;
+use crate::compiler::bytecode::{Op, Place};
+use crate::testing::suite;
+use crate::value::Values;
+
+fn vm(input: &str) -> Result<String, String> {
+  vmify(input, &mut Interpreter::new()).map(|value| value.text())
+}
+
+fn tree(input: &str) -> Result<String, String> {
+  evalify(input, &mut Interpreter::new()).map(|value| value.text())
+}
+
+#[test]
+fn machine_test() {
+  suite!("qoeurc::compiler::machine", {
+    "test expressions and variables" || {
+      must!(vm("val a := 1; val b := a + 2; b * 3;"); eq Ok("9".into()))?;
+      must!(vm("val s := \"a\"; s + \"b\";"); eq Ok("\"ab\"".into()))
+    }
+
+    "test functions, defaults and named arguments" || {
+      let input = "ƒ add(a: int, b: int = 10) -> int { return a + b; }
+        [add(1), add(1, b: 2)];";
+
+      must!(vm(input); eq Ok("[11, 3]".into()))
+    }
+
+    "test closures capture their variables" || {
+      let input = "ƒ counter(n: int) -> int {
+          val f := |x: int| { x + n };
+          return f(1);
+        }
+        counter(41);";
+
+      must!(vm(input); eq Ok("42".into()))
+    }
+
+    "test recursion through a local function" || {
+      let input = "ƒ f(n: int) -> int {
+          ƒ g(m: int) -> int {
+            if m <= 1 { return 1; }
+            return m * g(m - 1);
+          }
+          return g(n);
+        }
+        f(5);";
+
+      must!(vm(input); eq Ok("120".into()))
+    }
+
+    "test loops with labels, break and continue" || {
+      let input = "val a := [0];
+        for 0..10 |i| {
+          if i == 2 { continue; }
+          if i == 5 { break; }
+          a[0] = a[0] + i;
+        }
+        a[0];";
+
+      must!(vm(input); eq Ok("8".into()))?;
+      must!(vm("loop { break 3; };"); eq Ok("3".into()))
+    }
+
+    "test errors are the ones of the tree-walker" || {
+      must!(vm("break;"); eq tree("break;"))?;
+      must!(vm("val a := 1; val a := 2;"); eq tree("val a := 1; val a := 2;"))?;
+      must!(vm("unknown;"); eq tree("unknown;"))
+    }
+  });
+}
+
+const PROGRAMS: [&str; 8] = [
+  "val a := [1, 2, 3]; a[1] = 5; a;",
+  "val b := nil; b ?? 4;",
+  "val c := 1..=3; c;",
+  "map([1, 2], |x: int| { x * 2 });",
+  "ƒ f(a: int) -> int { return a; } [f(1), f(a: 2)];",
+  "val d := 2; match d { 1 => \"one\", _ => \"other\" };",
+  "val e := [1, 2]; [..e, 3];",
+  "upper(\"a\") == \"A\";",
+];
+
+#[test]
+fn parity_test() {
+  suite!("qoeurc::compiler::parity", {
+    "test the machine and the tree-walker agree" || {
+      let vm = PROGRAMS.iter().map(|input| vm(input)).collect::<Vec<_>>();
+      let tree = PROGRAMS.iter().map(|input| tree(input)).collect::<Vec<_>>();
+
+      must!(vm; eq tree)
+    }
+
+    "test calls do not grow the stack of rust" || {
+      // the size of the stack given to a thread by default
+      let run = |f: fn(&str) -> Result<String, String>, input: &'static str| {
+        std::thread::Builder::new()
+          .stack_size(2 * 1024 * 1024)
+          .spawn(move || f(input))
+          .unwrap()
+          .join()
+          .unwrap()
+      };
+
+      let endless = "ƒ f(n: int) -> int { return f(n + 1); } f(0);";
+      let deep = "ƒ f(n: int) -> int {
+          if n == 0 { return 0; }
+          return n + f(n - 1);
+        }
+        f(200);";
+
+      must!(run(vm, endless); eq run(tree, endless))?;
+      must!(
+        run(vm, endless);
+        eq Err("maximum call depth of 256 exceeded".into())
+      )?;
+      must!(run(vm, deep); eq run(tree, deep))
+    }
+  });
+}
+
+#[test]
+fn host_test() {
+  use crate::analyzer::environment::host::CaptureHost;
+  use crate::analyzer::environment::meter::Limits;
+
+  suite!("qoeurc::compiler::host", {
+    "test output goes through the host" || {
+      let host = CaptureHost::new().shared();
+      let mut interpreter = Interpreter::new();
+
+      interpreter.add_host(host.to_owned());
+      vmify("for 0..2 |i| { print(i); }", &mut interpreter).unwrap();
+
+      let host = host.borrow();
+
+      must!(host.stdout_lines(); eq vec!["0", "1"])
+    }
+
+    "test the limits hold on the machine" || {
+      let mut interpreter = Interpreter::new();
+
+      interpreter.add_limits(Limits::new().add_fuel(1000));
+
+      must!(
+        vmify("loop {}", &mut interpreter).map(|value| value.text());
+        eq Err("out of fuel after 1000 steps".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn file_test() {
+  fn load(input: &str) -> Result<String, String> {
+    let mut interpreter = Interpreter::new();
+    let bytes = bytecodify(input, &interpreter)?;
+
+    loadify(&bytes, &mut interpreter).map(|value| value.text())
+  }
+
+  suite!("qoeurc::compiler::file", {
+    "test programs run the same once loaded" || {
+      let vm = PROGRAMS.iter().map(|input| vm(input)).collect::<Vec<_>>();
+      let load = PROGRAMS.iter().map(|input| load(input)).collect::<Vec<_>>();
+
+      must!(load; eq vm)
+    }
+
+    "test nodes left to the tree-walker are read back" || {
+      let input = "struct Point { x: int, y: int }
+        val p := Point { x: 1, y: 2 };
+        val h := { \"a\": [p.x, 'c'], ..{ \"b\": 2 } };
+        ƒ f(a: int, b: int = 2) -> str { [{ \"k\": a + b }, '\\n']; }
+        [p.y, h, f(1), f];";
+
+      must!(load(input); eq vm(input))
+    }
+
+    "test the file starts with its magic and version" || {
+      let bytes = bytecodify("1;", &Interpreter::new()).unwrap();
+
+      must!(file::is_bytecode(&bytes); be.truthy)?;
+      must!(bytes[4..6].to_vec(); eq file::VERSION.to_le_bytes().to_vec())
+    }
+
+    "test malformed files are refused" || {
+      let bytes = bytecodify("1;", &Interpreter::new()).unwrap();
+      let mut future = bytes.to_owned();
+
+      future[4] = 99;
+
+      must!(
+        file::read(b"val a := 1;").map(|_| ());
+        eq Err("not a bytecode file".into())
+      )?;
+      must!(
+        file::read(&future).map(|_| ());
+        eq Err("bytecode version 99 is not supported".into())
+      )?;
+      must!(
+        file::read(&bytes[..bytes.len() - 1]).map(|_| ());
+        eq Err("bytecode file is truncated".into())
+      )
+    }
+
+    "test nodes are read back as they were written" || {
+      let input = "trait Shape { ƒ area(self) -> int; }
+        struct Square { side: int }
+        impl Shape for Square { ƒ area(self) -> int { self.side * self.side } }
+        val p := Square { side: 3 };
+        [p.area(), @outer loop { break @outer -1; }];";
+      let proto = compilify(input, &Interpreter::new()).unwrap();
+      let loaded = file::read(&file::write(&proto)).unwrap();
+      let text = |nodes: &Values| {
+        nodes.iter().map(|node| node.text()).collect::<Vec<_>>()
+      };
+
+      must!(text(&loaded.chunk.nodes); eq text(&proto.chunk.nodes))?;
+      must!(load(input); eq vm(input))
+    }
+
+    "test operands and jumps out of the code are refused" || {
+      let proto = compilify("val a := [1, 2]; a[0];", &Interpreter::new())
+        .unwrap();
+      let read = |op: Option<Op>| {
+        let mut proto = proto.to_owned();
+
+        match op {
+          Some(op) => proto.chunk.ops[0] = op,
+          None => {
+            proto.chunk.ops.pop();
+          }
+        }
+
+        file::read(&file::write(&proto)).map(|_| ())
+      };
+
+      must!(
+        read(Some(Op::Constant(99)));
+        eq Err("bytecode file has an operand out of range".into())
+      )?;
+      must!(
+        read(Some(Op::Get(Place::Local(0))));
+        eq Err("bytecode file has an operand out of range".into())
+      )?;
+      must!(
+        read(Some(Op::Jump(99)));
+        eq Err("bytecode file has a jump out of its code".into())
+      )?;
+      must!(
+        read(None);
+        eq Err("bytecode file has code that runs past its end".into())
+      )
+    }
+
+    "test code that takes more than the stack holds is refused" || {
+      let proto = compilify("for [1, 2] |x| { x; }", &Interpreter::new())
+        .unwrap();
+      let read = |op: Op| {
+        let mut proto = proto.to_owned();
+
+        proto.chunk.ops[0] = op;
+        file::read(&file::write(&proto)).map(|_| ())
+      };
+      let unbalanced = Err("bytecode file has an unbalanced stack".into());
+
+      must!(read(Op::Array(99)); eq unbalanced.to_owned())?;
+      must!(read(Op::Call(0)); eq unbalanced.to_owned())?;
+      must!(read(Op::Break(0)); eq unbalanced.to_owned())?;
+      must!(read(Op::Next(1)); eq unbalanced.to_owned())?;
+      must!(read(Op::Pop); eq unbalanced)
+    }
+
+    "test the machine fails on code it was not given checked" || {
+      let proto = compilify("val a := [1, 2]; a[0];", &Interpreter::new())
+        .unwrap();
+      let run = |op: Op| {
+        let mut proto = proto.to_owned();
+
+        proto.chunk.ops[0] = op;
+        Machine::run(&mut Interpreter::new(), proto).map(|value| value.text())
+      };
+
+      must!(
+        run(Op::Array(99));
+        eq Err("the stack of a frame is unbalanced".into())
+      )?;
+      must!(
+        run(Op::Continue(0));
+        eq Err("a jump leaves a loop it is not in".into())
+      )?;
+      must!(
+        run(Op::Next(1));
+        eq Err("a loop has nothing to iterate".into())
+      )
+    }
+
+    "test flipped bytes are refused or run to an end" || {
+      use crate::analyzer::environment::meter::Limits;
+
+      let input = "ƒ f(a: int, b: int = 2) -> int { a * b }
+        val h := { \"a\": [1, ..[2]] };
+        @outer for 0..3 |i| {
+          match i { 1 => { continue @outer; }, _ => f(i, b: 3) };
+        }
+        h[\"a\"];";
+      let bytes = bytecodify(input, &Interpreter::new()).unwrap();
+      let mut refused = 0;
+
+      // a byte flipped anywhere past the header, the loader refuses the file
+      // or the program ends, with a value or an error, but never crashes
+      for at in 6..bytes.len() {
+        for flip in &[0x01, 0x80, 0xff] {
+          let mut broken = bytes.to_owned();
+
+          broken[at] ^= flip;
+
+          match file::read(&broken) {
+            Ok(proto) => {
+              let mut interpreter = Interpreter::new();
+
+              interpreter
+                .add_limits(Limits::new().add_fuel(10_000).add_memory(1 << 16));
+              Machine::run(&mut interpreter, proto).ok();
+            }
+            Err(_) => refused += 1,
+          }
+        }
+      }
+
+      must!(refused > 0; be.truthy)
+    }
+
+    "test spans tell which statement an op comes from" || {
+      let input = "val a := 1;\nfor 0..2 |i| {\n  a + i;\n}";
+      let proto = compilify(input, &Interpreter::new()).unwrap();
+      let loaded = file::read(&file::write(&proto)).unwrap();
+      let chunk = &loaded.chunk;
+      let source = |op: Op| {
+        let at = chunk.ops.iter().position(|known| *known == op)?;
+
+        chunk.span(at).map(|span| span.source.clone())
+      };
+
+      must!(chunk.spans.to_vec(); eq proto.chunk.spans)?;
+      must!(source(Op::Iterate); eq Some("for 0..2 |i| { (a + i) }".into()))?;
+      must!(source(Op::Binary(0)); eq Some("(a + i)".into()))
+    }
+  });
+}
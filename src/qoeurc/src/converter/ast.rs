@@ -0,0 +1,350 @@
+use crate::reporter::location::Span;
+use crate::tokenizer::kind::*;
+use crate::tokenizer::token::Token;
+
+use std::fmt;
+
+// a program as plain data, passes match on it instead of downcasting values
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ast {
+  pub statements: Vec<Stmt>,
+}
+
+// a statement with the bytes of the input it was read from, statements a pass
+// makes up have an empty span
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stmt {
+  pub kind: StmtKind,
+  pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StmtKind {
+  Break(Option<String>, Option<Expr>),
+  Continue(Option<String>),
+  // an expression statement, `a + 1;` or the last value of a block
+  Expr(Expr),
+  Function(Function),
+  Impl(Impl),
+  Module(String, Option<Block>),
+  // an expression that stands on its own, a loop written as a statement or
+  // the body of a closure without braces
+  Node(Expr),
+  Public(Box<StmtKind>),
+  Return(Option<Expr>),
+  Struct(Struct),
+  Trait(Trait),
+  Use(Use),
+  Val(Val),
+}
+
+// an expression with the bytes of the input it was read from, like `Stmt`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expr {
+  pub kind: ExprKind,
+  pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExprKind {
+  Array(Vec<Expr>),
+  Assign(Box<Expr>, Box<Expr>),
+  Binary(Box<Expr>, BinaryOp, Box<Expr>),
+  Block(Block),
+  Bool(bool),
+  Call(Box<Expr>, Vec<Expr>),
+  Char(char),
+  Closure(Closure),
+  // a line comment, `# text`
+  Comment(String),
+  Delete(Box<Expr>),
+  Float(f64),
+  Group(Box<Expr>),
+  // a key of `None` is a spread
+  Hash(Vec<(Option<Expr>, Expr)>),
+  Identifier(String),
+  If(If),
+  Index(Box<Expr>, Box<Expr>),
+  Instance(String, Vec<(String, Expr)>),
+  Int(i64),
+  Loop(Loop),
+  Match(Box<Expr>, Vec<(Pattern, Expr)>),
+  // `object.property` or `object?.property` when optional
+  Member(Box<Expr>, bool, String),
+  NamedArg(String, Box<Expr>),
+  Nil,
+  Outcome(bool, Box<Expr>),
+  Range(Range),
+  Spread(Box<Expr>),
+  Str(String),
+  Try(Box<Expr>),
+  Unary(UnaryOp, Box<Expr>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOp {
+  Add,
+  Coalesce,
+  Div,
+  Eq,
+  Ge,
+  Gt,
+  Le,
+  Lt,
+  Mul,
+  Ne,
+  Sub,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnaryOp {
+  Neg,
+  Not,
+}
+
+// what a `match` arm or a destructuring `val` takes apart, a literal is one of
+// the literal expressions
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+  Array(Vec<Pattern>),
+  Binding(String),
+  Hash(Vec<(String, Pattern)>),
+  Literal(Expr),
+  Outcome(bool, Box<Pattern>),
+  // `..` or `..name`, only as the last element of an array
+  Rest(Option<String>),
+  Struct(String, Vec<(String, Pattern)>),
+  Tuple(Vec<Pattern>),
+  Wildcard,
+}
+
+// a type as it is written, `_` leaves it to be inferred
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ty {
+  Array,
+  ArrayOf(Box<Ty>),
+  Bool,
+  Char,
+  Float,
+  Fn,
+  Hash,
+  Infer,
+  Int,
+  Named(String, Vec<Ty>),
+  Nil,
+  Optional(Box<Ty>),
+  Str,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Block {
+  pub statements: Vec<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Closure {
+  pub block: Block,
+  pub kind: Ty,
+  pub params: Vec<Param>,
+}
+
+// a trait only gives the signature of its functions, they have no block
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+  pub block: Option<Block>,
+  pub generics: Vec<String>,
+  pub kind: Ty,
+  pub name: String,
+  pub params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct If {
+  pub alternative: Option<Box<Expr>>,
+  pub condition: Box<Expr>,
+  pub consequence: Block,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Impl {
+  pub functions: Vec<Function>,
+  pub interface: Option<String>,
+  pub ty: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loop {
+  pub block: Block,
+  pub kind: LoopKind,
+  pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoopKind {
+  For(Vec<String>, Box<Expr>),
+  Infinite,
+  While(Box<Expr>),
+}
+
+// a parameter binds either a name or a pattern
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+  pub default: Option<Expr>,
+  pub kind: Ty,
+  pub name: String,
+  pub pattern: Option<Pattern>,
+  pub variadic: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Range {
+  pub end: Box<Expr>,
+  pub inclusive: bool,
+  pub start: Box<Expr>,
+  pub step: Option<Box<Expr>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Struct {
+  pub fields: Vec<(String, Ty)>,
+  pub generics: Vec<String>,
+  pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trait {
+  pub functions: Vec<Function>,
+  pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Use {
+  pub glob: bool,
+  pub items: Vec<String>,
+  pub path: Vec<String>,
+}
+
+// a `val` binds either a name or a pattern
+#[derive(Clone, Debug, PartialEq)]
+pub struct Val {
+  pub immutable: bool,
+  pub kind: Option<Ty>,
+  pub name: String,
+  pub pattern: Option<Pattern>,
+  pub value: Expr,
+}
+
+impl BinaryOp {
+  pub fn from_kind(kind: TokenKind) -> Option<BinaryOp> {
+    match kind {
+      TokenKind::Operator(Equal) => Some(BinaryOp::Eq),
+      TokenKind::Operator(GreaterThan) => Some(BinaryOp::Gt),
+      TokenKind::Operator(GreaterThanOrEqual) => Some(BinaryOp::Ge),
+      TokenKind::Operator(LessThan) => Some(BinaryOp::Lt),
+      TokenKind::Operator(LessThanOrEqual) => Some(BinaryOp::Le),
+      TokenKind::Operator(Minus) => Some(BinaryOp::Sub),
+      TokenKind::Operator(NotEqual) => Some(BinaryOp::Ne),
+      TokenKind::Operator(Plus) => Some(BinaryOp::Add),
+      TokenKind::Operator(QuestionQuestion) => Some(BinaryOp::Coalesce),
+      TokenKind::Operator(Slash) => Some(BinaryOp::Div),
+      TokenKind::Operator(Star) => Some(BinaryOp::Mul),
+      _ => None,
+    }
+  }
+
+  pub fn kind(self) -> TokenKind {
+    match self {
+      BinaryOp::Add => TokenKind::Operator(Plus),
+      BinaryOp::Coalesce => TokenKind::Operator(QuestionQuestion),
+      BinaryOp::Div => TokenKind::Operator(Slash),
+      BinaryOp::Eq => TokenKind::Operator(Equal),
+      BinaryOp::Ge => TokenKind::Operator(GreaterThanOrEqual),
+      BinaryOp::Gt => TokenKind::Operator(GreaterThan),
+      BinaryOp::Le => TokenKind::Operator(LessThanOrEqual),
+      BinaryOp::Lt => TokenKind::Operator(LessThan),
+      BinaryOp::Mul => TokenKind::Operator(Star),
+      BinaryOp::Ne => TokenKind::Operator(NotEqual),
+      BinaryOp::Sub => TokenKind::Operator(Minus),
+    }
+  }
+
+  // the token the interpreter reads the operator from
+  pub fn token(self) -> Token {
+    let literal = self.to_string();
+
+    Token::new(self.kind(), &literal, literal.len())
+  }
+}
+
+impl fmt::Display for BinaryOp {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      BinaryOp::Add => write!(f, "+"),
+      BinaryOp::Coalesce => write!(f, "??"),
+      BinaryOp::Div => write!(f, "/"),
+      BinaryOp::Eq => write!(f, "=="),
+      BinaryOp::Ge => write!(f, ">="),
+      BinaryOp::Gt => write!(f, ">"),
+      BinaryOp::Le => write!(f, "<="),
+      BinaryOp::Lt => write!(f, "<"),
+      BinaryOp::Mul => write!(f, "*"),
+      BinaryOp::Ne => write!(f, "!="),
+      BinaryOp::Sub => write!(f, "-"),
+    }
+  }
+}
+
+impl UnaryOp {
+  pub fn token(self) -> Token {
+    match self {
+      UnaryOp::Neg => Token::new(TokenKind::Operator(Minus), "-", 1),
+      UnaryOp::Not => Token::new(TokenKind::Symbol(Bang), "!", 1),
+    }
+  }
+}
+
+impl fmt::Display for UnaryOp {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      UnaryOp::Neg => write!(f, "-"),
+      UnaryOp::Not => write!(f, "!"),
+    }
+  }
+}
+
+impl Pattern {
+  // the names the pattern binds, in the order they are written
+  pub fn names(&self) -> Vec<String> {
+    match self {
+      Pattern::Binding(name) | Pattern::Rest(Some(name)) => vec![name.into()],
+      Pattern::Outcome(_, pattern) => pattern.names(),
+      Pattern::Array(patterns) | Pattern::Tuple(patterns) => {
+        patterns.iter().flat_map(Pattern::names).collect()
+      }
+      Pattern::Hash(fields) | Pattern::Struct(_, fields) => fields
+        .iter()
+        .flat_map(|(_, pattern)| pattern.names())
+        .collect(),
+      Pattern::Literal(_) | Pattern::Rest(None) | Pattern::Wildcard => vec![],
+    }
+  }
+}
+
+impl Expr {
+  pub fn new(kind: ExprKind, span: Span) -> Self {
+    Expr { kind, span }
+  }
+}
+
+// an expression a pass makes up, with an empty span
+impl From<ExprKind> for Expr {
+  fn from(kind: ExprKind) -> Self {
+    Expr::new(kind, Span::default())
+  }
+}
+
+impl Stmt {
+  pub fn new(kind: StmtKind, span: Span) -> Self {
+    Stmt { kind, span }
+  }
+}
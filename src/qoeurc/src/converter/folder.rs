@@ -0,0 +1,331 @@
+use crate::converter::ast::*;
+
+use std::mem;
+
+// a pass that rebuilds the syntax tree, each method takes a node and gives
+// back the one to put in its place, by default the same node with its
+// children folded
+pub trait Folder: Sized {
+  fn fold_ast(&mut self, ast: Ast) -> Ast {
+    walk_ast(self, ast)
+  }
+
+  fn fold_block(&mut self, block: Block) -> Block {
+    walk_block(self, block)
+  }
+
+  fn fold_closure(&mut self, closure: Closure) -> Closure {
+    walk_closure(self, closure)
+  }
+
+  fn fold_expr(&mut self, expr: Expr) -> Expr {
+    walk_expr(self, expr)
+  }
+
+  fn fold_expr_kind(&mut self, kind: ExprKind) -> ExprKind {
+    walk_expr_kind(self, kind)
+  }
+
+  fn fold_function(&mut self, function: Function) -> Function {
+    walk_function(self, function)
+  }
+
+  fn fold_if(&mut self, control: If) -> If {
+    walk_if(self, control)
+  }
+
+  fn fold_impl(&mut self, implementation: Impl) -> Impl {
+    walk_impl(self, implementation)
+  }
+
+  fn fold_loop(&mut self, looping: Loop) -> Loop {
+    walk_loop(self, looping)
+  }
+
+  fn fold_param(&mut self, param: Param) -> Param {
+    walk_param(self, param)
+  }
+
+  fn fold_range(&mut self, range: Range) -> Range {
+    walk_range(self, range)
+  }
+
+  fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+    walk_stmt(self, stmt)
+  }
+
+  fn fold_stmt_kind(&mut self, kind: StmtKind) -> StmtKind {
+    walk_stmt_kind(self, kind)
+  }
+
+  fn fold_struct(&mut self, structure: Struct) -> Struct {
+    structure
+  }
+
+  fn fold_trait(&mut self, interface: Trait) -> Trait {
+    walk_trait(self, interface)
+  }
+
+  fn fold_use(&mut self, import: Use) -> Use {
+    import
+  }
+
+  fn fold_val(&mut self, val: Val) -> Val {
+    walk_val(self, val)
+  }
+}
+
+// the folded expression goes back in the box it came in
+fn fold_boxed<F: Folder>(folder: &mut F, mut expr: Box<Expr>) -> Box<Expr> {
+  *expr = folder.fold_expr(mem::replace(&mut *expr, ExprKind::Nil.into()));
+  expr
+}
+
+fn fold_exprs<F: Folder>(folder: &mut F, exprs: Vec<Expr>) -> Vec<Expr> {
+  exprs
+    .into_iter()
+    .map(|expr| folder.fold_expr(expr))
+    .collect()
+}
+
+fn fold_functions<F: Folder>(
+  folder: &mut F,
+  functions: Vec<Function>,
+) -> Vec<Function> {
+  functions
+    .into_iter()
+    .map(|function| folder.fold_function(function))
+    .collect()
+}
+
+fn fold_statements<F: Folder>(
+  folder: &mut F,
+  statements: Vec<Stmt>,
+) -> Vec<Stmt> {
+  statements
+    .into_iter()
+    .map(|stmt| folder.fold_stmt(stmt))
+    .collect()
+}
+
+pub fn walk_ast<F: Folder>(folder: &mut F, ast: Ast) -> Ast {
+  Ast {
+    statements: fold_statements(folder, ast.statements),
+  }
+}
+
+pub fn walk_block<F: Folder>(folder: &mut F, block: Block) -> Block {
+  Block {
+    statements: fold_statements(folder, block.statements),
+  }
+}
+
+pub fn walk_closure<F: Folder>(folder: &mut F, closure: Closure) -> Closure {
+  Closure {
+    params: closure
+      .params
+      .into_iter()
+      .map(|param| folder.fold_param(param))
+      .collect(),
+    block: folder.fold_block(closure.block),
+    kind: closure.kind,
+  }
+}
+
+pub fn walk_expr<F: Folder>(folder: &mut F, expr: Expr) -> Expr {
+  Expr::new(folder.fold_expr_kind(expr.kind), expr.span)
+}
+
+pub fn walk_expr_kind<F: Folder>(folder: &mut F, kind: ExprKind) -> ExprKind {
+  match kind {
+    ExprKind::Array(items) => ExprKind::Array(fold_exprs(folder, items)),
+    ExprKind::Assign(target, value) => {
+      ExprKind::Assign(fold_boxed(folder, target), fold_boxed(folder, value))
+    }
+    ExprKind::Binary(lhs, operator, rhs) => ExprKind::Binary(
+      fold_boxed(folder, lhs),
+      operator,
+      fold_boxed(folder, rhs),
+    ),
+    ExprKind::Block(block) => ExprKind::Block(folder.fold_block(block)),
+    ExprKind::Call(callee, args) => {
+      ExprKind::Call(fold_boxed(folder, callee), fold_exprs(folder, args))
+    }
+    ExprKind::Closure(closure) => {
+      ExprKind::Closure(folder.fold_closure(closure))
+    }
+    ExprKind::Delete(target) => ExprKind::Delete(fold_boxed(folder, target)),
+    ExprKind::Group(expression) => {
+      ExprKind::Group(fold_boxed(folder, expression))
+    }
+    ExprKind::Hash(items) => ExprKind::Hash(
+      items
+        .into_iter()
+        .map(|(key, value)| {
+          (
+            key.map(|key| folder.fold_expr(key)),
+            folder.fold_expr(value),
+          )
+        })
+        .collect(),
+    ),
+    ExprKind::If(control) => ExprKind::If(folder.fold_if(control)),
+    ExprKind::Index(lhs, rhs) => {
+      ExprKind::Index(fold_boxed(folder, lhs), fold_boxed(folder, rhs))
+    }
+    ExprKind::Instance(name, fields) => ExprKind::Instance(
+      name,
+      fields
+        .into_iter()
+        .map(|(name, value)| (name, folder.fold_expr(value)))
+        .collect(),
+    ),
+    ExprKind::Loop(looping) => ExprKind::Loop(folder.fold_loop(looping)),
+    ExprKind::Match(subject, arms) => ExprKind::Match(
+      fold_boxed(folder, subject),
+      arms
+        .into_iter()
+        .map(|(pattern, body)| (pattern, folder.fold_expr(body)))
+        .collect(),
+    ),
+    ExprKind::Member(object, optional, property) => {
+      ExprKind::Member(fold_boxed(folder, object), optional, property)
+    }
+    ExprKind::NamedArg(name, value) => {
+      ExprKind::NamedArg(name, fold_boxed(folder, value))
+    }
+    ExprKind::Outcome(is_ok, value) => {
+      ExprKind::Outcome(is_ok, fold_boxed(folder, value))
+    }
+    ExprKind::Range(range) => ExprKind::Range(folder.fold_range(range)),
+    ExprKind::Spread(value) => ExprKind::Spread(fold_boxed(folder, value)),
+    ExprKind::Try(value) => ExprKind::Try(fold_boxed(folder, value)),
+    ExprKind::Unary(operand, rhs) => {
+      ExprKind::Unary(operand, fold_boxed(folder, rhs))
+    }
+    ExprKind::Bool(_)
+    | ExprKind::Char(_)
+    | ExprKind::Comment(_)
+    | ExprKind::Float(_)
+    | ExprKind::Identifier(_)
+    | ExprKind::Int(_)
+    | ExprKind::Nil
+    | ExprKind::Str(_) => kind,
+  }
+}
+
+pub fn walk_function<F: Folder>(
+  folder: &mut F,
+  function: Function,
+) -> Function {
+  Function {
+    params: function
+      .params
+      .into_iter()
+      .map(|param| folder.fold_param(param))
+      .collect(),
+    block: function.block.map(|block| folder.fold_block(block)),
+    ..function
+  }
+}
+
+pub fn walk_if<F: Folder>(folder: &mut F, control: If) -> If {
+  If {
+    condition: fold_boxed(folder, control.condition),
+    consequence: folder.fold_block(control.consequence),
+    alternative: control
+      .alternative
+      .map(|alternative| fold_boxed(folder, alternative)),
+  }
+}
+
+pub fn walk_impl<F: Folder>(folder: &mut F, implementation: Impl) -> Impl {
+  Impl {
+    functions: fold_functions(folder, implementation.functions),
+    ..implementation
+  }
+}
+
+pub fn walk_loop<F: Folder>(folder: &mut F, looping: Loop) -> Loop {
+  let kind = match looping.kind {
+    LoopKind::For(variables, iterable) => {
+      LoopKind::For(variables, fold_boxed(folder, iterable))
+    }
+    LoopKind::Infinite => LoopKind::Infinite,
+    LoopKind::While(condition) => {
+      LoopKind::While(fold_boxed(folder, condition))
+    }
+  };
+
+  Loop {
+    block: folder.fold_block(looping.block),
+    kind,
+    label: looping.label,
+  }
+}
+
+pub fn walk_param<F: Folder>(folder: &mut F, param: Param) -> Param {
+  Param {
+    default: param.default.map(|default| folder.fold_expr(default)),
+    ..param
+  }
+}
+
+pub fn walk_range<F: Folder>(folder: &mut F, range: Range) -> Range {
+  Range {
+    start: fold_boxed(folder, range.start),
+    end: fold_boxed(folder, range.end),
+    step: range.step.map(|step| fold_boxed(folder, step)),
+    inclusive: range.inclusive,
+  }
+}
+
+pub fn walk_stmt<F: Folder>(folder: &mut F, stmt: Stmt) -> Stmt {
+  Stmt::new(folder.fold_stmt_kind(stmt.kind), stmt.span)
+}
+
+pub fn walk_stmt_kind<F: Folder>(folder: &mut F, kind: StmtKind) -> StmtKind {
+  match kind {
+    StmtKind::Break(label, value) => {
+      StmtKind::Break(label, value.map(|value| folder.fold_expr(value)))
+    }
+    StmtKind::Continue(label) => StmtKind::Continue(label),
+    StmtKind::Expr(expr) => StmtKind::Expr(folder.fold_expr(expr)),
+    StmtKind::Function(function) => {
+      StmtKind::Function(folder.fold_function(function))
+    }
+    StmtKind::Impl(implementation) => {
+      StmtKind::Impl(folder.fold_impl(implementation))
+    }
+    StmtKind::Module(name, block) => {
+      StmtKind::Module(name, block.map(|block| folder.fold_block(block)))
+    }
+    StmtKind::Node(expr) => StmtKind::Node(folder.fold_expr(expr)),
+    StmtKind::Public(kind) => {
+      StmtKind::Public(Box::new(folder.fold_stmt_kind(*kind)))
+    }
+    StmtKind::Return(value) => {
+      StmtKind::Return(value.map(|value| folder.fold_expr(value)))
+    }
+    StmtKind::Struct(structure) => {
+      StmtKind::Struct(folder.fold_struct(structure))
+    }
+    StmtKind::Trait(interface) => StmtKind::Trait(folder.fold_trait(interface)),
+    StmtKind::Use(import) => StmtKind::Use(folder.fold_use(import)),
+    StmtKind::Val(val) => StmtKind::Val(folder.fold_val(val)),
+  }
+}
+
+pub fn walk_trait<F: Folder>(folder: &mut F, interface: Trait) -> Trait {
+  Trait {
+    functions: fold_functions(folder, interface.functions),
+    ..interface
+  }
+}
+
+pub fn walk_val<F: Folder>(folder: &mut F, val: Val) -> Val {
+  Val {
+    value: folder.fold_expr(val.value),
+    ..val
+  }
+}
@@ -0,0 +1,405 @@
+use crate::converter::ast::*;
+use crate::reporter::location::Span;
+use crate::tokenizer::kind::{CommentKind, TokenKind};
+use crate::value::instruction::array::Array;
+use crate::value::instruction::assign::Assign;
+use crate::value::instruction::binary::Binary;
+use crate::value::instruction::block;
+use crate::value::instruction::call::Call;
+use crate::value::instruction::closure;
+use crate::value::instruction::comment::Comment;
+use crate::value::instruction::control;
+use crate::value::instruction::delete::Delete;
+use crate::value::instruction::expression::Expression;
+use crate::value::instruction::function;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::group::Group;
+use crate::value::instruction::hash::{Hash, HashKey};
+use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::implementation;
+use crate::value::instruction::import;
+use crate::value::instruction::index::Index;
+use crate::value::instruction::instance::Instance;
+use crate::value::instruction::interface;
+use crate::value::instruction::loop_break::Break;
+use crate::value::instruction::loop_continue::Continue;
+use crate::value::instruction::loop_for_in::LoopForIn;
+use crate::value::instruction::loop_infinite::LoopInfinite;
+use crate::value::instruction::loop_while::LoopWhile;
+use crate::value::instruction::match_value::Match;
+use crate::value::instruction::member::Member;
+use crate::value::instruction::module::Module;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::outcome::Outcome;
+use crate::value::instruction::pattern;
+use crate::value::instruction::program::Program;
+use crate::value::instruction::public::Public;
+use crate::value::instruction::range;
+use crate::value::instruction::return_value::Return;
+use crate::value::instruction::spread::Spread;
+use crate::value::instruction::structure;
+use crate::value::instruction::try_value::Try;
+use crate::value::instruction::ty::{self, TyKind};
+use crate::value::instruction::unary::Unary;
+use crate::value::instruction::val;
+use crate::value::primitive::{
+  bool::Bool, char::Char, float::Float, int::Int, str::Str,
+};
+use crate::value::Value;
+use crate::void;
+
+// the program a syntax tree stands for. the interpreter, the checker and the
+// compiler do not run on the tree yet, they run on the values built here until
+// the interpreter has a value type of its own, see `runtime values` in the
+// roadmap
+pub fn raise(ast: &Ast) -> Box<dyn Value> {
+  let mut program = Program::new(raise_statements(&ast.statements));
+
+  program.spans = raise_spans(&ast.statements);
+  program.boxed()
+}
+
+// statements the parser did not read have no spans to give back
+fn raise_spans(statements: &[Stmt]) -> Vec<Span> {
+  match statements.iter().all(|stmt| stmt.span != Span::default()) {
+    true => statements.iter().map(|stmt| stmt.span).collect(),
+    false => vec![],
+  }
+}
+
+fn raise_statements(statements: &[Stmt]) -> Vec<Box<dyn Value>> {
+  statements
+    .iter()
+    .map(|stmt| raise_stmt(&stmt.kind))
+    .collect()
+}
+
+fn raise_stmt(kind: &StmtKind) -> Box<dyn Value> {
+  match kind {
+    StmtKind::Break(label, value) => {
+      Break::new(label.to_owned(), raise_option(value)).boxed()
+    }
+    StmtKind::Continue(label) => Continue::new(label.to_owned()).boxed(),
+    StmtKind::Expr(expr) => Expression::new(raise_expr(expr)).boxed(),
+    StmtKind::Function(function) => raise_function(function),
+    StmtKind::Impl(implementation) => implementation::Impl::new(
+      Identifier::new(&implementation.ty).boxed(),
+      implementation
+        .interface
+        .as_ref()
+        .map(|name| Identifier::new(name).boxed()),
+      implementation
+        .functions
+        .iter()
+        .map(raise_function)
+        .collect(),
+    )
+    .boxed(),
+    StmtKind::Module(name, block) => Module::new(
+      Identifier::new(name).boxed(),
+      block.as_ref().map(raise_block),
+    )
+    .boxed(),
+    StmtKind::Node(expr) => raise_expr(expr),
+    StmtKind::Public(kind) => Public::new(raise_stmt(kind)).boxed(),
+    StmtKind::Return(value) => Return::new(raise_option(value)).boxed(),
+    StmtKind::Struct(structure) => {
+      let fields = structure
+        .fields
+        .iter()
+        .map(|(name, kind)| {
+          FunctionArg::new(Identifier::new(name).boxed(), raise_ty(kind))
+            .boxed()
+        })
+        .collect();
+
+      structure::Struct::new(Identifier::new(&structure.name).boxed(), fields)
+        .add_generics(raise_names(&structure.generics))
+        .boxed()
+    }
+    StmtKind::Trait(interface) => interface::Trait::new(
+      Identifier::new(&interface.name).boxed(),
+      interface.functions.iter().map(raise_function).collect(),
+    )
+    .boxed(),
+    StmtKind::Use(import) => {
+      let mut node =
+        import::Use::new(raise_names(&import.path), raise_names(&import.items));
+
+      node.glob = import.glob;
+      node.boxed()
+    }
+    StmtKind::Val(val) => {
+      let name = match &val.pattern {
+        Some(_) => void!(),
+        None => Identifier::new(&val.name).boxed(),
+      };
+      let kind = val.kind.as_ref().map(raise_ty);
+      let mut node =
+        val::Val::new(val.immutable, kind, name, Some(raise_expr(&val.value)));
+
+      if let Some(pattern) = &val.pattern {
+        node.add_pattern(raise_pattern(pattern));
+      }
+
+      node.boxed()
+    }
+  }
+}
+
+pub(crate) fn raise_expr(expr: &Expr) -> Box<dyn Value> {
+  let boxed = |expr: &Expr| raise_expr(expr);
+
+  match &expr.kind {
+    ExprKind::Array(items) => Array::new(raise_exprs(items)).boxed(),
+    ExprKind::Assign(target, value) => {
+      Assign::new(boxed(target), boxed(value)).boxed()
+    }
+    ExprKind::Binary(lhs, operator, rhs) => {
+      Binary::new(boxed(lhs), Box::new(operator.token()), boxed(rhs)).boxed()
+    }
+    ExprKind::Block(block) => raise_block(block),
+    ExprKind::Bool(value) => Bool::new(*value).boxed(),
+    ExprKind::Call(callee, args) => {
+      Call::new(boxed(callee), raise_exprs(args)).boxed()
+    }
+    ExprKind::Char(value) => Char::new(*value).boxed(),
+    ExprKind::Closure(closure) => closure::Closure::new(
+      raise_params(&closure.params),
+      raise_ty(&closure.kind),
+      raise_block(&closure.block),
+    )
+    .boxed(),
+    ExprKind::Comment(value) => {
+      Comment::new(TokenKind::Comment(CommentKind::Line), value).boxed()
+    }
+    ExprKind::Delete(target) => Delete::new(boxed(target)).boxed(),
+    ExprKind::Float(value) => Float::new(*value).boxed(),
+    ExprKind::Group(expression) => Group::new(boxed(expression)).boxed(),
+    ExprKind::Hash(items) => {
+      let mut hash = Hash::default();
+
+      for (key, value) in items {
+        match key {
+          Some(key) => hash.add_item((raise_key(key), raise_expr(value))),
+          None => hash.add_spread(raise_expr(value)),
+        };
+      }
+
+      hash.boxed()
+    }
+    ExprKind::Identifier(name) => Identifier::new(name).boxed(),
+    ExprKind::If(control) => control::If::new(
+      boxed(&control.condition),
+      raise_block(&control.consequence),
+      control.alternative.as_deref().map(boxed),
+    )
+    .boxed(),
+    ExprKind::Index(lhs, rhs) => Index::new(boxed(lhs), boxed(rhs)).boxed(),
+    ExprKind::Instance(name, fields) => Instance::new(
+      Identifier::new(name).boxed(),
+      fields
+        .iter()
+        .map(|(name, value)| (name.to_owned(), raise_expr(value)))
+        .collect(),
+    )
+    .boxed(),
+    ExprKind::Int(value) => Int::new(*value).boxed(),
+    ExprKind::Loop(looping) => raise_loop(looping),
+    ExprKind::Match(subject, arms) => Match::new(
+      boxed(subject),
+      arms
+        .iter()
+        .map(|(pattern, body)| (raise_pattern(pattern), raise_expr(body)))
+        .collect(),
+    )
+    .boxed(),
+    ExprKind::Member(object, optional, property) => {
+      let mut member =
+        Member::new(boxed(object), Identifier::new(property).boxed());
+
+      member.optional = *optional;
+      member.boxed()
+    }
+    ExprKind::NamedArg(name, value) => {
+      NamedArg::new(name, boxed(value)).boxed()
+    }
+    ExprKind::Nil => void!(),
+    ExprKind::Outcome(is_ok, value) => {
+      Outcome::new(*is_ok, boxed(value)).boxed()
+    }
+    ExprKind::Range(range) => {
+      let mut node = range::Range::new(
+        boxed(&range.start),
+        boxed(&range.end),
+        range.inclusive,
+      );
+
+      if let Some(step) = &range.step {
+        node.add_step(boxed(step));
+      }
+
+      node.boxed()
+    }
+    ExprKind::Spread(value) => Spread::new(boxed(value)).boxed(),
+    ExprKind::Str(value) => Str::new(value).boxed(),
+    ExprKind::Try(value) => Try::new(boxed(value)).boxed(),
+    ExprKind::Unary(operator, rhs) => {
+      Unary::new(Box::new(operator.token()), boxed(rhs)).boxed()
+    }
+  }
+}
+
+fn raise_exprs(exprs: &[Expr]) -> Vec<Box<dyn Value>> {
+  exprs.iter().map(raise_expr).collect()
+}
+
+fn raise_option(expr: &Option<Expr>) -> Option<Box<dyn Value>> {
+  expr.as_ref().map(raise_expr)
+}
+
+fn raise_block(block: &Block) -> Box<dyn Value> {
+  let mut node = block::Block::new(raise_statements(&block.statements));
+
+  node.spans = raise_spans(&block.statements);
+  node.boxed()
+}
+
+pub(crate) fn raise_function(function: &Function) -> Box<dyn Value> {
+  let mut node = function::Function::new(
+    raise_params(&function.params),
+    void!(),
+    raise_ty(&function.kind),
+    Identifier::new(&function.name).boxed(),
+    None,
+  );
+
+  node.block = function.block.as_ref().map(raise_block);
+  node.add_generics(raise_names(&function.generics)).boxed()
+}
+
+fn raise_loop(looping: &Loop) -> Box<dyn Value> {
+  let block = raise_block(&looping.block);
+  let label = looping.label.as_deref();
+
+  match &looping.kind {
+    LoopKind::For(variables, iterable) => {
+      let mut node =
+        LoopForIn::new(block, raise_expr(iterable), raise_names(variables));
+
+      label.map(|label| node.add_label(label));
+      node.boxed()
+    }
+    LoopKind::Infinite => {
+      let mut node = LoopInfinite::new(block);
+
+      label.map(|label| node.add_label(label));
+      node.boxed()
+    }
+    LoopKind::While(condition) => {
+      let mut node = LoopWhile::new(block, raise_expr(condition));
+
+      label.map(|label| node.add_label(label));
+      node.boxed()
+    }
+  }
+}
+
+fn raise_names(names: &[String]) -> Vec<Box<dyn Value>> {
+  names
+    .iter()
+    .map(|name| Identifier::new(name).boxed())
+    .collect()
+}
+
+pub(crate) fn raise_params(params: &[Param]) -> Vec<Box<dyn Value>> {
+  params
+    .iter()
+    .map(|param| {
+      let name = match &param.pattern {
+        Some(_) => void!(),
+        None => Identifier::new(&param.name).boxed(),
+      };
+      let mut arg = FunctionArg::new(name, raise_ty(&param.kind));
+
+      if let Some(pattern) = &param.pattern {
+        arg.add_pattern(raise_pattern(pattern));
+      }
+
+      if let Some(default) = &param.default {
+        arg.add_default(raise_expr(default));
+      }
+
+      arg.add_variadic(param.variadic).boxed()
+    })
+    .collect()
+}
+
+// the parser only keeps keys that are literals, or arrays and hashes of them
+fn raise_key(key: &Expr) -> HashKey {
+  match &key.kind {
+    ExprKind::Bool(value) => HashKey::Bool(*value),
+    ExprKind::Char(value) => HashKey::Char(*value),
+    ExprKind::Int(value) => HashKey::Int(*value),
+    ExprKind::Str(value) => HashKey::Str(value.to_owned()),
+    ExprKind::Array(items) => {
+      HashKey::Tuple(items.iter().map(raise_key).collect())
+    }
+    _ => HashKey::Value(raise_expr(key)),
+  }
+}
+
+fn raise_pattern(pattern: &Pattern) -> pattern::Pattern {
+  let fields = |fields: &[(String, Pattern)]| {
+    fields
+      .iter()
+      .map(|(key, pattern)| (key.to_owned(), raise_pattern(pattern)))
+      .collect()
+  };
+
+  match pattern {
+    Pattern::Array(patterns) => {
+      pattern::Pattern::Array(patterns.iter().map(raise_pattern).collect())
+    }
+    Pattern::Binding(name) => pattern::Pattern::Binding(name.to_owned()),
+    Pattern::Hash(items) => pattern::Pattern::Hash(fields(items)),
+    Pattern::Literal(literal) => pattern::Pattern::Literal(raise_expr(literal)),
+    Pattern::Outcome(is_ok, pattern) => {
+      pattern::Pattern::Outcome(*is_ok, Box::new(raise_pattern(pattern)))
+    }
+    Pattern::Rest(name) => pattern::Pattern::Rest(name.to_owned()),
+    Pattern::Struct(name, items) => {
+      pattern::Pattern::Struct(name.to_owned(), fields(items))
+    }
+    Pattern::Tuple(patterns) => {
+      pattern::Pattern::Tuple(patterns.iter().map(raise_pattern).collect())
+    }
+    Pattern::Wildcard => pattern::Pattern::Wildcard,
+  }
+}
+
+fn raise_ty(kind: &Ty) -> Box<dyn Value> {
+  ty::Ty::new(raise_ty_kind(kind)).boxed()
+}
+
+fn raise_ty_kind(kind: &Ty) -> TyKind {
+  let boxed = |kind: &Ty| Box::new(raise_ty_kind(kind));
+
+  match kind {
+    Ty::Array => TyKind::Array,
+    Ty::ArrayOf(kind) => TyKind::ArrayOf(boxed(kind)),
+    Ty::Bool => TyKind::Bool,
+    Ty::Char => TyKind::Char,
+    Ty::Float => TyKind::Float,
+    Ty::Fn => TyKind::Fn,
+    Ty::Hash => TyKind::Hash,
+    Ty::Infer => TyKind::Infer,
+    Ty::Int => TyKind::Int,
+    Ty::Named(name, args) => {
+      TyKind::Named(name.to_owned(), args.iter().map(raise_ty_kind).collect())
+    }
+    Ty::Nil => TyKind::Nil,
+    Ty::Optional(kind) => TyKind::Optional(boxed(kind)),
+    Ty::Str => TyKind::Str,
+  }
+}
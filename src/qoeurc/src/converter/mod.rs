@@ -1,20 +1,24 @@
+pub mod ast;
+pub mod folder;
+pub mod lowering;
 pub mod parser;
+pub mod visitor;
 
 #[cfg(test)]
 mod tests;
 
+use crate::converter::ast::Ast;
 use crate::converter::parser::{Parser, ParserResult};
 use crate::reporter::diagnostic::DiagnosticBuilder;
-use crate::value::Value;
 
-pub fn astify(input: &str) -> ParserResult<Box<dyn Value>> {
+pub fn astify(input: &str) -> ParserResult<Ast> {
   let mut parser = Parser::new(input);
-  let program = parser.parse()?;
+  let ast = parser.parse_ast();
 
   match parser.errors.is_empty() {
     false => DiagnosticBuilder::print_errors(parser.errors),
     true => (),
   };
 
-  Ok(program)
+  ast
 }
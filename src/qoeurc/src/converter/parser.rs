@@ -1,9 +1,13 @@
+use crate::converter::ast::{
+  self, Ast, BinaryOp, Expr, ExprKind, LoopKind, Param, Pattern, Stmt,
+  StmtKind, Ty, UnaryOp,
+};
+use crate::converter::lowering::{raise, raise_expr};
+use crate::reporter::location::Span;
 use crate::tokenizer::kind::*;
 use crate::tokenizer::scanner::Scanner;
 use crate::tokenizer::token::Token;
 use crate::tokenizer::tokenify;
-use crate::value::instruction::expression::Expression;
-use crate::value::instruction::program::Program;
 use crate::value::Value;
 
 use std::mem;
@@ -11,11 +15,18 @@ use std::mem;
 pub type ParserError = String;
 pub type ParserResult<T> = Result<T, ParserError>;
 
+// reads the tokens of the input into a syntax tree, `token` is the token the
+// parser stands on and `first` the one after it. each `parse_*` starts on the
+// first token of what it reads and stops on its last one. `depth` counts the
+// braces open at `token`
 #[derive(Debug, Clone)]
 pub struct Parser<'a> {
+  pub depth: usize,
   pub errors: Vec<Result<(), String>>,
   pub first: Box<Token>,
+  pub first_span: Span,
   pub scanner: Scanner<'a>,
+  pub span: Span,
   pub token: Box<Token>,
   pub tokens: Vec<Token>,
 }
@@ -23,11 +34,14 @@ pub struct Parser<'a> {
 impl<'a> Parser<'a> {
   pub fn new(input: &'a str) -> Self {
     Parser {
+      depth: 0,
       errors: vec![],
       first: Token::default().boxed(),
+      first_span: Span::default(),
       scanner: Scanner::new(input),
+      span: Span::default(),
       token: Token::default().boxed(),
-      tokens: tokenify(&input).collect(),
+      tokens: tokenify(input).collect(),
     }
   }
 
@@ -37,7 +51,9 @@ impl<'a> Parser<'a> {
 
   pub fn expect_first(&mut self, kind: TokenKind) -> ParserResult<()> {
     if self.first_is(kind) {
-      return Ok(self.next_token());
+      self.next_token();
+
+      return Ok(());
     }
 
     Err(format!(
@@ -50,23 +66,68 @@ impl<'a> Parser<'a> {
     self.first.kind == kind
   }
 
+  // block comments, doc comments and the shebang line carry nothing to run
   pub fn next_token(&mut self) {
-    self.token =
-      mem::replace(&mut self.first, Box::new(self.scanner.advance_token()));
+    let mut next = self.scanner.advance_token();
+
+    while let TokenKind::Comment(Block)
+    | TokenKind::Comment(Doc)
+    | TokenKind::Symbol(Shebang) = next.kind
+    {
+      next = self.scanner.advance_token();
+    }
+
+    self.token = mem::replace(&mut self.first, Box::new(next));
+    self.span = mem::replace(&mut self.first_span, self.scanner.span());
+
+    match self.token.kind {
+      TokenKind::GroupStart(Brace) => self.depth += 1,
+      TokenKind::GroupEnd(Brace) => self.depth = self.depth.saturating_sub(1),
+      _ => (),
+    }
   }
 
+  // the program as values, for the passes that run on them
   pub fn parse(&mut self) -> ParserResult<Box<dyn Value>> {
+    Ok(raise(&self.parse_ast()?))
+  }
+
+  // the program as a syntax tree. a statement that cannot be read is skipped
+  // so the ones after it are read too, the errors of all of them are kept in
+  // `errors` and given back together
+  pub fn parse_ast(&mut self) -> ParserResult<Ast> {
     self.next_token();
     self.next_token();
 
-    Ok(Program::default().parse(self)?)
+    let mut statements = vec![];
+
+    while !self.token_is(TokenKind::EOF) {
+      match self.parse_stmt() {
+        Ok(stmt) => statements.push(stmt),
+        Err(error) => {
+          self.errors.push(Err(error));
+          self.skip_statement();
+        }
+      }
+
+      self.next_token();
+    }
+
+    let errors = self
+      .errors
+      .iter()
+      .filter_map(|error| error.to_owned().err())
+      .collect::<Vec<String>>();
+
+    match errors.is_empty() {
+      true => Ok(Ast { statements }),
+      false => Err(errors.join("\n")),
+    }
   }
 
-  pub fn parse_until(
-    &mut self,
-    kind: TokenKind,
-  ) -> ParserResult<Vec<Box<dyn Value>>> {
-    let mut expressions: Vec<Box<dyn Value>> = vec![];
+  // expressions separated by commas up to the closing `kind`
+  pub fn parse_until(&mut self, kind: TokenKind) -> ParserResult<Vec<Expr>> {
+    let mut expressions = vec![];
 
     if self.first_is(kind) {
       self.next_token();
@@ -75,20 +136,12 @@ impl<'a> Parser<'a> {
     }
 
     self.next_token();
-
-    expressions.push(Expression::parse_expression_by_precedence(
-      self,
-      &mut PrecedenceKind::Lowest,
-    )?);
+    expressions.push(self.parse_expr(PrecedenceKind::Lowest)?);
 
     while self.first_is(TokenKind::Symbol(Comma)) {
       self.next_token();
       self.next_token();
-
-      expressions.push(Expression::parse_expression_by_precedence(
-        self,
-        &mut PrecedenceKind::Lowest,
-      )?);
+      expressions.push(self.parse_expr(PrecedenceKind::Lowest)?);
     }
 
     self.expect_first(kind)?;
@@ -96,14 +149,1286 @@ impl<'a> Parser<'a> {
     Ok(expressions)
   }
 
-  pub fn should_precedence_has_priority(
-    &self,
-    kind: &mut PrecedenceKind,
-  ) -> bool {
-    kind < &mut TokenKind::precedence(self.first.kind)
+  pub fn should_precedence_has_priority(&self, kind: &PrecedenceKind) -> bool {
+    kind < &TokenKind::precedence(self.first.kind)
   }
 
   pub fn token_is(&self, kind: TokenKind) -> bool {
     self.token.kind == kind
   }
+
+  fn expect_identifier(&mut self) -> ParserResult<String> {
+    self.expect_first(TokenKind::Identifier)?;
+
+    Ok(self.token.literal.to_owned())
+  }
+
+  // past the `;` or the `}` that ends the top level statement an error was
+  // found in, a `;` right after that `}` ends the statement too
+  fn skip_statement(&mut self) {
+    while !self.token_is(TokenKind::EOF) {
+      match self.token.kind {
+        TokenKind::Symbol(Semicolon) if self.depth == 0 => return,
+        TokenKind::GroupEnd(Brace) if self.depth == 0 => {
+          if self.first_is(TokenKind::Symbol(Semicolon)) {
+            self.next_token();
+          }
+
+          return;
+        }
+        _ => (),
+      }
+
+      self.next_token();
+    }
+  }
+
+  fn parse_stmt(&mut self) -> ParserResult<Stmt> {
+    let start = self.span.start;
+    let kind = self.parse_stmt_kind()?;
+
+    Ok(Stmt::new(kind, Span::new(start, self.span.end)))
+  }
+
+  fn parse_stmt_kind(&mut self) -> ParserResult<StmtKind> {
+    match self.token.kind {
+      TokenKind::Keyword(Break) => self.parse_break(),
+      TokenKind::Keyword(Continue) => {
+        let label = self.parse_jump_label()?;
+
+        if self.first_is(TokenKind::Symbol(Semicolon)) {
+          self.next_token();
+        }
+
+        Ok(StmtKind::Continue(label))
+      }
+      TokenKind::Keyword(Function) => {
+        Ok(StmtKind::Function(self.parse_function()?))
+      }
+      TokenKind::Keyword(Impl) => self.parse_impl(),
+      TokenKind::Keyword(For)
+      | TokenKind::Keyword(Loop)
+      | TokenKind::Keyword(While)
+      | TokenKind::Symbol(At) => {
+        let expr = self.parse_spanned(Parser::parse_labelled)?;
+
+        if self.first_is(TokenKind::Symbol(Semicolon)) {
+          self.next_token();
+        }
+
+        Ok(StmtKind::Node(expr))
+      }
+      TokenKind::Keyword(Module) => self.parse_module(),
+      TokenKind::Keyword(Public) => self.parse_public(),
+      TokenKind::Keyword(Return) => {
+        let value = self.parse_jump_value()?;
+
+        Ok(StmtKind::Return(value))
+      }
+      TokenKind::Keyword(Struct) => self.parse_struct(),
+      TokenKind::Keyword(Trait) => self.parse_trait(),
+      TokenKind::Keyword(Use) => self.parse_use(),
+      TokenKind::Keyword(Val) => Ok(StmtKind::Val(self.parse_val()?)),
+      _ => {
+        let expr = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        if self.first_is(TokenKind::Symbol(Semicolon)) {
+          self.next_token();
+        }
+
+        Ok(StmtKind::Expr(expr))
+      }
+    }
+  }
+
+  fn parse_block(&mut self) -> ParserResult<ast::Block> {
+    let mut statements = vec![];
+
+    self.next_token();
+
+    while !self.token_is(TokenKind::GroupEnd(Brace)) {
+      if self.token_is(TokenKind::EOF) {
+        return Err(String::from("the block is not closed, `}}` expected"));
+      }
+
+      statements.push(self.parse_stmt()?);
+      self.next_token();
+    }
+
+    Ok(ast::Block { statements })
+  }
+
+  // `break`, `break @label`, `break value` or `break @label value`
+  fn parse_break(&mut self) -> ParserResult<StmtKind> {
+    let label = self.parse_jump_label()?;
+    let value = self.parse_jump_value()?;
+
+    Ok(StmtKind::Break(label, value))
+  }
+
+  fn parse_jump_label(&mut self) -> ParserResult<Option<String>> {
+    if !self.first_is(TokenKind::Symbol(At)) {
+      return Ok(None);
+    }
+
+    self.next_token();
+
+    Ok(Some(self.expect_identifier()?))
+  }
+
+  // the value of a `break` or a `return`, none when the statement ends there
+  fn parse_jump_value(&mut self) -> ParserResult<Option<Expr>> {
+    let mut value = None;
+
+    if !self.first_is(TokenKind::Symbol(Semicolon))
+      && !self.first_is(TokenKind::GroupEnd(Brace))
+    {
+      self.next_token();
+      value = Some(self.parse_expr(PrecedenceKind::Lowest)?);
+    }
+
+    if self.first_is(TokenKind::Symbol(Semicolon)) {
+      self.next_token();
+    }
+
+    Ok(value)
+  }
+
+  fn parse_function(&mut self) -> ParserResult<ast::Function> {
+    let mut function = self.parse_signature()?;
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+    function.block = Some(self.parse_block()?);
+
+    Ok(function)
+  }
+
+  // `ƒ name<T>(params) -> ty`, a function without `->` gives back an array
+  pub fn parse_signature(&mut self) -> ParserResult<ast::Function> {
+    let name = self.expect_identifier()?;
+    let generics = match self.first_is(TokenKind::Operator(LessThan)) {
+      true => self.parse_generics()?,
+      false => vec![],
+    };
+
+    self.expect_first(TokenKind::GroupStart(Parenthesis))?;
+
+    let params = self.parse_params()?;
+    let kind = match self.first_is(TokenKind::Symbol(Arrow)) {
+      true => {
+        self.next_token();
+        self.next_token();
+        self.parse_ty()?
+      }
+      false => Ty::Array,
+    };
+
+    Ok(ast::Function {
+      block: None,
+      generics,
+      kind,
+      name,
+      params,
+    })
+  }
+
+  fn parse_generics(&mut self) -> ParserResult<Vec<String>> {
+    let mut generics = vec![];
+
+    self.expect_first(TokenKind::Operator(LessThan))?;
+
+    loop {
+      generics.push(self.expect_identifier()?);
+
+      if !self.first_is(TokenKind::Symbol(Comma)) {
+        break;
+      }
+
+      self.next_token();
+    }
+
+    self.expect_first(TokenKind::Operator(GreaterThan))?;
+
+    Ok(generics)
+  }
+
+  fn parse_params(&mut self) -> ParserResult<Vec<Param>> {
+    let mut params = vec![];
+
+    if self.first_is(TokenKind::GroupEnd(Parenthesis)) {
+      self.next_token();
+
+      return Ok(params);
+    }
+
+    params.push(self.parse_param()?);
+
+    while self.first_is(TokenKind::Symbol(Comma)) {
+      self.next_token();
+      params.push(self.parse_param()?);
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Parenthesis))?;
+
+    // only the last parameter collects the remaining arguments
+    if let Some(param) = params[..params.len() - 1].iter().find(|p| p.variadic)
+    {
+      return Err(format!(
+        "the variadic parameter `{}` must be the last one",
+        param.name,
+      ));
+    }
+
+    Ok(params)
+  }
+
+  // `self`, `name: ty`, `name: ty = default` or `..name: ty`
+  fn parse_param(&mut self) -> ParserResult<Param> {
+    self.next_token();
+
+    if self.token_is(TokenKind::Keyword(SelfLower)) {
+      return Ok(Param {
+        default: None,
+        kind: Ty::Named("Self".into(), vec![]),
+        name: "self".into(),
+        pattern: None,
+        variadic: false,
+      });
+    }
+
+    let variadic = self.token_is(TokenKind::Operator(Range));
+
+    if variadic {
+      self.next_token();
+    }
+
+    let (name, pattern) = self.parse_target()?;
+
+    self.expect_first(TokenKind::Symbol(Colon))?;
+    self.next_token();
+
+    let kind = self.parse_ty()?;
+    let mut default = None;
+
+    if self.first_is(TokenKind::Operator(Assign)) {
+      if variadic {
+        return Err(format!(
+          "the variadic parameter `{}` cannot have a default",
+          name,
+        ));
+      }
+
+      self.next_token();
+      self.next_token();
+      default = Some(self.parse_expr(PrecedenceKind::Lowest)?);
+    }
+
+    Ok(Param {
+      default,
+      kind,
+      name,
+      pattern,
+      variadic,
+    })
+  }
+
+  // what a parameter or a `val` binds, a name or a pattern
+  fn parse_target(&mut self) -> ParserResult<(String, Option<Pattern>)> {
+    match self.token_is(TokenKind::Identifier)
+      && !self.first_is(TokenKind::GroupStart(Brace))
+      && !self.first_is(TokenKind::GroupStart(Parenthesis))
+    {
+      true => Ok((self.token.literal.to_owned(), None)),
+      false => Ok((String::new(), Some(self.parse_pattern()?))),
+    }
+  }
+
+  // `impl Ty { .. }` or `impl Trait for Ty { .. }`
+  fn parse_impl(&mut self) -> ParserResult<StmtKind> {
+    let mut ty = self.expect_identifier()?;
+    let mut interface = None;
+
+    if self.first_is(TokenKind::Keyword(For)) {
+      self.next_token();
+      interface = Some(ty);
+      ty = self.expect_identifier()?;
+    }
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    let mut functions = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      self.expect_first(TokenKind::Keyword(Function))?;
+      functions.push(self.parse_function()?);
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(StmtKind::Impl(ast::Impl {
+      functions,
+      interface,
+      ty,
+    }))
+  }
+
+  // `mod name;` is read from its own file
+  fn parse_module(&mut self) -> ParserResult<StmtKind> {
+    let name = self.expect_identifier()?;
+
+    if self.first_is(TokenKind::Symbol(Semicolon)) {
+      self.next_token();
+
+      return Ok(StmtKind::Module(name, None));
+    }
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    Ok(StmtKind::Module(name, Some(self.parse_block()?)))
+  }
+
+  fn parse_public(&mut self) -> ParserResult<StmtKind> {
+    match self.first.kind {
+      TokenKind::Keyword(Function)
+      | TokenKind::Keyword(Struct)
+      | TokenKind::Keyword(Trait)
+      | TokenKind::Keyword(Val) => self.next_token(),
+      kind => {
+        return Err(format!("expected item after `pub`, found {:?}", kind))
+      }
+    }
+
+    match self.parse_stmt_kind()? {
+      StmtKind::Val(val) if val.pattern.is_some() => {
+        Err(String::from("`pub val` must bind a single name"))
+      }
+      kind => Ok(StmtKind::Public(Box::new(kind))),
+    }
+  }
+
+  fn parse_struct(&mut self) -> ParserResult<StmtKind> {
+    let name = self.expect_identifier()?;
+    let generics = match self.first_is(TokenKind::Operator(LessThan)) {
+      true => self.parse_generics()?,
+      false => vec![],
+    };
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    let mut fields = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      let field = self.expect_identifier()?;
+
+      self.expect_first(TokenKind::Symbol(Colon))?;
+      self.next_token();
+      fields.push((field, self.parse_ty()?));
+
+      if self.first_is(TokenKind::Symbol(Comma)) {
+        self.next_token();
+      }
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(StmtKind::Struct(ast::Struct {
+      fields,
+      generics,
+      name,
+    }))
+  }
+
+  // a trait lists the signatures of its functions, each ended by `;`
+  fn parse_trait(&mut self) -> ParserResult<StmtKind> {
+    let name = self.expect_identifier()?;
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    let mut functions = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      self.expect_first(TokenKind::Keyword(Function))?;
+      functions.push(self.parse_signature()?);
+      self.expect_first(TokenKind::Symbol(Semicolon))?;
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(StmtKind::Trait(ast::Trait { functions, name }))
+  }
+
+  // `use a::b;`, `use a::*;` or `use a::{b, c};`
+  fn parse_use(&mut self) -> ParserResult<StmtKind> {
+    let mut import = ast::Use {
+      glob: false,
+      items: vec![],
+      path: vec![],
+    };
+
+    loop {
+      match self.first.kind {
+        TokenKind::Keyword(Super) => {
+          self.next_token();
+          import.path.push("super".into());
+        }
+        TokenKind::Identifier => import.path.push(self.expect_identifier()?),
+        TokenKind::Operator(Star) if !import.path.is_empty() => {
+          self.next_token();
+          import.glob = true;
+          break;
+        }
+        TokenKind::GroupStart(Brace) if !import.path.is_empty() => {
+          self.next_token();
+
+          while !self.first_is(TokenKind::GroupEnd(Brace)) {
+            import.items.push(self.expect_identifier()?);
+
+            if !self.first_is(TokenKind::GroupEnd(Brace)) {
+              self.expect_first(TokenKind::Symbol(Comma))?;
+            }
+          }
+
+          self.expect_first(TokenKind::GroupEnd(Brace))?;
+          break;
+        }
+        kind => return Err(format!("unexpected {:?} in `use` path", kind)),
+      }
+
+      if !self.first_is(TokenKind::Symbol(ColonColon)) {
+        break;
+      }
+
+      self.next_token();
+    }
+
+    if !import.glob && import.items.is_empty() {
+      match import.path.pop() {
+        Some(item) if !import.path.is_empty() && item != "super" => {
+          import.items.push(item);
+        }
+        _ => {
+          return Err(String::from("expected an item to import in `use` path"))
+        }
+      }
+    }
+
+    self.expect_first(TokenKind::Symbol(Semicolon))?;
+
+    Ok(StmtKind::Use(import))
+  }
+
+  // `val name := value;`, `val name: ty = value;` or `val pattern := value;`
+  fn parse_val(&mut self) -> ParserResult<ast::Val> {
+    self.next_token();
+
+    let (name, pattern) = match !self.token_is(TokenKind::Identifier)
+      || self.first_is(TokenKind::GroupStart(Brace))
+      || self.first_is(TokenKind::GroupStart(Parenthesis))
+    {
+      true => (String::new(), Some(self.parse_pattern()?)),
+      false => (self.token.literal.to_owned(), None),
+    };
+
+    // `val name := value;` leaves the type to the checker
+    let kind = match self.first_is(TokenKind::Operator(AssignType)) {
+      true => {
+        self.next_token();
+        None
+      }
+      false => {
+        self.expect_first(TokenKind::Symbol(Colon))?;
+        self.next_token();
+
+        let kind = self.parse_ty()?;
+
+        self.expect_first(TokenKind::Operator(Assign))?;
+        Some(kind)
+      }
+    };
+
+    self.next_token();
+
+    let value = self.parse_expr(PrecedenceKind::Lowest)?;
+
+    self.expect_first(TokenKind::Symbol(Semicolon))?;
+
+    Ok(ast::Val {
+      immutable: true,
+      kind,
+      name,
+      pattern,
+      value,
+    })
+  }
+
+  // a loop, with the label written before it when there is one
+  fn parse_labelled(&mut self) -> ParserResult<ExprKind> {
+    let label = match self.token_is(TokenKind::Symbol(At)) {
+      true => {
+        let label = self.expect_identifier()?;
+
+        self.next_token();
+        Some(label)
+      }
+      false => None,
+    };
+
+    let kind = match self.token.kind {
+      TokenKind::Keyword(For) => self.parse_for()?,
+      TokenKind::Keyword(Loop) => LoopKind::Infinite,
+      TokenKind::Keyword(While) => {
+        self.next_token();
+        LoopKind::While(Box::new(self.parse_expr(PrecedenceKind::Lowest)?))
+      }
+      _ => {
+        return Err(format!(
+          "label `@{}` must be followed by a loop",
+          label.unwrap_or_default(),
+        ))
+      }
+    };
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    Ok(ExprKind::Loop(ast::Loop {
+      block: self.parse_block()?,
+      kind,
+      label,
+    }))
+  }
+
+  // `for iterable |a, b|`, the names are left out to only repeat the block
+  fn parse_for(&mut self) -> ParserResult<LoopKind> {
+    self.next_token();
+
+    let iterable = self.parse_expr(PrecedenceKind::Lowest)?;
+    let mut variables = vec![];
+
+    if self.first_is(TokenKind::Operator(Or)) {
+      self.next_token();
+
+      while !self.first_is(TokenKind::Operator(Or)) {
+        variables.push(self.expect_identifier()?);
+
+        if !self.first_is(TokenKind::Operator(Or)) {
+          self.expect_first(TokenKind::Symbol(Comma))?;
+        }
+      }
+
+      self.expect_first(TokenKind::Operator(Or))?;
+    }
+
+    Ok(LoopKind::For(variables, Box::new(iterable)))
+  }
+
+  pub fn parse_expr(
+    &mut self,
+    precedence: PrecedenceKind,
+  ) -> ParserResult<Expr> {
+    let start = self.span.start;
+    let mut expr = self.parse_prefix()?;
+
+    while !self.first_is(TokenKind::Symbol(Semicolon))
+      && self.should_precedence_has_priority(&precedence)
+    {
+      self.next_token();
+
+      let kind = self.parse_infix(expr)?;
+
+      expr = Expr::new(kind, Span::new(start, self.span.end));
+    }
+
+    Ok(expr)
+  }
+
+  // an expression from the token it starts on to the last one it was read from
+  fn parse_spanned(
+    &mut self,
+    parse: impl FnOnce(&mut Self) -> ParserResult<ExprKind>,
+  ) -> ParserResult<Expr> {
+    let start = self.span.start;
+    let kind = parse(self)?;
+
+    Ok(Expr::new(kind, Span::new(start, self.span.end)))
+  }
+
+  fn parse_prefix(&mut self) -> ParserResult<Expr> {
+    self.parse_spanned(Parser::parse_prefix_kind)
+  }
+
+  fn parse_prefix_kind(&mut self) -> ParserResult<ExprKind> {
+    match self.token.kind {
+      TokenKind::Comment(Line) => {
+        Ok(ExprKind::Comment(self.token.literal.to_owned()))
+      }
+      TokenKind::GroupStart(Brace) => self.parse_hash(),
+      TokenKind::GroupStart(Bracket) => Ok(ExprKind::Array(
+        self.parse_until(TokenKind::GroupEnd(Bracket))?,
+      )),
+      TokenKind::GroupStart(Parenthesis) => {
+        self.next_token();
+
+        let expr = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        self.expect_first(TokenKind::GroupEnd(Parenthesis))?;
+
+        Ok(ExprKind::Group(Box::new(expr)))
+      }
+      TokenKind::Identifier
+        if self.first_is(TokenKind::GroupStart(Brace))
+          && self.token.literal.starts_with(char::is_uppercase) =>
+      {
+        self.parse_instance()
+      }
+      TokenKind::Identifier
+        if self.first_is(TokenKind::GroupStart(Parenthesis))
+          && (self.token.literal == "ok" || self.token.literal == "err") =>
+      {
+        let is_ok = self.token.literal == "ok";
+
+        self.next_token();
+        self.next_token();
+
+        let value = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        self.expect_first(TokenKind::GroupEnd(Parenthesis))?;
+
+        Ok(ExprKind::Outcome(is_ok, Box::new(value)))
+      }
+      TokenKind::Identifier | TokenKind::Keyword(SelfLower) => {
+        Ok(ExprKind::Identifier(self.token.literal.to_owned()))
+      }
+      TokenKind::Keyword(False) => Ok(ExprKind::Bool(false)),
+      TokenKind::Keyword(True) => Ok(ExprKind::Bool(true)),
+      TokenKind::Keyword(Delete) => {
+        self.next_token();
+
+        Ok(ExprKind::Delete(Box::new(
+          self.parse_expr(PrecedenceKind::Lowest)?,
+        )))
+      }
+      TokenKind::Keyword(If) => Ok(ExprKind::If(self.parse_if()?)),
+      TokenKind::Keyword(For)
+      | TokenKind::Keyword(Loop)
+      | TokenKind::Keyword(While)
+      | TokenKind::Symbol(At) => self.parse_labelled(),
+      TokenKind::Keyword(Match) => self.parse_match(),
+      TokenKind::Keyword(Nil) => Ok(ExprKind::Nil),
+      TokenKind::Literal(Float) => match self.token.literal.parse() {
+        Ok(value) => Ok(ExprKind::Float(value)),
+        Err(error) => Err(format!("{}", error)),
+      },
+      TokenKind::Literal(Int) => {
+        match self.token.literal.replace('_', "").parse() {
+          Ok(value) => Ok(ExprKind::Int(value)),
+          Err(_) => Err(String::from("parse int error")),
+        }
+      }
+      TokenKind::Literal(Str) => {
+        Ok(ExprKind::Str(self.token.literal.to_owned()))
+      }
+      TokenKind::Operator(Range) => {
+        self.next_token();
+
+        Ok(ExprKind::Spread(Box::new(
+          self.parse_expr(PrecedenceKind::Interval)?,
+        )))
+      }
+      TokenKind::Operator(Or) | TokenKind::Operator(OrOr) => {
+        Ok(ExprKind::Closure(self.parse_closure()?))
+      }
+      TokenKind::Operator(Minus) | TokenKind::Symbol(Bang) => {
+        let operator = match self.token.kind {
+          TokenKind::Operator(Minus) => UnaryOp::Neg,
+          _ => UnaryOp::Not,
+        };
+
+        self.next_token();
+
+        let rhs = self.parse_expr(PrecedenceKind::Unary)?;
+
+        Ok(ExprKind::Unary(operator, Box::new(rhs)))
+      }
+      _ => Err(format!(
+        "expected an expression, found `{}`",
+        self.token.literal
+      )),
+    }
+  }
+
+  // what follows `lhs`, the parser stands on the operator
+  fn parse_infix(&mut self, lhs: Expr) -> ParserResult<ExprKind> {
+    let lhs = Box::new(lhs);
+
+    match self.token.kind {
+      TokenKind::GroupStart(Bracket) => {
+        self.next_token();
+
+        let rhs = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        self.expect_first(TokenKind::GroupEnd(Bracket))?;
+
+        Ok(ExprKind::Index(lhs, Box::new(rhs)))
+      }
+      TokenKind::GroupStart(Parenthesis) => {
+        Ok(ExprKind::Call(lhs, self.parse_args()?))
+      }
+      TokenKind::Symbol(Dot) | TokenKind::Symbol(QuestionDot) => {
+        let optional = self.token_is(TokenKind::Symbol(QuestionDot));
+
+        Ok(ExprKind::Member(lhs, optional, self.expect_identifier()?))
+      }
+      TokenKind::Operator(Range) | TokenKind::Operator(RangeInclusive) => {
+        let inclusive = self.token_is(TokenKind::Operator(RangeInclusive));
+
+        self.next_token();
+
+        let end = self.parse_expr(PrecedenceKind::Interval)?;
+        let mut step = None;
+
+        if self.first_is(TokenKind::Identifier) && self.first.literal == "step"
+        {
+          self.next_token();
+          self.next_token();
+          step = Some(Box::new(self.parse_expr(PrecedenceKind::Interval)?));
+        }
+
+        Ok(ExprKind::Range(ast::Range {
+          end: Box::new(end),
+          inclusive,
+          start: lhs,
+          step,
+        }))
+      }
+      TokenKind::Symbol(Question) => Ok(ExprKind::Try(lhs)),
+      TokenKind::Operator(Assign) => {
+        self.next_token();
+
+        let value = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        Ok(ExprKind::Assign(lhs, Box::new(value)))
+      }
+      kind => {
+        let operator = match BinaryOp::from_kind(kind) {
+          Some(operator) => operator,
+          None => {
+            return Err(format!(
+              "expected an operator, found `{}`",
+              self.token.literal
+            ))
+          }
+        };
+        let precedence = self.current_precedence();
+
+        self.next_token();
+
+        let rhs = self.parse_expr(precedence)?;
+
+        Ok(ExprKind::Binary(lhs, operator, Box::new(rhs)))
+      }
+    }
+  }
+
+  // the arguments of a call, named ones come after the positional ones
+  fn parse_args(&mut self) -> ParserResult<Vec<Expr>> {
+    let mut args: Vec<Expr> = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(Parenthesis)) {
+      if !args.is_empty() {
+        self.expect_first(TokenKind::Symbol(Comma))?;
+      }
+
+      self.next_token();
+
+      let arg = match self.token_is(TokenKind::Identifier)
+        && self.first_is(TokenKind::Symbol(Colon))
+      {
+        true => self.parse_spanned(|parser| {
+          let name = parser.token.literal.to_owned();
+
+          parser.next_token();
+          parser.next_token();
+
+          let value = parser.parse_expr(PrecedenceKind::Lowest)?;
+
+          Ok(ExprKind::NamedArg(name, Box::new(value)))
+        })?,
+        false => self.parse_expr(PrecedenceKind::Lowest)?,
+      };
+
+      if let Some(named) = args.last() {
+        if matches!(named.kind, ExprKind::NamedArg(..))
+          && !matches!(arg.kind, ExprKind::NamedArg(..))
+        {
+          return Err(format!(
+            "the positional argument `{}` follows the named argument `{}`",
+            raise_expr(&arg),
+            raise_expr(named),
+          ));
+        }
+      }
+
+      args.push(arg);
+    }
+
+    self.next_token();
+
+    Ok(args)
+  }
+
+  // `|a, b: int| -> int { .. }`, `|a| a + 1` or `|| ..`
+  fn parse_closure(&mut self) -> ParserResult<ast::Closure> {
+    let mut params = vec![];
+
+    if self.token_is(TokenKind::Operator(Or)) {
+      while !self.first_is(TokenKind::Operator(Or)) {
+        self.next_token();
+
+        let (name, pattern) = self.parse_target()?;
+        let mut kind = Ty::Infer;
+
+        if self.first_is(TokenKind::Symbol(Colon)) {
+          self.next_token();
+          self.next_token();
+          kind = self.parse_ty()?;
+        }
+
+        params.push(Param {
+          default: None,
+          kind,
+          name,
+          pattern,
+          variadic: false,
+        });
+
+        if !self.first_is(TokenKind::Operator(Or)) {
+          self.expect_first(TokenKind::Symbol(Comma))?;
+        }
+      }
+
+      self.expect_first(TokenKind::Operator(Or))?;
+    }
+
+    let mut kind = Ty::Infer;
+
+    if self.first_is(TokenKind::Symbol(Arrow)) {
+      self.next_token();
+      self.next_token();
+      kind = self.parse_ty()?;
+    }
+
+    self.next_token();
+
+    let block = match self.token_is(TokenKind::GroupStart(Brace)) {
+      true => self.parse_block()?,
+      false => {
+        let start = self.span.start;
+        let expr = self.parse_expr(PrecedenceKind::Lowest)?;
+        let span = Span::new(start, self.span.end);
+
+        ast::Block {
+          statements: vec![Stmt::new(StmtKind::Node(expr), span)],
+        }
+      }
+    };
+
+    Ok(ast::Closure {
+      block,
+      kind,
+      params,
+    })
+  }
+
+  // a key and its value, or a spread of another hash. keys are written as
+  // literals, each one once
+  fn parse_hash(&mut self) -> ParserResult<ExprKind> {
+    let mut items: Vec<(Option<Expr>, Expr)> = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      self.next_token();
+
+      if self.token_is(TokenKind::Operator(Range)) {
+        let spread = self.parse_spanned(|parser| {
+          parser.next_token();
+
+          let value = parser.parse_expr(PrecedenceKind::Interval)?;
+
+          Ok(ExprKind::Spread(Box::new(value)))
+        })?;
+
+        items.push((None, spread));
+      } else {
+        let key = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        self.expect_first(TokenKind::Symbol(Colon))?;
+        self.next_token();
+
+        let value = self.parse_expr(PrecedenceKind::Lowest)?;
+
+        Parser::check_key(&key)?;
+
+        if items.iter().any(|(other, _)| {
+          other.as_ref().map(|other| &other.kind) == Some(&key.kind)
+        }) {
+          return Err(format!(
+            "duplicate key `{}` in hash literal",
+            raise_expr(&key).text().trim_matches('"'),
+          ));
+        }
+
+        items.push((Some(key), value));
+      }
+
+      if !self.first_is(TokenKind::GroupEnd(Brace)) {
+        self.expect_first(TokenKind::Symbol(Comma))?;
+      }
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(ExprKind::Hash(items))
+  }
+
+  fn check_key(key: &Expr) -> ParserResult<()> {
+    match &key.kind {
+      ExprKind::Bool(_)
+      | ExprKind::Char(_)
+      | ExprKind::Int(_)
+      | ExprKind::Nil
+      | ExprKind::Str(_) => Ok(()),
+      ExprKind::Array(items) => items.iter().try_for_each(Parser::check_key),
+      ExprKind::Hash(items) => items
+        .iter()
+        .try_for_each(|(_, value)| Parser::check_key(value)),
+      ExprKind::Float(_) => Err(format!(
+        "`{}` cannot be used as a hash key, floats are not hashable",
+        raise_expr(key),
+      )),
+      _ => Err(format!(
+        "`{}` cannot be used as a hash key",
+        raise_expr(key)
+      )),
+    }
+  }
+
+  // `if condition { .. }` followed by `else if ..` or `else { .. }`
+  fn parse_if(&mut self) -> ParserResult<ast::If> {
+    self.next_token();
+
+    let condition = self.parse_expr(PrecedenceKind::Lowest)?;
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    let consequence = self.parse_block()?;
+    let mut alternative = None;
+
+    if self.first_is(TokenKind::Keyword(Else)) {
+      self.next_token();
+
+      alternative = match self.first_is(TokenKind::Keyword(If)) {
+        true => {
+          self.next_token();
+          Some(Box::new(self.parse_spanned(|parser| {
+            Ok(ExprKind::If(parser.parse_if()?))
+          })?))
+        }
+        false => {
+          self.expect_first(TokenKind::GroupStart(Brace))?;
+          Some(Box::new(self.parse_spanned(|parser| {
+            Ok(ExprKind::Block(parser.parse_block()?))
+          })?))
+        }
+      };
+    }
+
+    Ok(ast::If {
+      alternative,
+      condition: Box::new(condition),
+      consequence,
+    })
+  }
+
+  // `Name { field, field: value }`, a field alone takes the value of the
+  // variable of the same name
+  fn parse_instance(&mut self) -> ParserResult<ExprKind> {
+    let name = self.token.literal.to_owned();
+    let mut fields = vec![];
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      let field = self.expect_identifier()?;
+      let value = match self.first_is(TokenKind::Symbol(Colon)) {
+        true => {
+          self.next_token();
+          self.next_token();
+          self.parse_expr(PrecedenceKind::Lowest)?
+        }
+        false => Expr::new(ExprKind::Identifier(field.to_owned()), self.span),
+      };
+
+      fields.push((field, value));
+
+      if !self.first_is(TokenKind::GroupEnd(Brace)) {
+        self.expect_first(TokenKind::Symbol(Comma))?;
+      }
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(ExprKind::Instance(name, fields))
+  }
+
+  // `match subject { pattern => value, pattern => { .. } }`, the comma after
+  // a block is left out
+  fn parse_match(&mut self) -> ParserResult<ExprKind> {
+    self.next_token();
+
+    let subject = self.parse_expr(PrecedenceKind::Lowest)?;
+    let mut arms = vec![];
+
+    self.expect_first(TokenKind::GroupStart(Brace))?;
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      self.next_token();
+
+      let pattern = self.parse_pattern()?;
+
+      self.expect_first(TokenKind::Symbol(ArrowFunction))?;
+      self.next_token();
+
+      let body = match self.token_is(TokenKind::GroupStart(Brace)) {
+        true => self
+          .parse_spanned(|parser| Ok(ExprKind::Block(parser.parse_block()?)))?,
+        false => self.parse_expr(PrecedenceKind::Lowest)?,
+      };
+      let is_block = matches!(body.kind, ExprKind::Block(_));
+
+      arms.push((pattern, body));
+
+      if self.first_is(TokenKind::Symbol(Comma)) {
+        self.next_token();
+      } else if !is_block && !self.first_is(TokenKind::GroupEnd(Brace)) {
+        self.expect_first(TokenKind::Symbol(Comma))?;
+      }
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(ExprKind::Match(Box::new(subject), arms))
+  }
+
+  pub fn parse_pattern(&mut self) -> ParserResult<Pattern> {
+    match self.token.kind {
+      TokenKind::Keyword(Underscore) => Ok(Pattern::Wildcard),
+      TokenKind::Keyword(Nil)
+      | TokenKind::Keyword(False)
+      | TokenKind::Keyword(True)
+      | TokenKind::Literal(Float)
+      | TokenKind::Literal(Int)
+      | TokenKind::Literal(Str) => Ok(Pattern::Literal(self.parse_prefix()?)),
+      TokenKind::Operator(Minus) => {
+        let start = self.span.start;
+
+        self.next_token();
+
+        let kind = match self.parse_pattern()? {
+          Pattern::Literal(Expr {
+            kind: ExprKind::Int(value),
+            ..
+          }) => ExprKind::Int(-value),
+          Pattern::Literal(Expr {
+            kind: ExprKind::Float(value),
+            ..
+          }) => ExprKind::Float(-value),
+          _ => {
+            return Err(String::from("expected a number after `-` in pattern"))
+          }
+        };
+
+        Ok(Pattern::Literal(Expr::new(
+          kind,
+          Span::new(start, self.span.end),
+        )))
+      }
+      TokenKind::GroupStart(Bracket) => {
+        let patterns = self.parse_patterns(Bracket)?;
+
+        match patterns.iter().position(|p| matches!(p, Pattern::Rest(_))) {
+          Some(x) if x != patterns.len() - 1 => Err(String::from(
+            "`..` must be the last element of an array pattern",
+          )),
+          _ => Ok(Pattern::Array(patterns)),
+        }
+      }
+      TokenKind::GroupStart(Brace) => Ok(Pattern::Hash(self.parse_fields()?)),
+      TokenKind::GroupStart(Parenthesis) => {
+        let patterns = self.parse_patterns(Parenthesis)?;
+
+        match patterns.iter().any(|p| matches!(p, Pattern::Rest(_))) {
+          true => Err(String::from("`..` cannot be used in a tuple pattern")),
+          false => Ok(Pattern::Tuple(patterns)),
+        }
+      }
+      TokenKind::Identifier
+        if self.first_is(TokenKind::GroupStart(Parenthesis))
+          && (self.token.literal == "ok" || self.token.literal == "err") =>
+      {
+        let is_ok = self.token.literal == "ok";
+
+        self.next_token();
+        self.next_token();
+
+        let pattern = self.parse_pattern()?;
+
+        self.expect_first(TokenKind::GroupEnd(Parenthesis))?;
+
+        Ok(Pattern::Outcome(is_ok, Box::new(pattern)))
+      }
+      TokenKind::Identifier
+        if self.first_is(TokenKind::GroupStart(Brace))
+          && self.token.literal.starts_with(char::is_uppercase) =>
+      {
+        let name = self.token.literal.to_owned();
+
+        self.next_token();
+
+        Ok(Pattern::Struct(name, self.parse_fields()?))
+      }
+      TokenKind::Identifier => {
+        Ok(Pattern::Binding(self.token.literal.to_owned()))
+      }
+      _ => Err(format!(
+        "expected a pattern, found `{}`",
+        self.token.literal
+      )),
+    }
+  }
+
+  // the elements of an array or a tuple pattern, `..` and `..name` take the
+  // remaining ones
+  fn parse_patterns(&mut self, group: GroupKind) -> ParserResult<Vec<Pattern>> {
+    let mut patterns = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(group)) {
+      self.next_token();
+
+      let pattern = match self.token.kind {
+        TokenKind::Operator(Range) => {
+          match self.first_is(TokenKind::Identifier) {
+            true => Pattern::Rest(Some(self.expect_identifier()?)),
+            false => Pattern::Rest(None),
+          }
+        }
+        _ => self.parse_pattern()?,
+      };
+
+      patterns.push(pattern);
+
+      if !self.first_is(TokenKind::GroupEnd(group)) {
+        self.expect_first(TokenKind::Symbol(Comma))?;
+      }
+    }
+
+    self.expect_first(TokenKind::GroupEnd(group))?;
+
+    Ok(patterns)
+  }
+
+  // `{ key, key: pattern }`, a key alone binds the name of the key
+  fn parse_fields(&mut self) -> ParserResult<Vec<(String, Pattern)>> {
+    let mut fields = vec![];
+
+    while !self.first_is(TokenKind::GroupEnd(Brace)) {
+      let key = self.expect_identifier()?;
+      let pattern = match self.first_is(TokenKind::Symbol(Colon)) {
+        true => {
+          self.next_token();
+          self.next_token();
+          self.parse_pattern()?
+        }
+        false => Pattern::Binding(key.to_owned()),
+      };
+
+      fields.push((key, pattern));
+
+      if !self.first_is(TokenKind::GroupEnd(Brace)) {
+        self.expect_first(TokenKind::Symbol(Comma))?;
+      }
+    }
+
+    self.expect_first(TokenKind::GroupEnd(Brace))?;
+
+    Ok(fields)
+  }
+
+  // a type, `?` after it makes it optional
+  pub fn parse_ty(&mut self) -> ParserResult<Ty> {
+    let kind = self.parse_ty_required()?;
+
+    if self.first_is(TokenKind::Symbol(Question)) {
+      self.next_token();
+
+      return Ok(Ty::Optional(Box::new(kind)));
+    }
+
+    Ok(kind)
+  }
+
+  fn parse_ty_required(&mut self) -> ParserResult<Ty> {
+    if self.token_is(TokenKind::GroupStart(Bracket)) {
+      if self.first_is(TokenKind::GroupEnd(Bracket)) {
+        self.next_token();
+
+        return Ok(Ty::Array);
+      }
+
+      self.next_token();
+
+      let kind = self.parse_ty()?;
+
+      self.expect_first(TokenKind::GroupEnd(Bracket))?;
+
+      return Ok(Ty::ArrayOf(Box::new(kind)));
+    }
+
+    let name = self.token.literal.to_owned();
+
+    match name.as_str() {
+      "bool" => Ok(Ty::Bool),
+      "char" => Ok(Ty::Char),
+      "float" => Ok(Ty::Float),
+      "fn" => Ok(Ty::Fn),
+      "hash" => Ok(Ty::Hash),
+      "int" => Ok(Ty::Int),
+      "nil" => Ok(Ty::Nil),
+      "str" => Ok(Ty::Str),
+      _ => match self.token.kind {
+        TokenKind::Identifier | TokenKind::Keyword(SelfUpper) => {
+          let mut args = vec![];
+
+          if self.first_is(TokenKind::Operator(LessThan)) {
+            self.next_token();
+
+            loop {
+              self.next_token();
+              args.push(self.parse_ty()?);
+
+              if !self.first_is(TokenKind::Symbol(Comma)) {
+                break;
+              }
+
+              self.next_token();
+            }
+
+            self.expect_first(TokenKind::Operator(GreaterThan))?;
+          }
+
+          Ok(Ty::Named(name, args))
+        }
+        TokenKind::Keyword(Underscore) => Ok(Ty::Infer),
+        _ => Err(String::from("type error expression")),
+      },
+    }
+  }
 }
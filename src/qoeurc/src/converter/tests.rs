@@ -2,9 +2,227 @@ extern crate qutonium;
 
 use qutonium::prelude::*;
 
+use crate::testing::suite;
+
 #[test]
 fn from_test() {
   suite!("qoeurc::parser", {
-    "test syntax of comments" || { Ok(()) }
+    "test syntax of comments" || { must!(true; be.truthy) }
+  });
+}
+
+#[test]
+fn ast_test() {
+  use crate::converter::ast::{Expr, ExprKind, StmtKind};
+  use crate::converter::lowering::raise;
+  use crate::converter::parser::Parser;
+  use crate::converter::visitor::{self, Visitor};
+  use crate::reporter::location::Span;
+
+  const PROGRAMS: [&str; 6] = [
+    "val a := 1; val b : int = a + 2 * 3; print(-b, !true);",
+    "ƒ f<T>(x: T, n: int = 2, ..rest: [int]) -> T { return x; }",
+    "struct Point<T> { x: T, y: T } impl Point { ƒ x(self) -> int { 1; } }",
+    "val { a, b } := { \"a\": 1, \"b\": 2, ..c }; @outer for 0..10 step 2 |i| { break @outer; }",
+    "match a { [x, ..] => x?.y, _ => |n: int| n + 1 } if a { b; } else { c; }",
+    "use std::io::*; mod m { pub ƒ g() -> int { 1; } } trait T { ƒ h(); }",
+  ];
+
+  suite!("qoeurc::converter::ast", {
+    "test the syntax tree stands for the same program" || {
+      let texts = PROGRAMS
+        .iter()
+        .map(|input| Parser::new(input).parse().unwrap().text())
+        .collect::<Vec<String>>();
+      let raised = PROGRAMS
+        .iter()
+        .map(|input| raise(&Parser::new(input).parse_ast().unwrap()).text())
+        .collect::<Vec<String>>();
+
+      must!(raised; eq texts)
+    }
+
+    "test statements keep the bytes they were read from" || {
+      let input = "val a := 1;\n  print(a + 2);\nƒ f() -> int { 1; }";
+      let ast = Parser::new(input).parse_ast().unwrap();
+      let sources = ast
+        .statements
+        .iter()
+        .map(|stmt| &input[stmt.span.start..stmt.span.end])
+        .collect::<Vec<&str>>();
+
+      must!(
+        sources;
+        eq vec!["val a := 1;", "print(a + 2);", "ƒ f() -> int { 1; }"]
+      )
+    }
+
+    "test a one line input is read to its end" || {
+      let len = |input| {
+        Parser::new(input).parse_ast().map(|ast| ast.statements.len())
+      };
+
+      must!(len("1;"); eq Ok(1))?;
+      must!(len("val a := 1; a "); eq Ok(2))
+    }
+
+    "test expressions are plain data" || {
+      let ast = Parser::new("a.b(1);").parse_ast().unwrap();
+      let expr = |kind, start, end| Expr::new(kind, Span::new(start, end));
+
+      let call = expr(
+        ExprKind::Call(
+          Box::new(expr(
+            ExprKind::Member(
+              Box::new(expr(ExprKind::Identifier("a".into()), 0, 1)),
+              false,
+              "b".into(),
+            ),
+            0,
+            3,
+          )),
+          vec![expr(ExprKind::Int(1), 4, 5)],
+        ),
+        0,
+        6,
+      );
+
+      must!(ast.statements[0].kind.to_owned(); eq StmtKind::Expr(call))
+    }
+
+    "test expressions keep the bytes they were read from" || {
+      struct Sources<'a>(&'a str, Vec<&'a str>);
+
+      impl<'a> Visitor for Sources<'a> {
+        fn visit_expr(&mut self, expr: &Expr) {
+          self.1.push(&self.0[expr.span.start..expr.span.end]);
+          visitor::walk_expr(self, expr)
+        }
+      }
+
+      let input = "f(-a, n: [1, 2][0]) ?? { \"k\": 1 };";
+      let ast = Parser::new(input).parse_ast().unwrap();
+      let mut sources = Sources(input, vec![]);
+
+      sources.visit_ast(&ast);
+
+      must!(
+        sources.1;
+        eq vec![
+          "f(-a, n: [1, 2][0]) ?? { \"k\": 1 }",
+          "f(-a, n: [1, 2][0])",
+          "f",
+          "-a",
+          "a",
+          "n: [1, 2][0]",
+          "[1, 2][0]",
+          "[1, 2]",
+          "1",
+          "2",
+          "0",
+          "{ \"k\": 1 }",
+          "\"k\"",
+          "1",
+        ]
+      )
+    }
+  });
+}
+
+#[test]
+fn visitor_test() {
+  use crate::converter::ast::{Expr, ExprKind, Stmt};
+  use crate::converter::folder::{self, Folder};
+  use crate::converter::lowering::raise;
+  use crate::converter::parser::Parser;
+  use crate::converter::visitor::{self, Visitor, VisitorMut};
+
+  const INPUT: &str = "val a := 1; val h := { \"k\": a };
+    ƒ f(x: int, n: int = a) -> int { return x + n; }
+    for 0..a |i| { print(f(i)); }
+    val g := |y: int| if y > a { [y, a] } else { a };";
+
+  struct Names(Vec<String>);
+
+  impl Visitor for Names {
+    fn visit_expr(&mut self, expr: &Expr) {
+      if let ExprKind::Identifier(name) = &expr.kind {
+        self.0.push(name.to_owned());
+      }
+
+      visitor::walk_expr(self, expr)
+    }
+  }
+
+  struct Rename;
+
+  impl VisitorMut for Rename {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+      match &mut expr.kind {
+        ExprKind::Identifier(name) if name == "a" => *name = "b".into(),
+        _ => visitor::walk_expr_mut(self, expr),
+      }
+    }
+  }
+
+  struct Double(usize);
+
+  impl Folder for Double {
+    fn fold_expr_kind(&mut self, kind: ExprKind) -> ExprKind {
+      match folder::walk_expr_kind(self, kind) {
+        ExprKind::Int(value) => ExprKind::Int(value * 2),
+        kind => kind,
+      }
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+      self.0 += 1;
+      folder::walk_stmt(self, stmt)
+    }
+  }
+
+  suite!("qoeurc::converter::visitor", {
+    "test a visitor reaches every expression" || {
+      let ast = Parser::new(INPUT).parse_ast().unwrap();
+      let mut names = Names(vec![]);
+
+      names.visit_ast(&ast);
+
+      must!(
+        names.0.join(" ");
+        eq String::from("a a x n a print f i y a y a a")
+      )
+    }
+
+    "test a mutable visitor changes the tree in place" || {
+      let mut ast = Parser::new("val a := 1; a + [a, { \"a\": a }][0];")
+        .parse_ast()
+        .unwrap();
+
+      Rename.visit_ast_mut(&mut ast);
+
+      must!(
+        raise(&ast).text();
+        eq Parser::new("val a := 1; b + [b, { \"a\": b }][0];")
+          .parse()
+          .unwrap()
+          .text()
+      )
+    }
+
+    "test a folder rebuilds the tree from what it gives back" || {
+      let ast = Parser::new(INPUT).parse_ast().unwrap();
+      let mut double = Double(0);
+      let ast = double.fold_ast(ast);
+
+      must!(
+        raise(&ast).text();
+        eq Parser::new(&INPUT.replace("1", "2"))
+          .parse()
+          .unwrap()
+          .text()
+      )?;
+      must!(double.0; eq 10)
+    }
   });
 }
@@ -0,0 +1,502 @@
+use crate::converter::ast::*;
+
+// a pass that reads the syntax tree, each method walks the children of its
+// node by default, so a pass only overrides the nodes it cares about and
+// calls the matching `walk_*` to keep going below them
+pub trait Visitor: Sized {
+  fn visit_ast(&mut self, ast: &Ast) {
+    walk_ast(self, ast)
+  }
+
+  fn visit_block(&mut self, block: &Block) {
+    walk_block(self, block)
+  }
+
+  fn visit_closure(&mut self, closure: &Closure) {
+    walk_closure(self, closure)
+  }
+
+  fn visit_expr(&mut self, expr: &Expr) {
+    walk_expr(self, expr)
+  }
+
+  fn visit_expr_kind(&mut self, kind: &ExprKind) {
+    walk_expr_kind(self, kind)
+  }
+
+  fn visit_function(&mut self, function: &Function) {
+    walk_function(self, function)
+  }
+
+  fn visit_if(&mut self, control: &If) {
+    walk_if(self, control)
+  }
+
+  fn visit_impl(&mut self, implementation: &Impl) {
+    walk_impl(self, implementation)
+  }
+
+  fn visit_loop(&mut self, looping: &Loop) {
+    walk_loop(self, looping)
+  }
+
+  fn visit_param(&mut self, param: &Param) {
+    walk_param(self, param)
+  }
+
+  fn visit_range(&mut self, range: &Range) {
+    walk_range(self, range)
+  }
+
+  fn visit_stmt(&mut self, stmt: &Stmt) {
+    walk_stmt(self, stmt)
+  }
+
+  fn visit_stmt_kind(&mut self, kind: &StmtKind) {
+    walk_stmt_kind(self, kind)
+  }
+
+  fn visit_struct(&mut self, _structure: &Struct) {}
+
+  fn visit_trait(&mut self, interface: &Trait) {
+    walk_trait(self, interface)
+  }
+
+  fn visit_use(&mut self, _import: &Use) {}
+
+  fn visit_val(&mut self, val: &Val) {
+    walk_val(self, val)
+  }
+}
+
+pub fn walk_ast<V: Visitor>(visitor: &mut V, ast: &Ast) {
+  for stmt in &ast.statements {
+    visitor.visit_stmt(stmt);
+  }
+}
+
+pub fn walk_block<V: Visitor>(visitor: &mut V, block: &Block) {
+  for stmt in &block.statements {
+    visitor.visit_stmt(stmt);
+  }
+}
+
+pub fn walk_closure<V: Visitor>(visitor: &mut V, closure: &Closure) {
+  for param in &closure.params {
+    visitor.visit_param(param);
+  }
+
+  visitor.visit_block(&closure.block);
+}
+
+pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
+  visitor.visit_expr_kind(&expr.kind)
+}
+
+pub fn walk_expr_kind<V: Visitor>(visitor: &mut V, kind: &ExprKind) {
+  match kind {
+    ExprKind::Array(items) => {
+      for item in items {
+        visitor.visit_expr(item);
+      }
+    }
+    ExprKind::Assign(target, value) | ExprKind::Index(target, value) => {
+      visitor.visit_expr(target);
+      visitor.visit_expr(value);
+    }
+    ExprKind::Binary(lhs, _, rhs) => {
+      visitor.visit_expr(lhs);
+      visitor.visit_expr(rhs);
+    }
+    ExprKind::Block(block) => visitor.visit_block(block),
+    ExprKind::Call(callee, args) => {
+      visitor.visit_expr(callee);
+
+      for arg in args {
+        visitor.visit_expr(arg);
+      }
+    }
+    ExprKind::Closure(closure) => visitor.visit_closure(closure),
+    ExprKind::Delete(value)
+    | ExprKind::Group(value)
+    | ExprKind::Member(value, _, _)
+    | ExprKind::NamedArg(_, value)
+    | ExprKind::Outcome(_, value)
+    | ExprKind::Spread(value)
+    | ExprKind::Try(value)
+    | ExprKind::Unary(_, value) => visitor.visit_expr(value),
+    ExprKind::Hash(items) => {
+      for (key, value) in items {
+        if let Some(key) = key {
+          visitor.visit_expr(key);
+        }
+
+        visitor.visit_expr(value);
+      }
+    }
+    ExprKind::If(control) => visitor.visit_if(control),
+    ExprKind::Instance(_, fields) => {
+      for (_, value) in fields {
+        visitor.visit_expr(value);
+      }
+    }
+    ExprKind::Loop(looping) => visitor.visit_loop(looping),
+    ExprKind::Match(subject, arms) => {
+      visitor.visit_expr(subject);
+
+      for (_, body) in arms {
+        visitor.visit_expr(body);
+      }
+    }
+    ExprKind::Range(range) => visitor.visit_range(range),
+    ExprKind::Bool(_)
+    | ExprKind::Char(_)
+    | ExprKind::Comment(_)
+    | ExprKind::Float(_)
+    | ExprKind::Identifier(_)
+    | ExprKind::Int(_)
+    | ExprKind::Nil
+    | ExprKind::Str(_) => (),
+  }
+}
+
+pub fn walk_function<V: Visitor>(visitor: &mut V, function: &Function) {
+  for param in &function.params {
+    visitor.visit_param(param);
+  }
+
+  if let Some(block) = &function.block {
+    visitor.visit_block(block);
+  }
+}
+
+pub fn walk_if<V: Visitor>(visitor: &mut V, control: &If) {
+  visitor.visit_expr(&control.condition);
+  visitor.visit_block(&control.consequence);
+
+  if let Some(alternative) = &control.alternative {
+    visitor.visit_expr(alternative);
+  }
+}
+
+pub fn walk_impl<V: Visitor>(visitor: &mut V, implementation: &Impl) {
+  for function in &implementation.functions {
+    visitor.visit_function(function);
+  }
+}
+
+pub fn walk_loop<V: Visitor>(visitor: &mut V, looping: &Loop) {
+  match &looping.kind {
+    LoopKind::For(_, iterable) => visitor.visit_expr(iterable),
+    LoopKind::Infinite => (),
+    LoopKind::While(condition) => visitor.visit_expr(condition),
+  }
+
+  visitor.visit_block(&looping.block);
+}
+
+pub fn walk_param<V: Visitor>(visitor: &mut V, param: &Param) {
+  if let Some(default) = &param.default {
+    visitor.visit_expr(default);
+  }
+}
+
+pub fn walk_range<V: Visitor>(visitor: &mut V, range: &Range) {
+  visitor.visit_expr(&range.start);
+  visitor.visit_expr(&range.end);
+
+  if let Some(step) = &range.step {
+    visitor.visit_expr(step);
+  }
+}
+
+pub fn walk_stmt<V: Visitor>(visitor: &mut V, stmt: &Stmt) {
+  visitor.visit_stmt_kind(&stmt.kind);
+}
+
+pub fn walk_stmt_kind<V: Visitor>(visitor: &mut V, kind: &StmtKind) {
+  match kind {
+    StmtKind::Break(_, value) | StmtKind::Return(value) => {
+      if let Some(value) = value {
+        visitor.visit_expr(value);
+      }
+    }
+    StmtKind::Continue(_) => (),
+    StmtKind::Expr(expr) | StmtKind::Node(expr) => visitor.visit_expr(expr),
+    StmtKind::Function(function) => visitor.visit_function(function),
+    StmtKind::Impl(implementation) => visitor.visit_impl(implementation),
+    StmtKind::Module(_, block) => {
+      if let Some(block) = block {
+        visitor.visit_block(block);
+      }
+    }
+    StmtKind::Public(kind) => visitor.visit_stmt_kind(kind),
+    StmtKind::Struct(structure) => visitor.visit_struct(structure),
+    StmtKind::Trait(interface) => visitor.visit_trait(interface),
+    StmtKind::Use(import) => visitor.visit_use(import),
+    StmtKind::Val(val) => visitor.visit_val(val),
+  }
+}
+
+pub fn walk_trait<V: Visitor>(visitor: &mut V, interface: &Trait) {
+  for function in &interface.functions {
+    visitor.visit_function(function);
+  }
+}
+
+pub fn walk_val<V: Visitor>(visitor: &mut V, val: &Val) {
+  visitor.visit_expr(&val.value);
+}
+
+// a pass that changes the syntax tree in place, with the same defaults as
+// `Visitor`
+pub trait VisitorMut: Sized {
+  fn visit_ast_mut(&mut self, ast: &mut Ast) {
+    walk_ast_mut(self, ast)
+  }
+
+  fn visit_block_mut(&mut self, block: &mut Block) {
+    walk_block_mut(self, block)
+  }
+
+  fn visit_closure_mut(&mut self, closure: &mut Closure) {
+    walk_closure_mut(self, closure)
+  }
+
+  fn visit_expr_mut(&mut self, expr: &mut Expr) {
+    walk_expr_mut(self, expr)
+  }
+
+  fn visit_expr_kind_mut(&mut self, kind: &mut ExprKind) {
+    walk_expr_kind_mut(self, kind)
+  }
+
+  fn visit_function_mut(&mut self, function: &mut Function) {
+    walk_function_mut(self, function)
+  }
+
+  fn visit_if_mut(&mut self, control: &mut If) {
+    walk_if_mut(self, control)
+  }
+
+  fn visit_impl_mut(&mut self, implementation: &mut Impl) {
+    walk_impl_mut(self, implementation)
+  }
+
+  fn visit_loop_mut(&mut self, looping: &mut Loop) {
+    walk_loop_mut(self, looping)
+  }
+
+  fn visit_param_mut(&mut self, param: &mut Param) {
+    walk_param_mut(self, param)
+  }
+
+  fn visit_range_mut(&mut self, range: &mut Range) {
+    walk_range_mut(self, range)
+  }
+
+  fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+    walk_stmt_mut(self, stmt)
+  }
+
+  fn visit_stmt_kind_mut(&mut self, kind: &mut StmtKind) {
+    walk_stmt_kind_mut(self, kind)
+  }
+
+  fn visit_struct_mut(&mut self, _structure: &mut Struct) {}
+
+  fn visit_trait_mut(&mut self, interface: &mut Trait) {
+    walk_trait_mut(self, interface)
+  }
+
+  fn visit_use_mut(&mut self, _import: &mut Use) {}
+
+  fn visit_val_mut(&mut self, val: &mut Val) {
+    walk_val_mut(self, val)
+  }
+}
+
+pub fn walk_ast_mut<V: VisitorMut>(visitor: &mut V, ast: &mut Ast) {
+  for stmt in &mut ast.statements {
+    visitor.visit_stmt_mut(stmt);
+  }
+}
+
+pub fn walk_block_mut<V: VisitorMut>(visitor: &mut V, block: &mut Block) {
+  for stmt in &mut block.statements {
+    visitor.visit_stmt_mut(stmt);
+  }
+}
+
+pub fn walk_closure_mut<V: VisitorMut>(visitor: &mut V, closure: &mut Closure) {
+  for param in &mut closure.params {
+    visitor.visit_param_mut(param);
+  }
+
+  visitor.visit_block_mut(&mut closure.block);
+}
+
+pub fn walk_expr_mut<V: VisitorMut>(visitor: &mut V, expr: &mut Expr) {
+  visitor.visit_expr_kind_mut(&mut expr.kind)
+}
+
+pub fn walk_expr_kind_mut<V: VisitorMut>(visitor: &mut V, kind: &mut ExprKind) {
+  match kind {
+    ExprKind::Array(items) => {
+      for item in items {
+        visitor.visit_expr_mut(item);
+      }
+    }
+    ExprKind::Assign(target, value) | ExprKind::Index(target, value) => {
+      visitor.visit_expr_mut(target);
+      visitor.visit_expr_mut(value);
+    }
+    ExprKind::Binary(lhs, _, rhs) => {
+      visitor.visit_expr_mut(lhs);
+      visitor.visit_expr_mut(rhs);
+    }
+    ExprKind::Block(block) => visitor.visit_block_mut(block),
+    ExprKind::Call(callee, args) => {
+      visitor.visit_expr_mut(callee);
+
+      for arg in args {
+        visitor.visit_expr_mut(arg);
+      }
+    }
+    ExprKind::Closure(closure) => visitor.visit_closure_mut(closure),
+    ExprKind::Delete(value)
+    | ExprKind::Group(value)
+    | ExprKind::Member(value, _, _)
+    | ExprKind::NamedArg(_, value)
+    | ExprKind::Outcome(_, value)
+    | ExprKind::Spread(value)
+    | ExprKind::Try(value)
+    | ExprKind::Unary(_, value) => visitor.visit_expr_mut(value),
+    ExprKind::Hash(items) => {
+      for (key, value) in items {
+        if let Some(key) = key {
+          visitor.visit_expr_mut(key);
+        }
+
+        visitor.visit_expr_mut(value);
+      }
+    }
+    ExprKind::If(control) => visitor.visit_if_mut(control),
+    ExprKind::Instance(_, fields) => {
+      for (_, value) in fields {
+        visitor.visit_expr_mut(value);
+      }
+    }
+    ExprKind::Loop(looping) => visitor.visit_loop_mut(looping),
+    ExprKind::Match(subject, arms) => {
+      visitor.visit_expr_mut(subject);
+
+      for (_, body) in arms {
+        visitor.visit_expr_mut(body);
+      }
+    }
+    ExprKind::Range(range) => visitor.visit_range_mut(range),
+    ExprKind::Bool(_)
+    | ExprKind::Char(_)
+    | ExprKind::Comment(_)
+    | ExprKind::Float(_)
+    | ExprKind::Identifier(_)
+    | ExprKind::Int(_)
+    | ExprKind::Nil
+    | ExprKind::Str(_) => (),
+  }
+}
+
+pub fn walk_function_mut<V: VisitorMut>(
+  visitor: &mut V,
+  function: &mut Function,
+) {
+  for param in &mut function.params {
+    visitor.visit_param_mut(param);
+  }
+
+  if let Some(block) = &mut function.block {
+    visitor.visit_block_mut(block);
+  }
+}
+
+pub fn walk_if_mut<V: VisitorMut>(visitor: &mut V, control: &mut If) {
+  visitor.visit_expr_mut(&mut control.condition);
+  visitor.visit_block_mut(&mut control.consequence);
+
+  if let Some(alternative) = &mut control.alternative {
+    visitor.visit_expr_mut(alternative);
+  }
+}
+
+pub fn walk_impl_mut<V: VisitorMut>(
+  visitor: &mut V,
+  implementation: &mut Impl,
+) {
+  for function in &mut implementation.functions {
+    visitor.visit_function_mut(function);
+  }
+}
+
+pub fn walk_loop_mut<V: VisitorMut>(visitor: &mut V, looping: &mut Loop) {
+  match &mut looping.kind {
+    LoopKind::For(_, iterable) => visitor.visit_expr_mut(iterable),
+    LoopKind::Infinite => (),
+    LoopKind::While(condition) => visitor.visit_expr_mut(condition),
+  }
+
+  visitor.visit_block_mut(&mut looping.block);
+}
+
+pub fn walk_param_mut<V: VisitorMut>(visitor: &mut V, param: &mut Param) {
+  if let Some(default) = &mut param.default {
+    visitor.visit_expr_mut(default);
+  }
+}
+
+pub fn walk_range_mut<V: VisitorMut>(visitor: &mut V, range: &mut Range) {
+  visitor.visit_expr_mut(&mut range.start);
+  visitor.visit_expr_mut(&mut range.end);
+
+  if let Some(step) = &mut range.step {
+    visitor.visit_expr_mut(step);
+  }
+}
+
+pub fn walk_stmt_mut<V: VisitorMut>(visitor: &mut V, stmt: &mut Stmt) {
+  visitor.visit_stmt_kind_mut(&mut stmt.kind);
+}
+
+pub fn walk_stmt_kind_mut<V: VisitorMut>(visitor: &mut V, kind: &mut StmtKind) {
+  match kind {
+    StmtKind::Break(_, value) | StmtKind::Return(value) => {
+      if let Some(value) = value {
+        visitor.visit_expr_mut(value);
+      }
+    }
+    StmtKind::Continue(_) => (),
+    StmtKind::Expr(expr) | StmtKind::Node(expr) => visitor.visit_expr_mut(expr),
+    StmtKind::Function(function) => visitor.visit_function_mut(function),
+    StmtKind::Impl(implementation) => visitor.visit_impl_mut(implementation),
+    StmtKind::Module(_, block) => {
+      if let Some(block) = block {
+        visitor.visit_block_mut(block);
+      }
+    }
+    StmtKind::Public(kind) => visitor.visit_stmt_kind_mut(kind),
+    StmtKind::Struct(structure) => visitor.visit_struct_mut(structure),
+    StmtKind::Trait(interface) => visitor.visit_trait_mut(interface),
+    StmtKind::Use(import) => visitor.visit_use_mut(import),
+    StmtKind::Val(val) => visitor.visit_val_mut(val),
+  }
+}
+
+pub fn walk_trait_mut<V: VisitorMut>(visitor: &mut V, interface: &mut Trait) {
+  for function in &mut interface.functions {
+    visitor.visit_function_mut(function);
+  }
+}
+
+pub fn walk_val_mut<V: VisitorMut>(visitor: &mut V, val: &mut Val) {
+  visitor.visit_expr_mut(&mut val.value);
+}
@@ -1,7 +1,12 @@
 pub mod analyzer;
+pub mod compiler;
 pub mod converter;
+pub mod optimizer;
 pub mod reporter;
 pub mod tokenizer;
 pub mod transformer;
 pub mod utils;
 pub mod value;
+
+#[cfg(test)]
+mod testing;
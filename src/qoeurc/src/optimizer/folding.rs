@@ -0,0 +1,319 @@
+use crate::analyzer::environment::scope::Scope;
+use crate::analyzer::interpreter::Interpreter;
+use crate::converter::ast::{
+  Ast, BinaryOp, Block, Expr, ExprKind, If, Stmt, StmtKind, UnaryOp,
+};
+use crate::converter::folder::{self, Folder};
+use crate::value::instruction::binary::Binary;
+use crate::value::instruction::unary::Unary;
+use crate::value::instruction::IKind;
+use crate::value::primitive::{
+  bool::Bool, char::Char, float::Float, int::Int, str::Str,
+};
+use crate::value::Value;
+use crate::void;
+
+// computes the expressions made of literals before running, with the same
+// operators the interpreter uses, and drops the branches a literal condition
+// never takes. an operation the interpreter would fail or panic on is left
+// for it to report when it runs
+pub struct ConstantFolder {
+  interpreter: Interpreter,
+}
+
+impl Default for ConstantFolder {
+  fn default() -> Self {
+    ConstantFolder::new()
+  }
+}
+
+impl Folder for ConstantFolder {
+  fn fold_ast(&mut self, ast: Ast) -> Ast {
+    Ast {
+      statements: self.fold_statements(ast.statements),
+    }
+  }
+
+  fn fold_block(&mut self, block: Block) -> Block {
+    Block {
+      statements: self.fold_statements(block.statements),
+    }
+  }
+
+  // what an expression is folded to takes its place, and its span
+  fn fold_expr(&mut self, expr: Expr) -> Expr {
+    let Expr { kind, span } = folder::walk_expr(self, expr);
+    let kind = match kind {
+      ExprKind::Binary(lhs, operator, rhs) => {
+        self.fold_binary(*lhs, operator, *rhs)
+      }
+      ExprKind::Group(expression) if is_literal(&expression) => expression.kind,
+      ExprKind::If(control) => fold_branch(control),
+      ExprKind::Unary(operator, rhs) => self.fold_unary(operator, *rhs),
+      kind => kind,
+    };
+
+    Expr::new(kind, span)
+  }
+}
+
+impl ConstantFolder {
+  // operators on literals need neither the natives nor the prelude
+  pub fn new() -> Self {
+    ConstantFolder {
+      interpreter: Interpreter::new_with_outer(Scope::new()),
+    }
+  }
+
+  fn fold_binary(
+    &mut self,
+    lhs: Expr,
+    operator: BinaryOp,
+    rhs: Expr,
+  ) -> ExprKind {
+    // `a ?? b` only needs `a` to be known
+    if operator == BinaryOp::Coalesce {
+      match lhs.kind {
+        ExprKind::Nil => return rhs.kind,
+        _ if is_literal(&lhs) => return lhs.kind,
+        _ => (),
+      }
+    }
+
+    if let (Some(lhs), Some(rhs)) = (to_value(&lhs), to_value(&rhs)) {
+      if let Some(value) = self.operate(lhs, operator, rhs) {
+        return value;
+      }
+    }
+
+    simplify_binary(lhs, operator, rhs)
+  }
+
+  fn fold_unary(&mut self, operator: UnaryOp, rhs: Expr) -> ExprKind {
+    if let Some(value) = to_value(&rhs) {
+      let token = operator.token();
+      let value = match (operator, &rhs.kind) {
+        (UnaryOp::Neg, ExprKind::Int(int)) => int
+          .checked_neg()
+          .map(|_| Unary::operate(&token, value.as_ref())),
+        _ => Some(Unary::operate(&token, value.as_ref())),
+      };
+
+      if let Some(literal) = value.and_then(|value| value.ok()) {
+        if let Some(literal) = to_literal(literal.as_ref()) {
+          return literal;
+        }
+      }
+    }
+
+    // `!!b` is `b` when `b` is a boolean, on anything else it converts it
+    match (operator, rhs.kind) {
+      (UnaryOp::Not, ExprKind::Unary(UnaryOp::Not, value))
+        if is_bool(&value) =>
+      {
+        value.kind
+      }
+      (_, kind) => {
+        ExprKind::Unary(operator, Box::new(Expr::new(kind, rhs.span)))
+      }
+    }
+  }
+
+  // what fails to run, an overflow or a division by zero, is not folded and
+  // fails where it is
+  fn operate(
+    &mut self,
+    lhs: Box<dyn Value>,
+    operator: BinaryOp,
+    rhs: Box<dyn Value>,
+  ) -> Option<ExprKind> {
+    let value =
+      Binary::operate(&mut self.interpreter, lhs, &operator.token(), rhs);
+
+    to_literal(value.ok()?.as_ref())
+  }
+
+  // the statements of a branch a literal condition always takes run in the
+  // enclosing block, unless they bind names that would then clash with it
+  fn fold_statements(&mut self, statements: Vec<Stmt>) -> Vec<Stmt> {
+    let count = statements.len();
+    let mut folded = vec![];
+
+    for (at, stmt) in statements.into_iter().enumerate() {
+      let Stmt { kind, span } = self.fold_stmt(stmt);
+      let is_last = at + 1 == count;
+
+      match kind {
+        StmtKind::Expr(Expr {
+          kind: ExprKind::Nil,
+          ..
+        }) if !is_last => (),
+        StmtKind::Expr(Expr {
+          kind: ExprKind::If(control),
+          span: at,
+        }) => match take_branch(control) {
+          Ok(statements) if statements.is_empty() && is_last => {
+            let nil = Expr::new(ExprKind::Nil, at);
+
+            folded.push(Stmt::new(StmtKind::Expr(nil), span))
+          }
+          Ok(statements) => folded.extend(statements),
+          Err(control) => {
+            let control = Expr::new(ExprKind::If(control), at);
+
+            folded.push(Stmt::new(StmtKind::Expr(control), span))
+          }
+        },
+        kind => folded.push(Stmt::new(kind, span)),
+      }
+    }
+
+    folded
+  }
+}
+
+// `x * 1`, `1 * x`, `x / 1`, `x + 0`, `0 + x` and `x - 0` are `x` when `x`
+// is an integer, on anything else they are errors the interpreter reports
+fn simplify_binary(lhs: Expr, operator: BinaryOp, rhs: Expr) -> ExprKind {
+  match (&lhs.kind, operator, &rhs.kind) {
+    (_, BinaryOp::Mul, ExprKind::Int(1))
+    | (_, BinaryOp::Div, ExprKind::Int(1))
+    | (_, BinaryOp::Add, ExprKind::Int(0))
+    | (_, BinaryOp::Sub, ExprKind::Int(0))
+      if is_int(&lhs) =>
+    {
+      lhs.kind
+    }
+    (ExprKind::Int(1), BinaryOp::Mul, _)
+    | (ExprKind::Int(0), BinaryOp::Add, _)
+      if is_int(&rhs) =>
+    {
+      rhs.kind
+    }
+    _ => ExprKind::Binary(Box::new(lhs), operator, Box::new(rhs)),
+  }
+}
+
+// an expression that can only give an integer, whatever the variables hold,
+// such as an operation on integers the folder left to the interpreter
+fn is_int(expr: &Expr) -> bool {
+  match &expr.kind {
+    ExprKind::Int(_) => true,
+    ExprKind::Group(value) => is_int(value),
+    ExprKind::Binary(lhs, operator, rhs) => {
+      matches!(
+        operator,
+        BinaryOp::Add | BinaryOp::Div | BinaryOp::Mul | BinaryOp::Sub
+      ) && is_int(lhs)
+        && is_int(rhs)
+    }
+    _ => false,
+  }
+}
+
+// an expression that can only give a boolean, or an error the interpreter
+// reports, whatever the variables hold. equality is left out on anything but
+// literals and integers, an implementation of `Eq` may give any value
+fn is_bool(expr: &Expr) -> bool {
+  match &expr.kind {
+    ExprKind::Bool(_) | ExprKind::Unary(UnaryOp::Not, _) => true,
+    ExprKind::Group(value) => is_bool(value),
+    ExprKind::Binary(lhs, operator, rhs) => match operator {
+      BinaryOp::Ge | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Lt => true,
+      BinaryOp::Eq | BinaryOp::Ne => {
+        (is_literal(lhs) || is_int(lhs)) && (is_literal(rhs) || is_int(rhs))
+      }
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+// the branch an `if` with a literal condition takes, `None` when the condition
+// is not known and `Some(None)` when no branch is taken
+fn decide(control: &If) -> Option<Option<ExprKind>> {
+  let condition = to_value(&control.condition)?;
+
+  match condition.is_truthy() {
+    true => Some(Some(ExprKind::Block(control.consequence.to_owned()))),
+    false => Some(
+      control
+        .alternative
+        .as_ref()
+        .map(|alternative| alternative.kind.to_owned()),
+    ),
+  }
+}
+
+// an `if` in place of a value is replaced by the value of the branch it takes
+// when that branch is a single expression
+fn fold_branch(control: If) -> ExprKind {
+  match decide(&control) {
+    None => ExprKind::If(control),
+    Some(None) => ExprKind::Nil,
+    Some(Some(ExprKind::Block(block))) => match block.statements.as_slice() {
+      [Stmt {
+        kind: StmtKind::Expr(value),
+        ..
+      }] => value.kind.to_owned(),
+      _ => ExprKind::If(control),
+    },
+    Some(Some(branch)) => branch,
+  }
+}
+
+// the statements of the branch an `if` written as a statement takes, the `if`
+// left in place when it is not decided or its branch binds names
+fn take_branch(control: If) -> Result<Vec<Stmt>, If> {
+  match decide(&control) {
+    None => Err(control),
+    Some(None) => Ok(vec![]),
+    Some(Some(ExprKind::Block(block))) if !is_binding(&block) => {
+      Ok(block.statements)
+    }
+    Some(Some(ExprKind::If(alternative))) => take_branch(alternative),
+    Some(Some(_)) => Err(control),
+  }
+}
+
+fn is_binding(block: &Block) -> bool {
+  block.statements.iter().any(|stmt| {
+    !matches!(
+      stmt.kind,
+      StmtKind::Expr(_)
+        | StmtKind::Node(_)
+        | StmtKind::Break(_, _)
+        | StmtKind::Continue(_)
+        | StmtKind::Return(_)
+    )
+  })
+}
+
+fn is_literal(expr: &Expr) -> bool {
+  to_value(expr).is_some()
+}
+
+fn to_value(expr: &Expr) -> Option<Box<dyn Value>> {
+  match &expr.kind {
+    ExprKind::Bool(value) => Some(Bool::new(*value).boxed()),
+    ExprKind::Char(value) => Some(Char::new(*value).boxed()),
+    ExprKind::Float(value) => Some(Float::new(*value).boxed()),
+    ExprKind::Int(value) => Some(Int::new(*value).boxed()),
+    ExprKind::Nil => Some(void!()),
+    ExprKind::Str(value) => Some(Str::new(value).boxed()),
+    _ => None,
+  }
+}
+
+// a float that is not finite has no literal to write it with
+fn to_literal(value: &dyn Value) -> Option<ExprKind> {
+  match value.ikind() {
+    IKind::Bool(value) => Some(ExprKind::Bool(value)),
+    IKind::Char(value) => Some(ExprKind::Char(value)),
+    IKind::Float(value) if value.is_finite() => Some(ExprKind::Float(value)),
+    IKind::Int(value) => Some(ExprKind::Int(value)),
+    IKind::Nil => Some(ExprKind::Nil),
+    IKind::Str(value) => Some(ExprKind::Str(value)),
+    _ => None,
+  }
+}
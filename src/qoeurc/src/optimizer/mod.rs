@@ -0,0 +1,23 @@
+pub mod folding;
+
+#[cfg(test)]
+mod tests;
+
+use crate::converter::ast::Ast;
+use crate::converter::folder::Folder;
+use crate::converter::lowering::raise;
+use crate::converter::parser::{Parser, ParserResult};
+use crate::optimizer::folding::ConstantFolder;
+use crate::value::Value;
+
+pub fn optimizify(input: &str) -> ParserResult<Ast> {
+  let ast = Parser::new(input).parse_ast()?;
+
+  Ok(ConstantFolder::new().fold_ast(ast))
+}
+
+// the program that runs in place of `ast`, the same values computed with less
+// work
+pub fn optimize(ast: Ast) -> Box<dyn Value> {
+  raise(&ConstantFolder::new().fold_ast(ast))
+}
@@ -0,0 +1,129 @@
+extern crate qutonium;
+
+use qutonium::prelude::*;
+
+use crate::testing::suite;
+
+#[test]
+fn folding_test() {
+  use crate::analyzer::evalify;
+  use crate::analyzer::interpreter::Interpreter;
+  use crate::converter::ast::{Expr, ExprKind, StmtKind};
+  use crate::converter::lowering::raise;
+  use crate::converter::parser::Parser;
+  use crate::optimizer::optimizify;
+  use crate::reporter::location::Span;
+  use crate::transformer::transformify;
+
+  fn optimized(input: &str) -> String {
+    raise(&optimizify(input).unwrap()).text()
+  }
+
+  fn written(input: &str) -> String {
+    Parser::new(input).parse().unwrap().text()
+  }
+
+  fn eval(input: &str) -> String {
+    evalify(input, &mut Interpreter::new()).unwrap().text()
+  }
+
+  fn fail(input: &str) -> Result<String, String> {
+    evalify(input, &mut Interpreter::new()).map(|value| value.text())
+  }
+
+  suite!("qoeurc::optimizer::folding", {
+    "test expressions made of literals are computed" || {
+      must!(
+        optimized("[1 + 2 * 3, \"a\" + \"b\", 2.5 * 2.0, (1 < 2) == true];");
+        eq written("[7, \"ab\", 5.0, true];")
+      )?;
+      must!(
+        optimizify("-(4);").unwrap().statements[0].kind.to_owned();
+        eq StmtKind::Expr(Expr::new(ExprKind::Int(-4), Span::new(0, 4)))
+      )?;
+      must!(
+        optimized("[!true, nil ?? a, 1 ?? a];");
+        eq written("[false, a, 1];")
+      )
+    }
+
+    "test operations with no effect on integers are dropped" || {
+      must!(
+        optimized("[(1 / 0) * 1, 0 + (9223372036854775807 + 1)];");
+        eq written("[(1 / 0), (9223372036854775807 + 1)];")
+      )?;
+      must!(
+        optimized("[a * 1, 1 * a, a / 1, a + 0, 0 + a, a - 0, a * 2];");
+        eq written("[a * 1, 1 * a, a / 1, a + 0, 0 + a, a - 0, a * 2];")
+      )
+    }
+
+    "test double negations are dropped only on booleans" || {
+      must!(
+        optimized("[!!(a < 1), !!!a, !!(1 / 0 == 2), !!b, !!(a == b)];");
+        eq written("[(a < 1), !a, (1 / 0 == 2), !!b, !!(a == b)];")
+      )?;
+      must!(eval("val x := 5; !!x;"); eq String::from("true"))?;
+      must!(
+        fail("val x := nil; !!x;");
+        eq Err("eval_unary_expression bang operand error".into())
+      )?;
+      must!(
+        fail("val x := \"a\"; !!x;");
+        eq Err("eval_unary_expression bang operand error".into())
+      )
+    }
+
+    "test operations on what is not a number still fail" || {
+      must!(
+        fail("val a := \"s\"; a + 0;");
+        eq Err("cannot use `+` on `\"s\"` and `0`".into())
+      )?;
+      must!(
+        fail("val a := nil; a - 0;");
+        eq Err("cannot use `-` on `nil`".into())
+      )?;
+      must!(
+        transformify("val a := \"s\"; a + 0;", "inline", &Interpreter::new());
+        eq Ok("var a = \"s\";\n(a + 0);".into())
+      )
+    }
+
+    "test branches a literal condition never takes are dropped" || {
+      must!(
+        optimized("if 1 > 2 { a; } else if b { c; } if true { d; e; }");
+        eq written("if b { c; } d; e;")
+      )?;
+      must!(
+        optimized("val x := if nil { 1 } else { 2 };");
+        eq written("val x := 2;")
+      )
+    }
+
+    "test branches that bind names are kept" || {
+      must!(
+        optimized("if true { val a := 1; print(a); }");
+        eq written("if true { val a := 1; print(a); }")
+      )
+    }
+
+    "test operations the interpreter fails on are left to it" || {
+      must!(
+        optimized("[9223372036854775807 + 1, 1 / 0];");
+        eq written("[9223372036854775807 + 1, 1 / 0];")
+      )?;
+      must!(optimized("1.0 / 0.0;"); eq written("1.0 / 0.0;"))
+    }
+
+    "test optimized programs give the same values" || {
+      let input = "val x := 5;
+        ƒ f(n: int) -> int {
+          if false { print(0); } else if x > 1 { print(n * 1); }
+          return (0 + n) - 0;
+        }
+        [1 + 2 * 3, !!(x > 2), f(7), if 3 > 2 { \"t\" } else { \"f\" }];";
+
+      must!(eval(input); eq String::from("[7, true, 7, \"t\"]"))
+    }
+  });
+}
@@ -9,3 +9,16 @@ impl Location {
     Location { column, line }
   }
 }
+
+// the bytes `start..end` of the input a token or a statement was read from
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Span {
+  pub end: usize,
+  pub start: usize,
+}
+
+impl Span {
+  pub fn new(start: usize, end: usize) -> Self {
+    Span { end, start }
+  }
+}
@@ -2,7 +2,11 @@ extern crate qutonium;
 
 use qutonium::prelude::*;
 
+use crate::testing::suite;
+
 #[test]
 fn from_test() {
-  suite!("qoeurc::reporter", { "test empty input" || { Ok(()) } });
+  suite!("qoeurc::reporter", {
+    "test empty input" || { must!(true; be.truthy) }
+  });
 }
@@ -0,0 +1,20 @@
+// `suite!` of qutonium only reports its failed cases, this one also fails
+// the test that runs it
+macro_rules! suite {
+  ($name:expr, { $($case:tt $closure:expr)* }) => {{
+    let hooks: Vec<Hook> = vec![];
+    let mut test_cases: Vec<TestCase> = vec![];
+
+    $(test_cases.push(TestCase::new($case, $closure));)*
+
+    let state = state!(hooks, test_cases);
+
+    SuiteCase::new($name).set_state(state.clone()).render();
+
+    let failed = state.lock().unwrap().failed;
+
+    assert!(failed == 0, "{} of the suite {} failed", failed, $name);
+  }};
+}
+
+pub(crate) use suite;
@@ -10,6 +10,7 @@ pub enum KeywordKind {
   Capsule,
   Const,
   Continue,
+  Delete,
   Do,
   Else,
   Enum,
@@ -25,6 +26,7 @@ pub enum KeywordKind {
   Match,
   Module,
   Move,
+  Nil,
   Program,
   Public,
   Ref,
@@ -34,6 +36,7 @@ pub enum KeywordKind {
   Static,
   Struct,
   Super,
+  Trait,
   True,
   Type,
   Typeof,
@@ -66,7 +69,8 @@ symbols! {
     Break: "break",
     Capsule: "capsule",
     Const: "const",
-    Continue: "contiue",
+    Continue: "continue",
+    Delete: "delete",
     Do: "do",
     Else: "else",
     Enum: "enum",
@@ -82,6 +86,7 @@ symbols! {
     Match: "match",
     Module: "mod",
     Move: "move",
+    Nil: "nil",
     Program: "program",
     Public: "pub",
     Ref: "ref",
@@ -91,6 +96,7 @@ symbols! {
     Static: "static",
     Struct: "struct",
     Super: "super",
+    Trait: "trait",
     True: "true",
     Type: "type",
     Typeof: "typeof",
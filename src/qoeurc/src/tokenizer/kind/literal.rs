@@ -68,6 +68,7 @@ impl Literal {
       "capsule" => Keyword(Capsule),
       "const" => Keyword(Const),
       "continue" => Keyword(Continue),
+      "delete" => Keyword(Delete),
       "do" => Keyword(Do),
       "else" => Keyword(Else),
       "enum" => Keyword(Enum),
@@ -83,6 +84,7 @@ impl Literal {
       "match" => Keyword(Match),
       "mod" => Keyword(Module),
       "move" => Keyword(Move),
+      "nil" => Keyword(Nil),
       "program" => Keyword(Program),
       "pub" => Keyword(Public),
       "ref" => Keyword(Ref),
@@ -92,6 +94,7 @@ impl Literal {
       "static" => Keyword(Static),
       "struct" => Keyword(Struct),
       "super" => Keyword(Super),
+      "trait" => Keyword(Trait),
       "true" => Keyword(True),
       "type" => Keyword(Type),
       "typeof" => Keyword(Typeof),
@@ -18,7 +18,9 @@ pub enum OperatorKind {
   Assign,
   Equal,
   NotEqual,
+  QuestionQuestion,
   Range,
+  RangeInclusive,
   GreaterThan,
   GreaterThanOrEqual,
   LessThan,
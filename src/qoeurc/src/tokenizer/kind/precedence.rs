@@ -3,8 +3,11 @@ pub use crate::tokenizer::kind::precedence::PrecedenceKind::*;
 #[derive(Debug, PartialEq, PartialOrd)]
 pub enum PrecedenceKind {
   Lowest,
+  Store,
+  Interval,
   Assignement,
   Conditional,
+  Coalesce,
   Sum,
   Exponent,
   Unary,
@@ -10,6 +10,7 @@ pub enum SymbolKind {
   Colon,
   Semicolon,
   Question,
+  QuestionDot,
   At,
   Dot,
   Dollar,
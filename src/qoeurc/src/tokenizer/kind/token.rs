@@ -29,9 +29,13 @@ impl TokenKind {
       | Operator(LessThanOrEqual)
       | Operator(GreaterThanOrEqual) => PrecedenceKind::Conditional,
 
+      Operator(QuestionQuestion) => PrecedenceKind::Coalesce,
+      Operator(Assign) => PrecedenceKind::Store,
       Operator(Equal) | Operator(NotEqual) => PrecedenceKind::Assignement,
+      Operator(Range) | Operator(RangeInclusive) => PrecedenceKind::Interval,
       GroupStart(Parenthesis) => PrecedenceKind::Call,
-      GroupStart(Bracket) => PrecedenceKind::Index,
+      GroupStart(Bracket) | Symbol(Dot) | Symbol(Question)
+      | Symbol(QuestionDot) => PrecedenceKind::Index,
       _ => PrecedenceKind::Lowest,
     }
   }
@@ -1,4 +1,4 @@
-use crate::reporter::location::Location;
+use crate::reporter::location::{Location, Span};
 use crate::tokenizer::ascii::*;
 use crate::tokenizer::kind;
 use crate::tokenizer::token::{Token, TokenStream};
@@ -12,34 +12,44 @@ pub struct Scanner<'a> {
   pub input: &'a str,
   initial_len: usize,
   chars: Chars<'a>,
+  exhausted: bool,
   location: Location,
   position: usize,
   prev: char,
   read_position: usize,
+  span: Span,
 }
 
 impl<'a> Scanner<'a> {
   pub fn new(input: &'a str) -> Scanner<'a> {
     let mut scanner = Scanner {
       chars: input.chars(),
+      exhausted: false,
       initial_len: input.len(),
       input: input.into(),
       location: Location::new(0, 0),
       position: 0,
       prev: EOF_CHAR,
       read_position: 0,
+      span: Span::default(),
     };
 
     scanner.bump();
     scanner
   }
 
+  // once the last character is read, or only whitespace is left, every token
+  // is `EOF`
   pub fn advance_token(&mut self) -> Token {
     self.move_until_whitespace();
 
+    let start = self.position;
     let mut token_stream = TokenStream::default();
+    let is_over =
+      self.exhausted || (self.is_eof() && Ascii::is_whitespace(self.prev()));
 
     match Ascii::to_kind(self.prev()) {
+      _ if is_over => token_stream.add_token(self.scan_eof()),
       AsciiKind::EOF => token_stream.add_token(self.scan_eof()),
       AsciiKind::Comment => token_stream.add_token(self.scan_comment()),
       AsciiKind::Group => token_stream.add_token(self.scan_group()),
@@ -51,7 +61,14 @@ impl<'a> Scanner<'a> {
       AsciiKind::Unknown(_) => token_stream.add_token(self.scan_unknown()),
     };
 
-    self.bump();
+    self.span = match is_over {
+      true => Span::new(self.read_position, self.read_position),
+      false => Span::new(start, self.read_position),
+    };
+
+    if self.bump().is_none() {
+      self.exhausted = true;
+    }
 
     token_stream.token()
   }
@@ -76,6 +93,11 @@ impl<'a> Scanner<'a> {
     self.location
   }
 
+  // where the last token read starts and ends
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
   pub fn is_eof(&self) -> bool {
     self.chars.as_str().is_empty()
   }
@@ -408,6 +430,18 @@ impl<'a> Scanner<'a> {
 
           Token::new(kind::Operator(kind::Equal), &literal, self.len_consumed())
         }
+        '>' => {
+          let prev = self.prev();
+          let literal = format!("{}{}", prev, self.first());
+
+          self.bump();
+
+          Token::new(
+            kind::Symbol(kind::ArrowFunction),
+            &literal,
+            self.len_consumed(),
+          )
+        }
         _ => Token::new(
           kind::Operator(kind::Assign),
           &self.prev.to_string(),
@@ -636,6 +670,19 @@ impl<'a> Scanner<'a> {
         self.len_consumed(),
       ),
       '.' => match self.first() {
+        '.' if self.second() == '=' => {
+          let prev = self.prev();
+          let literal = format!("{}{}{}", prev, self.first(), self.second());
+
+          self.bump();
+          self.bump();
+
+          Token::new(
+            kind::Operator(kind::RangeInclusive),
+            &literal,
+            self.len_consumed(),
+          )
+        }
         '.' => {
           let prev = self.prev();
           let literal = format!("{}{}", prev, self.first());
@@ -650,16 +697,56 @@ impl<'a> Scanner<'a> {
           self.len_consumed(),
         ),
       },
-      '!' => Token::new(
-        kind::Symbol(kind::Bang),
-        &self.prev.to_string(),
-        self.len_consumed(),
-      ),
-      '?' => Token::new(
-        kind::Symbol(kind::Question),
-        &self.prev.to_string(),
-        self.len_consumed(),
-      ),
+      '!' => match self.first() {
+        '=' => {
+          let prev = self.prev();
+          let literal = format!("{}{}", prev, self.first());
+
+          self.bump();
+
+          Token::new(
+            kind::Operator(kind::NotEqual),
+            &literal,
+            self.len_consumed(),
+          )
+        }
+        _ => Token::new(
+          kind::Symbol(kind::Bang),
+          &self.prev.to_string(),
+          self.len_consumed(),
+        ),
+      },
+      '?' => match self.first() {
+        '?' => {
+          let prev = self.prev();
+          let literal = format!("{}{}", prev, self.first());
+
+          self.bump();
+
+          Token::new(
+            kind::Operator(kind::QuestionQuestion),
+            &literal,
+            self.len_consumed(),
+          )
+        }
+        '.' => {
+          let prev = self.prev();
+          let literal = format!("{}{}", prev, self.first());
+
+          self.bump();
+
+          Token::new(
+            kind::Symbol(kind::QuestionDot),
+            &literal,
+            self.len_consumed(),
+          )
+        }
+        _ => Token::new(
+          kind::Symbol(kind::Question),
+          &self.prev.to_string(),
+          self.len_consumed(),
+        ),
+      },
       '@' => Token::new(
         kind::Symbol(kind::At),
         &self.prev.to_string(),
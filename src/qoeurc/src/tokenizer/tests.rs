@@ -151,9 +151,9 @@ fn from_test() {
     "test keywords" || {
       let code = "
         _ as async await box break continue capsule do else enum extern
-        ƒ for if impl loop macro match false mod import move program pub 
-        ref static type return Self self struct	super true typeof	unsafe 
-        use const val while
+        ƒ for if impl loop macro match false mod import move nil program pub 
+        ref static type return Self self struct	super trait true typeof	unsafe 
+        use const val while delete
       ";
 
       let input: Vec<Token> = tokenify(code).collect();
@@ -182,6 +182,7 @@ fn from_test() {
         Token::new(Keyword(Module), "mod".into(), 3),
         Token::new(Keyword(Import), "import".into(), 6),
         Token::new(Keyword(Move), "move".into(), 4),
+        Token::new(Keyword(Nil), "nil".into(), 3),
         Token::new(Keyword(Program), "program".into(), 7),
         Token::new(Keyword(Public), "pub".into(), 3),
         Token::new(Keyword(Ref), "ref".into(), 3),
@@ -192,6 +193,7 @@ fn from_test() {
         Token::new(Keyword(SelfLower), "self".into(), 4),
         Token::new(Keyword(Struct), "struct".into(), 6),
         Token::new(Keyword(Super), "super".into(), 5),
+        Token::new(Keyword(Trait), "trait".into(), 5),
         Token::new(Keyword(True), "true".into(), 4),
         Token::new(Keyword(Typeof), "typeof".into(), 6),
         Token::new(Keyword(Unsafe), "unsafe".into(), 6),
@@ -199,6 +201,7 @@ fn from_test() {
         Token::new(Keyword(Const), "const".into(), 3),
         Token::new(Keyword(Val), "val".into(), 3),
         Token::new(Keyword(While), "while".into(), 5),
+        Token::new(Keyword(Delete), "delete".into(), 6),
       ];
 
       expect!(input).to(be_equal(output))
@@ -275,7 +278,7 @@ fn from_test() {
     }
 
     "test operators" || {
-      let code = "+ - * / += -= = == != => < << >> > ^ | & % . .. ... $";
+      let code = "+ - * / += -= = == != => < << >> > ^ | & % . .. ... ..= $";
 
       let input: Vec<Token> = tokenify(code).collect();
 
@@ -290,10 +293,8 @@ fn from_test() {
         Token::new(Operator(Assign), "=".into(), 1),
         Token::new(Operator(Assign), "=".into(), 1),
         Token::new(Operator(Equal), "==".into(), 2),
-        Token::new(Symbol(Shebang), "!".into(), 1),
-        Token::new(Operator(Assign), "=".into(), 1),
-        Token::new(Operator(Assign), "=".into(), 1),
-        Token::new(Operator(GreaterThan), ">".into(), 1),
+        Token::new(Operator(NotEqual), "!=".into(), 2),
+        Token::new(Symbol(ArrowFunction), "=>".into(), 2),
         Token::new(Operator(LessThan), "<".into(), 1),
         Token::new(Operator(ShiftLeft), "<<".into(), 2),
         Token::new(Operator(ShiftRight), ">>".into(), 2),
@@ -306,6 +307,7 @@ fn from_test() {
         Token::new(Operator(Range), "..".into(), 2),
         Token::new(Operator(Range), "..".into(), 2),
         Token::new(Symbol(Dot), ".".into(), 1),
+        Token::new(Operator(RangeInclusive), "..=".into(), 3),
         Token::new(Identifier, "$".into(), 1),
       ];
 
@@ -360,7 +362,7 @@ fn from_test() {
     }
 
     "test symbols" || {
-      let code = ": ; $ . ! ? @";
+      let code = ": ; $ . ! ? ?. ?? @";
 
       let input: Vec<Token> = tokenify(code).collect();
 
@@ -371,6 +373,8 @@ fn from_test() {
         Token::new(Symbol(Dot), ".".into(), 1),
         Token::new(Symbol(Shebang), "!".into(), 1),
         Token::new(Symbol(Question), "?".into(), 1),
+        Token::new(Symbol(QuestionDot), "?.".into(), 2),
+        Token::new(Operator(QuestionQuestion), "??".into(), 2),
         Token::new(Symbol(At), "@".into(), 1),
       ];
 
@@ -0,0 +1,630 @@
+use crate::converter::ast::{
+  BinaryOp, Closure, Expr, ExprKind, If, Range, Stmt, StmtKind, UnaryOp,
+};
+use crate::converter::lowering::raise_params;
+use crate::converter::visitor::Visitor;
+use crate::transformer::transpiler::{TKind, Transpiler};
+use crate::value::instruction::function_arg::FunctionArg;
+
+impl Transpiler {
+  // the javascript of an expression, written by `visit_expr`
+  pub fn transpile_expr(&mut self, expr: &Expr) -> String {
+    self.visit_expr(expr);
+    self.written()
+  }
+
+  pub fn transpile_array(&mut self, items: &[Expr]) -> String {
+    let (delimiter, format): (&str, fn(String) -> String) = match self.mode() {
+      TKind::Json => (", ", |items| {
+        format!(
+          r#"{{
+            "elements": [{}],
+            "type": "ArrayExpression"
+          }}"#,
+          items,
+        )
+      }),
+      TKind::Inline => (", ", |items| format!("[{}]", items)),
+      TKind::Pretty => ("\n", |items| format!("[\n{}\n]", items)),
+    };
+
+    format(self.transpile_exprs(items, delimiter))
+  }
+
+  pub fn transpile_assign(&mut self, target: &Expr, value: &Expr) -> String {
+    let target = self.transpile_expr(target);
+    let value = self.transpile_expr(value);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "left": {},
+          "operator": "=",
+          "right": {},
+          "type": "AssignmentExpression"
+        }}"#,
+        target, value,
+      ),
+      _ => format!("{} = {}", target, value),
+    }
+  }
+
+  pub fn transpile_binary(
+    &mut self,
+    lhs: &Expr,
+    operator: BinaryOp,
+    rhs: &Expr,
+  ) -> String {
+    let lhs = self.transpile_expr(lhs);
+    let rhs = self.transpile_expr(rhs);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "left": {},
+          "operator": "{}",
+          "right": {},
+          "type": "BinaryExpression"
+        }}"#,
+        lhs, operator, rhs,
+      ),
+      _ => format!("({} {} {})", lhs, operator, rhs),
+    }
+  }
+
+  pub fn transpile_closure(&mut self, closure: &Closure) -> String {
+    let params = self.transpile_params(&closure.params);
+    let block = self.transpile_callable(&closure.block);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": {},
+          "params": [{}],
+          "type": "ArrowFunctionExpression"
+        }}"#,
+        block, params,
+      ),
+      TKind::Inline => format!("(({}) => {{ {} }})", params, block),
+      TKind::Pretty => format!("(({}) => {{\n\t{}\n}})", params, block),
+    }
+  }
+
+  pub fn transpile_group(&mut self, expression: &Expr) -> String {
+    match self.mode() {
+      TKind::Json => self.transpile_expr(expression),
+      _ => format!("({})", self.transpile_expr(expression)),
+    }
+  }
+
+  pub fn transpile_member(
+    &mut self,
+    object: &Expr,
+    optional: bool,
+    property: &str,
+  ) -> String {
+    let object = self.transpile_expr(object);
+    let property = self.transpile_identifier(property);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "computed": false,
+          "object": {},
+          "optional": {},
+          "property": {},
+          "type": "MemberExpression"
+        }}"#,
+        object, optional, property,
+      ),
+      _ => match optional {
+        true => format!("{}?.{}", object, property),
+        false => format!("{}.{}", object, property),
+      },
+    }
+  }
+
+  pub fn transpile_nil(&self) -> String {
+    match self.mode() {
+      TKind::Json => String::from(
+        r#"{
+          "type": "Literal",
+          "raw": "null",
+          "value": null
+        }"#,
+      ),
+      _ => String::from("null"),
+    }
+  }
+
+  pub fn transpile_outcome(&mut self, is_ok: bool, value: &Expr) -> String {
+    let value = self.transpile_expr(value);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "properties": [{{
+            "key": {{
+              "name": "ok",
+              "type": "Identifier"
+            }},
+            "value": {{
+              "type": "Literal",
+              "value": {}
+            }},
+            "type": "Property"
+          }}, {{
+            "key": {{
+              "name": "value",
+              "type": "Identifier"
+            }},
+            "value": {},
+            "type": "Property"
+          }}],
+          "type": "ObjectExpression"
+        }}"#,
+        is_ok, value,
+      ),
+      _ => format!("({{ ok: {}, value: {} }})", is_ok, value),
+    }
+  }
+
+  pub fn transpile_spread(&mut self, value: &Expr) -> String {
+    let value = self.transpile_expr(value);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "argument": {},
+          "type": "SpreadElement"
+        }}"#,
+        value,
+      ),
+      _ => format!("...{}", value),
+    }
+  }
+
+  pub fn transpile_try(&mut self, value: &Expr) -> String {
+    let value = self.transpile_expr(value);
+    let unwrap = "((r) => { if (r === null || r.ok === false) throw { $$err: r }; return r.ok === true ? r.value : r; })";
+
+    self.tries += 1;
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [{}],
+          "callee": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "CallExpression"
+        }}"#,
+        value, unwrap,
+      ),
+      _ => format!("{}({})", unwrap, value),
+    }
+  }
+
+  pub fn transpile_unary(&mut self, operator: UnaryOp, rhs: &Expr) -> String {
+    let rhs = self.transpile_expr(rhs);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "argument": {},
+          "operator": "{}",
+          "prefix": true,
+          "type": "UnaryExpression"
+        }}"#,
+        rhs, operator,
+      ),
+      _ => format!("({}{})", operator, rhs),
+    }
+  }
+
+  fn transpile_exprs(&mut self, exprs: &[Expr], delimiter: &str) -> String {
+    exprs
+      .iter()
+      .map(|expr| self.transpile_expr(expr))
+      .collect::<Vec<String>>()
+      .join(delimiter)
+  }
+
+  pub fn transpile_literal(&self, raw: &str) -> String {
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "type": "Literal",
+          "raw": "{}",
+          "value": {}
+        }}"#,
+        raw.replace('"', "\\\""),
+        raw,
+      ),
+      _ => raw.to_owned(),
+    }
+  }
+
+  pub fn transpile_call(&mut self, callee: &Expr, args: &[Expr]) -> String {
+    if let ExprKind::Identifier(name) = &callee.kind {
+      self.use_native(name);
+    }
+
+    let args = self.transpile_args(callee, args);
+    let callee = self.transpile_expr(callee);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [{}],
+          "callee": {},
+          "type": "CallExpression"
+        }}"#,
+        args, callee,
+      ),
+      _ => format!("{}({})", callee, args),
+    }
+  }
+
+  // named arguments are put in the place of their parameter, the parameters
+  // left out between them are `undefined` to take their default. arguments
+  // that fit no parameter are reported as the interpreter reports them
+  fn transpile_args(&mut self, callee: &Expr, args: &[Expr]) -> String {
+    let params = match &callee.kind {
+      ExprKind::Identifier(name)
+        if args
+          .iter()
+          .any(|arg| matches!(arg.kind, ExprKind::NamedArg(..))) =>
+      {
+        self.signatures.get(name).cloned()
+      }
+      _ => None,
+    };
+    let (name, params) = match (&callee.kind, params) {
+      (ExprKind::Identifier(name), Some(params)) => (name, params),
+      _ => return self.transpile_exprs(args, ", "),
+    };
+
+    let labelled = args
+      .iter()
+      .map(|arg| match &arg.kind {
+        ExprKind::NamedArg(name, _) => (Some(name.to_owned()), arg),
+        _ => (None, arg),
+      })
+      .collect();
+
+    let assigned =
+      match FunctionArg::assign(name, &raise_params(&params), labelled) {
+        Ok(assigned) => assigned,
+        Err(error) => {
+          self.errors.push(error);
+          return self.transpile_exprs(args, ", ");
+        }
+      };
+
+    let undefined = self.transpile_identifier("undefined");
+    let mut args = params
+      .iter()
+      .zip(assigned)
+      .map(|(param, args)| match args.is_empty() && !param.variadic {
+        true => vec![undefined.to_owned()],
+        false => args
+          .into_iter()
+          .map(|arg| self.transpile_expr(arg))
+          .collect(),
+      })
+      .collect::<Vec<Vec<String>>>();
+
+    // trailing defaults are left out
+    while args
+      .last()
+      .is_some_and(|last| last == &[undefined.to_owned()])
+    {
+      args.pop();
+    }
+
+    args.concat().join(", ")
+  }
+
+  // `delete` gives back the value it removes
+  pub fn transpile_delete(&mut self, target: &Expr) -> String {
+    let remove = "((o, k) => { var v = o[k]; if (Array.isArray(o)) o.splice(k, 1); else delete o[k]; return v; })";
+    let (data, key) = match &target.kind {
+      ExprKind::Index(lhs, rhs) => {
+        (self.transpile_expr(lhs), self.transpile_expr(rhs))
+      }
+      _ => (self.transpile_expr(target), String::from("undefined")),
+    };
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [{}, {}],
+          "callee": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "CallExpression"
+        }}"#,
+        data, key, remove,
+      ),
+      _ => format!("{}({}, {})", remove, data, key),
+    }
+  }
+
+  pub fn transpile_hash(&mut self, items: &[(Option<Expr>, Expr)]) -> String {
+    let items = items
+      .iter()
+      .map(|(key, value)| {
+        let key = key.as_ref().map(|key| self.transpile_key(key));
+        let value = self.transpile_expr(value);
+
+        match (self.mode(), key) {
+          (TKind::Json, Some(key)) => format!(
+            r#"{{
+              "key": {},
+              "kind": "init",
+              "type": "Property",
+              "value": {}
+            }}"#,
+            key, value,
+          ),
+          (TKind::Pretty, Some(key)) => format!("\n{}: {}\n", key, value),
+          (TKind::Pretty, None) => format!("\n{}\n", value),
+          (_, Some(key)) => format!("{}: {}", key, value),
+          (_, None) => value,
+        }
+      })
+      .collect::<Vec<String>>()
+      .join(", ");
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "properties": [{}],
+          "type": "ObjectExpression"
+        }}"#,
+        items,
+      ),
+      _ => format!("{{ {} }}", items),
+    }
+  }
+
+  // a javascript object only has string keys, a literal is written as one
+  fn transpile_key(&mut self, key: &Expr) -> String {
+    match &key.kind {
+      ExprKind::Bool(_)
+      | ExprKind::Char(_)
+      | ExprKind::Int(_)
+      | ExprKind::Str(_) => self.transpile_expr(key),
+      _ => format!("[{}]", self.transpile_expr(key)),
+    }
+  }
+
+  // an `if` in place of a value, a conditional when each branch is a single
+  // expression and a function called in place otherwise
+  pub fn transpile_if(&mut self, control: &If) -> String {
+    if !is_simple(control) {
+      let statement = self.transpile_if_statement(control, true);
+
+      return match self.mode() {
+        TKind::Json => format!(
+          r#"{{
+            "arguments": [],
+            "callee": {{
+              "body": {{
+                "body": [{}],
+                "type": "BlockStatement"
+              }},
+              "params": [],
+              "type": "ArrowFunctionExpression"
+            }},
+            "type": "CallExpression"
+          }}"#,
+          statement,
+        ),
+        _ => format!("(() => {{ {} }})()", statement),
+      };
+    }
+
+    let condition = self.transpile_expr(&control.condition);
+    let consequence = self.transpile_branch(&control.consequence.statements);
+    let alternative = match control.alternative.as_deref() {
+      Some(Expr {
+        kind: ExprKind::Block(block),
+        ..
+      }) => self.transpile_branch(&block.statements),
+      Some(alternative) => self.transpile_expr(alternative),
+      None => self.transpile_nil(),
+    };
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "alternate": {},
+          "consequent": {},
+          "test": {},
+          "type": "ConditionalExpression"
+        }}"#,
+        alternative, consequence, condition
+      ),
+      _ => format!("({} ? {} : {})", condition, consequence, alternative),
+    }
+  }
+
+  fn transpile_branch(&mut self, statements: &[Stmt]) -> String {
+    match statements {
+      [stmt] => match &stmt.kind {
+        StmtKind::Expr(expr) => self.transpile_expr(expr),
+        kind => self.transpile_stmt(kind),
+      },
+      _ => String::new(),
+    }
+  }
+
+  pub fn transpile_index(&mut self, lhs: &Expr, rhs: &Expr) -> String {
+    let is_slice = matches!(rhs.kind, ExprKind::Range(_));
+    let lhs = self.transpile_expr(lhs);
+    let rhs = self.transpile_expr(rhs);
+
+    if is_slice {
+      let slice = "((o, r) => { var s = r.map((i) => o[i]); return typeof o === \"string\" ? s.join(\"\") : s; })";
+
+      return match self.mode() {
+        TKind::Json => format!(
+          r#"{{
+            "arguments": [{}, {}],
+            "callee": {{
+              "name": "{}",
+              "type": "Identifier"
+            }},
+            "type": "CallExpression"
+          }}"#,
+          lhs,
+          rhs,
+          slice.replace('"', "\\\""),
+        ),
+        _ => format!("{}({}, {})", slice, lhs, rhs),
+      };
+    }
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "computed": true,
+          "object": {},
+          "property": {},
+          "type": "MemberExpression"
+        }}"#,
+        lhs, rhs,
+      ),
+      _ => format!("{}[{}]", lhs, rhs),
+    }
+  }
+
+  // an instance is an object made from the prototype of its struct
+  pub fn transpile_instance(
+    &mut self,
+    name: &str,
+    fields: &[(String, Expr)],
+  ) -> String {
+    let name = self.transpile_identifier(name);
+    let fields = fields
+      .iter()
+      .map(|(key, value)| {
+        let value = self.transpile_expr(value);
+
+        match self.mode() {
+          TKind::Json => format!(
+            r#"{{
+              "key": {},
+              "kind": "init",
+              "type": "Property",
+              "value": {}
+            }}"#,
+            self.transpile_identifier(key),
+            value,
+          ),
+          _ => format!("{}: {}", key, value),
+        }
+      })
+      .collect::<Vec<String>>()
+      .join(", ");
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [{{
+            "arguments": [{{
+              "computed": false,
+              "object": {},
+              "property": {{
+                "name": "prototype",
+                "type": "Identifier"
+              }},
+              "type": "MemberExpression"
+            }}],
+            "callee": {{
+              "name": "Object.create",
+              "type": "Identifier"
+            }},
+            "type": "CallExpression"
+          }}, {{
+            "properties": [{}],
+            "type": "ObjectExpression"
+          }}],
+          "callee": {{
+            "name": "Object.assign",
+            "type": "Identifier"
+          }},
+          "type": "CallExpression"
+        }}"#,
+        name, fields,
+      ),
+      _ => format!(
+        "Object.assign(Object.create({}.prototype), {{ {} }})",
+        name, fields,
+      ),
+    }
+  }
+
+  pub fn transpile_range(&mut self, range: &Range) -> String {
+    let start = self.transpile_expr(&range.start);
+    let end = self.transpile_expr(&range.end);
+    let step = match &range.step {
+      Some(step) => self.transpile_expr(step),
+      None => String::from("1"),
+    };
+    let (lower, upper) = match range.inclusive {
+      true => ("<=", ">="),
+      false => ("<", ">"),
+    };
+    let range = format!(
+      "((s, e, k) => {{ var r = []; for (var i = s; k > 0 ? i {} e : i {} e; i += k) r.push(i); return r; }})",
+      lower, upper,
+    );
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [{}, {}, {}],
+          "callee": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "CallExpression"
+        }}"#,
+        start, end, step, range,
+      ),
+      _ => format!("{}({}, {}, {})", range, start, end, step),
+    }
+  }
+}
+
+// whether every branch is a single expression
+fn is_simple(control: &If) -> bool {
+  let is_simple_block = |statements: &[Stmt]| match statements {
+    [stmt] => match &stmt.kind {
+      StmtKind::Expr(Expr {
+        kind: ExprKind::If(control),
+        ..
+      }) => is_simple(control),
+      StmtKind::Expr(_) => true,
+      _ => false,
+    },
+    _ => false,
+  };
+
+  is_simple_block(&control.consequence.statements)
+    && match control
+      .alternative
+      .as_deref()
+      .map(|alternative| &alternative.kind)
+    {
+      Some(ExprKind::If(control)) => is_simple(control),
+      Some(ExprKind::Block(block)) => is_simple_block(&block.statements),
+      Some(_) => false,
+      None => true,
+    }
+}
@@ -1,9 +1,13 @@
+pub mod expression;
+pub mod pattern;
 pub mod transpiler;
 
 pub use crate::transformer::TKind::*;
 
 use crate::analyzer::interpreter::Interpreter;
+use crate::converter::folder::Folder;
 use crate::converter::parser::Parser;
+use crate::optimizer::folding::ConstantFolder;
 use crate::transformer::transpiler::{TKind, Transpiler, TranspilerResult};
 
 #[cfg(test)]
@@ -14,10 +18,10 @@ pub fn transformify(
   mode: &str,
   interpreter: &Interpreter,
 ) -> TranspilerResult<String> {
-  let mut parser = Parser::new(input);
-  let program = &parser.parse()?;
+  let ast = Parser::new(input).parse_ast()?;
+  let ast = ConstantFolder::new().fold_ast(ast);
   let mut transpiler =
     Transpiler::new(interpreter.to_owned(), TKind::from(mode));
 
-  transpiler.transpile(program)
+  transpiler.transpile(&ast)
 }
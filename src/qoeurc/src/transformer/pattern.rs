@@ -0,0 +1,174 @@
+use crate::converter::ast::{Expr, ExprKind, Pattern};
+use crate::transformer::transpiler::{TKind, Transpiler};
+
+impl Transpiler {
+  // each arm tests the subject and binds its names before its body, no arm
+  // matching is an error as it is when interpreted
+  pub fn transpile_match(
+    &mut self,
+    subject: &Expr,
+    arms: &[(Pattern, Expr)],
+  ) -> String {
+    let subject = self.transpile_expr(subject);
+    let arms = arms
+      .iter()
+      .map(|(pattern, body)| {
+        let test = self.transpile_test(pattern, "$$m");
+        let mut body = match &body.kind {
+          ExprKind::Block(block) => vec![self.transpile_block(block)],
+          _ => vec![format!("return {};", self.transpile_expr(body))],
+        };
+
+        body.splice(0..0, transpile_bindings(pattern, "$$m"));
+
+        format!("if ({}) {{ {} }}", test, body.join(" "))
+      })
+      .collect::<Vec<String>>()
+      .join(" ");
+    let error =
+      "throw new Error(\"no arm of `match` matches `\" + $$m + \"`\");";
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [{}],
+          "callee": {{
+            "body": "{} {}",
+            "params": ["$$m"],
+            "type": "ArrowFunctionExpression"
+          }},
+          "type": "CallExpression"
+        }}"#,
+        subject,
+        arms.replace('"', "\\\""),
+        error.replace('"', "\\\""),
+      ),
+      TKind::Inline => {
+        format!("(($$m) => {{ {} {} }})({})", arms, error, subject)
+      }
+      TKind::Pretty => {
+        format!("(($$m) => {{\n\t{}\n\t{}\n}})({})", arms, error, subject)
+      }
+    }
+  }
+
+  // the condition for `subject` to match the pattern
+  fn transpile_test(&mut self, pattern: &Pattern, subject: &str) -> String {
+    match pattern {
+      Pattern::Binding(_) | Pattern::Rest(_) | Pattern::Wildcard => {
+        String::from("true")
+      }
+      Pattern::Literal(value) => {
+        format!("{} === {}", subject, self.transpile_expr(value))
+      }
+      Pattern::Outcome(is_ok, pattern) => format!(
+        "{} !== null && {}.ok === {} && {}",
+        subject,
+        subject,
+        is_ok,
+        self.transpile_test(pattern, &format!("{}.value", subject)),
+      ),
+      Pattern::Array(patterns) | Pattern::Tuple(patterns) => {
+        let length = match patterns.last() {
+          Some(Pattern::Rest(_)) => {
+            format!("{}.length >= {}", subject, patterns.len() - 1)
+          }
+          _ => format!("{}.length === {}", subject, patterns.len()),
+        };
+        let mut tests = vec![format!("Array.isArray({})", subject), length];
+
+        for (x, pattern) in patterns.iter().enumerate() {
+          let element = format!("{}[{}]", subject, x);
+
+          tests.push(self.transpile_test(pattern, &element));
+        }
+
+        tests.join(" && ")
+      }
+      Pattern::Hash(fields) | Pattern::Struct(_, fields) => {
+        let mut tests = match pattern {
+          Pattern::Struct(name, _) => {
+            vec![format!("{} instanceof {}", subject, name)]
+          }
+          _ => vec![format!(
+            "{} !== null && typeof {} === \"object\"",
+            subject, subject
+          )],
+        };
+
+        for (key, pattern) in fields {
+          let field = format!("{}.{}", subject, key);
+
+          tests.push(format!("\"{}\" in {}", key, subject));
+          tests.push(self.transpile_test(pattern, &field));
+        }
+
+        tests.join(" && ")
+      }
+    }
+  }
+
+  // the destructuring target a `val` or a parameter binds with
+  pub fn transpile_target(&self, pattern: &Pattern) -> String {
+    match pattern {
+      Pattern::Binding(name) => name.into(),
+      Pattern::Outcome(_, pattern) => {
+        format!("{{ value: {} }}", self.transpile_target(pattern))
+      }
+      Pattern::Array(patterns) | Pattern::Tuple(patterns) => format!(
+        "[{}]",
+        patterns
+          .iter()
+          .filter(|pattern| !matches!(pattern, Pattern::Rest(None)))
+          .map(|pattern| match pattern {
+            Pattern::Rest(Some(name)) => format!("...{}", name),
+            pattern => self.transpile_target(pattern),
+          })
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Pattern::Hash(fields) | Pattern::Struct(_, fields) => format!(
+        "{{ {} }}",
+        fields
+          .iter()
+          .filter(|(_, pattern)| !pattern.names().is_empty())
+          .map(|(key, pattern)| match pattern {
+            Pattern::Binding(name) if name == key => key.into(),
+            pattern => format!("{}: {}", key, self.transpile_target(pattern)),
+          })
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Pattern::Literal(_) | Pattern::Rest(_) | Pattern::Wildcard => {
+        String::new()
+      }
+    }
+  }
+}
+
+// the variables a matching arm binds, read from `subject`
+fn transpile_bindings(pattern: &Pattern, subject: &str) -> Vec<String> {
+  match pattern {
+    Pattern::Binding(name) => vec![format!("var {} = {};", name, subject)],
+    Pattern::Outcome(_, pattern) => {
+      transpile_bindings(pattern, &format!("{}.value", subject))
+    }
+    Pattern::Array(patterns) | Pattern::Tuple(patterns) => patterns
+      .iter()
+      .enumerate()
+      .flat_map(|(x, pattern)| match pattern {
+        Pattern::Rest(Some(name)) => {
+          vec![format!("var {} = {}.slice({});", name, subject, x)]
+        }
+        _ => transpile_bindings(pattern, &format!("{}[{}]", subject, x)),
+      })
+      .collect(),
+    Pattern::Hash(fields) | Pattern::Struct(_, fields) => fields
+      .iter()
+      .flat_map(|(key, pattern)| {
+        transpile_bindings(pattern, &format!("{}.{}", subject, key))
+      })
+      .collect(),
+    Pattern::Literal(_) | Pattern::Rest(_) | Pattern::Wildcard => vec![],
+  }
+}
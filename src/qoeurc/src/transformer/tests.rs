@@ -2,7 +2,106 @@ extern crate qutonium;
 
 use qutonium::prelude::*;
 
+use super::transformify;
+use crate::analyzer::interpreter::Interpreter;
+use crate::testing::suite;
+
+fn js(input: &str) -> Result<String, String> {
+  transformify(input, "inline", &Interpreter::new())
+}
+
 #[test]
 fn from_test() {
-  suite!("qoeurc::transformer", { "test empty input" || { Ok(()) } });
+  suite!("qoeurc::transformer", {
+    "test empty input" || { must!(true; be.truthy) }
+  });
+}
+
+#[test]
+fn loop_test() {
+  suite!("qoeurc::transformer::loop", {
+    "test loops used as values run in a function" || {
+      must!(
+        js("val x := loop { break; };");
+        eq Ok(
+          "var x = (() => { let $$break = null; for (;;) { break; } \
+          return $$break; })();\n"
+            .into()
+        )
+      )?;
+      must!(
+        js("val x := loop { break 3; };");
+        eq Ok(
+          "var x = (() => { let $$break = null; for (;;) { \
+          { $$break = 3; break; } } return $$break; })();\n"
+            .into()
+        )
+      )?;
+      must!(
+        js("val x := while false { };");
+        eq Ok(
+          "var x = (() => { let $$break = null; while (false) {  } \
+          return $$break; })();\n"
+            .into()
+        )
+      )
+    }
+
+    "test loops used as statements declare the value of their breaks" || {
+      must!(js("loop { break; }"); eq Ok("for (;;) { break; }".into()))?;
+      must!(
+        js("while true { break 5; }");
+        eq Ok(
+          "{ let $$break = null; while (true) { \
+          { $$break = 5; break; } } }"
+            .into()
+        )
+      )
+    }
+
+    "test breaks aimed at an outer loop are declared by it" || {
+      must!(
+        js("@a loop { loop { break @a 4; } }");
+        eq Ok(
+          "{ let $$break = null; a: for (;;) { for (;;) { \
+          { $$break = 4; break a; } } } }"
+            .into()
+        )
+      )
+    }
+  });
+}
+
+#[test]
+fn param_test() {
+  suite!("qoeurc::transformer::param", {
+    "test defaults and variadics become parameters of javascript" || {
+      must!(
+        js("ƒ f(a: int, b: int = 2) -> int { return a * b; }");
+        eq Ok("function f (a, b = 2) { return (a * b); }".into())
+      )?;
+      must!(
+        js("ƒ f(a: int, ..xs: int) -> int { return a; }");
+        eq Ok("function f (a, ...xs) { return a; }".into())
+      )
+    }
+
+    "test named arguments are put in the order of the parameters" || {
+      must!(
+        js("ƒ f(a: int, b: int = 2) -> int { return a; } f(b: 1, a: 2);");
+        eq Ok("function f (a, b = 2) { return a; }f(2, 1);".into())
+      )
+    }
+
+    "test named arguments that fit no parameter are errors" || {
+      must!(
+        js("ƒ f(a: int, b: int = 2) -> int { return a; } f(1, a: 2);");
+        eq Err("the argument `a` of function `f` is supplied more than once".into())
+      )?;
+      must!(
+        js("ƒ f(x: int) -> int { return x; } f(x: 1, y: 2);");
+        eq Err("function `f` has no parameter named `y`".into())
+      )
+    }
+  });
 }
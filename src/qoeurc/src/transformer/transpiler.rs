@@ -1,5 +1,15 @@
 use crate::analyzer::interpreter::Interpreter;
-use crate::value::Value;
+use crate::converter::ast::{
+  Ast, Block, Closure, Expr, ExprKind, Function, If, Impl, Loop, LoopKind,
+  Param, Range, Stmt, StmtKind, Struct, Trait, Use, Val,
+};
+use crate::converter::visitor::{self, Visitor};
+use crate::value::instruction::native::Native;
+
+use inflector::cases::camelcase::to_camel_case;
+
+use std::collections::HashMap;
+use std::mem;
 
 pub type TranspilerError = String;
 pub type TranspilerResult<T> = Result<T, TranspilerError>;
@@ -21,25 +31,1263 @@ impl From<&str> for TKind {
   }
 }
 
+// writes a syntax tree as javascript, or as its estree in json
 #[derive(Clone, Debug)]
 pub struct Transpiler {
+  pub breaks: Vec<Option<String>>,
+  pub errors: Vec<TranspilerError>,
   pub mode: TKind,
   pub interpreter: Interpreter,
+  pub modules: Vec<String>,
+  pub natives: Vec<Native>,
+  pub output: Vec<String>,
+  pub signatures: HashMap<String, Vec<Param>>,
+  pub tries: usize,
 }
 
 impl Transpiler {
   pub fn new(interpreter: Interpreter, mode: TKind) -> Self {
-    Transpiler { mode, interpreter }
+    Transpiler {
+      breaks: vec![],
+      errors: vec![],
+      mode,
+      interpreter,
+      modules: vec![],
+      natives: vec![],
+      output: vec![],
+      signatures: HashMap::new(),
+      tries: 0,
+    }
   }
 
   pub fn mode(&self) -> TKind {
     self.mode.to_owned()
   }
 
-  pub fn transpile(
+  // a call to a function of the standard library that the program does not
+  // define itself, its definition is put at the top of the output
+  pub fn use_native(&mut self, name: &str) {
+    if self.signatures.contains_key(name)
+      || self.natives.iter().any(|native| native.name() == name)
+    {
+      return;
+    }
+
+    let function = self.interpreter.scope().get_function(name);
+
+    if let Some(native) = function
+      .and_then(|function| function.as_ref().downcast_ref::<Native>().cloned())
+    {
+      self.natives.push(native);
+    }
+  }
+
+  pub fn transpile(&mut self, ast: &Ast) -> TranspilerResult<String> {
+    self.visit_ast(ast);
+
+    let statements = mem::take(&mut self.output);
+
+    // the first error found while writing the program is the one reported
+    if let Some(error) = mem::take(&mut self.errors).into_iter().next() {
+      return Err(error);
+    }
+
+    match self.mode() {
+      TKind::Json => Ok(format!(
+        r#"{{
+          "body": [{}],
+          "type": "Program"
+        }}"#,
+        statements.join(", "),
+      )),
+      _ => {
+        let natives = self
+          .natives
+          .iter()
+          .map(|native| native.js)
+          .collect::<Vec<&str>>()
+          .join("");
+
+        Ok(format!("{}{}", natives, statements.join("")))
+      }
+    }
+  }
+
+  // the parameters of the functions the program defines, to place the named
+  // arguments of the calls to them
+  fn declare_signatures(&mut self, ast: &Ast) {
+    for stmt in &ast.statements {
+      let kind = match &stmt.kind {
+        StmtKind::Public(kind) => kind.as_ref(),
+        kind => kind,
+      };
+
+      if let StmtKind::Function(function) = kind {
+        self
+          .signatures
+          .insert(function.name.to_owned(), function.params.to_owned());
+      }
+    }
+  }
+
+  // the javascript of a statement, written by `visit_stmt_kind`
+  pub fn transpile_stmt(&mut self, kind: &StmtKind) -> String {
+    self.visit_stmt_kind(kind);
+    self.written()
+  }
+
+  // what the last `visit_*` wrote, taken by the node around it
+  pub fn written(&mut self) -> String {
+    self.output.pop().unwrap_or_default()
+  }
+
+  fn transpile_continue(&self, label: &Option<String>) -> String {
+    match (self.mode(), label) {
+      (TKind::Json, Some(label)) => format!(
+        r#"{{
+          "label": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "ContinueStatement"
+        }}"#,
+        label,
+      ),
+      (TKind::Json, None) => String::from(
+        r#"{
+          "label": null,
+          "type": "ContinueStatement"
+        }"#,
+      ),
+      (_, Some(label)) => format!("continue {};", label),
+      (_, None) => String::from("continue;"),
+    }
+  }
+
+  // an expression written as a statement, `a + 1;`
+  fn transpile_expression(&mut self, expr: &Expr) -> String {
+    let node = self.transpile_expr(expr);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "expression": {},
+          "type": "ExpressionStatement"
+        }}"#,
+        node,
+      ),
+      _ => format!("{};", node),
+    }
+  }
+
+  fn transpile_struct(&self, structure: &Struct) -> String {
+    let name = self.transpile_identifier(&structure.name);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": {{
+            "body": [],
+            "type": "ClassBody"
+          }},
+          "id": {},
+          "superClass": null,
+          "type": "ClassDeclaration"
+        }}"#,
+        name,
+      ),
+      _ => format!("class {} {{}}", name),
+    }
+  }
+
+  // the statements of a function or a closure, the value of the last one is
+  // returned
+  pub fn transpile_block(&mut self, block: &Block) -> String {
+    let count = block.statements.len();
+    let statements = block
+      .statements
+      .iter()
+      .enumerate()
+      .map(|(at, stmt)| match (&stmt.kind, at + 1 == count) {
+        (
+          StmtKind::Expr(Expr {
+            kind: ExprKind::If(control),
+            ..
+          }),
+          is_last,
+        ) => self.transpile_if_statement(control, is_last),
+        (
+          StmtKind::Expr(Expr {
+            kind: ExprKind::Comment(_),
+            ..
+          }),
+          _,
+        ) => String::new(),
+        (StmtKind::Expr(expr), true) | (StmtKind::Node(expr), true) => {
+          let value = self.transpile_expr(expr);
+
+          self.transpile_returned(Some(value))
+        }
+        (kind, _) => self.transpile_stmt(kind),
+      })
+      .collect::<Vec<String>>();
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": [{}],
+          "type": "BlockStatement"
+        }}"#,
+        statements.join(", "),
+      ),
+      _ => statements.join(""),
+    }
+  }
+
+  // the statements of a loop or a branch, without the implicit `return` of a
+  // block
+  pub fn transpile_body(&mut self, block: &Block) -> String {
+    let statements = self.transpile_statements(&block.statements);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": [{}],
+          "type": "BlockStatement"
+        }}"#,
+        statements.join(", "),
+      ),
+      _ => statements.join(" "),
+    }
+  }
+
+  fn transpile_statements(&mut self, statements: &[Stmt]) -> Vec<String> {
+    statements
+      .iter()
+      .map(|stmt| self.transpile_stmt(&stmt.kind))
+      .collect()
+  }
+
+  // `?` in a function throws the failed outcome, it is caught where the
+  // function starts and given back as its value
+  fn transpile_catch(&self, block: String) -> String {
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "block": {},
+          "handler": {{
+            "body": "if ($$e && \"$$err\" in $$e) return $$e.$$err; throw $$e;",
+            "param": {{
+              "name": "$$e",
+              "type": "Identifier"
+            }},
+            "type": "CatchClause"
+          }},
+          "type": "TryStatement"
+        }}"#,
+        block,
+      ),
+      _ => format!(
+        "try {{ {} }} catch ($$e) {{ if ($$e && \"$$err\" in $$e) return $$e.$$err; throw $$e; }}",
+        block,
+      ),
+    }
+  }
+
+  pub fn transpile_label(
+    &self,
+    label: &Option<String>,
+    statement: String,
+  ) -> String {
+    match (self.mode(), label) {
+      (_, None) => statement,
+      (TKind::Json, Some(label)) => format!(
+        r#"{{
+          "body": {},
+          "label": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "LabeledStatement"
+        }}"#,
+        statement, label,
+      ),
+      (_, Some(label)) => format!("{}: {}", label, statement),
+    }
+  }
+
+  // the block of a function or a closure, wrapped to catch the outcomes its
+  // `?` throw
+  pub fn transpile_callable(&mut self, block: &Block) -> String {
+    let tries = self.tries;
+    let mut block = self.transpile_block(block);
+
+    if self.tries > tries {
+      block = self.transpile_catch(block);
+      self.tries = tries;
+    }
+
+    block
+  }
+
+  fn transpile_function(&mut self, function: &Function) -> String {
+    let params = self.transpile_params(&function.params);
+    let block = match &function.block {
+      Some(block) => self.transpile_callable(block),
+      None => String::new(),
+    };
+    let name = self.transpile_identifier(&function.name);
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": {},
+          "id": {},
+          "params": [{}],
+          "type": "FunctionDeclaration"
+        }}"#,
+        block, name, params,
+      ),
+      TKind::Inline => {
+        format!("function {} ({}) {{ {} }}", name, params, block)
+      }
+      TKind::Pretty => {
+        format!("function {} ({}) {{\n\t{}\n}}", name, params, block)
+      }
+    }
+  }
+
+  pub fn transpile_params(&mut self, params: &[Param]) -> String {
+    params
+      .iter()
+      .map(|param| {
+        self.visit_param(param);
+        self.written()
+      })
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+
+  fn transpile_param(&mut self, param: &Param) -> String {
+    let target = match &param.pattern {
+      Some(pattern) => self.transpile_target(pattern),
+      None => param.name.to_owned(),
+    };
+
+    match self.mode() {
+      TKind::Json => {
+        let target = format!(
+          r#"{{
+            "name": "{}",
+            "type": "Identifier"
+          }}"#,
+          target,
+        );
+
+        match (&param.default, param.variadic) {
+          (Some(default), _) => format!(
+            r#"{{
+              "left": {},
+              "right": {},
+              "type": "AssignmentPattern"
+            }}"#,
+            target,
+            self.transpile_expr(default),
+          ),
+          (None, true) => format!(
+            r#"{{
+              "argument": {},
+              "type": "RestElement"
+            }}"#,
+            target,
+          ),
+          (None, false) => target,
+        }
+      }
+      _ => match (&param.default, param.variadic) {
+        (Some(default), _) => {
+          format!("{} = {}", target, self.transpile_expr(default))
+        }
+        (None, true) => format!("...{}", target),
+        (None, false) => target,
+      },
+    }
+  }
+
+  // the methods of a type, a function that takes `self` is put on the
+  // prototype of its instances
+  fn transpile_impl(&mut self, implementation: &Impl) -> String {
+    let ty = self.transpile_identifier(&implementation.ty);
+    let methods = implementation
+      .functions
+      .iter()
+      .map(|method| self.transpile_method(&ty, method))
+      .collect::<Vec<String>>();
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": [{}],
+          "type": "BlockStatement"
+        }}"#,
+        methods.join(", "),
+      ),
+      TKind::Inline => methods.join(" "),
+      TKind::Pretty => methods.join("\n"),
+    }
+  }
+
+  fn transpile_method(&mut self, ty: &str, method: &Function) -> String {
+    let is_method =
+      matches!(method.params.first(), Some(param) if param.name == "self");
+    let params = match is_method {
+      true => self.transpile_params(&method.params[1..]),
+      false => self.transpile_params(&method.params),
+    };
+    let block = match &method.block {
+      Some(block) => self.transpile_block(block),
+      None => String::new(),
+    };
+    let target = match is_method {
+      true => format!("{}.prototype.{}", ty, method.name),
+      false => format!("{}.{}", ty, method.name),
+    };
+    let this = match is_method {
+      true => "var self = this; ",
+      false => "",
+    };
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "expression": {{
+            "left": {{
+              "name": "{}",
+              "type": "Identifier"
+            }},
+            "operator": "=",
+            "right": {{
+              "body": {},
+              "params": [{}],
+              "type": "FunctionExpression"
+            }},
+            "type": "AssignmentExpression"
+          }},
+          "type": "ExpressionStatement"
+        }}"#,
+        target, block, params,
+      ),
+      TKind::Inline => {
+        format!(
+          "{} = function ({}) {{ {}{} }};",
+          target, params, this, block
+        )
+      }
+      TKind::Pretty => format!(
+        "{} = function ({}) {{\n\t{}{}\n}};",
+        target, params, this, block
+      ),
+    }
+  }
+
+  // a module written in place runs once and gives back what it exports
+  fn transpile_module(&mut self, name: &str, block: &Option<Block>) -> String {
+    let init = match block {
+      Some(block) => {
+        self.modules.push(name.to_owned());
+
+        let body = self.transpile_statements(&block.statements);
+
+        match self.mode() {
+          TKind::Json => format!(
+            r#"{{
+              "arguments": [],
+              "callee": {{
+                "body": {{
+                  "body": [{}],
+                  "type": "BlockStatement"
+                }},
+                "params": [],
+                "type": "FunctionExpression"
+              }},
+              "type": "CallExpression"
+            }}"#,
+            body.join(", "),
+          ),
+          _ => format!(
+            "(function () {{ var exports = {{}}; {} return exports; }})()",
+            body.join(" "),
+          ),
+        }
+      }
+      None => match self.mode() {
+        TKind::Json => format!(
+          r#"{{
+            "arguments": [{{
+              "type": "Literal",
+              "value": "./{}"
+            }}],
+            "callee": {{
+              "name": "require",
+              "type": "Identifier"
+            }},
+            "type": "CallExpression"
+          }}"#,
+          name,
+        ),
+        _ => format!("require(\"./{}\")", name),
+      },
+    };
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "declarations": [{{
+            "id": {{
+              "name": "{}",
+              "type": "Identifier"
+            }},
+            "init": {},
+            "type": "VariableDeclarator"
+          }}],
+          "kind": "var",
+          "type": "VariableDeclaration"
+        }}"#,
+        name, init,
+      ),
+      _ => format!("var {} = {};\n", name, init),
+    }
+  }
+
+  fn transpile_public(&mut self, kind: &StmtKind) -> String {
+    let node = self.transpile_stmt(kind);
+    let name = match kind {
+      StmtKind::Function(function) => function.name.to_owned(),
+      StmtKind::Struct(structure) => structure.name.to_owned(),
+      StmtKind::Trait(interface) => interface.name.to_owned(),
+      StmtKind::Val(val) => val.name.to_owned(),
+      _ => String::new(),
+    };
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": [{}, {{
+            "expression": {{
+              "left": {{
+                "name": "exports.{}",
+                "type": "Identifier"
+              }},
+              "operator": "=",
+              "right": {{
+                "name": "{}",
+                "type": "Identifier"
+              }},
+              "type": "AssignmentExpression"
+            }},
+            "type": "ExpressionStatement"
+          }}],
+          "type": "BlockStatement"
+        }}"#,
+        node, name, name,
+      ),
+      TKind::Inline => format!("{} exports.{} = {};", node, name, name),
+      TKind::Pretty => format!("{}\nexports.{} = {};\n", node, name, name),
+    }
+  }
+
+  fn transpile_return(&mut self, value: &Option<Expr>) -> String {
+    let value = value.as_ref().map(|value| self.transpile_expr(value));
+
+    self.transpile_returned(value)
+  }
+
+  fn transpile_returned(&self, value: Option<String>) -> String {
+    match (self.mode(), value) {
+      (TKind::Json, value) => format!(
+        r#"{{
+          "argument": {},
+          "type": "ReturnStatement"
+        }}"#,
+        value.unwrap_or_else(|| String::from("null")),
+      ),
+      (_, Some(value)) => format!("return {};", value),
+      (_, None) => String::from("return;"),
+    }
+  }
+
+  // a `break` with a value stores it for the loop to give back
+  fn transpile_break(
     &mut self,
-    program: &Box<dyn Value>,
-  ) -> TranspilerResult<String> {
-    Ok(program.to_owned().transpile(self))
+    label: &Option<String>,
+    value: &Option<Expr>,
+  ) -> String {
+    let jump = match (self.mode(), label) {
+      (TKind::Json, Some(label)) => format!(
+        r#"{{
+          "label": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "BreakStatement"
+        }}"#,
+        label,
+      ),
+      (TKind::Json, None) => String::from(
+        r#"{
+          "label": null,
+          "type": "BreakStatement"
+        }"#,
+      ),
+      (_, Some(label)) => format!("break {};", label),
+      (_, None) => String::from("break;"),
+    };
+
+    let value = match value {
+      Some(value) => self.transpile_expr(value),
+      None => return jump,
+    };
+
+    self.breaks.push(label.to_owned());
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": [{{
+            "expression": {{
+              "left": {{
+                "name": "$$break",
+                "type": "Identifier"
+              }},
+              "operator": "=",
+              "right": {},
+              "type": "AssignmentExpression"
+            }},
+            "type": "ExpressionStatement"
+          }}, {}],
+          "type": "BlockStatement"
+        }}"#,
+        value, jump,
+      ),
+      _ => format!("{{ $$break = {}; {} }}", value, jump),
+    }
+  }
+
+  // the module of the path is bound by a `mod` of the program or required from
+  // its file
+  fn transpile_use(&mut self, import: &Use) -> String {
+    let module = import.path.join("::");
+    let init = match self.modules.contains(&module) {
+      true => module.replace("::", "."),
+      false => format!("require(\"{}\")", module_file(&import.path)),
+    };
+
+    match (self.mode(), import.glob) {
+      (TKind::Json, true) => format!(
+        r#"{{
+          "expression": {{
+            "arguments": [{{
+              "name": "globalThis",
+              "type": "Identifier"
+            }}, {{
+              "name": "{}",
+              "type": "Identifier"
+            }}],
+            "callee": {{
+              "name": "Object.assign",
+              "type": "Identifier"
+            }},
+            "type": "CallExpression"
+          }},
+          "type": "ExpressionStatement"
+        }}"#,
+        init.replace('"', "\\\""),
+      ),
+      (TKind::Json, false) => format!(
+        r#"{{
+          "declarations": [{{
+            "id": {{
+              "properties": [{}],
+              "type": "ObjectPattern"
+            }},
+            "init": {{
+              "name": "{}",
+              "type": "Identifier"
+            }},
+            "type": "VariableDeclarator"
+          }}],
+          "kind": "var",
+          "type": "VariableDeclaration"
+        }}"#,
+        import
+          .items
+          .iter()
+          .map(|item| {
+            let item = self.transpile_identifier(item);
+
+            format!(
+              r#"{{
+                "key": {},
+                "kind": "init",
+                "shorthand": true,
+                "type": "Property",
+                "value": {}
+              }}"#,
+              item, item,
+            )
+          })
+          .collect::<Vec<String>>()
+          .join(", "),
+        init.replace('"', "\\\""),
+      ),
+      (_, true) => format!("Object.assign(globalThis, {});\n", init),
+      (_, false) => {
+        format!("var {{ {} }} = {};\n", import.items.join(", "), init)
+      }
+    }
+  }
+
+  fn transpile_val(&mut self, val: &Val) -> String {
+    let value = self.transpile_expr(&val.value);
+    let name = match &val.pattern {
+      Some(pattern) => {
+        let target = self.transpile_target(pattern);
+
+        match self.mode() {
+          TKind::Json => format!(
+            r#"{{
+              "name": "{}",
+              "type": "Identifier"
+            }}"#,
+            target,
+          ),
+          _ => target,
+        }
+      }
+      None => self.transpile_identifier(&val.name),
+    };
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "declarations": [{{
+            "id": {},
+            "init": {},
+            "type": "VariableDeclarator"
+          }}],
+          "kind": "var",
+          "type": "VariableDeclaration"
+        }}"#,
+        name, value,
+      ),
+      _ => format!("var {} = {};\n", name, value),
+    }
+  }
+
+  // the statement form of an `if`, at the `tail` of a function the value of
+  // each branch is returned
+  pub fn transpile_if_statement(&mut self, control: &If, tail: bool) -> String {
+    let condition = self.transpile_expr(&control.condition);
+    let consequence = match tail {
+      true => self.transpile_block(&control.consequence),
+      false => self.transpile_body(&control.consequence),
+    };
+    let alternative = control.alternative.as_ref().map(|alternative| {
+      match (&alternative.kind, tail) {
+        (ExprKind::If(control), _) => {
+          self.transpile_if_statement(control, tail)
+        }
+        (ExprKind::Block(block), true) => self.transpile_block(block),
+        (ExprKind::Block(block), false) => self.transpile_body(block),
+        (_, _) => self.transpile_expr(alternative),
+      }
+    });
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "alternate": {},
+          "consequent": {},
+          "test": {},
+          "type": "IfStatement"
+        }}"#,
+        alternative.unwrap_or_else(|| String::from("null")),
+        consequence,
+        condition
+      ),
+      _ => {
+        let is_chained = matches!(
+          control
+            .alternative
+            .as_deref()
+            .map(|alternative| &alternative.kind),
+          Some(ExprKind::If(_))
+        );
+        let mut content = format!("if ({}) {{ {} }}", condition, consequence);
+
+        match alternative {
+          Some(alternative) if is_chained => {
+            content += &format!(" else {}", alternative)
+          }
+          Some(alternative) => {
+            content += &format!(" else {{ {} }}", alternative)
+          }
+          None => (),
+        }
+
+        content
+      }
+    }
+  }
+
+  // a loop used as a value runs in a function that gives back the value of
+  // its `break`, `null` when it has none
+  pub fn transpile_loop(&mut self, looping: &Loop) -> String {
+    let statement = self.transpile_looping(looping).0;
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "arguments": [],
+          "callee": {{
+            "body": {{
+              "body": [{}, {}, {{
+                "argument": {{
+                  "name": "$$break",
+                  "type": "Identifier"
+                }},
+                "type": "ReturnStatement"
+              }}],
+              "type": "BlockStatement"
+            }},
+            "params": [],
+            "type": "ArrowFunctionExpression"
+          }},
+          "type": "CallExpression"
+        }}"#,
+        self.transpile_break_declaration(),
+        statement,
+      ),
+      _ => format!(
+        "(() => {{ {} {} return $$break; }})()",
+        self.transpile_break_declaration(),
+        statement,
+      ),
+    }
+  }
+
+  // a loop used as a statement only declares where its breaks store their
+  // value, when one of them has one
+  pub fn transpile_loop_statement(&mut self, looping: &Loop) -> String {
+    let (statement, has_value) = self.transpile_looping(looping);
+
+    if !has_value {
+      return statement;
+    }
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": [{}, {}],
+          "type": "BlockStatement"
+        }}"#,
+        self.transpile_break_declaration(),
+        statement,
+      ),
+      _ => {
+        format!("{{ {} {} }}", self.transpile_break_declaration(), statement)
+      }
+    }
+  }
+
+  // `let $$break = null;`
+  fn transpile_break_declaration(&self) -> String {
+    match self.mode() {
+      TKind::Json => String::from(
+        r#"{
+          "declarations": [{
+            "id": {
+              "name": "$$break",
+              "type": "Identifier"
+            },
+            "init": {
+              "type": "Literal",
+              "raw": "null",
+              "value": null
+            },
+            "type": "VariableDeclarator"
+          }],
+          "kind": "let",
+          "type": "VariableDeclaration"
+        }"#,
+      ),
+      _ => String::from("let $$break = null;"),
+    }
+  }
+
+  // the loop and whether a `break` with a value leaves it
+  fn transpile_looping(&mut self, looping: &Loop) -> (String, bool) {
+    let breaks = self.breaks.len();
+    let statement = match &looping.kind {
+      LoopKind::For(variables, iterable) => {
+        self.transpile_for(variables, iterable, &looping.block)
+      }
+      LoopKind::Infinite => {
+        let block = self.transpile_body(&looping.block);
+
+        match self.mode() {
+          TKind::Json => format!(
+            r#"{{
+              "body": {},
+              "init": null,
+              "test": null,
+              "update": null,
+              "type": "ForStatement"
+            }}"#,
+            block,
+          ),
+          TKind::Inline => format!("for (;;) {{ {} }}", block),
+          TKind::Pretty => format!("for (;;) {{\n\t{}\n}}", block),
+        }
+      }
+      LoopKind::While(condition) => {
+        let condition = self.transpile_expr(condition);
+        let block = self.transpile_body(&looping.block);
+
+        match self.mode() {
+          TKind::Json => format!(
+            r#"{{
+              "body": {},
+              "test": {},
+              "type": "WhileStatement"
+            }}"#,
+            block, condition,
+          ),
+          TKind::Inline => format!("while ({}) {{ {} }}", condition, block),
+          TKind::Pretty => format!("while ({}) {{\n\t{}\n}}", condition, block),
+        }
+      }
+    };
+    let statement = self.transpile_label(&looping.label, statement);
+
+    // breaks aimed at an outer loop are left for it
+    let (own, outer): (Vec<_>, Vec<_>) = self
+      .breaks
+      .split_off(breaks)
+      .into_iter()
+      .partition(|label| label.is_none() || label == &looping.label);
+
+    self.breaks.extend(outer);
+    (statement, !own.is_empty())
+  }
+
+  // a range with a single variable is counted through, anything else is
+  // iterated, a hash by its entries
+  fn transpile_for(
+    &mut self,
+    variables: &[String],
+    iterable: &Expr,
+    block: &Block,
+  ) -> String {
+    let block = self.transpile_body(block);
+    let variable = match variables.len() {
+      0 => String::from("$$x"),
+      1 => variables[0].to_owned(),
+      _ => format!("[{}]", variables.join(", ")),
+    };
+
+    if let ExprKind::Range(range) = &iterable.kind {
+      if variables.len() < 2 {
+        let start = self.transpile_expr(&range.start);
+        let end = self.transpile_expr(&range.end);
+        let (lower, upper) = match range.inclusive {
+          true => ("<=", ">="),
+          false => ("<", ">"),
+        };
+        let (test, update) = match &range.step {
+          Some(step) => {
+            let step = self.transpile_expr(step);
+
+            (
+              format!(
+                "{} > 0 ? {} {} {} : {} {} {}",
+                step, variable, lower, end, variable, upper, end
+              ),
+              format!("{} += {}", variable, step),
+            )
+          }
+          None => (
+            format!("{} {} {}", variable, lower, end),
+            format!("{}++", variable),
+          ),
+        };
+
+        return match self.mode() {
+          TKind::Json => format!(
+            r#"{{
+              "body": {},
+              "init": {{
+                "declarations": [{{
+                  "id": {{
+                    "name": "{}",
+                    "type": "Identifier"
+                  }},
+                  "init": {},
+                  "type": "VariableDeclarator"
+                }}],
+                "kind": "var",
+                "type": "VariableDeclaration"
+              }},
+              "test": {{
+                "name": "{}",
+                "type": "Identifier"
+              }},
+              "type": "ForStatement",
+              "update": {{
+                "name": "{}",
+                "type": "Identifier"
+              }}
+            }}"#,
+            block, variable, start, test, update,
+          ),
+          TKind::Inline => format!(
+            "for (var {} = {}; {}; {}) {{ {} }}",
+            variable, start, test, update, block
+          ),
+          TKind::Pretty => format!(
+            "for (var {} = {}; {}; {}) {{\n\t{}\n}}",
+            variable, start, test, update, block
+          ),
+        };
+      }
+    }
+
+    let iterable = format!(
+      "((i) => typeof i === \"object\" && !Array.isArray(i) ? Object.entries(i) : i)({})",
+      self.transpile_expr(iterable),
+    );
+
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "body": {},
+          "left": {{
+            "declarations": [{{
+              "id": {{
+                "name": "{}",
+                "type": "Identifier"
+              }},
+              "init": null,
+              "type": "VariableDeclarator"
+            }}],
+            "kind": "var",
+            "type": "VariableDeclaration"
+          }},
+          "right": {{
+            "name": "{}",
+            "type": "Identifier"
+          }},
+          "type": "ForOfStatement"
+        }}"#,
+        block,
+        variable,
+        iterable.replace('"', "\\\""),
+      ),
+      TKind::Inline => {
+        format!("for (var {} of {}) {{ {} }}", variable, iterable, block)
+      }
+      TKind::Pretty => {
+        format!("for (var {} of {}) {{\n\t{}\n}}", variable, iterable, block)
+      }
+    }
+  }
+
+  pub fn transpile_identifier(&self, name: &str) -> String {
+    match self.mode() {
+      TKind::Json => format!(
+        r#"{{
+          "name": "{}",
+          "type": "Identifier"
+        }}"#,
+        to_camel_case(name),
+      ),
+      _ => name.to_owned(),
+    }
+  }
+}
+
+// every node is written by its `visit_*`, which leaves it on `output` for the
+// node around it to take, what is left once the program is visited are its
+// statements, one after the other
+impl Visitor for Transpiler {
+  fn visit_ast(&mut self, ast: &Ast) {
+    self.declare_signatures(ast);
+
+    visitor::walk_ast(self, ast)
+  }
+
+  // the value of a block in place of an expression
+  fn visit_block(&mut self, block: &Block) {
+    let node = self.transpile_block(block);
+
+    self.output.push(node);
+  }
+
+  fn visit_closure(&mut self, closure: &Closure) {
+    let node = self.transpile_closure(closure);
+
+    self.output.push(node);
+  }
+
+  fn visit_expr_kind(&mut self, kind: &ExprKind) {
+    let node = match kind {
+      ExprKind::Array(items) => self.transpile_array(items),
+      ExprKind::Assign(target, value) => self.transpile_assign(target, value),
+      ExprKind::Binary(lhs, operator, rhs) => {
+        self.transpile_binary(lhs, *operator, rhs)
+      }
+      ExprKind::Block(block) => return self.visit_block(block),
+      ExprKind::Bool(value) => self.transpile_literal(&value.to_string()),
+      ExprKind::Call(callee, args) => self.transpile_call(callee, args),
+      ExprKind::Char(value) => {
+        self.transpile_literal(&format!("\"{}\"", value))
+      }
+      ExprKind::Closure(closure) => return self.visit_closure(closure),
+      ExprKind::Comment(_) => String::new(),
+      ExprKind::Delete(target) => self.transpile_delete(target),
+      ExprKind::Float(value) => self.transpile_literal(&value.to_string()),
+      ExprKind::Group(expression) => self.transpile_group(expression),
+      ExprKind::Hash(items) => self.transpile_hash(items),
+      ExprKind::Identifier(name) => self.transpile_identifier(name),
+      ExprKind::If(control) => return self.visit_if(control),
+      ExprKind::Index(lhs, rhs) => self.transpile_index(lhs, rhs),
+      ExprKind::Instance(name, fields) => self.transpile_instance(name, fields),
+      ExprKind::Int(value) => self.transpile_literal(&value.to_string()),
+      ExprKind::Loop(looping) => return self.visit_loop(looping),
+      ExprKind::Match(subject, arms) => self.transpile_match(subject, arms),
+      ExprKind::Member(object, optional, property) => {
+        self.transpile_member(object, *optional, property)
+      }
+      ExprKind::NamedArg(_, value) => return self.visit_expr(value),
+      ExprKind::Nil => self.transpile_nil(),
+      ExprKind::Outcome(is_ok, value) => self.transpile_outcome(*is_ok, value),
+      ExprKind::Range(range) => return self.visit_range(range),
+      ExprKind::Spread(value) => self.transpile_spread(value),
+      ExprKind::Str(value) => self.transpile_literal(&format!("\"{}\"", value)),
+      ExprKind::Try(value) => self.transpile_try(value),
+      ExprKind::Unary(operator, rhs) => self.transpile_unary(*operator, rhs),
+    };
+
+    self.output.push(node);
+  }
+
+  fn visit_function(&mut self, function: &Function) {
+    let node = self.transpile_function(function);
+
+    self.output.push(node);
+  }
+
+  // an `if` in place of a value, the statement form is chosen by the
+  // statement around it
+  fn visit_if(&mut self, control: &If) {
+    let node = self.transpile_if(control);
+
+    self.output.push(node);
+  }
+
+  fn visit_impl(&mut self, implementation: &Impl) {
+    let node = self.transpile_impl(implementation);
+
+    self.output.push(node);
+  }
+
+  // a loop in place of a value, see `visit_if`
+  fn visit_loop(&mut self, looping: &Loop) {
+    let node = self.transpile_loop(looping);
+
+    self.output.push(node);
+  }
+
+  fn visit_param(&mut self, param: &Param) {
+    let node = self.transpile_param(param);
+
+    self.output.push(node);
+  }
+
+  fn visit_range(&mut self, range: &Range) {
+    let node = self.transpile_range(range);
+
+    self.output.push(node);
+  }
+
+  fn visit_stmt_kind(&mut self, kind: &StmtKind) {
+    let node = match kind {
+      StmtKind::Break(label, value) => self.transpile_break(label, value),
+      StmtKind::Continue(label) => self.transpile_continue(label),
+      StmtKind::Expr(expr) => match &expr.kind {
+        ExprKind::Comment(_) => String::new(),
+        ExprKind::If(control) => self.transpile_if_statement(control, false),
+        _ => self.transpile_expression(expr),
+      },
+      StmtKind::Function(function) => return self.visit_function(function),
+      StmtKind::Impl(implementation) => return self.visit_impl(implementation),
+      StmtKind::Module(name, block) => self.transpile_module(name, block),
+      StmtKind::Node(expr) => match &expr.kind {
+        ExprKind::Loop(looping) => self.transpile_loop_statement(looping),
+        _ => return self.visit_expr(expr),
+      },
+      StmtKind::Public(kind) => self.transpile_public(kind),
+      StmtKind::Return(value) => self.transpile_return(value),
+      StmtKind::Struct(structure) => return self.visit_struct(structure),
+      StmtKind::Trait(interface) => return self.visit_trait(interface),
+      StmtKind::Use(import) => return self.visit_use(import),
+      StmtKind::Val(val) => return self.visit_val(val),
+    };
+
+    self.output.push(node);
+  }
+
+  fn visit_struct(&mut self, structure: &Struct) {
+    let node = self.transpile_struct(structure);
+
+    self.output.push(node);
+  }
+
+  // a trait only exists for the checker
+  fn visit_trait(&mut self, _interface: &Trait) {
+    let node = match self.mode() {
+      TKind::Json => String::from(r#"{ "type": "EmptyStatement" }"#),
+      _ => String::new(),
+    };
+
+    self.output.push(node);
+  }
+
+  fn visit_use(&mut self, import: &Use) {
+    let node = self.transpile_use(import);
+
+    self.output.push(node);
+  }
+
+  fn visit_val(&mut self, val: &Val) {
+    let node = self.transpile_val(val);
+
+    self.output.push(node);
+  }
+}
+
+// the file of a module, `super` is the directory above
+fn module_file(path: &[String]) -> String {
+  let segments = path
+    .iter()
+    .map(|segment| match segment.as_str() {
+      "super" => "..".into(),
+      _ => segment.to_owned(),
+    })
+    .collect::<Vec<String>>();
+
+  match segments.first().map(|segment| segment.as_str()) {
+    Some("..") => segments.join("/"),
+    _ => format!("./{}", segments.join("/")),
   }
 }
@@ -1,9 +1,15 @@
+use crate::analyzer::environment::signal::Signal;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::transformer::transpiler::Transpiler;
-use crate::value::instruction::IKind;
 use crate::value::{Value, Values};
 use crate::void;
 
+// what a loop does with the value of one of its iterations
+pub enum Flow {
+  Next,
+  Exit(Option<Box<dyn Value>>),
+  Escape,
+}
+
 pub fn eval_expressions(
   interpreter: &mut Interpreter,
   exprs: Vec<Box<dyn Value>>,
@@ -23,19 +29,35 @@ pub fn eval_statements(
   interpreter: &mut Interpreter,
   stmts: Vec<Box<dyn Value>>,
 ) -> ValueResult<Box<dyn Value>> {
-  let mut value = void!().boxed();
+  let mut value: Box<dyn Value> = void!();
+
+  interpreter.step()?;
 
   for stmt in &stmts {
+    interpreter.step()?;
     value = stmt.to_owned().eval(interpreter)?;
 
-    if let IKind::Return = value.ikind() {
-      return Ok(value);
+    if interpreter.is_unwinding() {
+      break;
     }
   }
 
   Ok(value)
 }
 
+// a `break` or a `continue` without label belongs to the innermost loop,
+// any other signal keeps unwinding
+pub fn loop_flow(label: &Option<String>, interpreter: &Interpreter) -> Flow {
+  match interpreter.signal() {
+    None => Flow::Next,
+    Some(signal) if !signal.is_aimed_at(label) => Flow::Escape,
+    Some(_) => match interpreter.take_signal() {
+      Some(Signal::Break(_, value)) => Flow::Exit(value),
+      _ => Flow::Next,
+    },
+  }
+}
+
 pub fn strip_exprs(expr: &Values, delimiter: &str) -> String {
   expr
     .iter()
@@ -43,15 +65,3 @@ pub fn strip_exprs(expr: &Values, delimiter: &str) -> String {
     .collect::<Vec<String>>()
     .join(delimiter)
 }
-
-pub fn transpile_exprs(
-  transpiler: &mut Transpiler,
-  data: &Values,
-  delimiter: &str,
-) -> String {
-  data
-    .iter()
-    .map(|d| d.to_owned().transpile(transpiler))
-    .collect::<Vec<String>>()
-    .join(delimiter)
-}
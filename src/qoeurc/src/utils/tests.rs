@@ -2,7 +2,11 @@ extern crate qutonium;
 
 use qutonium::prelude::*;
 
+use crate::testing::suite;
+
 #[test]
 fn from_test() {
-  suite!("pub mod utils", { "test empty input" || { Ok(()) } });
+  suite!("pub mod utils", {
+    "test empty input" || { must!(true; be.truthy) }
+  });
 }
@@ -0,0 +1,181 @@
+use crate::analyzer::environment::scope::TScope;
+use crate::compiler::lambda::Lambda;
+use crate::value::instruction::closure::Closure;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::IKind;
+use crate::value::Value;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::rc::Rc;
+
+// arrays, hashes and struct instances are compared by their content,
+// functions and closures are only equal to themselves
+impl PartialEq for dyn Value {
+  fn eq(&self, rhs: &dyn Value) -> bool {
+    if let Some(identity) = identity(self, rhs) {
+      return identity;
+    }
+
+    match (self.ikind(), rhs.ikind()) {
+      (IKind::Bool(lhs), IKind::Bool(rhs)) => lhs == rhs,
+      (IKind::Char(lhs), IKind::Char(rhs)) => lhs == rhs,
+      (IKind::Float(lhs), IKind::Float(rhs)) => lhs == rhs,
+      (IKind::Int(lhs), IKind::Int(rhs)) => lhs == rhs,
+      (IKind::Str(lhs), IKind::Str(rhs)) => lhs == rhs,
+      (IKind::Nil, IKind::Nil) => true,
+      (IKind::Array(lhs), IKind::Array(rhs)) => lhs == rhs,
+      (IKind::Hash(lhs), IKind::Hash(rhs)) => lhs == rhs,
+      (IKind::Outcome(lhs_ok, lhs), IKind::Outcome(rhs_ok, rhs)) => {
+        lhs_ok == rhs_ok && lhs == rhs
+      }
+      (IKind::Instance(lhs_name, lhs), IKind::Instance(rhs_name, rhs)) => {
+        lhs_name == rhs_name
+          && lhs.len() == rhs.len()
+          && lhs.iter().all(|(name, value)| {
+            rhs.iter().any(|(field, rhs)| field == name && value == rhs)
+          })
+      }
+      // nodes that are not runtime values are equal when they read the same
+      (lhs_kind, rhs_kind) => {
+        mem::discriminant(&lhs_kind) == mem::discriminant(&rhs_kind)
+          && self.text() == rhs.text()
+      }
+    }
+  }
+}
+
+impl Eq for dyn Value {}
+
+// only values of the same kind have an order, arrays are compared
+// lexicographically
+impl PartialOrd for dyn Value {
+  fn partial_cmp(&self, rhs: &dyn Value) -> Option<Ordering> {
+    match (self.ikind(), rhs.ikind()) {
+      (IKind::Bool(lhs), IKind::Bool(rhs)) => lhs.partial_cmp(&rhs),
+      (IKind::Char(lhs), IKind::Char(rhs)) => lhs.partial_cmp(&rhs),
+      (IKind::Float(lhs), IKind::Float(rhs)) => lhs.partial_cmp(&rhs),
+      (IKind::Int(lhs), IKind::Int(rhs)) => lhs.partial_cmp(&rhs),
+      (IKind::Str(lhs), IKind::Str(rhs)) => lhs.partial_cmp(&rhs),
+      (IKind::Array(lhs), IKind::Array(rhs)) => lhs.partial_cmp(&rhs),
+      _ => None,
+    }
+  }
+}
+
+// consistent with `eq`: the entries of hashes and the fields of instances are
+// hashed without regard to their order
+impl Hash for Box<dyn Value> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    if let Some(function) = self.downcast_ref::<Function>() {
+      address(&function.scope).hash(state);
+      return function.name().hash(state);
+    }
+
+    if let Some(lambda) = self.downcast_ref::<Lambda>() {
+      return lambda.address().hash(state);
+    }
+
+    let kind = self.ikind();
+
+    mem::discriminant(&kind).hash(state);
+
+    match kind {
+      IKind::Bool(value) => value.hash(state),
+      IKind::Char(value) => value.hash(state),
+      // `0.0` and `-0.0` are equal so they must hash the same
+      IKind::Float(0.0) => 0.0f64.to_bits().hash(state),
+      IKind::Float(value) => value.to_bits().hash(state),
+      IKind::Int(value) => value.hash(state),
+      IKind::Str(value) => value.hash(state),
+      IKind::Nil => {}
+      IKind::Array(items) => items.hash(state),
+      IKind::Hash(entries) => {
+        entries.len().hash(state);
+        entries
+          .iter()
+          .map(|(key, value)| unordered((key, value)))
+          .fold(0u64, u64::wrapping_add)
+          .hash(state)
+      }
+      IKind::Outcome(is_ok, value) => {
+        is_ok.hash(state);
+        value.hash(state);
+      }
+      IKind::Instance(name, fields) => {
+        name.hash(state);
+        fields.len().hash(state);
+        fields
+          .iter()
+          .map(unordered)
+          .fold(0u64, u64::wrapping_add)
+          .hash(state)
+      }
+      _ => {
+        if let Some(closure) = self.downcast_ref::<Closure>() {
+          match &closure.identity {
+            Some(identity) => Rc::as_ptr(identity).hash(state),
+            None => address(&closure.scope).hash(state),
+          }
+        }
+
+        self.text().hash(state)
+      }
+    }
+  }
+}
+
+// functions are the same when declared by the same name in the same scope,
+// closures and compiled functions when they come from the same run of their
+// code
+fn identity(lhs: &dyn Value, rhs: &dyn Value) -> Option<bool> {
+  if let (Some(lhs), Some(rhs)) = (
+    lhs.downcast_ref::<Function>(),
+    rhs.downcast_ref::<Function>(),
+  ) {
+    return Some(
+      same_scope(&lhs.scope, &rhs.scope) && lhs.name() == rhs.name(),
+    );
+  }
+
+  if let (Some(lhs), Some(rhs)) =
+    (lhs.downcast_ref::<Closure>(), rhs.downcast_ref::<Closure>())
+  {
+    return match (&lhs.identity, &rhs.identity) {
+      (Some(lhs), Some(rhs)) => Some(Rc::ptr_eq(lhs, rhs)),
+      _ => Some(same_scope(&lhs.scope, &rhs.scope) && lhs.text() == rhs.text()),
+    };
+  }
+
+  if let (Some(lhs), Some(rhs)) =
+    (lhs.downcast_ref::<Lambda>(), rhs.downcast_ref::<Lambda>())
+  {
+    return Some(lhs.is_same(rhs));
+  }
+
+  None
+}
+
+fn address(scope: &Option<TScope>) -> usize {
+  match scope {
+    Some(scope) => Rc::as_ptr(scope) as usize,
+    None => 0,
+  }
+}
+
+fn same_scope(lhs: &Option<TScope>, rhs: &Option<TScope>) -> bool {
+  match (lhs, rhs) {
+    (Some(lhs), Some(rhs)) => Rc::ptr_eq(lhs, rhs),
+    (None, None) => true,
+    _ => false,
+  }
+}
+
+fn unordered<T: Hash>(item: T) -> u64 {
+  let mut hasher = DefaultHasher::new();
+
+  item.hash(&mut hasher);
+  hasher.finish()
+}
@@ -0,0 +1,195 @@
+use crate::analyzer::interpreter::ValueResult;
+use crate::value::instruction::array::Array;
+use crate::value::instruction::hash::{Entries, Hash, HashKey};
+use crate::value::instruction::IKind;
+use crate::value::primitive::{
+  bool::Bool, char::Char, float::Float, int::Int, str::Str,
+};
+use crate::value::Value;
+use crate::void;
+
+use std::collections::HashMap;
+
+// a rust value read from a qoeur one, the error says what was expected
+pub trait FromValue: Sized {
+  fn from_value(value: &dyn Value) -> ValueResult<Self>;
+}
+
+pub trait IntoValue {
+  fn into_value(self) -> Box<dyn Value>;
+}
+
+fn mismatch<T>(expected: &str, found: &dyn Value) -> ValueResult<T> {
+  Err(format!("expected `{}`, found `{}`", expected, found.text()))
+}
+
+impl FromValue for Box<dyn Value> {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    Ok(value.boxed())
+  }
+}
+
+impl FromValue for bool {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Bool(value) => Ok(value),
+      _ => mismatch("bool", value),
+    }
+  }
+}
+
+impl FromValue for char {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Char(value) => Ok(value),
+      _ => mismatch("char", value),
+    }
+  }
+}
+
+impl FromValue for f64 {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Float(value) => Ok(value),
+      _ => mismatch("float", value),
+    }
+  }
+}
+
+impl FromValue for i64 {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Int(value) => Ok(value),
+      _ => mismatch("int", value),
+    }
+  }
+}
+
+impl FromValue for String {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Str(value) => Ok(value),
+      _ => mismatch("str", value),
+    }
+  }
+}
+
+// `nil` is `None`
+impl<T: FromValue> FromValue for Option<T> {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Nil => Ok(None),
+      _ => T::from_value(value).map(Some),
+    }
+  }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    match value.ikind() {
+      IKind::Array(items) => items
+        .iter()
+        .map(|item| T::from_value(item.as_ref()))
+        .collect(),
+      _ => mismatch("[]", value),
+    }
+  }
+}
+
+impl<T: FromValue> FromValue for HashMap<String, T> {
+  fn from_value(value: &dyn Value) -> ValueResult<Self> {
+    let entries = match value.ikind() {
+      IKind::Hash(entries) => entries,
+      _ => return mismatch("hash", value),
+    };
+
+    entries
+      .iter()
+      .map(|(key, value)| match key {
+        HashKey::Str(key) => {
+          Ok((key.to_owned(), T::from_value(value.as_ref())?))
+        }
+        _ => Err(format!("expected `str` keys, found `{}`", key)),
+      })
+      .collect()
+  }
+}
+
+impl IntoValue for Box<dyn Value> {
+  fn into_value(self) -> Box<dyn Value> {
+    self
+  }
+}
+
+impl IntoValue for () {
+  fn into_value(self) -> Box<dyn Value> {
+    void!()
+  }
+}
+
+impl IntoValue for bool {
+  fn into_value(self) -> Box<dyn Value> {
+    Bool::new(self).boxed()
+  }
+}
+
+impl IntoValue for char {
+  fn into_value(self) -> Box<dyn Value> {
+    Char::new(self).boxed()
+  }
+}
+
+impl IntoValue for f64 {
+  fn into_value(self) -> Box<dyn Value> {
+    Float::new(self).boxed()
+  }
+}
+
+impl IntoValue for i64 {
+  fn into_value(self) -> Box<dyn Value> {
+    Int::new(self).boxed()
+  }
+}
+
+impl IntoValue for &str {
+  fn into_value(self) -> Box<dyn Value> {
+    Str::new(self).boxed()
+  }
+}
+
+impl IntoValue for String {
+  fn into_value(self) -> Box<dyn Value> {
+    Str::new(&self).boxed()
+  }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+  fn into_value(self) -> Box<dyn Value> {
+    match self {
+      Some(value) => value.into_value(),
+      None => void!(),
+    }
+  }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+  fn into_value(self) -> Box<dyn Value> {
+    Array::new(self.into_iter().map(IntoValue::into_value).collect()).boxed()
+  }
+}
+
+// the entries are sorted by key, a `HashMap` has no order of its own
+impl<T: IntoValue> IntoValue for HashMap<String, T> {
+  fn into_value(self) -> Box<dyn Value> {
+    let mut items = self.into_iter().collect::<Vec<(String, T)>>();
+
+    items.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    let entries = items
+      .into_iter()
+      .map(|(key, value)| (HashKey::Str(key), value.into_value()))
+      .collect::<Entries>();
+
+    Hash::new(entries).boxed()
+  }
+}
@@ -1,8 +1,6 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::utils::iters::{eval_expressions, strip_exprs, transpile_exprs};
+use crate::utils::iters::strip_exprs;
+use crate::value::instruction::spread::Spread;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -30,9 +28,29 @@ impl Value for Array {
     &mut self,
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    let data = eval_expressions(interpreter, self.data.to_vec())?;
+    let mut data = vec![];
+
+    for item in &self.data {
+      let spread = match item.as_ref().downcast_ref::<Spread>() {
+        Some(spread) => spread,
+        None => {
+          data.push(item.to_owned().eval(interpreter)?);
+          continue;
+        }
+      };
+
+      match spread.value.to_owned().eval(interpreter)?.ikind() {
+        IKind::Array(items) => data.extend(items),
+        _ => {
+          return Err(format!("cannot spread `{}` in an array", spread.value))
+        }
+      }
+    }
 
-    Ok(self.add_data(data).boxed())
+    let value = Array::new(data).boxed();
+
+    interpreter.allocate(value.as_ref())?;
+    Ok(value)
   }
 
   fn vkind(&self) -> VKind {
@@ -43,41 +61,9 @@ impl Value for Array {
     IKind::Array(self.data.to_vec())
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let data = parser.parse_until(TokenKind::GroupEnd(Bracket))?;
-
-    Ok(self.add_data(data).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("[{}]", strip_exprs(&self.data, ", "))
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let data =
-      &eval_expressions(&mut transpiler.interpreter, self.data.to_vec())
-        .unwrap();
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "elements": [{}],
-          "type": "ArrayExpression"
-        }}"#,
-        transpile_exprs(transpiler, data, ", "),
-      ),
-      TKind::Inline => {
-        format!("[{}]", transpile_exprs(transpiler, data, ", "),)
-      }
-      TKind::Pretty => {
-        format!("[\n{}\n]", transpile_exprs(transpiler, data, "\n"),)
-      }
-    }
-  }
 }
 
 impl Array {
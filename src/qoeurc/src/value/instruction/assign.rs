@@ -0,0 +1,113 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::index::Index;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Assign {
+  pub target: Box<dyn Value>,
+  pub value: Box<dyn Value>,
+}
+
+impl Default for Assign {
+  fn default() -> Self {
+    Assign::new(void!(), void!())
+  }
+}
+
+impl fmt::Display for Assign {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl From<Box<dyn Value>> for Assign {
+  fn from(target: Box<dyn Value>) -> Assign {
+    Assign::new(target, void!())
+  }
+}
+
+impl Value for Assign {
+  // arrays and hashes are values, the updated copy is stored back in the
+  // variable the target starts from
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    if !self.target.as_ref().is::<Index>() {
+      return Err(format!("cannot assign to `{}`", self.target));
+    }
+
+    let value = self.value.eval(interpreter)?;
+
+    if interpreter.is_unwinding() {
+      return Ok(void!());
+    }
+
+    Assign::store(interpreter, self.target.as_ref(), value.to_owned())?;
+
+    Ok(value)
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Assign
+  }
+
+  fn text(&self) -> String {
+    format!("{} = {}", self.target, self.value)
+  }
+}
+
+impl Assign {
+  pub fn new(target: Box<dyn Value>, value: Box<dyn Value>) -> Self {
+    Assign { target, value }
+  }
+
+  pub fn add_target(&mut self, target: Box<dyn Value>) -> &mut Self {
+    self.target = target;
+    self
+  }
+
+  pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
+    self.value = value;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  // `a[i][j] = v` stores `a[i]` with `j` set to `v` in `a[i]`, and so on up
+  // to the variable `a`
+  pub fn store(
+    interpreter: &mut Interpreter,
+    target: &dyn Value,
+    value: Box<dyn Value>,
+  ) -> ValueResult<()> {
+    if let Some(identifier) = target.downcast_ref::<Identifier>() {
+      return interpreter.scope_mut().assign(&identifier.name, value);
+    }
+
+    match target.downcast_ref::<Index>() {
+      Some(index) => {
+        let data = index.lhs.to_owned().eval(interpreter)?;
+        let key = index.rhs.to_owned().eval(interpreter)?;
+
+        Assign::store(
+          interpreter,
+          index.lhs.as_ref(),
+          Index::set(data.as_ref(), key.as_ref(), value)?,
+        )
+      }
+      None => Err(format!("cannot assign to `{}`", target)),
+    }
+  }
+}
@@ -1,9 +1,5 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::Transpiler;
 use crate::utils::iters::strip_exprs;
-use crate::value::instruction::identifier::Identifier;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -44,31 +40,11 @@ impl Value for Attribute {
     IKind::Attribute
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.expect_first(TokenKind::Identifier)?;
-
-    let label = Identifier::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::Symbol(Colon))?;
-
-    let args = parser.parse_until(TokenKind::Symbol(Dot))?;
-
-    Ok(self.add_label(label).add_args(args).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     let args = &self.args.as_ref().unwrap();
 
     format!("|> {} : {}.", self.label, strip_exprs(args, ", "))
   }
-
-  fn transpile(&mut self, _: &mut Transpiler) -> String {
-    self.text()
-  }
 }
 
 impl Attribute {
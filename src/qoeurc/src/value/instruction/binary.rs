@@ -1,11 +1,8 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
 use crate::tokenizer::kind::*;
 use crate::tokenizer::token::Token;
-use crate::transformer::transpiler::{TKind, Transpiler};
 use crate::value::instruction::call::Call;
-use crate::value::instruction::expression::Expression;
-use crate::value::instruction::index::Index;
+use crate::value::instruction::instance::Instance;
 use crate::value::instruction::IKind;
 use crate::value::primitive::{bool::Bool, float::Float, int::Int, str::Str};
 use crate::value::{VKind, Value};
@@ -44,24 +41,26 @@ impl Value for Binary {
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
     let binary_lhs = self.lhs.eval(interpreter)?;
+
+    if interpreter.is_unwinding() {
+      return Ok(void!());
+    }
+
+    // `a ?? b` only evaluates `b` when `a` is `nil`
+    if self.operator.kind == TokenKind::Operator(QuestionQuestion) {
+      return match binary_lhs.ikind() {
+        IKind::Nil => self.rhs.eval(interpreter),
+        _ => Ok(binary_lhs),
+      };
+    }
+
     let binary_rhs = self.rhs.eval(interpreter)?;
-    let operator = &self.operator.to_owned();
 
-    match (binary_lhs.ikind(), binary_rhs.ikind()) {
-      (IKind::Bool(lhs), IKind::Bool(rhs)) => {
-        Binary::eval_binary_bool_expression(&lhs, operator, &rhs)
-      }
-      (IKind::Int(lhs), IKind::Int(rhs)) => {
-        Binary::eval_binary_int_expression(&lhs, operator, &rhs)
-      }
-      (IKind::Float(lhs), IKind::Float(rhs)) => {
-        Binary::eval_binary_float_expression(&lhs, operator, &rhs)
-      }
-      (IKind::Str(lhs), IKind::Str(rhs)) => {
-        Binary::eval_binary_str_expression(&lhs, operator, &rhs)
-      }
-      (_, _) => Err(format!("error binary expression")),
+    if interpreter.is_unwinding() {
+      return Ok(void!());
     }
+
+    Binary::operate(interpreter, binary_lhs, &self.operator, binary_rhs)
   }
 
   fn vkind(&self) -> VKind {
@@ -72,54 +71,9 @@ impl Value for Binary {
     IKind::Binary
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let mut precedence = parser.current_precedence();
-    let operator = parser.token.to_owned();
-
-    parser.next_token();
-
-    let rhs =
-      Expression::parse_expression_by_precedence(parser, &mut precedence)?;
-
-    Ok(self.add_operator(operator).add_rhs(rhs).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("({} {} {})", self.lhs, self.operator.text(), self.rhs)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let lhs = self
-      .lhs
-      // .eval(&mut transpiler.interpreter)
-      // .unwrap()
-      .transpile(transpiler);
-
-    let operator = self.operator.text();
-
-    let rhs = self
-      .rhs
-      // .eval(&mut transpiler.interpreter)
-      // .unwrap()
-      .transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "left": {},
-          "operator": "{}",
-          "right": {},
-          "type": "BinaryExpression"
-        }}"#,
-        lhs, operator, rhs,
-      ),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Binary {
@@ -150,14 +104,49 @@ impl Binary {
     Box::new(self.to_owned())
   }
 
-  pub fn to_binary(
-    parser: &mut Parser,
-    lhs: Box<dyn Value>,
+  // `operator` applied to evaluated operands, struct values dispatch to the
+  // implementation of its trait
+  pub fn operate(
+    interpreter: &mut Interpreter,
+    binary_lhs: Box<dyn Value>,
+    operator: &Token,
+    binary_rhs: Box<dyn Value>,
   ) -> ValueResult<Box<dyn Value>> {
-    match parser.token.kind {
-      TokenKind::GroupStart(Bracket) => Index::from(lhs).parse(parser),
-      TokenKind::GroupStart(Parenthesis) => Call::from(lhs).parse(parser),
-      _ => Binary::from(lhs).parse(parser),
+    if let Some(instance) = binary_lhs.as_ref().downcast_ref::<Instance>() {
+      return Binary::eval_binary_instance_expression(
+        interpreter,
+        instance,
+        operator,
+        binary_rhs,
+      );
+    }
+
+    match (binary_lhs.ikind(), binary_rhs.ikind()) {
+      (IKind::Bool(lhs), IKind::Bool(rhs)) => {
+        Binary::eval_binary_bool_expression(&lhs, operator, &rhs)
+      }
+      (IKind::Int(lhs), IKind::Int(rhs)) => {
+        Binary::eval_binary_int_expression(&lhs, operator, &rhs)
+      }
+      (IKind::Float(lhs), IKind::Float(rhs)) => {
+        Binary::eval_binary_float_expression(&lhs, operator, &rhs)
+      }
+      (IKind::Str(lhs), IKind::Str(rhs))
+        if operator.kind == TokenKind::Operator(Plus) =>
+      {
+        let value = Binary::eval_binary_str_expression(&lhs, operator, &rhs)?;
+
+        interpreter.allocate(value.as_ref())?;
+        Ok(value)
+      }
+      (lhs, rhs) if lhs == IKind::Nil || rhs == IKind::Nil => {
+        Binary::eval_binary_nil_expression(lhs == rhs, operator)
+      }
+      (_, _) => Binary::eval_binary_value_expression(
+        binary_lhs.as_ref(),
+        operator,
+        binary_rhs.as_ref(),
+      ),
     }
   }
 
@@ -176,16 +165,78 @@ impl Binary {
     }
   }
 
+  // operators on struct values are dispatched to their trait implementation
+  fn eval_binary_instance_expression(
+    interpreter: &mut Interpreter,
+    lhs: &Instance,
+    operator: &Token,
+    rhs: Box<dyn Value>,
+  ) -> ValueResult<Box<dyn Value>> {
+    let (interface, method) = match operator.kind {
+      TokenKind::Operator(Plus) => ("Add", "add"),
+      TokenKind::Operator(Minus) => ("Sub", "sub"),
+      TokenKind::Operator(Star) => ("Mul", "mul"),
+      TokenKind::Operator(Slash) => ("Div", "div"),
+      TokenKind::Operator(Equal) | TokenKind::Operator(NotEqual) => {
+        ("Eq", "eq")
+      }
+      _ => {
+        return Err(format!(
+          "cannot use `{}` on `{}`",
+          operator.text(),
+          lhs.name()
+        ))
+      }
+    };
+
+    let function = match lhs.implements(interface) {
+      true => lhs.get_method(method).unwrap(),
+      // without an `Eq` implementation instances compare field by field
+      false if interface == "Eq" => {
+        return Binary::eval_binary_value_expression(
+          lhs,
+          operator,
+          rhs.as_ref(),
+        )
+      }
+      false => {
+        return Err(format!(
+          "cannot use `{}` on `{}`, the trait `{}` is not implemented",
+          operator.text(),
+          lhs.name(),
+          interface,
+        ))
+      }
+    };
+
+    let args = vec![lhs.boxed(), rhs];
+    let value = Call::call_function(interpreter, function.as_ref(), args)?;
+
+    match (operator.kind, value.ikind()) {
+      (TokenKind::Operator(NotEqual), IKind::Bool(value)) => {
+        Ok(Bool::new(!value).boxed())
+      }
+      _ => Ok(value),
+    }
+  }
+
   fn eval_binary_int_expression(
     lhs: &i64,
     operator: &Token,
     rhs: &i64,
   ) -> ValueResult<Box<dyn Value>> {
+    // an `int` that does not fit is an error rather than a crash
+    let checked = |value: Option<i64>| match value {
+      Some(value) => Ok(Int::new(value).boxed()),
+      None if *rhs == 0 => Err(format!("cannot divide `{}` by zero", lhs)),
+      None => Err(format!("`{} {} {}` overflows", lhs, operator, rhs)),
+    };
+
     match operator.kind {
-      TokenKind::Operator(Plus) => Ok(Int::new(lhs + rhs).boxed()),
-      TokenKind::Operator(Minus) => Ok(Int::new(lhs - rhs).boxed()),
-      TokenKind::Operator(Star) => Ok(Int::new(lhs * rhs).boxed()),
-      TokenKind::Operator(Slash) => Ok(Int::new(lhs / rhs).boxed()),
+      TokenKind::Operator(Plus) => checked(lhs.checked_add(*rhs)),
+      TokenKind::Operator(Minus) => checked(lhs.checked_sub(*rhs)),
+      TokenKind::Operator(Star) => checked(lhs.checked_mul(*rhs)),
+      TokenKind::Operator(Slash) => checked(lhs.checked_div(*rhs)),
       TokenKind::Operator(Equal) => Ok(Bool::new(lhs == rhs).boxed()),
       TokenKind::Operator(NotEqual) => Ok(Bool::new(lhs != rhs).boxed()),
       TokenKind::Operator(LessThan) => Ok(Bool::new(lhs < rhs).boxed()),
@@ -220,6 +271,54 @@ impl Binary {
     }
   }
 
+  // equality is structural for any two values, ordering only exists between
+  // values of the same kind
+  fn eval_binary_value_expression(
+    lhs: &dyn Value,
+    operator: &Token,
+    rhs: &dyn Value,
+  ) -> ValueResult<Box<dyn Value>> {
+    let ordering = || match lhs.partial_cmp(rhs) {
+      Some(ordering) => Ok(ordering),
+      None => Err(format!("cannot compare `{}` with `{}`", lhs, rhs)),
+    };
+
+    match operator.kind {
+      TokenKind::Operator(Equal) => Ok(Bool::new(lhs == rhs).boxed()),
+      TokenKind::Operator(NotEqual) => Ok(Bool::new(lhs != rhs).boxed()),
+      TokenKind::Operator(LessThan) => {
+        Ok(Bool::new(ordering()?.is_lt()).boxed())
+      }
+      TokenKind::Operator(LessThanOrEqual) => {
+        Ok(Bool::new(ordering()?.is_le()).boxed())
+      }
+      TokenKind::Operator(GreaterThan) => {
+        Ok(Bool::new(ordering()?.is_gt()).boxed())
+      }
+      TokenKind::Operator(GreaterThanOrEqual) => {
+        Ok(Bool::new(ordering()?.is_ge()).boxed())
+      }
+      _ => Err(format!(
+        "cannot use `{}` on `{}` and `{}`",
+        operator.text(),
+        lhs,
+        rhs
+      )),
+    }
+  }
+
+  // `nil` only compares equal to itself
+  fn eval_binary_nil_expression(
+    is_equal: bool,
+    operator: &Token,
+  ) -> ValueResult<Box<dyn Value>> {
+    match operator.kind {
+      TokenKind::Operator(Equal) => Ok(Bool::new(is_equal).boxed()),
+      TokenKind::Operator(NotEqual) => Ok(Bool::new(!is_equal).boxed()),
+      _ => Err(format!("cannot use `{}` on `nil`", operator.text())),
+    }
+  }
+
   fn eval_binary_str_expression(
     lhs: &str,
     operator: &Token,
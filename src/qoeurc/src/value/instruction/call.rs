@@ -1,10 +1,17 @@
 use crate::analyzer::environment::scope::Scope;
+use crate::analyzer::environment::signal::Signal;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::utils::iters::{eval_expressions, strip_exprs, transpile_exprs};
+use crate::compiler::lambda::Lambda;
+use crate::compiler::machine::Machine;
+use crate::utils::iters::{eval_expressions, strip_exprs};
+use crate::value::instruction::array::Array;
+use crate::value::instruction::closure::Closure;
 use crate::value::instruction::function::Function;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::instance::Instance;
+use crate::value::instruction::member::Member;
+use crate::value::instruction::named_arg::NamedArg;
+use crate::value::instruction::native::Native;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -40,42 +47,38 @@ impl Value for Call {
     &mut self,
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    let callee = self.callee.eval(interpreter)?;
-    let function = callee.as_ref().downcast_ref::<Function>().unwrap();
-
     let args_expected =
       eval_expressions(interpreter, self.args.as_ref().unwrap().to_vec())?;
 
-    let Function {
-      args,
-      block,
-      kind: _,
-      name: _,
-      scope,
-    } = function;
-
-    if args.as_ref().unwrap().len() != args_expected.len() {
-      return Err(format!("error parameter count"));
+    if interpreter.is_unwinding() {
+      return Ok(void!());
     }
 
-    let scope = Scope::extend_scope(
-      scope.as_ref().unwrap().to_owned(),
-      args.as_ref().unwrap().to_vec(),
-      args_expected,
-    );
+    if let Some(member) = self.callee.as_ref().downcast_ref::<Member>() {
+      let object = member.object.to_owned().eval(interpreter)?;
+
+      if member.optional && object.ikind() == IKind::Nil {
+        return Ok(void!());
+      }
+
+      if let Some(instance) = object.as_ref().downcast_ref::<Instance>() {
+        let method = instance.get_method(&member.name()).ok_or(format!(
+          "no method named `{}` found for `{}`",
+          member.name(),
+          instance.name(),
+        ))?;
 
-    let mut interpreter_with_outer = Interpreter::new_with_outer(scope);
-    let mut value = void!().boxed();
+        let mut args = vec![object.to_owned()];
 
-    for stmt in block {
-      value = stmt.to_owned().eval(&mut interpreter_with_outer)?;
+        args.extend(args_expected);
 
-      if let IKind::Return = value.ikind() {
-        return Ok(value);
+        return Call::call_function(interpreter, method.as_ref(), args);
       }
     }
 
-    Ok(value)
+    let callee = self.callee.eval(interpreter)?;
+
+    Call::call_function(interpreter, callee.as_ref(), args_expected)
   }
 
   fn ikind(&self) -> IKind {
@@ -86,39 +89,12 @@ impl Value for Call {
     VKind::Expression
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let args = parser.parse_until(TokenKind::GroupEnd(Parenthesis))?;
-
-    Ok(self.add_args(args).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     let args = strip_exprs(&self.args.as_ref().unwrap(), ", ");
     let callee = self.callee.text();
 
     format!("{}({})", callee, args)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let args = transpile_exprs(transpiler, &self.args.as_ref().unwrap(), ", ");
-    let callee = self.callee.transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          arguments: [{}],
-          callee: {},
-          "type": "CallExpression"
-        }}"#,
-        args, callee,
-      ),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Call {
@@ -142,4 +118,99 @@ impl Call {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  // the body runs in an interpreter of its own that shares the host of the
+  // caller
+  pub fn call_function(
+    interpreter: &mut Interpreter,
+    callee: &dyn Value,
+    args_expected: Vec<Box<dyn Value>>,
+  ) -> ValueResult<Box<dyn Value>> {
+    if let Some(native) = callee.downcast_ref::<Native>() {
+      return native.call(interpreter, args_expected);
+    }
+
+    if let Some(lambda) = callee.downcast_ref::<Lambda>() {
+      return Machine::call(interpreter, lambda, args_expected);
+    }
+
+    let (name, params, block, scope) =
+      if let Some(function) = callee.downcast_ref::<Function>() {
+        (
+          function.name(),
+          &function.args,
+          &function.block,
+          &function.scope,
+        )
+      } else if let Some(closure) = callee.downcast_ref::<Closure>() {
+        (
+          "closure".into(),
+          &closure.args,
+          &closure.block,
+          &closure.scope,
+        )
+      } else {
+        return Err(format!("`{}` is not callable", callee.text()));
+      };
+
+    let params = params.as_ref().unwrap();
+    let assigned =
+      FunctionArg::assign(&name, params, Call::label_args(args_expected))?;
+
+    let mut interpreter_with_outer = Interpreter::new_with_outer(
+      Scope::new_with_outer(scope.as_ref().unwrap().to_owned()),
+    );
+
+    interpreter_with_outer
+      .add_host(interpreter.host.to_owned())
+      .add_meter(interpreter.meter.to_owned());
+
+    // defaults are evaluated at each call and see the parameters before them
+    for (param, mut args) in params.iter().zip(assigned) {
+      let param = param.as_ref().downcast_ref::<FunctionArg>().unwrap();
+
+      let value = match (param.variadic, &param.default) {
+        (true, _) => Array::new(args).boxed(),
+        (false, Some(default)) if args.is_empty() => {
+          default.to_owned().eval(&mut interpreter_with_outer)?
+        }
+        (false, _) => args.remove(0),
+      };
+
+      for (name, value) in param.bind(value)? {
+        interpreter_with_outer.scope_mut().bind(&name, value);
+      }
+    }
+
+    interpreter.meter.borrow_mut().enter()?;
+
+    let value: ValueResult<Box<dyn Value>> = match block {
+      Some(block) => block.to_owned().eval(&mut interpreter_with_outer),
+      None => Ok(void!()),
+    };
+
+    interpreter.meter.borrow_mut().leave();
+
+    let value = value?;
+
+    // the call is where a `return` stops unwinding
+    match interpreter_with_outer.take_signal() {
+      None => Ok(value),
+      Some(Signal::Return(value)) => Ok(value),
+      Some(signal) => Err(format!("`{}` outside of a loop", signal.keyword())),
+    }
+  }
+
+  // evaluated arguments with the name they are given, if any
+  pub fn label_args(
+    args: Vec<Box<dyn Value>>,
+  ) -> Vec<(Option<String>, Box<dyn Value>)> {
+    args
+      .into_iter()
+      .map(|arg| match arg.as_ref().downcast_ref::<NamedArg>() {
+        Some(named) => (Some(named.name.to_owned()), named.value.to_owned()),
+        None => (None, arg),
+      })
+      .collect()
+  }
 }
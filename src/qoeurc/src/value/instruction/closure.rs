@@ -1,27 +1,32 @@
+use crate::analyzer::environment::scope::TScope;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::utils::iters::transpile_exprs;
+use crate::utils::iters::strip_exprs;
 use crate::value::instruction::block::Block;
-use crate::value::instruction::function_arg::FunctionArg;
-use crate::value::instruction::ty::Ty;
+use crate::value::instruction::ty::{Ty, TyKind};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
-use crate::void;
 
 use std::fmt;
+use std::rc::Rc;
 
 #[derive(Clone, Debug)]
 pub struct Closure {
   pub args: Option<Vec<Box<dyn Value>>>,
   pub block: Option<Box<dyn Value>>,
+  // given when the closure is made, copies of it share it and a closure made
+  // again from the same code does not
+  pub identity: Option<Rc<()>>,
   pub kind: Option<Box<dyn Value>>,
+  pub scope: Option<TScope>,
 }
 
 impl Default for Closure {
   fn default() -> Self {
-    Closure::new(vec![], void!(), void!())
+    Closure::new(
+      vec![],
+      Ty::new(TyKind::Infer).boxed(),
+      Block::default().boxed(),
+    )
   }
 }
 
@@ -32,11 +37,14 @@ impl fmt::Display for Closure {
 }
 
 impl Value for Closure {
+  // the closure keeps the scope it is defined in, not a copy of it
   fn eval(
     &mut self,
-    _interpreter: &mut Interpreter,
+    interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    Ok(self.boxed())
+    self.identity = Some(Rc::new(()));
+
+    Ok(self.add_scope(interpreter.scope.to_owned()).boxed())
   }
 
   fn ikind(&self) -> IKind {
@@ -47,52 +55,15 @@ impl Value for Closure {
     VKind::Expression
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let args = FunctionArg::parse_args(parser)?;
-
-    parser.expect_first(TokenKind::Symbol(Arrow))?;
-    parser.next_token();
-
-    let ty = Ty::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-    let block = Block::default().parse(parser)?;
-
-    Ok(self.add_args(args).add_kind(ty).add_block(block).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
-    match &self.kind {
-      None => format!("|{:?}| {{ {:?} }}", self.args, self.block),
-      Some(k) => format!("|{:?}| -> {:?} {{ {:?} }}", self.args, k, self.block),
-    }
-  }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let args = transpile_exprs(transpiler, &self.args.as_ref().unwrap(), ", ");
-    let block = self
-      .block
-      .as_ref()
-      .unwrap()
-      .to_owned()
-      .transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "body": {},
-          "params": [{}],
-          "type": "ArrowFunctionExpression"
-        }}"#,
-        block, args,
-      ),
-      TKind::Inline => format!("({}) => {{ {} }}", args, block),
-      TKind::Pretty => format!("({}) => {{\n\t{}\n}}", args, block),
+    let args = strip_exprs(self.args.as_ref().unwrap(), ", ");
+    let block = self.block.as_ref().unwrap();
+
+    match self.kind.as_ref().map(|kind| kind.text()) {
+      Some(kind) if kind != "_" => {
+        format!("|{}| -> {} {{ {} }}", args, kind, block)
+      }
+      _ => format!("|{}| {{ {} }}", args, block),
     }
   }
 }
@@ -106,7 +77,9 @@ impl Closure {
     Closure {
       args: Some(args),
       block: Some(block),
+      identity: None,
       kind: Some(kind),
+      scope: None,
     }
   }
 
@@ -125,6 +98,11 @@ impl Closure {
     self
   }
 
+  pub fn add_scope(&mut self, scope: TScope) -> &mut Self {
+    self.scope = Some(scope);
+    self
+  }
+
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
@@ -1,7 +1,5 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
 use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::Transpiler;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -41,31 +39,9 @@ impl Value for Comment {
     IKind::Comment
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    match parser.token.kind {
-      TokenKind::Comment(Line) => {
-        let kind = parser.token.kind;
-        let value = &parser.token.literal;
-
-        Ok(self.add_kind(kind).add_value(value).boxed())
-      }
-      _ => Err(format!("comment error: {}", parser.token.literal)),
-    }
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("# {}", self.value)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match (self.kind, transpiler.mode()) {
-      _ => format!(""),
-    }
-  }
 }
 
 impl Comment {
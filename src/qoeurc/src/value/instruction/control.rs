@@ -1,7 +1,5 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
+
 use crate::value::instruction::block::Block;
 use crate::value::instruction::expression::Expression;
 use crate::value::instruction::IKind;
@@ -39,12 +37,10 @@ impl Value for If {
     if condition.is_truthy() {
       self.consequence.eval(interpreter)
     } else {
-      self
-        .alternative
-        .as_ref()
-        .unwrap()
-        .to_owned()
-        .eval(interpreter)
+      match self.alternative.as_mut() {
+        Some(alternative) => alternative.eval(interpreter),
+        None => Ok(void!()),
+      }
     }
   }
 
@@ -56,40 +52,6 @@ impl Value for If {
     IKind::If
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.next_token();
-
-    let condition = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-    let consequence = Block::default().parse(parser)?;
-
-    let alternative = if parser.first_is(TokenKind::Keyword(Else)) {
-      parser.next_token();
-      parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-      Some(Block::default().parse(parser)?)
-    } else {
-      None
-    };
-
-    Ok(
-      self
-        .add_condition(condition)
-        .add_alternative(alternative)
-        .add_consequence(consequence)
-        .boxed(),
-    )
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     let mut content = format!(
       "if {} {{ {} }}",
@@ -97,48 +59,16 @@ impl Value for If {
       self.consequence.text(),
     );
 
-    if let Some(alt) = &self.alternative {
-      content += format!(" else {}", alt.text()).as_str();
+    match &self.alternative {
+      Some(alt) if alt.as_ref().is::<If>() => {
+        content += format!(" else {}", alt.text()).as_str()
+      }
+      Some(alt) => content += format!(" else {{ {} }}", alt.text()).as_str(),
+      None => (),
     }
 
     content
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let condition = &self.condition.transpile(transpiler);
-    let consequence = &self.consequence.transpile(transpiler);
-
-    let alternative = &self
-      .alternative
-      .as_ref()
-      .unwrap()
-      .to_owned()
-      .transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "alternate": {},
-          "consequent": {},
-          "test": {},
-          "type": "IfStatement"
-        }}"#,
-        alternative, consequence, condition
-      ),
-      TKind::Inline => {
-        let mut content = format!("if ({}) {{ {} }}", condition, consequence);
-
-        if let Some(alt) = &self.alternative {
-          content +=
-            format!(" else {{ {} }}", alt.to_owned().transpile(transpiler),)
-              .as_str();
-        }
-
-        content
-      }
-      _ => self.text(),
-    }
-  }
 }
 
 impl If {
@@ -175,4 +105,29 @@ impl If {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  // whether every branch is a single expression
+  pub fn is_simple(&self) -> bool {
+    let is_simple = |branch: &dyn Value| {
+      if let Some(control) = branch.downcast_ref::<If>() {
+        return control.is_simple();
+      }
+
+      match branch.downcast_ref::<Block>() {
+        Some(block) if block.statements.len() == 1 => {
+          match block.statements[0].downcast_ref::<Expression>() {
+            Some(expression) => match expression.node.downcast_ref::<If>() {
+              Some(control) => control.is_simple(),
+              None => true,
+            },
+            None => false,
+          }
+        }
+        _ => false,
+      }
+    };
+
+    is_simple(self.consequence.as_ref())
+      && self.alternative.as_deref().is_none_or(is_simple)
+  }
 }
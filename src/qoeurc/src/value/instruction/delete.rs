@@ -0,0 +1,78 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::assign::Assign;
+use crate::value::instruction::index::Index;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Delete {
+  pub target: Box<dyn Value>,
+}
+
+impl Default for Delete {
+  fn default() -> Self {
+    Delete::new(void!())
+  }
+}
+
+impl fmt::Display for Delete {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Delete {
+  // the removed value, the rest of the array or hash is stored back
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let index = match self.target.as_ref().downcast_ref::<Index>() {
+      Some(index) => index,
+      None => return Err(format!("cannot delete `{}`", self.target)),
+    };
+
+    let data = index.lhs.to_owned().eval(interpreter)?;
+    let key = index.rhs.to_owned().eval(interpreter)?;
+
+    if interpreter.is_unwinding() {
+      return Ok(void!());
+    }
+
+    let (data, value) = Index::remove(data.as_ref(), key.as_ref())?;
+
+    Assign::store(interpreter, index.lhs.as_ref(), data)?;
+
+    Ok(value)
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Delete
+  }
+
+  fn text(&self) -> String {
+    format!("delete {}", self.target)
+  }
+}
+
+impl Delete {
+  pub fn new(target: Box<dyn Value>) -> Self {
+    Delete { target }
+  }
+
+  pub fn add_target(&mut self, target: Box<dyn Value>) -> &mut Self {
+    self.target = target;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
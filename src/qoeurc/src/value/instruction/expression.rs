@@ -1,9 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::binary::Binary;
-use crate::value::instruction::unary::Unary;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -43,41 +38,9 @@ impl Value for Expression {
     IKind::Expression
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let node = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    if parser.first_is(Symbol(Semicolon)) {
-      parser.next_token();
-    }
-
-    Ok(self.add_node(node).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("{}", self.node.text())
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let node = self.node.transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "expression": {},
-          "type": "ExpressionStatement"
-        }}"#,
-        node,
-      ),
-      _ => node,
-    }
-  }
 }
 
 impl Expression {
@@ -93,21 +56,4 @@ impl Expression {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
-
-  pub fn parse_expression_by_precedence(
-    parser: &mut Parser,
-    precedence: &mut PrecedenceKind,
-  ) -> ParserResult<Box<dyn Value>> {
-    let mut node = Unary::to_unary(parser, parser.token.kind)?;
-
-    while !parser.first_is(Symbol(Semicolon))
-      && parser.should_precedence_has_priority(precedence)
-    {
-      parser.next_token();
-
-      node = Binary::to_binary(parser, node)?;
-    }
-
-    Ok(node)
-  }
 }
@@ -1,13 +1,7 @@
 use crate::analyzer::environment::scope::TScope;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::utils::iters::{strip_exprs, transpile_exprs};
+use crate::utils::iters::strip_exprs;
 use crate::value::instruction::block::Block;
-use crate::value::instruction::function_arg::FunctionArg;
-use crate::value::instruction::identifier::Identifier;
-use crate::value::instruction::ty::{Ty, TyKind};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -18,6 +12,7 @@ use std::fmt;
 pub struct Function {
   pub args: Option<Vec<Box<dyn Value>>>,
   pub block: Option<Box<dyn Value>>,
+  pub generics: Vec<Box<dyn Value>>,
   pub kind: Option<Box<dyn Value>>,
   pub name: Box<dyn Value>,
   pub scope: Option<TScope>,
@@ -61,48 +56,21 @@ impl Value for Function {
     )
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.expect_first(TokenKind::Identifier)?;
-
-    let name = Identifier::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::GroupStart(Parenthesis))?;
-
-    let args = FunctionArg::parse_args(parser)?;
-
-    let mut ty = Ty::default();
-
-    if parser.first_is(TokenKind::Symbol(Arrow)) {
-      parser.next_token();
-      parser.next_token();
-      self.add_kind(ty.parse(parser)?);
-    } else {
-      self.add_kind(ty.add_kind(TyKind::Array).boxed());
-    }
-
-    parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-    let block = Block::default().parse(parser)?;
-
-    Ok(self.add_name(name).add_args(args).add_block(block).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     let args = strip_exprs(&self.args.as_ref().unwrap(), ", ");
-    let name = &self.name;
-    let block = &self
-      .block
-      .as_ref()
-      .unwrap()
-      .downcast_ref::<Block>()
-      .unwrap();
-    let stmts = strip_exprs(&block.statements, " ");
+    let name = match self.generics.is_empty() {
+      true => self.name.text(),
+      false => format!("{}<{}>", self.name, strip_exprs(&self.generics, ", ")),
+    };
     let kind = &self.kind.as_ref();
 
+    let block = match self.block.as_ref() {
+      None => return format!("ƒ {} ({}) -> {};", name, args, kind.unwrap()),
+      Some(block) => block.downcast_ref::<Block>().unwrap(),
+    };
+
+    let stmts = strip_exprs(&block.statements, " ");
+
     match kind {
       None => format!("ƒ {} ({}) {{ {} }}", name, args, stmts),
       Some(k) => {
@@ -110,33 +78,6 @@ impl Value for Function {
       }
     }
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let args = transpile_exprs(transpiler, &self.args.as_ref().unwrap(), ", ");
-    let block = self
-      .block
-      .as_ref()
-      .unwrap()
-      .to_owned()
-      .transpile(transpiler);
-    let name = self.name.transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "body": {},
-          "id": {},
-          "params": [{}],
-          "type": "FunctionDeclaration"
-        }}"#,
-        block, name, args,
-      ),
-      TKind::Inline => format!("function {} ({}) {{ {} }}", name, args, block,),
-      TKind::Pretty => {
-        format!("function {} ({}) {{\n\t{}\n}}", name, args, block,)
-      }
-    }
-  }
 }
 
 impl Function {
@@ -150,6 +91,7 @@ impl Function {
     Function {
       args: Some(args),
       block: Some(block),
+      generics: vec![],
       kind: Some(kind),
       name,
       scope,
@@ -166,6 +108,11 @@ impl Function {
     self
   }
 
+  pub fn add_generics(&mut self, generics: Vec<Box<dyn Value>>) -> &mut Self {
+    self.generics = generics;
+    self
+  }
+
   pub fn add_kind(&mut self, kind: Box<dyn Value>) -> &mut Self {
     self.kind = Some(kind);
     self
@@ -184,4 +131,12 @@ impl Function {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  pub fn generic_names(&self) -> Vec<String> {
+    self.generics.iter().map(|generic| generic.text()).collect()
+  }
+
+  pub fn name(&self) -> String {
+    self.name.text()
+  }
 }
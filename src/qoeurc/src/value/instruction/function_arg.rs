@@ -1,8 +1,5 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::pattern::{Bindings, Pattern};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -13,6 +10,9 @@ use std::fmt;
 pub struct FunctionArg {
   pub name: Box<dyn Value>,
   pub kind: Box<dyn Value>,
+  pub pattern: Option<Pattern>,
+  pub default: Option<Box<dyn Value>>,
+  pub variadic: bool,
 }
 
 impl fmt::Display for FunctionArg {
@@ -43,29 +43,37 @@ impl Value for FunctionArg {
     IKind::FunctionArg
   }
 
-  fn parse(&mut self, _parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    Ok(self.boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
-    format!("{} : {}", self.name, self.kind)
-  }
+    let spread = if self.variadic { ".." } else { "" };
+
+    // the receiver is written without the type it is given
+    if self.name() == "self" {
+      return self.name();
+    }
 
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!("{}", self.name.transpile(transpiler)),
-      _ => format!("{}", self.name),
+    match &self.default {
+      Some(default) => {
+        format!("{}{} : {} = {}", spread, self.name(), self.kind, default)
+      }
+      None => format!("{}{} : {}", spread, self.name(), self.kind),
     }
   }
 }
 
 impl FunctionArg {
   pub fn new(name: Box<dyn Value>, kind: Box<dyn Value>) -> Self {
-    FunctionArg { name, kind }
+    FunctionArg {
+      name,
+      kind,
+      pattern: None,
+      default: None,
+      variadic: false,
+    }
+  }
+
+  pub fn add_default(&mut self, default: Box<dyn Value>) -> &mut Self {
+    self.default = Some(default);
+    self
   }
 
   pub fn add_kind(&mut self, kind: Box<dyn Value>) -> &mut Self {
@@ -78,40 +86,110 @@ impl FunctionArg {
     self
   }
 
-  pub fn boxed(&self) -> Box<dyn Value> {
-    Box::new(self.to_owned())
+  pub fn add_pattern(&mut self, pattern: Pattern) -> &mut Self {
+    self.pattern = Some(pattern);
+    self
   }
 
-  pub fn parse_arg(parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.next_token();
-
-    let name = Identifier::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::Symbol(Colon))?;
-    parser.next_token();
-
-    let kind = Identifier::default().parse(parser)?;
-
-    Ok(FunctionArg::default().add_name(name).add_kind(kind).boxed())
+  pub fn add_variadic(&mut self, variadic: bool) -> &mut Self {
+    self.variadic = variadic;
+    self
   }
 
-  pub fn parse_args(parser: &mut Parser) -> ParserResult<Vec<Box<dyn Value>>> {
-    let mut function_args = vec![];
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
 
-    if parser.first_is(TokenKind::GroupEnd(Parenthesis)) {
-      parser.next_token();
-      return Ok(function_args);
+  pub fn name(&self) -> String {
+    match &self.pattern {
+      Some(pattern) => pattern.text(),
+      None => self.name.text(),
     }
+  }
 
-    function_args.push(FunctionArg::parse_arg(parser)?);
+  // the variables a call binds for this parameter
+  pub fn bind(&self, value: Box<dyn Value>) -> ValueResult<Bindings> {
+    match &self.pattern {
+      Some(pattern) => pattern.matches(value.as_ref()).ok_or(format!(
+        "the argument `{}` does not match the parameter `{}`",
+        value.text(),
+        pattern,
+      )),
+      None => Ok(vec![(self.name(), value)]),
+    }
+  }
 
-    while parser.first_is(TokenKind::Symbol(Comma)) {
-      parser.next_token();
-      function_args.push(FunctionArg::parse_arg(parser)?);
+  // the arguments each parameter receives: positional ones in order, named
+  // ones by name and the remaining positional ones for a variadic parameter,
+  // an empty list leaves the parameter to its default
+  pub fn assign<T>(
+    name: &str,
+    params: &[Box<dyn Value>],
+    args: Vec<(Option<String>, T)>,
+  ) -> ValueResult<Vec<Vec<T>>> {
+    let params = params
+      .iter()
+      .map(|param| param.as_ref().downcast_ref::<FunctionArg>().unwrap())
+      .collect::<Vec<&FunctionArg>>();
+
+    let mut assigned = params.iter().map(|_| vec![]).collect::<Vec<Vec<T>>>();
+    let mut position = 0;
+    let supplied = args.len();
+
+    for (label, arg) in args {
+      let index = match label {
+        Some(label) => {
+          let index = params
+            .iter()
+            .position(|param| param.name() == label)
+            .ok_or(format!(
+              "function `{}` has no parameter named `{}`",
+              name, label,
+            ))?;
+
+          if !assigned[index].is_empty() {
+            return Err(format!(
+              "the argument `{}` of function `{}` is supplied more than once",
+              label, name,
+            ));
+          }
+
+          index
+        }
+        None => {
+          while position < params.len()
+            && !params[position].variadic
+            && !assigned[position].is_empty()
+          {
+            position += 1;
+          }
+
+          if position == params.len() {
+            return Err(format!(
+              "function `{}` takes {} arguments but {} were supplied",
+              name,
+              params.len(),
+              supplied,
+            ));
+          }
+
+          position
+        }
+      };
+
+      assigned[index].push(arg);
     }
 
-    parser.expect_first(TokenKind::GroupEnd(Parenthesis))?;
+    for (param, args) in params.iter().zip(&assigned) {
+      if args.is_empty() && param.default.is_none() && !param.variadic {
+        return Err(format!(
+          "function `{}` is missing the argument `{}`",
+          name,
+          param.name(),
+        ));
+      }
+    }
 
-    Ok(function_args)
+    Ok(assigned)
   }
 }
@@ -1,8 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::Transpiler;
-use crate::value::instruction::expression::Expression;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -29,9 +25,9 @@ impl fmt::Display for Group {
 impl Value for Group {
   fn eval(
     &mut self,
-    _interpreter: &mut Interpreter,
+    interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    Ok(self.boxed())
+    self.expression.eval(interpreter)
   }
 
   fn vkind(&self) -> VKind {
@@ -42,32 +38,9 @@ impl Value for Group {
     IKind::Group
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.next_token();
-
-    let expression = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    parser.expect_first(TokenKind::GroupEnd(Parenthesis))?;
-
-    Ok(self.add_expression(expression).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("({})", self.expression.text())
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      _ => self.text(),
-    }
-  }
 }
 
 impl Group {
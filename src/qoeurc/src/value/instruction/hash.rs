@@ -1,21 +1,27 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::expression::Expression;
-use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::array::Array;
+use crate::value::instruction::spread::Spread;
 use crate::value::instruction::IKind;
 use crate::value::primitive::bool::Bool;
+use crate::value::primitive::char::Char;
 use crate::value::primitive::int::Int;
+use crate::value::primitive::str::Str;
 use crate::value::{VKind, Value};
 
+use std::collections::HashMap;
 use std::fmt;
+use std::iter::FromIterator;
 
-#[derive(Clone, Debug, PartialEq)]
+// arrays of keys are the keys of tuples, floats have no equality to hash on,
+// any other value without a float in it is hashed as a whole
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum HashKey {
   Bool(bool),
+  Char(char),
   Int(i64),
   Str(String),
+  Tuple(Vec<HashKey>),
+  Value(Box<dyn Value>),
 }
 
 impl fmt::Display for HashKey {
@@ -24,176 +30,312 @@ impl fmt::Display for HashKey {
   }
 }
 
-impl From<Box<dyn Value>> for HashKey {
-  fn from(instruction: Box<dyn Value>) -> HashKey {
-    match instruction.ikind() {
-      IKind::Bool(value) => HashKey::Bool(value),
-      IKind::Int(value) => HashKey::Int(value),
-      IKind::Str(value) => HashKey::Str(value),
-      _ => unreachable!(),
+impl HashKey {
+  pub fn new(value: &dyn Value) -> ValueResult<HashKey> {
+    match value.ikind() {
+      IKind::Bool(value) => Ok(HashKey::Bool(value)),
+      IKind::Char(value) => Ok(HashKey::Char(value)),
+      IKind::Int(value) => Ok(HashKey::Int(value)),
+      IKind::Str(value) => Ok(HashKey::Str(value)),
+      IKind::Array(items) => Ok(HashKey::Tuple(
+        items
+          .iter()
+          .map(|item| HashKey::new(item.as_ref()))
+          .collect::<ValueResult<Vec<HashKey>>>()?,
+      )),
+      IKind::Float(_) => Err(format!(
+        "`{}` cannot be used as a hash key, floats are not hashable",
+        value.text(),
+      )),
+      IKind::Hash(entries) => {
+        for (_, value) in entries.iter() {
+          HashKey::new(value.as_ref())?;
+        }
+
+        Ok(HashKey::Value(value.boxed()))
+      }
+      IKind::Instance(_, fields) => {
+        for (_, value) in fields.iter() {
+          HashKey::new(value.as_ref())?;
+        }
+
+        Ok(HashKey::Value(value.boxed()))
+      }
+      IKind::Outcome(_, inner) => {
+        HashKey::new(inner.as_ref())?;
+        Ok(HashKey::Value(value.boxed()))
+      }
+      IKind::Nil | IKind::Closure | IKind::Function(..) => {
+        Ok(HashKey::Value(value.boxed()))
+      }
+      _ => Err(format!("`{}` cannot be used as a hash key", value.text())),
     }
   }
-}
 
-impl HashKey {
   pub fn text(&self) -> String {
     match self {
       HashKey::Bool(value) => format!("{}", value),
+      HashKey::Char(value) => format!("{}", value),
       HashKey::Int(value) => format!("{}", value),
       HashKey::Str(value) => format!("{}", value),
+      HashKey::Tuple(keys) => format!(
+        "[{}]",
+        keys
+          .iter()
+          .map(|key| key.to_value().text())
+          .collect::<Vec<String>>()
+          .join(", "),
+      ),
+      HashKey::Value(value) => value.text(),
     }
   }
 
-  pub fn transpile(&self, transpiler: &mut Transpiler) -> String {
+  pub fn to_value(&self) -> Box<dyn Value> {
     match self {
-      HashKey::Bool(value) => Bool::new(*value).transpile(transpiler),
-      HashKey::Int(value) => Int::new(*value).transpile(transpiler),
-      HashKey::Str(value) => Identifier::new(value).transpile(transpiler),
+      HashKey::Bool(value) => Bool::new(*value).boxed(),
+      HashKey::Char(value) => Char::new(*value).boxed(),
+      HashKey::Int(value) => Int::new(*value).boxed(),
+      HashKey::Str(value) => Str::new(value).boxed(),
+      HashKey::Tuple(keys) => {
+        Array::new(keys.iter().map(HashKey::to_value).collect()).boxed()
+      }
+      HashKey::Value(value) => value.to_owned(),
     }
   }
 }
 
-#[derive(Clone, Debug)]
-pub struct Hash {
-  data: Vec<(HashKey, Box<dyn Value>)>,
+// the entries of a hash in insertion order, `index` holds the position of
+// each key in `entries`
+#[derive(Clone, Debug, Default)]
+pub struct Entries {
+  entries: Vec<(HashKey, Box<dyn Value>)>,
+  index: HashMap<HashKey, usize>,
 }
 
-impl Default for Hash {
-  fn default() -> Self {
-    Hash::new(vec![])
+// two hashes are equal when they hold the same entries, in any order
+impl PartialEq for Entries {
+  fn eq(&self, rhs: &Entries) -> bool {
+    self.len() == rhs.len()
+      && self.iter().all(|(key, value)| {
+        rhs.index.get(key).map(|at| &rhs.entries[*at].1) == Some(value)
+      })
   }
 }
 
-impl Value for Hash {
-  fn eval(
-    &mut self,
-    interpreter: &mut Interpreter,
-  ) -> ValueResult<Box<dyn Value>> {
-    let mut data = vec![];
+impl FromIterator<(HashKey, Box<dyn Value>)> for Entries {
+  fn from_iter<I: IntoIterator<Item = (HashKey, Box<dyn Value>)>>(
+    iter: I,
+  ) -> Self {
+    let mut entries = Entries::new();
+
+    for (key, value) in iter {
+      entries.insert(key, value);
+    }
 
-    for (key, value) in &self.data {
-      let k = HashKey::from(key.to_owned());
-      let v = value.to_owned().eval(interpreter)?;
+    entries
+  }
+}
+
+impl IntoIterator for Entries {
+  type Item = (HashKey, Box<dyn Value>);
+  type IntoIter = std::vec::IntoIter<(HashKey, Box<dyn Value>)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.into_iter()
+  }
+}
+
+impl Entries {
+  pub fn new() -> Self {
+    Entries::default()
+  }
+
+  pub fn contains_key(&self, key: &HashKey) -> bool {
+    self.index.contains_key(key)
+  }
+
+  pub fn get(&self, key: &HashKey) -> Option<&dyn Value> {
+    self
+      .index
+      .get(key)
+      .map(|position| self.entries[*position].1.as_ref())
+  }
 
-      data.push((k, v));
+  // a key that is already there keeps its position and gets the new value
+  pub fn insert(
+    &mut self,
+    key: HashKey,
+    value: Box<dyn Value>,
+  ) -> Option<Box<dyn Value>> {
+    match self.index.get(&key) {
+      Some(position) => {
+        Some(std::mem::replace(&mut self.entries[*position].1, value))
+      }
+      None => {
+        self.index.insert(key.to_owned(), self.entries.len());
+        self.entries.push((key, value));
+        None
+      }
     }
+  }
 
-    Ok(self.add_data(data).boxed())
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
   }
 
-  fn vkind(&self) -> VKind {
-    VKind::Expression
+  pub fn iter(&self) -> impl Iterator<Item = (&HashKey, &Box<dyn Value>)> {
+    self.entries.iter().map(|(key, value)| (key, value))
   }
 
-  fn ikind(&self) -> IKind {
-    IKind::Hash(self.data.to_vec())
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Value>> {
+    self.entries.iter_mut().map(|(_, value)| value)
+  }
+
+  // the entries after the removed one move up to keep their order
+  pub fn remove(&mut self, key: &HashKey) -> Option<Box<dyn Value>> {
+    let position = self.index.remove(key)?;
+    let (_, value) = self.entries.remove(position);
+
+    for (key, _) in &self.entries[position..] {
+      *self.index.get_mut(key).unwrap() -= 1;
+    }
+
+    Some(value)
   }
+}
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    while !parser.first_is(TokenKind::GroupEnd(Brace)) {
-      parser.next_token();
+#[derive(Clone, Debug)]
+pub struct Hash {
+  data: Entries,
+  spreads: Vec<(usize, Box<dyn Value>)>,
+}
 
-      let key = Expression::parse_expression_by_precedence(
-        parser,
-        &mut PrecedenceKind::Lowest,
-      )?;
+impl Default for Hash {
+  fn default() -> Self {
+    Hash::new(Entries::new())
+  }
+}
+
+impl Value for Hash {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let mut data = Entries::new();
 
-      parser.expect_first(TokenKind::Symbol(Colon))?;
-      parser.next_token();
+    // a spread entry for a key that is already there replaces its value
+    for (key, mut value) in self.items() {
+      if let Some(key) = key {
+        let value = value.eval(interpreter)?;
 
-      let value = Expression::parse_expression_by_precedence(
-        parser,
-        &mut PrecedenceKind::Lowest,
-      )?;
+        data.insert(key, value);
+        continue;
+      }
 
-      self.add_item((HashKey::from(key), value));
+      let spread = value.as_ref().downcast_ref::<Spread>().unwrap();
+      let value = spread.value.to_owned().eval(interpreter)?;
 
-      if !parser.first_is(TokenKind::GroupEnd(Brace)) {
-        parser.expect_first(TokenKind::Symbol(Comma))?;
+      match value.ikind() {
+        IKind::Hash(entries) => {
+          for (key, value) in entries {
+            data.insert(key, value);
+          }
+        }
+        _ => return Err(format!("cannot spread `{}` in a hash", value.text())),
       }
     }
 
-    parser.expect_first(TokenKind::GroupEnd(Brace))?;
+    let value = Hash::new(data).boxed();
 
-    Ok(self.boxed())
+    interpreter.allocate(value.as_ref())?;
+    Ok(value)
   }
 
-  fn print(&self) {
-    println!("{}", self.text());
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Hash(self.data.to_owned())
   }
 
   fn text(&self) -> String {
     let output = self
-      .data
+      .items()
       .iter()
-      .map(|(k, v)| format!("{}: {}", k.text(), v.text()))
+      .map(|(k, v)| match k {
+        Some(k) => format!("{}: {}", k.text(), v.text()),
+        None => v.text(),
+      })
       .collect::<Vec<String>>()
       .join(", ");
 
     format!("{{ {} }}", output)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "properties": [{}],
-          "type": "ObjectExpression"
-        }}"#,
-        self
-          .data
-          .iter()
-          .map(|(key, value)| format!(
-            r#"{{
-              "key": {},
-              "kind": "init",
-
-              "type": "Property",
-              "value": {}
-            }}"#,
-            key.transpile(transpiler),
-            value.to_owned().transpile(transpiler),
-          ))
-          .collect::<Vec<String>>()
-          .join(", "),
-      ),
-      TKind::Pretty => format!(
-        r#"{{ {} }}"#,
-        self
-          .data
-          .iter()
-          .map(|(key, value)| format!(
-            "\n{}: {}\n",
-            key.transpile(transpiler),
-            value.to_owned().transpile(transpiler),
-          ))
-          .collect::<Vec<String>>()
-          .join(", "),
-      ),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Hash {
-  pub fn new(data: Vec<(HashKey, Box<dyn Value>)>) -> Self {
-    Hash { data }
+  pub fn new(data: Entries) -> Self {
+    Hash {
+      data,
+      spreads: vec![],
+    }
   }
 
-  pub fn add_data(
-    &mut self,
-    data: Vec<(HashKey, Box<dyn Value>)>,
-  ) -> &mut Self {
-    self.data = data.to_owned();
+  pub fn add_data(&mut self, data: Entries) -> &mut Self {
+    self.data = data;
     self
   }
 
   pub fn add_item(&mut self, item: (HashKey, Box<dyn Value>)) -> &mut Self {
-    self.data.push(item);
+    self.data.insert(item.0, item.1);
+    self
+  }
+
+  // a spread goes between the entries written before and after it
+  pub fn add_spread(&mut self, spread: Box<dyn Value>) -> &mut Self {
+    self.spreads.push((self.data.len(), spread));
     self
   }
 
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  pub fn entries(&self) -> &Entries {
+    &self.data
+  }
+
+  // the items and spread hashes in the order they are written, a spread has
+  // no key
+  pub fn items(&self) -> Vec<(Option<HashKey>, Box<dyn Value>)> {
+    let mut items = vec![];
+    let mut data = self.data.iter();
+
+    for position in 0..=self.data.len() {
+      for (at, spread) in &self.spreads {
+        if *at == position {
+          items.push((None, spread.to_owned()));
+        }
+      }
+
+      if let Some((key, value)) = data.next() {
+        items.push((Some(key.to_owned()), value.to_owned()));
+      }
+    }
+
+    items
+  }
+
+  // the values of the entries, then the ones that are spread
+  pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Value>> {
+    self
+      .data
+      .values_mut()
+      .chain(self.spreads.iter_mut().map(|(_, spread)| spread))
+  }
 }
@@ -1,17 +1,15 @@
+use crate::analyzer::environment::slots::Slot;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
+use crate::value::instruction::native::Native;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
 use std::fmt;
 
-use inflector::cases::camelcase::to_camel_case;
-
 #[derive(Clone, Debug)]
 pub struct Identifier {
   pub name: String,
+  pub slot: Option<Slot>,
 }
 
 impl Default for Identifier {
@@ -33,10 +31,22 @@ impl Value for Identifier {
   ) -> ValueResult<Box<dyn Value>> {
     let name = &self.name;
 
-    if let Some(function) = interpreter.scope().get_function(name) {
-      Ok(function.boxed())
+    if let Some(slot) = self.slot {
+      if let Some(variable) = interpreter.scope().get_slot(slot, name) {
+        return Ok(variable);
+      }
+    }
+
+    let function = interpreter.scope().get_function(name);
+
+    // a variable hides a function of the standard library with its name
+    if let Some(function) = function.filter(|function| !function.is::<Native>())
+    {
+      Ok(function)
     } else if let Some(variable) = interpreter.scope().get_variable(name) {
-      Ok(variable.boxed())
+      Ok(variable)
+    } else if let Some(native) = interpreter.scope().get_function(name) {
+      Ok(native)
     } else {
       Err(format!(
         "error eval unknown identifier expression: {}",
@@ -53,45 +63,17 @@ impl Value for Identifier {
     IKind::Identifier
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let name = &parser.token.literal;
-
-    match parser.token.kind {
-      TokenKind::Identifier => Ok(self.add_name(name).boxed()),
-      _ => Err(format!(
-        "unexpected error on identifier parse with {}",
-        name
-      )),
-    }
-  }
-
-  fn print(&self) {
-    println!("{}", self.text());
-  }
-
   fn text(&self) -> String {
     format!("{}", self.name)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let name = self.text();
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "name": "{}",
-          "type": "Identifier"
-        }}"#,
-        to_camel_case(&name),
-      ),
-      _ => name,
-    }
-  }
 }
 
 impl Identifier {
   pub fn new(name: &str) -> Self {
-    Identifier { name: name.into() }
+    Identifier {
+      name: name.into(),
+      slot: None,
+    }
   }
 
   pub fn add_name(&mut self, name: &str) -> &mut Self {
@@ -99,6 +81,11 @@ impl Identifier {
     self
   }
 
+  pub fn add_slot(&mut self, slot: Slot) -> &mut Self {
+    self.slot = Some(slot);
+    self
+  }
+
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
@@ -0,0 +1,113 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::utils::iters::strip_exprs;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::structure::Struct;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Impl {
+  pub functions: Vec<Box<dyn Value>>,
+  pub interface: Option<Box<dyn Value>>,
+  pub ty: Box<dyn Value>,
+}
+
+impl Default for Impl {
+  fn default() -> Self {
+    Impl::new(void!(), None, vec![])
+  }
+}
+
+impl fmt::Display for Impl {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Impl {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let tname = self.ty.text();
+    let structure = interpreter
+      .scope()
+      .get_struct(&tname)
+      .ok_or(format!("cannot find type `{}` in this scope", tname))?;
+
+    let structure = structure.as_ref().downcast_ref::<Struct>().unwrap();
+
+    if let Some(interface) = &self.interface {
+      interpreter
+        .scope()
+        .get_trait(&interface.text())
+        .ok_or(format!("cannot find trait `{}` in this scope", interface))?;
+
+      structure.add_trait(&interface.text())?;
+    }
+
+    for function in &self.functions {
+      let mut method = function.downcast_ref::<Function>().unwrap().to_owned();
+
+      method.add_scope(interpreter.scope.to_owned());
+      structure.add_method(method.boxed())?;
+    }
+
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Impl
+  }
+
+  fn text(&self) -> String {
+    let functions = strip_exprs(&self.functions, " ");
+
+    match &self.interface {
+      None => format!("impl {} {{ {} }}", self.ty, functions),
+      Some(interface) => {
+        format!("impl {} for {} {{ {} }}", interface, self.ty, functions)
+      }
+    }
+  }
+}
+
+impl Impl {
+  pub fn new(
+    ty: Box<dyn Value>,
+    interface: Option<Box<dyn Value>>,
+    functions: Vec<Box<dyn Value>>,
+  ) -> Self {
+    Impl {
+      functions,
+      interface,
+      ty,
+    }
+  }
+
+  pub fn add_function(&mut self, function: Box<dyn Value>) -> &mut Self {
+    self.functions.push(function);
+    self
+  }
+
+  pub fn add_interface(&mut self, interface: Box<dyn Value>) -> &mut Self {
+    self.interface = Some(interface);
+    self
+  }
+
+  pub fn add_ty(&mut self, ty: Box<dyn Value>) -> &mut Self {
+    self.ty = ty;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
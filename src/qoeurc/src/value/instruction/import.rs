@@ -0,0 +1,120 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Use {
+  pub glob: bool,
+  pub items: Vec<Box<dyn Value>>,
+  pub path: Vec<Box<dyn Value>>,
+}
+
+impl Default for Use {
+  fn default() -> Self {
+    Use::new(vec![], vec![])
+  }
+}
+
+impl fmt::Display for Use {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Use {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let exports = interpreter.import(&self.segments())?;
+
+    let names = match self.glob {
+      true => exports.names.to_vec(),
+      false => self.item_names(),
+    };
+
+    for name in names {
+      if !exports.names.contains(&name) {
+        let is_defined = exports.scope.borrow().get_function(&name).is_some()
+          || exports.scope.borrow().get_struct(&name).is_some()
+          || exports.scope.borrow().get_trait(&name).is_some()
+          || exports.scope.borrow().get_variable(&name).is_some();
+
+        return match is_defined {
+          true => Err(format!(
+            "`{}` is private in module `{}`",
+            name,
+            self.module_name()
+          )),
+          false => Err(format!(
+            "unresolved import `{}::{}`",
+            self.module_name(),
+            name
+          )),
+        };
+      }
+
+      interpreter
+        .scope_mut()
+        .import(&name, &exports.scope.borrow())?;
+    }
+
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Use
+  }
+
+  fn text(&self) -> String {
+    let items = match (self.glob, self.items.len()) {
+      (true, _) => String::from("*"),
+      (false, 1) => self.items[0].text(),
+      (false, _) => format!("{{{}}}", self.item_names().join(", ")),
+    };
+
+    format!("use {}::{};", self.module_name(), items)
+  }
+}
+
+impl Use {
+  pub fn new(path: Vec<Box<dyn Value>>, items: Vec<Box<dyn Value>>) -> Self {
+    Use {
+      glob: false,
+      items,
+      path,
+    }
+  }
+
+  pub fn add_item(&mut self, item: Box<dyn Value>) -> &mut Self {
+    self.items.push(item);
+    self
+  }
+
+  pub fn add_path(&mut self, segment: Box<dyn Value>) -> &mut Self {
+    self.path.push(segment);
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn item_names(&self) -> Vec<String> {
+    self.items.iter().map(|item| item.text()).collect()
+  }
+
+  pub fn module_name(&self) -> String {
+    self.segments().join("::")
+  }
+
+  pub fn segments(&self) -> Vec<String> {
+    self.path.iter().map(|segment| segment.text()).collect()
+  }
+}
@@ -1,10 +1,11 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::expression::Expression;
-use crate::value::instruction::hash::HashKey;
+use crate::utils::iters::strip_exprs;
+use crate::value::instruction::array::Array;
+use crate::value::instruction::hash::{Hash, HashKey};
+use crate::value::instruction::range::Range;
 use crate::value::instruction::IKind;
+use crate::value::primitive::char::Char;
+use crate::value::primitive::str::Str;
 use crate::value::{VKind, Value};
 use crate::void;
 
@@ -42,25 +43,11 @@ impl Value for Index {
     let lhs = self.lhs.eval(interpreter)?;
     let rhs = self.rhs.eval(interpreter)?;
 
-    match (lhs.ikind(), rhs.ikind()) {
-      (IKind::Array(data), IKind::Int(value)) => {
-        Ok(self.eval_index_array_int(&data, value)?)
-      }
-      (IKind::Hash(data), IKind::Int(value)) => {
-        Ok(self.eval_index_hash_int(&data, value)?)
-      }
-      (IKind::Hash(data), IKind::Bool(value)) => {
-        Ok(self.eval_index_hash_bool(&data, value)?)
-      }
-      (IKind::Hash(data), IKind::Str(value)) => {
-        Ok(self.eval_index_hash_str(&data, &value)?)
-      }
-      (_, _) => Err(format!(
-        "error index expression: {}[{}]",
-        lhs.text(),
-        rhs.text()
-      )),
+    if interpreter.is_unwinding() {
+      return Ok(void!());
     }
+
+    Index::get(lhs.as_ref(), rhs.as_ref())
   }
 
   fn vkind(&self) -> VKind {
@@ -71,42 +58,9 @@ impl Value for Index {
     IKind::Index
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.next_token();
-
-    let rhs = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    parser.expect_first(TokenKind::GroupEnd(Bracket))?;
-
-    Ok(self.add_rhs(rhs).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text());
-  }
-
   fn text(&self) -> String {
     format!("({}[{}])", self.lhs, self.rhs)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "computed": true,
-          "object": {},
-          "property": {},
-          "type": "MemberExpression"
-        }}"#,
-        self.lhs.transpile(transpiler),
-        self.rhs.transpile(transpiler),
-      ),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Index {
@@ -128,49 +82,125 @@ impl Index {
     Box::new(self.to_owned())
   }
 
-  fn eval_index_array_int(
-    &self,
-    data: &Vec<Box<dyn Value>>,
-    value: i64,
-  ) -> ValueResult<Box<dyn Value>> {
-    Ok(data[value as usize].boxed())
+  // `data[key]`, indices out of bounds and missing keys are errors
+  pub fn get(data: &dyn Value, key: &dyn Value) -> ValueResult<Box<dyn Value>> {
+    if let Some(range) = key.downcast_ref::<Range>() {
+      return Index::slice(data, range);
+    }
+
+    match (data.ikind(), key.ikind()) {
+      (IKind::Array(items), IKind::Int(index)) => {
+        Ok(items[Index::position(items.len(), index)?].to_owned())
+      }
+      (IKind::Str(text), IKind::Int(index)) => {
+        let chars = text.chars().collect::<Vec<char>>();
+
+        Ok(Char::new(chars[Index::position(chars.len(), index)?]).boxed())
+      }
+      (IKind::Hash(entries), _) => {
+        let key = HashKey::new(key)?;
+
+        entries
+          .get(&key)
+          .map(|value| value.boxed())
+          .ok_or(format!("no entry found for key `{}`", key))
+      }
+      (_, _) => Err(format!(
+        "cannot index into `{}` with `{}`",
+        data.text(),
+        key.text(),
+      )),
+    }
   }
 
-  fn eval_index_hash_int(
-    &self,
-    data: &Vec<(HashKey, Box<dyn Value>)>,
-    value: i64,
+  // a copy of `data` where `key` holds `value`, hashes get a new entry for
+  // a missing key
+  pub fn set(
+    data: &dyn Value,
+    key: &dyn Value,
+    value: Box<dyn Value>,
   ) -> ValueResult<Box<dyn Value>> {
-    Ok(data[value as usize].1.boxed())
+    match (data.ikind(), key.ikind()) {
+      (IKind::Array(mut items), IKind::Int(index)) => {
+        let position = Index::position(items.len(), index)?;
+
+        items[position] = value;
+
+        Ok(Array::new(items).boxed())
+      }
+      (IKind::Hash(mut entries), _) => {
+        entries.insert(HashKey::new(key)?, value);
+
+        Ok(Hash::new(entries).boxed())
+      }
+      (_, _) => Err(format!(
+        "cannot assign to `{}[{}]`",
+        data.text(),
+        key.text(),
+      )),
+    }
   }
 
-  fn eval_index_hash_bool(
-    &self,
-    data: &Vec<(HashKey, Box<dyn Value>)>,
-    value: bool,
-  ) -> ValueResult<Box<dyn Value>> {
-    let x = data
-      .iter()
-      .enumerate()
-      .find(|(_, data)| data.0.text().parse::<bool>().unwrap() == value)
-      .unwrap()
-      .0;
+  // a copy of `data` without `key` and the value it held
+  pub fn remove(
+    data: &dyn Value,
+    key: &dyn Value,
+  ) -> ValueResult<(Box<dyn Value>, Box<dyn Value>)> {
+    match (data.ikind(), key.ikind()) {
+      (IKind::Array(mut items), IKind::Int(index)) => {
+        let value = items.remove(Index::position(items.len(), index)?);
+
+        Ok((Array::new(items).boxed(), value))
+      }
+      (IKind::Hash(mut entries), _) => {
+        let key = HashKey::new(key)?;
+        let value = entries
+          .remove(&key)
+          .ok_or(format!("no entry found for key `{}`", key))?;
+
+        Ok((Hash::new(entries).boxed(), value))
+      }
+      (_, _) => {
+        Err(format!("cannot delete `{}[{}]`", data.text(), key.text(),))
+      }
+    }
+  }
 
-    Ok(data[x].1.boxed())
+  fn position(len: usize, index: i64) -> ValueResult<usize> {
+    match index >= 0 && (index as usize) < len {
+      true => Ok(index as usize),
+      false => Err(format!(
+        "index out of bounds: the length is {} but the index is {}",
+        len, index,
+      )),
+    }
   }
 
-  fn eval_index_hash_str(
-    &self,
-    data: &Vec<(HashKey, Box<dyn Value>)>,
-    value: &str,
-  ) -> ValueResult<Box<dyn Value>> {
-    let x = data
-      .iter()
-      .enumerate()
-      .find(|(_, data)| data.0.text() == value)
-      .unwrap()
-      .0;
-
-    Ok(data[x].1.boxed())
+  fn slice(data: &dyn Value, range: &Range) -> ValueResult<Box<dyn Value>> {
+    let items = match data.ikind() {
+      IKind::Array(items) => items,
+      IKind::Str(text) => text
+        .chars()
+        .map(|c| Char::new(c).boxed())
+        .collect::<Vec<Box<dyn Value>>>(),
+      _ => return Err(format!("cannot slice `{}`", data.text())),
+    };
+
+    let len = items.len();
+    let slice = range
+      .values()
+      .map(|index| match index >= 0 && (index as usize) < len {
+        true => Ok(items[index as usize].to_owned()),
+        false => Err(format!(
+          "range `{}` out of bounds for length {}",
+          range, len,
+        )),
+      })
+      .collect::<ValueResult<Vec<Box<dyn Value>>>>()?;
+
+    match data.ikind() {
+      IKind::Str(_) => Ok(Str::new(&strip_exprs(&slice, "")).boxed()),
+      _ => Ok(Array::new(slice).boxed()),
+    }
   }
 }
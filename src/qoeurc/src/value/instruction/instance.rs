@@ -0,0 +1,180 @@
+use crate::analyzer::environment::scope::Scope;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::call::Call;
+use crate::value::instruction::structure::Struct;
+use crate::value::instruction::IKind;
+use crate::value::primitive::str::Str;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Instance {
+  pub fields: Vec<(String, Box<dyn Value>)>,
+  pub name: Box<dyn Value>,
+  pub structure: Option<Box<dyn Value>>,
+}
+
+impl Default for Instance {
+  fn default() -> Self {
+    Instance::new(void!(), vec![])
+  }
+}
+
+impl fmt::Display for Instance {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Instance {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let sname = self.name();
+    let structure = interpreter
+      .scope()
+      .get_struct(&sname)
+      .ok_or(format!("cannot find struct `{}` in this scope", sname))?;
+
+    let decl = structure.as_ref().downcast_ref::<Struct>().unwrap();
+    let names = decl.field_names();
+
+    for (name, _) in &self.fields {
+      if !names.contains(name) {
+        return Err(format!(
+          "struct `{}` has no field named `{}`",
+          sname, name
+        ));
+      }
+    }
+
+    let mut fields = vec![];
+
+    for name in names {
+      let value = self
+        .fields
+        .iter()
+        .find(|(field, _)| field == &name)
+        .ok_or(format!("missing field `{}` in `{}`", name, sname))?
+        .1
+        .to_owned()
+        .eval(interpreter)?;
+
+      fields.push((name, value));
+    }
+
+    let value = Instance::new(self.name.to_owned(), fields)
+      .add_structure(structure)
+      .boxed();
+
+    interpreter.allocate(value.as_ref())?;
+    Ok(value)
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Instance(self.name(), self.fields.to_vec())
+  }
+
+  fn text(&self) -> String {
+    if let Some(text) = self.display() {
+      return text;
+    }
+
+    let fields = self
+      .fields
+      .iter()
+      .map(|(name, value)| format!("{}: {}", name, value.text()))
+      .collect::<Vec<String>>()
+      .join(", ");
+
+    format!("{} {{ {} }}", self.name, fields)
+  }
+}
+
+impl Instance {
+  pub fn new(
+    name: Box<dyn Value>,
+    fields: Vec<(String, Box<dyn Value>)>,
+  ) -> Self {
+    Instance {
+      fields,
+      name,
+      structure: None,
+    }
+  }
+
+  pub fn add_field(&mut self, name: &str, value: Box<dyn Value>) -> &mut Self {
+    self.fields.push((name.into(), value));
+    self
+  }
+
+  pub fn add_name(&mut self, name: Box<dyn Value>) -> &mut Self {
+    self.name = name;
+    self
+  }
+
+  pub fn add_structure(&mut self, structure: Box<dyn Value>) -> &mut Self {
+    self.structure = Some(structure);
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn get_field(&self, name: &str) -> Option<Box<dyn Value>> {
+    self
+      .fields
+      .iter()
+      .find(|(field, _)| field == name)
+      .map(|(_, value)| value.to_owned())
+  }
+
+  pub fn get_method(&self, name: &str) -> Option<Box<dyn Value>> {
+    self.structure()?.get_method(name)
+  }
+
+  pub fn implements(&self, name: &str) -> bool {
+    match self.structure() {
+      Some(structure) => structure.implements(name),
+      None => false,
+    }
+  }
+
+  pub fn name(&self) -> String {
+    self.name.text()
+  }
+
+  pub fn structure(&self) -> Option<&Struct> {
+    self.structure.as_ref()?.downcast_ref::<Struct>()
+  }
+
+  // uses the `Display` implementation of the struct if there is one, text
+  // has no interpreter at hand so `fmt` runs with the default host
+  fn display(&self) -> Option<String> {
+    if !self.implements("Display") {
+      return None;
+    }
+
+    let method = self.get_method("fmt")?;
+    let mut interpreter = Interpreter::new_with_outer(Scope::new());
+    let value = Call::call_function(
+      &mut interpreter,
+      method.as_ref(),
+      vec![self.boxed()],
+    )
+    .ok()?;
+
+    match value.as_ref().downcast_ref::<Str>() {
+      Some(text) => Some(text.value.to_owned()),
+      None => Some(value.text()),
+    }
+  }
+}
@@ -0,0 +1,85 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::utils::iters::strip_exprs;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Trait {
+  pub functions: Vec<Box<dyn Value>>,
+  pub name: Box<dyn Value>,
+}
+
+impl Default for Trait {
+  fn default() -> Self {
+    Trait::new(void!(), vec![])
+  }
+}
+
+impl fmt::Display for Trait {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Trait {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    interpreter.scope_mut().add_trait(self)?;
+
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Trait
+  }
+
+  fn text(&self) -> String {
+    format!(
+      "trait {} {{ {} }}",
+      self.name,
+      strip_exprs(&self.functions, " ")
+    )
+  }
+}
+
+impl Trait {
+  pub fn new(name: Box<dyn Value>, functions: Vec<Box<dyn Value>>) -> Self {
+    Trait { functions, name }
+  }
+
+  pub fn add_function(&mut self, function: Box<dyn Value>) -> &mut Self {
+    self.functions.push(function);
+    self
+  }
+
+  pub fn add_name(&mut self, name: Box<dyn Value>) -> &mut Self {
+    self.name = name;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn get_function(&self, name: &str) -> Option<&Function> {
+    self
+      .functions
+      .iter()
+      .filter_map(|function| function.downcast_ref::<Function>())
+      .find(|function| function.name() == name)
+  }
+
+  pub fn name(&self) -> String {
+    self.name.text()
+  }
+}
@@ -0,0 +1,72 @@
+use crate::analyzer::environment::signal::Signal;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug, Default)]
+pub struct Break {
+  pub label: Option<String>,
+  pub value: Option<Box<dyn Value>>,
+}
+
+impl fmt::Display for Break {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Break {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let value = match self.value.as_mut() {
+      Some(value) => Some(value.eval(interpreter)?),
+      None => None,
+    };
+
+    interpreter.raise(Signal::Break(self.label.to_owned(), value));
+
+    Ok(void!())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Break
+  }
+
+  fn text(&self) -> String {
+    match (&self.label, &self.value) {
+      (Some(label), Some(value)) => format!("break @{} {};", label, value),
+      (Some(label), None) => format!("break @{};", label),
+      (None, Some(value)) => format!("break {};", value),
+      (None, None) => String::from("break;"),
+    }
+  }
+}
+
+impl Break {
+  pub fn new(label: Option<String>, value: Option<Box<dyn Value>>) -> Self {
+    Break { label, value }
+  }
+
+  pub fn add_label(&mut self, label: &str) -> &mut Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
+    self.value = Some(value);
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
@@ -0,0 +1,59 @@
+use crate::analyzer::environment::signal::Signal;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug, Default)]
+pub struct Continue {
+  pub label: Option<String>,
+}
+
+impl fmt::Display for Continue {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Continue {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    interpreter.raise(Signal::Continue(self.label.to_owned()));
+
+    Ok(void!())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Continue
+  }
+
+  fn text(&self) -> String {
+    match &self.label {
+      Some(label) => format!("continue @{};", label),
+      None => String::from("continue;"),
+    }
+  }
+}
+
+impl Continue {
+  pub fn new(label: Option<String>) -> Self {
+    Continue { label }
+  }
+
+  pub fn add_label(&mut self, label: &str) -> &mut Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
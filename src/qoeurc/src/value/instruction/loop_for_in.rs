@@ -1,26 +1,30 @@
+use crate::analyzer::environment::scope::Scope;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
+use crate::utils::iters::{loop_flow, strip_exprs, Flow};
 use crate::value::instruction::array::Array;
-use crate::value::instruction::block::Block;
-use crate::value::instruction::identifier::Identifier;
+use crate::value::instruction::pattern::Bindings;
+use crate::value::instruction::range::Range;
 use crate::value::instruction::IKind;
+use crate::value::primitive::{char::Char, int::Int};
 use crate::value::{VKind, Value};
 use crate::void;
 
 use std::fmt;
 
+// what a `for` loop walks through, one item at a time
+pub type Items = Box<dyn Iterator<Item = Box<dyn Value>>>;
+
 #[derive(Clone, Debug)]
 pub struct LoopForIn {
   pub block: Box<dyn Value>,
   pub iterable: Box<dyn Value>,
-  pub variable: Box<dyn Value>,
+  pub label: Option<String>,
+  pub variables: Vec<Box<dyn Value>>,
 }
 
 impl Default for LoopForIn {
   fn default() -> Self {
-    LoopForIn::new(void!(), void!(), void!())
+    LoopForIn::new(void!(), void!(), vec![])
   }
 }
 
@@ -31,11 +35,34 @@ impl fmt::Display for LoopForIn {
 }
 
 impl Value for LoopForIn {
+  // every iteration runs in a fresh scope holding the loop variables
   fn eval(
     &mut self,
-    _interpreter: &mut Interpreter,
+    interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    Ok(self.boxed())
+    let iterable = self.iterable.eval(interpreter)?;
+
+    for item in LoopForIn::items(iterable.as_ref())? {
+      let mut scope = Scope::new_with_outer(interpreter.scope.to_owned());
+
+      self.bind(&mut scope, item)?;
+
+      self
+        .block
+        .to_owned()
+        .eval(&mut interpreter.new_with_scope(scope))?;
+
+      match loop_flow(&self.label, interpreter) {
+        Flow::Next => continue,
+        Flow::Exit(None) => break,
+        Flow::Exit(Some(_)) => {
+          return Err(String::from("`break` with value from a `for` loop"))
+        }
+        Flow::Escape => return Ok(void!()),
+      }
+    }
+
+    Ok(void!())
   }
 
   fn vkind(&self) -> VKind {
@@ -46,78 +73,20 @@ impl Value for LoopForIn {
     IKind::LoopForIn
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.first_is(TokenKind::GroupStart(Bracket));
-
-    let iterable = Array::default().parse(parser)?;
-
-    parser.next_token();
-    parser.expect_first(TokenKind::Operator(Or))?;
-    parser.expect_first(TokenKind::Identifier)?;
-
-    let variable = Identifier::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::Operator(Or))?;
-    parser.next_token();
-    parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-    let block = Block::default().parse(parser)?;
-
-    Ok(
-      self
-        .add_block(block)
-        .add_iterable(iterable)
-        .add_variable(variable)
-        .boxed(),
-    )
-  }
-
-  fn print(&self) {
-    println!("{}", self.text());
-  }
-
   fn text(&self) -> String {
-    format!(
-      "for {} |{}| {{ {} }}",
-      self.iterable.text(),
-      self.variable.text(),
-      self.block.text()
-    )
-  }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "body": {},
-          "left": {{
-            "declarations": [{{
-              "id": {},
-              "init": null,
-              "type": "VariableDeclarator"
-            }}],
-            "kind": "var",
-            "type": "VariableDeclaration"
-          }},
-          "right": {},
-          "type": "ForInStatement"
-        }}"#,
-        self.block.transpile(transpiler),
-        self.variable.transpile(transpiler),
-        self.iterable.transpile(transpiler),
-      ),
-      TKind::Inline => format!(
-        "for (var {} in {}) {{ {} }}",
-        self.variable.transpile(transpiler),
-        self.iterable.transpile(transpiler),
-        self.block.transpile(transpiler),
-      ),
-      TKind::Pretty => format!(
-        "for (var {} in {}) {{\n\t{}\n}}",
-        self.variable.transpile(transpiler),
-        self.iterable.transpile(transpiler),
-        self.block.transpile(transpiler),
+    let text = match self.variables.is_empty() {
+      true => format!("for {} {{ {} }}", self.iterable, self.block),
+      false => format!(
+        "for {} |{}| {{ {} }}",
+        self.iterable,
+        strip_exprs(&self.variables, ", "),
+        self.block,
       ),
+    };
+
+    match &self.label {
+      Some(label) => format!("@{} {}", label, text),
+      None => text,
     }
   }
 }
@@ -126,12 +95,13 @@ impl LoopForIn {
   pub fn new(
     block: Box<dyn Value>,
     iterable: Box<dyn Value>,
-    variable: Box<dyn Value>,
+    variables: Vec<Box<dyn Value>>,
   ) -> Self {
     LoopForIn {
       block,
       iterable,
-      variable,
+      label: None,
+      variables,
     }
   }
 
@@ -145,12 +115,81 @@ impl LoopForIn {
     self
   }
 
+  pub fn add_label(&mut self, label: &str) -> &mut Self {
+    self.label = Some(label.into());
+    self
+  }
+
   pub fn add_variable(&mut self, variable: Box<dyn Value>) -> &mut Self {
-    self.variable = variable;
+    self.variables.push(variable);
     self
   }
 
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  // arrays yield their elements, hashes `[key, value]` pairs, strings their
+  // chars and ranges their integers
+  pub fn items(iterable: &dyn Value) -> ValueResult<Items> {
+    if let Some(range) = iterable.downcast_ref::<Range>() {
+      return Ok(Box::new(
+        range.values().map(|value| Int::new(value).boxed()),
+      ));
+    }
+
+    match iterable.ikind() {
+      IKind::Array(data) => Ok(Box::new(data.into_iter())),
+      IKind::Hash(data) => {
+        Ok(Box::new(data.into_iter().map(|(key, value)| {
+          Array::new(vec![key.to_value(), value]).boxed()
+        })))
+      }
+      IKind::Str(value) => Ok(Box::new(
+        value
+          .chars()
+          .map(|c| Char::new(c).boxed())
+          .collect::<Vec<Box<dyn Value>>>()
+          .into_iter(),
+      )),
+      _ => Err(format!("`{}` is not iterable", iterable.text())),
+    }
+  }
+
+  // the loop variables of an item, several of them take an array apart
+  pub fn bindings(
+    variables: &[String],
+    item: Box<dyn Value>,
+  ) -> ValueResult<Bindings> {
+    match variables.len() {
+      0 => Ok(vec![]),
+      1 => Ok(vec![(variables[0].to_owned(), item)]),
+      len => match item.ikind() {
+        IKind::Array(data) if data.len() == len => {
+          Ok(variables.iter().cloned().zip(data).collect())
+        }
+        _ => Err(format!(
+          "cannot bind `{}` to {} loop variables",
+          item.text(),
+          len
+        )),
+      },
+    }
+  }
+
+  pub fn names(&self) -> Vec<String> {
+    self
+      .variables
+      .iter()
+      .map(|variable| variable.text())
+      .collect()
+  }
+
+  fn bind(&self, scope: &mut Scope, item: Box<dyn Value>) -> ValueResult<()> {
+    for (name, value) in LoopForIn::bindings(&self.names(), item)? {
+      scope.bind(&name, value);
+    }
+
+    Ok(())
+  }
 }
@@ -1,8 +1,6 @@
+use crate::analyzer::environment::scope::Scope;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::block::Block;
+use crate::utils::iters::{loop_flow, Flow};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -12,6 +10,7 @@ use std::fmt;
 #[derive(Clone, Debug)]
 pub struct LoopInfinite {
   pub block: Box<dyn Value>,
+  pub label: Option<String>,
 }
 
 impl Default for LoopInfinite {
@@ -27,13 +26,25 @@ impl fmt::Display for LoopInfinite {
 }
 
 impl Value for LoopInfinite {
+  // the loop is worth the value it breaks with
   fn eval(
     &mut self,
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    let block = self.block.eval(interpreter)?;
-
-    Ok(self.add_block(block).boxed())
+    loop {
+      let scope = Scope::new_with_outer(interpreter.scope.to_owned());
+
+      self
+        .block
+        .to_owned()
+        .eval(&mut interpreter.new_with_scope(scope))?;
+
+      match loop_flow(&self.label, interpreter) {
+        Flow::Next => continue,
+        Flow::Exit(value) => return Ok(value.unwrap_or(void!())),
+        Flow::Escape => return Ok(void!()),
+      }
+    }
   }
 
   fn vkind(&self) -> VKind {
@@ -44,45 +55,17 @@ impl Value for LoopInfinite {
     IKind::LoopInfinite
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-    let block = Block::default().parse(parser)?;
-
-    Ok(self.add_block(block).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text());
-  }
-
   fn text(&self) -> String {
-    format!("loop {{ {} }}", self.block.text())
-  }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let block = self.block.transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "body": {},
-          "init": null,
-          "test": null,
-          "update": null,
-          "type": "ForStatement"
-        }}"#,
-        block,
-      ),
-      TKind::Inline => format!("for (;) {{ {} }}", block),
-      TKind::Pretty => format!("for (;) {{\n\t{}\n}}", block),
+    match &self.label {
+      Some(label) => format!("@{} loop {{ {} }}", label, self.block.text()),
+      None => format!("loop {{ {} }}", self.block.text()),
     }
   }
 }
 
 impl LoopInfinite {
   pub fn new(block: Box<dyn Value>) -> Self {
-    LoopInfinite { block }
+    LoopInfinite { block, label: None }
   }
 
   pub fn add_block(&mut self, block: Box<dyn Value>) -> &mut Self {
@@ -90,6 +73,11 @@ impl LoopInfinite {
     self
   }
 
+  pub fn add_label(&mut self, label: &str) -> &mut Self {
+    self.label = Some(label.into());
+    self
+  }
+
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
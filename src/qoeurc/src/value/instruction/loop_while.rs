@@ -1,9 +1,6 @@
+use crate::analyzer::environment::scope::Scope;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::block::Block;
-use crate::value::instruction::expression::Expression;
+use crate::utils::iters::{loop_flow, Flow};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -14,6 +11,7 @@ use std::fmt;
 pub struct LoopWhile {
   pub block: Box<dyn Value>,
   pub condition: Box<dyn Value>,
+  pub label: Option<String>,
 }
 
 impl Default for LoopWhile {
@@ -33,14 +31,25 @@ impl Value for LoopWhile {
     &mut self,
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    let mut block = void!().boxed();
-    let condition = self.condition.eval(interpreter)?;
-
-    while condition.is_truthy() {
-      block = self.block.eval(interpreter)?;
+    while self.condition.to_owned().eval(interpreter)?.is_truthy() {
+      let scope = Scope::new_with_outer(interpreter.scope.to_owned());
+
+      self
+        .block
+        .to_owned()
+        .eval(&mut interpreter.new_with_scope(scope))?;
+
+      match loop_flow(&self.label, interpreter) {
+        Flow::Next => continue,
+        Flow::Exit(None) => break,
+        Flow::Exit(Some(_)) => {
+          return Err(String::from("`break` with value from a `while` loop"))
+        }
+        Flow::Escape => return Ok(void!()),
+      }
     }
 
-    Ok(self.add_condition(condition).add_block(block).boxed())
+    Ok(void!())
   }
 
   fn vkind(&self) -> VKind {
@@ -51,57 +60,23 @@ impl Value for LoopWhile {
     IKind::LoopWhile
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.next_token();
-
-    let condition = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    parser.expect_first(TokenKind::GroupStart(Brace))?;
-
-    let block = Block::default().parse(parser)?;
-
-    Ok(self.add_condition(condition).add_block(block).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text());
-  }
-
   fn text(&self) -> String {
-    format!("while {} {{ {} }}", self.condition, self.block.text())
-  }
+    let text = format!("while {} {{ {} }}", self.condition, self.block.text());
 
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "body": {},
-          "test": {},
-          "type": "WhileStatement"
-        }}"#,
-        self.block.transpile(transpiler),
-        self.condition.transpile(transpiler),
-      ),
-      TKind::Inline => format!(
-        "while ({}) {{ {} }}",
-        self.condition.transpile(transpiler),
-        self.block.transpile(transpiler),
-      ),
-      TKind::Pretty => format!(
-        "while ({}) {{\n\t{}\n}}",
-        self.condition.transpile(transpiler),
-        self.block.transpile(transpiler),
-      ),
+    match &self.label {
+      Some(label) => format!("@{} {}", label, text),
+      None => text,
     }
   }
 }
 
 impl LoopWhile {
   pub fn new(block: Box<dyn Value>, condition: Box<dyn Value>) -> Self {
-    LoopWhile { block, condition }
+    LoopWhile {
+      block,
+      condition,
+      label: None,
+    }
   }
 
   pub fn add_block(&mut self, block: Box<dyn Value>) -> &mut Self {
@@ -114,6 +89,11 @@ impl LoopWhile {
     self
   }
 
+  pub fn add_label(&mut self, label: &str) -> &mut Self {
+    self.label = Some(label.into());
+    self
+  }
+
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
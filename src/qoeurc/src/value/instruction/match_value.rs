@@ -0,0 +1,104 @@
+use crate::analyzer::environment::scope::Scope;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::block::Block;
+use crate::value::instruction::pattern::Pattern;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Match {
+  pub arms: Vec<(Pattern, Box<dyn Value>)>,
+  pub subject: Box<dyn Value>,
+}
+
+impl Default for Match {
+  fn default() -> Self {
+    Match::new(void!(), vec![])
+  }
+}
+
+impl fmt::Display for Match {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Match {
+  // the first arm whose pattern matches runs in a scope holding its bindings
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let subject = self.subject.eval(interpreter)?;
+
+    if interpreter.is_unwinding() {
+      return Ok(void!());
+    }
+
+    for (pattern, body) in &self.arms {
+      if let Some(bindings) = pattern.matches(subject.as_ref()) {
+        let mut scope = Scope::new_with_outer(interpreter.scope.to_owned());
+
+        for (name, value) in bindings {
+          scope.bind(&name, value);
+        }
+
+        return body.to_owned().eval(&mut interpreter.new_with_scope(scope));
+      }
+    }
+
+    Err(format!("no arm of `match` matches `{}`", subject.text()))
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Match
+  }
+
+  fn text(&self) -> String {
+    let arms = self
+      .arms
+      .iter()
+      .map(|(pattern, body)| match body.as_ref().is::<Block>() {
+        true => format!("{} => {{ {} }}", pattern, body),
+        false => format!("{} => {}", pattern, body),
+      })
+      .collect::<Vec<String>>()
+      .join(", ");
+
+    format!("match {} {{ {} }}", self.subject, arms)
+  }
+}
+
+impl Match {
+  pub fn new(
+    subject: Box<dyn Value>,
+    arms: Vec<(Pattern, Box<dyn Value>)>,
+  ) -> Self {
+    Match { arms, subject }
+  }
+
+  pub fn add_arm(
+    &mut self,
+    pattern: Pattern,
+    body: Box<dyn Value>,
+  ) -> &mut Self {
+    self.arms.push((pattern, body));
+    self
+  }
+
+  pub fn add_subject(&mut self, subject: Box<dyn Value>) -> &mut Self {
+    self.subject = subject;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
@@ -0,0 +1,125 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::instance::Instance;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Member {
+  pub object: Box<dyn Value>,
+  pub optional: bool,
+  pub property: Box<dyn Value>,
+}
+
+impl Default for Member {
+  fn default() -> Self {
+    Member::new(void!(), void!())
+  }
+}
+
+impl fmt::Display for Member {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl From<Box<dyn Value>> for Member {
+  fn from(object: Box<dyn Value>) -> Member {
+    Member::new(object, void!())
+  }
+}
+
+impl Value for Member {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let object = self.object.eval(interpreter)?;
+    let name = self.name();
+
+    if interpreter.is_unwinding() {
+      return Ok(void!());
+    }
+
+    Member::get(object.as_ref(), &name, self.optional)
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Member
+  }
+
+  fn text(&self) -> String {
+    format!("{}{}{}", self.object, self.operator(), self.property)
+  }
+}
+
+impl Member {
+  pub fn new(object: Box<dyn Value>, property: Box<dyn Value>) -> Self {
+    Member {
+      object,
+      optional: false,
+      property,
+    }
+  }
+
+  pub fn add_object(&mut self, object: Box<dyn Value>) -> &mut Self {
+    self.object = object;
+    self
+  }
+
+  pub fn add_property(&mut self, property: Box<dyn Value>) -> &mut Self {
+    self.property = property;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn name(&self) -> String {
+    self.property.text()
+  }
+
+  // the field `name` of an evaluated object, `?.` gives `nil` for `nil`
+  pub fn get(
+    object: &dyn Value,
+    name: &str,
+    optional: bool,
+  ) -> ValueResult<Box<dyn Value>> {
+    if optional && object.ikind() == IKind::Nil {
+      return Ok(void!());
+    }
+
+    match object.downcast_ref::<Instance>() {
+      Some(instance) => match instance.get_field(name) {
+        Some(value) => Ok(value),
+        None if instance.get_method(name).is_some() => Err(format!(
+          "attempted to take value of method `{}` on `{}`",
+          name,
+          instance.name(),
+        )),
+        None => {
+          Err(format!("no field `{}` on type `{}`", name, instance.name()))
+        }
+      },
+      None => Err(format!(
+        "error member expression: {}.{}",
+        object.text(),
+        name
+      )),
+    }
+  }
+
+  fn operator(&self) -> &'static str {
+    match self.optional {
+      true => "?.",
+      false => ".",
+    }
+  }
+}
@@ -1,4 +1,5 @@
 pub mod array;
+pub mod assign;
 pub mod attribute;
 pub mod binary;
 pub mod block;
@@ -6,21 +7,39 @@ pub mod call;
 pub mod closure;
 pub mod comment;
 pub mod control;
+pub mod delete;
 pub mod expression;
 pub mod function;
 pub mod function_arg;
 pub mod group;
 pub mod hash;
 pub mod identifier;
+pub mod implementation;
+pub mod import;
 pub mod index;
+pub mod instance;
+pub mod interface;
+pub mod loop_break;
+pub mod loop_continue;
 pub mod loop_for_in;
-pub mod loop_for_range;
 pub mod loop_infinite;
 pub mod loop_while;
+pub mod match_value;
+pub mod member;
+pub mod module;
+pub mod named_arg;
+pub mod native;
+pub mod outcome;
+pub mod pattern;
 pub mod program;
+pub mod public;
+pub mod range;
 pub mod return_value;
 pub mod shebang;
+pub mod spread;
 pub mod statement;
+pub mod structure;
+pub mod try_value;
 pub mod ty;
 pub mod unary;
 pub mod val;
@@ -28,20 +47,23 @@ pub mod val;
 pub use crate::value::instruction::{
   array::Array, binary::Binary, block::Block, call::Call, control::If,
   expression::Expression, function::Function, hash::Hash,
-  identifier::Identifier, index::Index, loop_for_in::LoopForIn,
-  loop_for_range::LoopForRange, loop_infinite::LoopInfinite,
-  loop_while::LoopWhile, program::Program, return_value::Return,
-  statement::Statement, unary::Unary, val::Val,
+  identifier::Identifier, implementation::Impl, import::Use, index::Index,
+  instance::Instance, interface::Trait, loop_break::Break,
+  loop_continue::Continue, loop_for_in::LoopForIn, loop_infinite::LoopInfinite,
+  loop_while::LoopWhile, match_value::Match, member::Member, module::Module,
+  outcome::Outcome, program::Program, public::Public, range::Range,
+  return_value::Return, statement::Statement, structure::Struct,
+  try_value::Try, unary::Unary, val::Val,
 };
 
 use crate::analyzer::environment::scope::TScope;
-use crate::value::instruction::hash::HashKey;
+use crate::value::instruction::hash::Entries;
 use crate::value::Value;
 
 #[macro_export]
 macro_rules! void {
   () => {
-    Box::new($crate::value::instruction::array::Array::default())
+    Box::new($crate::value::primitive::nil::Nil::default())
   };
 }
 
@@ -49,15 +71,19 @@ macro_rules! void {
 pub enum IKind {
   NOOP,
   Array(Vec<Box<dyn Value>>),
+  Assign,
   Attribute,
   Binary,
   Block(Vec<Box<dyn Value>>),
   Bool(bool),
+  Break,
   Call,
   Char(char),
   Capsule,
   Closure,
   Comment,
+  Continue,
+  Delete,
   Expression,
   If,
   Function(
@@ -70,22 +96,37 @@ pub enum IKind {
   FunctionArg,
   Float(f64),
   Group,
-  Hash(Vec<(HashKey, Box<dyn Value>)>),
+  Hash(Entries),
   Identifier,
+  Impl,
   Index,
+  Instance(String, Vec<(String, Box<dyn Value>)>),
   Int(i64),
   Keyword,
   LoopForIn,
-  LoopForRange,
   LoopInfinite,
   LoopWhile,
+  Match,
+  NamedArg,
+  Native,
+  Member,
+  Module,
+  Nil,
+  Outcome(bool, Box<dyn Value>),
   Program(Vec<Box<dyn Value>>),
+  Public,
+  Range,
   Return,
   Shebang,
+  Spread,
   Statement,
   Str(String),
+  Struct,
+  Trait,
+  Try,
   Ty,
   Unary,
+  Use,
   Val,
   While,
 }
@@ -0,0 +1,111 @@
+use crate::analyzer::checker::Checker;
+use crate::analyzer::environment::registry::Exports;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::analyzer::resolver::Resolver;
+use crate::value::instruction::block::Block;
+use crate::value::instruction::program::Program;
+use crate::value::instruction::public::Public;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Module {
+  pub block: Option<Box<dyn Value>>,
+  pub name: Box<dyn Value>,
+}
+
+impl Default for Module {
+  fn default() -> Self {
+    Module::new(void!(), None)
+  }
+}
+
+impl fmt::Display for Module {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Module {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let name = self.name();
+
+    let block = match &self.block {
+      Some(block) => block.downcast_ref::<Block>().unwrap(),
+      None => {
+        interpreter.import(&[name])?;
+
+        return Ok(self.boxed());
+      }
+    };
+
+    let mut program = Program::new(block.statements.to_vec()).boxed();
+    let mut module = Interpreter::new();
+
+    module
+      .add_host(interpreter.host.to_owned())
+      .add_meter(interpreter.meter.to_owned())
+      .add_registry(interpreter.registry.to_owned());
+    module.path = interpreter.path.to_owned();
+
+    Resolver::new(module.scope.to_owned())
+      .resolve(&mut program)
+      .and_then(|_| {
+        Checker::new(module.scope.to_owned()).check(program.as_ref())
+      })
+      .and_then(|_| module.eval(&program))
+      .map_err(|error| format!("in module `{}`: {}", name, error))?;
+
+    interpreter.registry.borrow_mut().insert(
+      &name,
+      Exports::new(Public::exports(&block.statements), module.scope),
+    );
+
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Module
+  }
+
+  fn text(&self) -> String {
+    match &self.block {
+      Some(block) => format!("mod {} {{ {} }}", self.name, block),
+      None => format!("mod {};", self.name),
+    }
+  }
+}
+
+impl Module {
+  pub fn new(name: Box<dyn Value>, block: Option<Box<dyn Value>>) -> Self {
+    Module { block, name }
+  }
+
+  pub fn add_block(&mut self, block: Box<dyn Value>) -> &mut Self {
+    self.block = Some(block);
+    self
+  }
+
+  pub fn add_name(&mut self, name: Box<dyn Value>) -> &mut Self {
+    self.name = name;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn name(&self) -> String {
+    self.name.text()
+  }
+}
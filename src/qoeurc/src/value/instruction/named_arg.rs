@@ -0,0 +1,66 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct NamedArg {
+  pub name: String,
+  pub value: Box<dyn Value>,
+}
+
+impl Default for NamedArg {
+  fn default() -> Self {
+    NamedArg::new("", void!())
+  }
+}
+
+impl fmt::Display for NamedArg {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for NamedArg {
+  // the name is kept so that the call can bind the value to its parameter
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let value = self.value.eval(interpreter)?;
+
+    Ok(NamedArg::new(&self.name, value).boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::NamedArg
+  }
+
+  fn text(&self) -> String {
+    format!("{}: {}", self.name, self.value)
+  }
+}
+
+impl NamedArg {
+  pub fn new(name: &str, value: Box<dyn Value>) -> Self {
+    NamedArg {
+      name: name.into(),
+      value,
+    }
+  }
+
+  pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
+    self.value = value;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
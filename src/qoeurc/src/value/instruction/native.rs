@@ -0,0 +1,195 @@
+use crate::analyzer::environment::scope::Scope;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::converter::lowering::raise_function;
+use crate::converter::parser::{Parser, ParserResult};
+use crate::tokenizer::kind::*;
+use crate::value::convert::{FromValue, IntoValue};
+use crate::value::instruction::array::Array;
+use crate::value::instruction::call::Call;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value, Values};
+
+use std::fmt;
+use std::rc::Rc;
+
+pub type NativeFn =
+  Rc<dyn Fn(&mut Interpreter, Values) -> ValueResult<Box<dyn Value>>>;
+
+// a function written in rust, from the standard library or registered by the
+// host, the checker only sees its `signature` and `js` is its definition in
+// the transpiled output
+#[derive(Clone)]
+pub struct Native {
+  pub function: NativeFn,
+  pub js: &'static str,
+  pub signature: Box<dyn Value>,
+}
+
+impl fmt::Debug for Native {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Native")
+      .field("js", &self.js)
+      .field("signature", &self.signature)
+      .finish()
+  }
+}
+
+impl fmt::Display for Native {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Native {
+  fn eval(
+    &mut self,
+    _interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Native
+  }
+
+  fn text(&self) -> String {
+    format!("{}", self.signature)
+  }
+}
+
+impl Native {
+  // `signature` is the head of a function: `ƒ len(value: _) -> int`
+  pub fn new<F>(signature: &str, function: F, js: &'static str) -> Self
+  where
+    F: Fn(&mut Interpreter, Values) -> ValueResult<Box<dyn Value>> + 'static,
+  {
+    Native::try_new(signature, function, js).unwrap()
+  }
+
+  pub fn try_new<F>(
+    signature: &str,
+    function: F,
+    js: &'static str,
+  ) -> ParserResult<Self>
+  where
+    F: Fn(&mut Interpreter, Values) -> ValueResult<Box<dyn Value>> + 'static,
+  {
+    let mut parser = Parser::new(signature);
+
+    parser.next_token();
+    parser.next_token();
+
+    if !parser.token_is(TokenKind::Keyword(Function)) {
+      return Err(format!("expected a signature, found `{}`", signature));
+    }
+
+    let head = parser.parse_signature()?;
+
+    Ok(Native {
+      function: Rc::new(function),
+      js,
+      signature: raise_function(&head),
+    })
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  // the arguments are given to `function` in the order of the parameters,
+  // missing ones take their default and a variadic one gets an array
+  pub fn call(
+    &self,
+    interpreter: &mut Interpreter,
+    args: Values,
+  ) -> ValueResult<Box<dyn Value>> {
+    let name = self.name();
+    let params = self.params();
+    let assigned = FunctionArg::assign(&name, params, Call::label_args(args))?;
+
+    let mut defaults = interpreter.new_with_scope(Scope::new());
+    let mut values = vec![];
+
+    for (param, mut args) in params.iter().zip(assigned) {
+      let param = param.as_ref().downcast_ref::<FunctionArg>().unwrap();
+
+      values.push(match (param.variadic, &param.default) {
+        (true, _) => Array::new(args).boxed(),
+        (false, Some(default)) if args.is_empty() => {
+          default.to_owned().eval(&mut defaults)?
+        }
+        (false, _) => args.remove(0),
+      });
+    }
+
+    let value = (self.function)(interpreter, values)?;
+
+    interpreter.allocate(value.as_ref())?;
+    Ok(value)
+  }
+
+  pub fn name(&self) -> String {
+    self.signature().name()
+  }
+
+  pub fn params(&self) -> &Values {
+    self.signature().args.as_ref().unwrap()
+  }
+
+  pub fn signature(&self) -> &Function {
+    self.signature.as_ref().downcast_ref::<Function>().unwrap()
+  }
+}
+
+// a rust closure whose arguments and result convert from and to qoeur values,
+// implemented for closures of up to four arguments
+pub trait HostFunction<Args> {
+  const ARITY: usize;
+
+  fn call(&self, name: &str, args: Values) -> ValueResult<Box<dyn Value>>;
+}
+
+macro_rules! host_function {
+  ($($arg:ident),*) => {
+    impl<F, R, $($arg),*> HostFunction<($($arg,)*)> for F
+    where
+      F: Fn($($arg),*) -> ValueResult<R>,
+      R: IntoValue,
+      $($arg: FromValue),*
+    {
+      const ARITY: usize = <[&str]>::len(&[$(stringify!($arg)),*]);
+
+      #[allow(unused_mut, unused_variables)]
+      fn call(&self, name: &str, args: Values) -> ValueResult<Box<dyn Value>> {
+        let mut args = args.iter().enumerate();
+
+        let value = self($({
+          let (index, arg) = args.next().unwrap();
+
+          $arg::from_value(arg.as_ref()).map_err(|error| {
+            format!(
+              "mismatched types for the argument {} of `{}`: {}",
+              index + 1,
+              name,
+              error,
+            )
+          })?
+        }),*)?;
+
+        Ok(value.into_value())
+      }
+    }
+  };
+}
+
+host_function!();
+host_function!(A);
+host_function!(A, B);
+host_function!(A, B, C);
+host_function!(A, B, C, D);
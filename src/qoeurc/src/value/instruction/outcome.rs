@@ -0,0 +1,69 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Outcome {
+  pub is_ok: bool,
+  pub value: Box<dyn Value>,
+}
+
+impl Default for Outcome {
+  fn default() -> Self {
+    Outcome::new(true, void!())
+  }
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Outcome {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let value = self.value.eval(interpreter)?;
+
+    Ok(Outcome::new(self.is_ok, value).boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Outcome(self.is_ok, self.value.to_owned())
+  }
+
+  fn text(&self) -> String {
+    format!("{}({})", self.keyword(), self.value)
+  }
+}
+
+impl Outcome {
+  pub fn new(is_ok: bool, value: Box<dyn Value>) -> Self {
+    Outcome { is_ok, value }
+  }
+
+  pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
+    self.value = value;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn keyword(&self) -> &'static str {
+    match self.is_ok {
+      true => "ok",
+      false => "err",
+    }
+  }
+}
@@ -0,0 +1,252 @@
+use crate::value::instruction::array::Array;
+use crate::value::instruction::hash::HashKey;
+use crate::value::instruction::IKind;
+use crate::value::Value;
+
+use std::fmt;
+
+pub type Bindings = Vec<(String, Box<dyn Value>)>;
+
+// the left-hand side of a `match` arm, a `val` or a parameter. there are no
+// tuple values, `(a, b)` takes apart an array of exactly two items
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+  Array(Vec<Pattern>),
+  Binding(String),
+  Hash(Vec<(String, Pattern)>),
+  Literal(Box<dyn Value>),
+  Outcome(bool, Box<Pattern>),
+  Rest(Option<String>),
+  Struct(String, Vec<(String, Pattern)>),
+  Tuple(Vec<Pattern>),
+  Wildcard,
+}
+
+impl fmt::Display for Pattern {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Pattern {
+  // the variables bound by the pattern when `value` matches it
+  pub fn matches(&self, value: &dyn Value) -> Option<Bindings> {
+    match (self, value.ikind()) {
+      (Pattern::Wildcard, _) => Some(vec![]),
+      (Pattern::Binding(name), _) => Some(vec![(name.into(), value.boxed())]),
+      (Pattern::Literal(literal), _) => match **literal == *value {
+        true => Some(vec![]),
+        false => None,
+      },
+      (Pattern::Outcome(is_ok, pattern), IKind::Outcome(ok, value))
+        if *is_ok == ok =>
+      {
+        pattern.matches(value.as_ref())
+      }
+      (Pattern::Array(patterns), IKind::Array(data)) => {
+        Pattern::matches_elements(patterns, &data)
+      }
+      (Pattern::Tuple(patterns), IKind::Array(data))
+        if patterns.len() == data.len() =>
+      {
+        Pattern::matches_elements(patterns, &data)
+      }
+      (Pattern::Hash(fields), IKind::Hash(data)) => {
+        Pattern::matches_fields(fields, |key| {
+          data
+            .get(&HashKey::Str(key.into()))
+            .map(|value| value.boxed())
+        })
+      }
+      (Pattern::Hash(fields), IKind::Instance(_, data)) => {
+        Pattern::matches_fields(fields, |key| Pattern::field(&data, key))
+      }
+      (Pattern::Struct(name, fields), IKind::Instance(sname, data))
+        if *name == sname =>
+      {
+        Pattern::matches_fields(fields, |key| Pattern::field(&data, key))
+      }
+      _ => None,
+    }
+  }
+
+  // every name the pattern binds, in order
+  pub fn names(&self) -> Vec<String> {
+    match self {
+      Pattern::Binding(name) | Pattern::Rest(Some(name)) => vec![name.into()],
+      Pattern::Outcome(_, pattern) => pattern.names(),
+      Pattern::Array(patterns) | Pattern::Tuple(patterns) => patterns
+        .iter()
+        .flat_map(|pattern| pattern.names())
+        .collect(),
+      Pattern::Hash(fields) | Pattern::Struct(_, fields) => fields
+        .iter()
+        .flat_map(|(_, pattern)| pattern.names())
+        .collect(),
+      Pattern::Literal(_) | Pattern::Rest(None) | Pattern::Wildcard => vec![],
+    }
+  }
+
+  pub fn text(&self) -> String {
+    match self {
+      Pattern::Array(patterns) => format!("[{}]", Pattern::join(patterns)),
+      Pattern::Binding(name) => name.into(),
+      Pattern::Hash(fields) => {
+        format!("{{ {} }}", Pattern::join_fields(fields))
+      }
+      Pattern::Literal(value) => value.text(),
+      Pattern::Outcome(true, pattern) => format!("ok({})", pattern),
+      Pattern::Outcome(false, pattern) => format!("err({})", pattern),
+      Pattern::Rest(Some(name)) => format!("..{}", name),
+      Pattern::Rest(None) => String::from(".."),
+      Pattern::Struct(name, fields) => {
+        format!("{} {{ {} }}", name, Pattern::join_fields(fields))
+      }
+      Pattern::Tuple(patterns) => format!("({})", Pattern::join(patterns)),
+      Pattern::Wildcard => String::from("_"),
+    }
+  }
+
+  // the javascript declarations of the variables bound by the pattern
+  pub fn transpile_bindings(&self, subject: &str) -> Vec<String> {
+    match self {
+      Pattern::Binding(name) => vec![format!("var {} = {};", name, subject)],
+      Pattern::Outcome(_, pattern) => {
+        pattern.transpile_bindings(&format!("{}.value", subject))
+      }
+      Pattern::Array(patterns) | Pattern::Tuple(patterns) => patterns
+        .iter()
+        .enumerate()
+        .flat_map(|(x, pattern)| match pattern {
+          Pattern::Rest(Some(name)) => {
+            vec![format!("var {} = {}.slice({});", name, subject, x)]
+          }
+          _ => pattern.transpile_bindings(&format!("{}[{}]", subject, x)),
+        })
+        .collect(),
+      Pattern::Hash(fields) | Pattern::Struct(_, fields) => fields
+        .iter()
+        .flat_map(|(key, pattern)| {
+          pattern.transpile_bindings(&format!("{}.{}", subject, key))
+        })
+        .collect(),
+      Pattern::Literal(_) | Pattern::Rest(_) | Pattern::Wildcard => vec![],
+    }
+  }
+
+  // the pattern as a javascript destructuring target, the parts binding
+  // nothing become holes
+  pub fn transpile_target(&self) -> String {
+    match self {
+      Pattern::Binding(name) => name.into(),
+      Pattern::Outcome(_, pattern) => {
+        format!("{{ value: {} }}", pattern.transpile_target())
+      }
+      Pattern::Array(patterns) | Pattern::Tuple(patterns) => format!(
+        "[{}]",
+        patterns
+          .iter()
+          .filter(|pattern| !matches!(pattern, Pattern::Rest(None)))
+          .map(|pattern| match pattern {
+            Pattern::Rest(Some(name)) => format!("...{}", name),
+            pattern => pattern.transpile_target(),
+          })
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Pattern::Hash(fields) | Pattern::Struct(_, fields) => format!(
+        "{{ {} }}",
+        fields
+          .iter()
+          .filter(|(_, pattern)| !pattern.names().is_empty())
+          .map(|(key, pattern)| match pattern {
+            Pattern::Binding(name) if name == key => key.into(),
+            pattern => format!("{}: {}", key, pattern.transpile_target()),
+          })
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Pattern::Literal(_) | Pattern::Rest(_) | Pattern::Wildcard => {
+        String::from("")
+      }
+    }
+  }
+
+  fn field(
+    fields: &[(String, Box<dyn Value>)],
+    key: &str,
+  ) -> Option<Box<dyn Value>> {
+    fields
+      .iter()
+      .find(|(name, _)| name == key)
+      .map(|(_, value)| value.to_owned())
+  }
+
+  fn join(patterns: &[Pattern]) -> String {
+    patterns
+      .iter()
+      .map(|pattern| pattern.text())
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+
+  fn join_fields(fields: &[(String, Pattern)]) -> String {
+    fields
+      .iter()
+      .map(|(key, pattern)| match pattern {
+        Pattern::Binding(name) if name == key => key.into(),
+        pattern => format!("{}: {}", key, pattern),
+      })
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+
+  // an array matches when its length fits and each element matches, a
+  // trailing `..rest` takes the remaining ones
+  fn matches_elements(
+    patterns: &[Pattern],
+    data: &[Box<dyn Value>],
+  ) -> Option<Bindings> {
+    let (patterns, rest) = match patterns.split_last() {
+      Some((Pattern::Rest(rest), patterns)) => (patterns, Some(rest)),
+      _ => (patterns, None),
+    };
+
+    let fits = match rest {
+      Some(_) => data.len() >= patterns.len(),
+      None => data.len() == patterns.len(),
+    };
+
+    if !fits {
+      return None;
+    }
+
+    let mut bindings = vec![];
+
+    for (pattern, value) in patterns.iter().zip(data) {
+      bindings.append(&mut pattern.matches(value.as_ref())?);
+    }
+
+    if let Some(Some(name)) = rest {
+      let rest = Array::new(data[patterns.len()..].to_vec()).boxed();
+
+      bindings.push((name.into(), rest));
+    }
+
+    Some(bindings)
+  }
+
+  // a hash or a struct matches when every listed field is there and matches
+  fn matches_fields<F>(fields: &[(String, Pattern)], get: F) -> Option<Bindings>
+  where
+    F: Fn(&str) -> Option<Box<dyn Value>>,
+  {
+    let mut bindings = vec![];
+
+    for (key, pattern) in fields {
+      bindings.append(&mut pattern.matches(get(key)?.as_ref())?);
+    }
+
+    Some(bindings)
+  }
+}
@@ -1,9 +1,7 @@
+use crate::analyzer::environment::signal::Signal;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::utils::iters::{eval_statements, transpile_exprs};
-use crate::value::instruction::statement::Statement;
+use crate::reporter::location::Span;
+use crate::utils::iters::eval_statements;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -11,6 +9,7 @@ use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Program {
+  pub spans: Vec<Span>,
   pub statements: Vec<Box<dyn Value>>,
 }
 
@@ -31,7 +30,14 @@ impl Value for Program {
     &mut self,
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    Ok(eval_statements(interpreter, self.statements.to_owned())?)
+    let value = eval_statements(interpreter, self.statements.to_owned())?;
+
+    // a `return` at the top ends the program with its value
+    match interpreter.take_signal() {
+      None => Ok(value),
+      Some(Signal::Return(value)) => Ok(value),
+      Some(signal) => Err(format!("`{}` outside of a loop", signal.keyword())),
+    }
   }
 
   fn vkind(&self) -> VKind {
@@ -42,29 +48,6 @@ impl Value for Program {
     IKind::Program(self.statements.to_vec())
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    loop {
-      if parser.scanner.is_eof() {
-        self.parse_program(parser);
-        break;
-      }
-
-      match parser.token.kind {
-        TokenKind::Unknown => break,
-        _ => {
-          self.parse_program(parser);
-          parser.next_token();
-        }
-      }
-    }
-
-    Ok(self.boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     let statements = self
       .statements
@@ -75,24 +58,14 @@ impl Value for Program {
 
     format!("(function () {{ {} }})();", statements)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "body": [{}],
-          "type": "Program"
-        }}"#,
-        transpile_exprs(transpiler, &self.statements, ", ")
-      ),
-      _ => transpile_exprs(transpiler, &self.statements, ""),
-    }
-  }
 }
 
 impl Program {
   pub fn new(statements: Vec<Box<dyn Value>>) -> Self {
-    Program { statements }
+    Program {
+      spans: vec![],
+      statements,
+    }
   }
 
   pub fn add_statement(&mut self, statement: Box<dyn Value>) {
@@ -102,11 +75,4 @@ impl Program {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
-
-  fn parse_program(&mut self, parser: &mut Parser) {
-    match Statement::default().parse(parser) {
-      Err(error) => parser.errors.push(Err(format!("{}", error))),
-      Ok(statement) => self.add_statement(statement),
-    }
-  }
 }
@@ -0,0 +1,88 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::function::Function;
+use crate::value::instruction::interface::Trait;
+use crate::value::instruction::structure::Struct;
+use crate::value::instruction::val::Val;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Public {
+  pub node: Box<dyn Value>,
+}
+
+impl Default for Public {
+  fn default() -> Self {
+    Public::new(void!())
+  }
+}
+
+impl fmt::Display for Public {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Public {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    self.node.eval(interpreter)
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Public
+  }
+
+  fn text(&self) -> String {
+    format!("pub {}", self.node)
+  }
+}
+
+impl Public {
+  pub fn new(node: Box<dyn Value>) -> Self {
+    Public { node }
+  }
+
+  pub fn add_node(&mut self, node: Box<dyn Value>) -> &mut Self {
+    self.node = node;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  // names of the items marked `pub` among the statements of a module
+  pub fn exports(statements: &[Box<dyn Value>]) -> Vec<String> {
+    statements
+      .iter()
+      .filter_map(|statement| statement.downcast_ref::<Public>())
+      .map(|public| public.name())
+      .collect()
+  }
+
+  pub fn name(&self) -> String {
+    let node = self.node.as_ref();
+
+    if let Some(function) = node.downcast_ref::<Function>() {
+      function.name()
+    } else if let Some(structure) = node.downcast_ref::<Struct>() {
+      structure.name()
+    } else if let Some(interface) = node.downcast_ref::<Trait>() {
+      interface.name()
+    } else if let Some(val) = node.downcast_ref::<Val>() {
+      val.name.text()
+    } else {
+      node.text()
+    }
+  }
+}
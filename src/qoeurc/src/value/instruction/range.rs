@@ -0,0 +1,157 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::primitive::int::Int;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Range {
+  pub end: Box<dyn Value>,
+  pub inclusive: bool,
+  pub start: Box<dyn Value>,
+  pub step: Option<Box<dyn Value>>,
+}
+
+impl Default for Range {
+  fn default() -> Self {
+    Range::new(void!(), void!(), false)
+  }
+}
+
+impl fmt::Display for Range {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl From<Box<dyn Value>> for Range {
+  fn from(start: Box<dyn Value>) -> Range {
+    Range::new(start, void!(), false)
+  }
+}
+
+impl Value for Range {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let start = Range::eval_bound(&mut self.start, interpreter)?;
+    let end = Range::eval_bound(&mut self.end, interpreter)?;
+
+    let mut range = Range::new(start, end, self.inclusive);
+
+    if let Some(step) = self.step.as_mut() {
+      let step = Range::eval_bound(step, interpreter)?;
+
+      if let IKind::Int(0) = step.ikind() {
+        return Err(String::from("range step cannot be zero"));
+      }
+
+      range.add_step(step);
+    }
+
+    Ok(range.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Range
+  }
+
+  fn text(&self) -> String {
+    let operator = match self.inclusive {
+      true => "..=",
+      false => "..",
+    };
+
+    match &self.step {
+      Some(step) => {
+        format!("{}{}{} step {}", self.start, operator, self.end, step)
+      }
+      None => format!("{}{}{}", self.start, operator, self.end),
+    }
+  }
+}
+
+impl Range {
+  pub fn new(
+    start: Box<dyn Value>,
+    end: Box<dyn Value>,
+    inclusive: bool,
+  ) -> Self {
+    Range {
+      end,
+      inclusive,
+      start,
+      step: None,
+    }
+  }
+
+  pub fn add_end(&mut self, end: Box<dyn Value>) -> &mut Self {
+    self.end = end;
+    self
+  }
+
+  pub fn add_start(&mut self, start: Box<dyn Value>) -> &mut Self {
+    self.start = start;
+    self
+  }
+
+  pub fn add_step(&mut self, step: Box<dyn Value>) -> &mut Self {
+    self.step = Some(step);
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  // the integers of an evaluated range, in order, they are made as they are
+  // taken so a wide range costs nothing until it is walked
+  pub fn values(&self) -> impl Iterator<Item = i64> {
+    let bound = |value: &dyn Value| match value.ikind() {
+      IKind::Int(value) => value,
+      _ => 0,
+    };
+
+    let start = bound(self.start.as_ref());
+    let end = bound(self.end.as_ref());
+    let step = self.step.as_deref().map(bound).unwrap_or(1);
+    let inclusive = self.inclusive;
+
+    std::iter::successors(Some(start), move |value| value.checked_add(step))
+      .take_while(move |value| {
+        let is_done = match (step > 0, inclusive) {
+          (true, true) => *value > end,
+          (true, false) => *value >= end,
+          (false, true) => *value < end,
+          (false, false) => *value <= end,
+        };
+
+        !is_done && step != 0
+      })
+  }
+
+  // an evaluated start, end or step, which must be an `int`
+  pub fn bound(value: Box<dyn Value>) -> ValueResult<Box<dyn Value>> {
+    match value.ikind() {
+      IKind::Int(value) => Ok(Int::new(value).boxed()),
+      _ => Err(format!(
+        "range bounds must be `int`, found `{}`",
+        value.text()
+      )),
+    }
+  }
+
+  fn eval_bound(
+    bound: &mut Box<dyn Value>,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    Range::bound(bound.eval(interpreter)?)
+  }
+}
@@ -1,23 +1,14 @@
+use crate::analyzer::environment::signal::Signal;
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::expression::Expression;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
 
 use std::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Return {
-  pub value: Box<dyn Value>,
-}
-
-impl Default for Return {
-  fn default() -> Self {
-    Return::new(void!())
-  }
+  pub value: Option<Box<dyn Value>>,
 }
 
 impl fmt::Display for Return {
@@ -27,13 +18,19 @@ impl fmt::Display for Return {
 }
 
 impl Value for Return {
+  // the value is handed to the enclosing call, see `Call::call_function`
   fn eval(
     &mut self,
     interpreter: &mut Interpreter,
   ) -> ValueResult<Box<dyn Value>> {
-    let value = self.value.eval(interpreter)?;
+    let value = match self.value.as_mut() {
+      Some(value) => value.eval(interpreter)?,
+      None => void!(),
+    };
+
+    interpreter.raise(Signal::Return(value));
 
-    Ok(self.add_value(value).boxed())
+    Ok(void!())
   }
 
   fn vkind(&self) -> VKind {
@@ -44,54 +41,21 @@ impl Value for Return {
     IKind::Return
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.next_token();
-
-    let value = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    while parser.token.kind != TokenKind::Symbol(Semicolon)
-      && parser.token.kind != TokenKind::NewLine
-    {
-      parser.next_token();
-    }
-
-    Ok(self.add_value(value).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text());
-  }
-
   fn text(&self) -> String {
-    format!("return {};", self.value)
-  }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    // let mut value = self.value.eval(&mut transpiler.interpreter).unwrap();
-
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "argument": {},
-          "type": "ReturnStatement"
-        }}"#,
-        self.value.transpile(transpiler),
-      ),
-      _ => format!("return {};", self.value.transpile(transpiler)),
+    match &self.value {
+      Some(value) => format!("return {};", value),
+      None => String::from("return;"),
     }
   }
 }
 
 impl Return {
-  pub fn new(value: Box<dyn Value>) -> Self {
+  pub fn new(value: Option<Box<dyn Value>>) -> Self {
     Return { value }
   }
 
   pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
-    self.value = value;
+    self.value = Some(value);
     self
   }
 
@@ -1,6 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::transformer::transpiler::Transpiler;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -39,23 +37,9 @@ impl Value for Shebang {
     IKind::Shebang
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    Ok(self.add_value(&parser.token.literal).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("{}", self.value)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      _ => self.text(),
-    }
-  }
 }
 
 impl Shebang {
@@ -0,0 +1,63 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Spread {
+  pub value: Box<dyn Value>,
+}
+
+impl Default for Spread {
+  fn default() -> Self {
+    Spread::new(void!())
+  }
+}
+
+impl fmt::Display for Spread {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Spread {
+  // see `Array::eval` and `Hash::eval`, which take the items in place
+  fn eval(
+    &mut self,
+    _interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    Err(format!(
+      "`{}` can only be spread in an array or a hash",
+      self.value
+    ))
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Spread
+  }
+
+  fn text(&self) -> String {
+    format!("..{}", self.value)
+  }
+}
+
+impl Spread {
+  pub fn new(value: Box<dyn Value>) -> Self {
+    Spread { value }
+  }
+
+  pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
+    self.value = value;
+    self
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
@@ -1,14 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::expression::Expression;
-use crate::value::instruction::function::Function;
-use crate::value::instruction::loop_for_range::LoopForRange;
-use crate::value::instruction::loop_infinite::LoopInfinite;
-use crate::value::instruction::loop_while::LoopWhile;
-use crate::value::instruction::return_value::Return;
-use crate::value::instruction::val::Val;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -48,32 +38,9 @@ impl Value for Statement {
     IKind::Statement
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    match parser.token.kind {
-      TokenKind::Keyword(For) => LoopForRange::default().parse(parser),
-      TokenKind::Keyword(Function) => Function::default().parse(parser),
-      TokenKind::Keyword(Loop) => LoopInfinite::default().parse(parser),
-      TokenKind::Keyword(Return) => Return::default().parse(parser),
-      TokenKind::Keyword(Val) => Val::default().parse(parser),
-      TokenKind::Keyword(While) => LoopWhile::default().parse(parser),
-      _ => Expression::default().parse(parser),
-    }
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("{}", self.node.text())
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(r#"{{ {} }}"#, self.node.transpile(transpiler)),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Statement {
@@ -0,0 +1,158 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::utils::iters::strip_exprs;
+use crate::value::instruction::function::Function;
+use crate::value::instruction::function_arg::FunctionArg;
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+pub type TMethods = Rc<RefCell<HashMap<String, Box<dyn Value>>>>;
+pub type TTraits = Rc<RefCell<Vec<String>>>;
+
+#[derive(Clone, Debug)]
+pub struct Struct {
+  pub fields: Vec<Box<dyn Value>>,
+  pub generics: Vec<Box<dyn Value>>,
+  pub methods: TMethods,
+  pub name: Box<dyn Value>,
+  pub traits: TTraits,
+}
+
+impl Default for Struct {
+  fn default() -> Self {
+    Struct::new(void!(), vec![])
+  }
+}
+
+impl fmt::Display for Struct {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Struct {
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    interpreter.scope_mut().add_struct(self)?;
+
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Statement
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Struct
+  }
+
+  fn text(&self) -> String {
+    let fields = strip_exprs(&self.fields, ", ");
+
+    match self.generics.is_empty() {
+      true => format!("struct {} {{ {} }}", self.name, fields),
+      false => format!(
+        "struct {}<{}> {{ {} }}",
+        self.name,
+        strip_exprs(&self.generics, ", "),
+        fields
+      ),
+    }
+  }
+}
+
+impl Struct {
+  pub fn new(name: Box<dyn Value>, fields: Vec<Box<dyn Value>>) -> Self {
+    Struct {
+      fields,
+      generics: vec![],
+      methods: Rc::new(RefCell::new(HashMap::new())),
+      name,
+      traits: Rc::new(RefCell::new(vec![])),
+    }
+  }
+
+  pub fn add_field(&mut self, field: Box<dyn Value>) -> &mut Self {
+    self.fields.push(field);
+    self
+  }
+
+  pub fn add_generics(&mut self, generics: Vec<Box<dyn Value>>) -> &mut Self {
+    self.generics = generics;
+    self
+  }
+
+  pub fn add_method(&self, method: Box<dyn Value>) -> ValueResult<()> {
+    let mname = method.as_ref().downcast_ref::<Function>().unwrap().name();
+
+    if self.methods.borrow().contains_key(&mname) {
+      return Err(format!(
+        "method `{}` already exist for `{}`",
+        mname,
+        self.name()
+      ));
+    }
+
+    self.methods.borrow_mut().insert(mname, method);
+
+    Ok(())
+  }
+
+  pub fn add_name(&mut self, name: Box<dyn Value>) -> &mut Self {
+    self.name = name;
+    self
+  }
+
+  pub fn add_trait(&self, name: &str) -> ValueResult<()> {
+    if self.implements(name) {
+      return Err(format!(
+        "trait `{}` already implemented for `{}`",
+        name,
+        self.name()
+      ));
+    }
+
+    self.traits.borrow_mut().push(name.into());
+
+    Ok(())
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+
+  pub fn field_names(&self) -> Vec<String> {
+    self
+      .fields
+      .iter()
+      .map(|field| field.downcast_ref::<FunctionArg>().unwrap().name())
+      .collect()
+  }
+
+  pub fn generic_names(&self) -> Vec<String> {
+    self.generics.iter().map(|generic| generic.text()).collect()
+  }
+
+  pub fn get_method(&self, name: &str) -> Option<Box<dyn Value>> {
+    self
+      .methods
+      .borrow()
+      .get(name)
+      .map(|method| method.to_owned())
+  }
+
+  pub fn implements(&self, name: &str) -> bool {
+    self.traits.borrow().iter().any(|t| t == name)
+  }
+
+  pub fn name(&self) -> String {
+    self.name.text()
+  }
+}
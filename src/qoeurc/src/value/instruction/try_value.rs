@@ -0,0 +1,77 @@
+use crate::analyzer::environment::signal::Signal;
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+use crate::void;
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Try {
+  pub value: Box<dyn Value>,
+}
+
+impl Default for Try {
+  fn default() -> Self {
+    Try::new(void!())
+  }
+}
+
+impl fmt::Display for Try {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl From<Box<dyn Value>> for Try {
+  fn from(value: Box<dyn Value>) -> Try {
+    Try::new(value)
+  }
+}
+
+impl Value for Try {
+  // `ok(v)?` is `v`, `err(e)?` and `nil?` return from the current function,
+  // any other value is a present optional
+  fn eval(
+    &mut self,
+    interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    let value = self.value.eval(interpreter)?;
+
+    if interpreter.is_unwinding() {
+      return Ok(void!());
+    }
+
+    match value.ikind() {
+      IKind::Outcome(true, value) => Ok(value),
+      IKind::Outcome(false, _) | IKind::Nil => {
+        interpreter.raise(Signal::Return(value));
+
+        Ok(void!())
+      }
+      _ => Ok(value),
+    }
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Try
+  }
+
+  fn text(&self) -> String {
+    format!("{}?", self.value)
+  }
+}
+
+impl Try {
+  pub fn new(value: Box<dyn Value>) -> Self {
+    Try { value }
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
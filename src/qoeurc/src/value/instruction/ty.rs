@@ -1,21 +1,23 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::Transpiler;
-use crate::value::instruction::array::Array;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
 use std::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum TyKind {
   Array,
+  ArrayOf(Box<TyKind>),
   Bool,
   Char,
   Float,
+  Fn,
   Hash,
+  Infer,
   Int,
+  Named(String, Vec<TyKind>),
+  Nil,
+  Optional(Box<TyKind>),
   Str,
 }
 
@@ -23,16 +25,39 @@ impl fmt::Display for TyKind {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
       TyKind::Array => write!(f, "[]"),
+      TyKind::ArrayOf(kind) => write!(f, "[{}]", kind),
       TyKind::Bool => write!(f, "bool"),
       TyKind::Char => write!(f, "char"),
       TyKind::Float => write!(f, "float"),
+      TyKind::Fn => write!(f, "fn"),
       TyKind::Hash => write!(f, "hash"),
+      TyKind::Infer => write!(f, "_"),
       TyKind::Int => write!(f, "int"),
+      TyKind::Named(name, args) if args.is_empty() => write!(f, "{}", name),
+      TyKind::Named(name, args) => write!(
+        f,
+        "{}<{}>",
+        name,
+        args
+          .iter()
+          .map(|arg| arg.to_string())
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      TyKind::Nil => write!(f, "nil"),
+      TyKind::Optional(kind) => write!(f, "{}?", kind),
       TyKind::Str => write!(f, "str"),
     }
   }
 }
 
+impl TyKind {
+  // `nil` itself is optional
+  pub fn is_optional(&self) -> bool {
+    matches!(self, TyKind::Nil | TyKind::Optional(_))
+  }
+}
+
 #[derive(Clone, Debug)]
 pub struct Ty {
   kind: TyKind,
@@ -66,41 +91,9 @@ impl Value for Ty {
     IKind::Ty
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    let mut ty = Ty::default();
-    let kind;
-
-    if parser.token_is(TokenKind::GroupStart(Bracket)) {
-      kind = Array::default().parse(parser)?.boxed().text();
-    } else {
-      kind = parser.token.literal.to_string();
-    }
-
-    match kind.as_str() {
-      "[]" => Ok(ty.add_kind(TyKind::Array).boxed()),
-      "bool" => Ok(ty.add_kind(TyKind::Bool).boxed()),
-      "char" => Ok(ty.add_kind(TyKind::Char).boxed()),
-      "float" => Ok(ty.add_kind(TyKind::Float).boxed()),
-      "hash" => Ok(ty.add_kind(TyKind::Hash).boxed()),
-      "int" => Ok(ty.add_kind(TyKind::Int).boxed()),
-      "str" => Ok(ty.add_kind(TyKind::Str).boxed()),
-      _ => Err(format!("type error expression")),
-    }
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("{}", self.kind)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      _ => self.text(),
-    }
-  }
 }
 
 impl Ty {
@@ -116,4 +109,8 @@ impl Ty {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  pub fn kind(&self) -> TyKind {
+    self.kind.to_owned()
+  }
 }
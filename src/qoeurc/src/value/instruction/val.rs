@@ -1,10 +1,5 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
-use crate::value::instruction::expression::Expression;
-use crate::value::instruction::identifier::Identifier;
-use crate::value::instruction::ty::Ty;
+use crate::value::instruction::pattern::Pattern;
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 use crate::void;
@@ -16,6 +11,7 @@ pub struct Val {
   pub immutable: bool,
   pub kind: Option<Box<dyn Value>>,
   pub name: Box<dyn Value>,
+  pub pattern: Option<Pattern>,
   pub value: Option<Box<dyn Value>>,
 }
 
@@ -38,9 +34,22 @@ impl Value for Val {
   ) -> ValueResult<Box<dyn Value>> {
     let value = self.value.as_ref().unwrap().to_owned().eval(interpreter)?;
 
-    self.add_value(value);
+    self.add_value(value.to_owned());
 
-    interpreter.scope_mut().add_variable(&self.boxed())?;
+    match &self.pattern {
+      Some(pattern) => {
+        let bindings = pattern.matches(value.as_ref()).ok_or(format!(
+          "the pattern `{}` does not match `{}`",
+          pattern,
+          value.text(),
+        ))?;
+
+        for (name, value) in bindings {
+          interpreter.scope_mut().add_binding(&name, value)?;
+        }
+      }
+      None => interpreter.scope_mut().add_variable(&self.boxed())?,
+    }
 
     Ok(self.boxed())
   }
@@ -53,35 +62,8 @@ impl Value for Val {
     IKind::Val
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    parser.expect_first(TokenKind::Identifier)?;
-
-    let name = Identifier::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::Symbol(Colon))?;
-    parser.next_token();
-
-    let kind = Ty::default().parse(parser)?;
-
-    parser.expect_first(TokenKind::Operator(Assign))?;
-    parser.next_token();
-
-    let value = Expression::parse_expression_by_precedence(
-      parser,
-      &mut PrecedenceKind::Lowest,
-    )?;
-
-    parser.expect_first(TokenKind::Symbol(Semicolon))?;
-
-    Ok(self.add_name(name).add_kind(kind).add_value(value).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
-    let name = &self.name;
+    let name = self.name();
     let value = self.value.as_ref().unwrap();
 
     match (self.immutable, self.kind.as_ref()) {
@@ -93,36 +75,6 @@ impl Value for Val {
       (false, None) => format!("val mut {} := {};", name, value),
     }
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    let value = self
-      .value
-      .as_ref()
-      .unwrap()
-      .to_owned()
-      .transpile(transpiler);
-    let name = self.name.transpile(transpiler);
-
-    match transpiler.mode() {
-      TKind::Json => {
-        format!(
-          r#"{{
-            "declarations": [{{
-              "id": {},
-              "init": {},
-              "type": "VariableDeclarator"
-            }}],
-            "kind": "var",
-            "type": "VariableDeclaration"
-          }}"#,
-          name, value,
-        )
-      }
-      _ => {
-        format!("var {} = {};\n", name, value)
-      }
-    }
-  }
 }
 
 impl Val {
@@ -136,6 +88,7 @@ impl Val {
       immutable,
       kind,
       name,
+      pattern: None,
       value,
     }
   }
@@ -155,6 +108,11 @@ impl Val {
     self
   }
 
+  pub fn add_pattern(&mut self, pattern: Pattern) -> &mut Self {
+    self.pattern = Some(pattern);
+    self
+  }
+
   pub fn add_value(&mut self, value: Box<dyn Value>) -> &mut Self {
     self.value = Some(value);
     self
@@ -163,4 +121,11 @@ impl Val {
   pub fn boxed(&self) -> Box<dyn Value> {
     Box::new(self.to_owned())
   }
+
+  pub fn name(&self) -> String {
+    match &self.pattern {
+      Some(pattern) => pattern.text(),
+      None => self.name.text(),
+    }
+  }
 }
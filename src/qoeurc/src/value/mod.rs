@@ -1,3 +1,5 @@
+pub mod compare;
+pub mod convert;
 pub mod instruction;
 pub mod primitive;
 
@@ -5,8 +7,6 @@ pub mod primitive;
 mod tests;
 
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::transformer::transpiler::Transpiler;
 use crate::value::instruction::IKind;
 
 use std::fmt;
@@ -47,14 +47,11 @@ pub trait Value: fmt::Debug + VClone + Downcast {
   fn ikind(&self) -> IKind;
   fn is_truthy(&self) -> bool {
     match self.ikind() {
-      IKind::Bool(false) => false,
+      IKind::Bool(false) | IKind::Nil => false,
       _ => true,
     }
   }
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>>;
-  fn print(&self);
   fn text(&self) -> String;
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String;
   fn vkind(&self) -> VKind;
 }
 
@@ -71,9 +68,3 @@ impl Clone for Box<dyn Value> {
     self.cloned()
   }
 }
-
-impl PartialEq for Box<dyn Value> {
-  fn eq(&self, rhs: &Box<dyn Value>) -> bool {
-    self.ikind() == rhs.ikind()
-  }
-}
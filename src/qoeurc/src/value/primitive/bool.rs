@@ -1,7 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::tokenizer::kind::*;
-use crate::transformer::transpiler::{TKind, Transpiler};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -40,32 +37,9 @@ impl Value for Bool {
     IKind::Bool(self.value)
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    Ok(Bool::new(parser.token_is(Keyword(True))).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("{}", self.value)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "type": "Literal",
-          "raw": "{}",
-          "value": {}
-        }}"#,
-        self.text(),
-        self.text(),
-      ),
-      _ => format!("{}", self.text()),
-    }
-  }
 }
 
 impl Bool {
@@ -1,6 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::transformer::transpiler::{TKind, Transpiler};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -39,35 +37,9 @@ impl Value for Float {
     IKind::Float(self.value)
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    match parser.token.literal.parse() {
-      Err(error) => Err(format!("{}", error)),
-      Ok(expression) => Ok(Box::new(Float::new(expression))),
-    }
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("{}", self.value)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "type": "Literal",
-          "raw": "{}",
-          "value": {}
-        }}"#,
-        self.text(),
-        self.text(),
-      ),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Float {
@@ -2,4 +2,5 @@ pub mod bool;
 pub mod char;
 pub mod float;
 pub mod int;
+pub mod nil;
 pub mod str;
@@ -0,0 +1,45 @@
+use crate::analyzer::interpreter::{Interpreter, ValueResult};
+use crate::value::instruction::IKind;
+use crate::value::{VKind, Value};
+
+use std::fmt;
+
+#[derive(Clone, Debug, Default)]
+pub struct Nil {}
+
+impl fmt::Display for Nil {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.text())
+  }
+}
+
+impl Value for Nil {
+  fn eval(
+    &mut self,
+    _interpreter: &mut Interpreter,
+  ) -> ValueResult<Box<dyn Value>> {
+    Ok(self.boxed())
+  }
+
+  fn vkind(&self) -> VKind {
+    VKind::Expression
+  }
+
+  fn ikind(&self) -> IKind {
+    IKind::Nil
+  }
+
+  fn text(&self) -> String {
+    String::from("nil")
+  }
+}
+
+impl Nil {
+  pub fn new() -> Self {
+    Nil {}
+  }
+
+  pub fn boxed(&self) -> Box<dyn Value> {
+    Box::new(self.to_owned())
+  }
+}
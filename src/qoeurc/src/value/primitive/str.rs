@@ -1,6 +1,4 @@
 use crate::analyzer::interpreter::{Interpreter, ValueResult};
-use crate::converter::parser::{Parser, ParserResult};
-use crate::transformer::transpiler::{TKind, Transpiler};
 use crate::value::instruction::IKind;
 use crate::value::{VKind, Value};
 
@@ -39,32 +37,9 @@ impl Value for Str {
     IKind::Str(self.value.to_owned())
   }
 
-  fn parse(&mut self, parser: &mut Parser) -> ParserResult<Box<dyn Value>> {
-    Ok(Str::new(&parser.token.literal).boxed())
-  }
-
-  fn print(&self) {
-    println!("{}", self.text())
-  }
-
   fn text(&self) -> String {
     format!("\"{}\"", self.value)
   }
-
-  fn transpile(&mut self, transpiler: &mut Transpiler) -> String {
-    match transpiler.mode() {
-      TKind::Json => format!(
-        r#"{{
-          "type": "Literal",
-          "raw": "{}",
-          "value": {}
-        }}"#,
-        self.text(),
-        self.text(),
-      ),
-      _ => self.text(),
-    }
-  }
 }
 
 impl Str {
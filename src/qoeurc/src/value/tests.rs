@@ -2,7 +2,222 @@ extern crate qutonium;
 
 use qutonium::prelude::*;
 
+use crate::testing::suite;
+
 #[test]
 fn from_test() {
-  suite!("pub mod value", { "test empty input" || { Ok(()) } });
+  suite!("pub mod value", {
+    "test empty input" || { must!(true; be.truthy) }
+  });
+}
+
+#[test]
+fn hash_test() {
+  use super::instruction::hash::{Entries, HashKey};
+  use super::primitive::{float::Float, int::Int};
+  use super::Values;
+  use crate::analyzer::evalify;
+  use crate::analyzer::interpreter::Interpreter;
+
+  fn eval(input: &str) -> Result<String, String> {
+    evalify(input, &mut Interpreter::new()).map(|value| value.text())
+  }
+
+  fn key(name: &str) -> HashKey {
+    HashKey::Str(name.into())
+  }
+
+  fn entries(items: Vec<(&str, i64)>) -> Entries {
+    items
+      .into_iter()
+      .map(|(name, value)| (key(name), Int::new(value).boxed()))
+      .collect()
+  }
+
+  fn keys(entries: &Entries) -> Vec<HashKey> {
+    entries.iter().map(|(key, _)| key.to_owned()).collect()
+  }
+
+  suite!("qoeurc::value::hash", {
+    "test insertion order" || {
+      let input = entries(vec![("b", 1), ("a", 2), ("c", 3)]);
+
+      must!(keys(&input); eq vec![key("b"), key("a"), key("c")])
+    }
+
+    "test insert replaces in place" || {
+      let mut input = entries(vec![("a", 1), ("b", 2), ("c", 3)]);
+
+      input.insert(key("a"), Int::new(9).boxed());
+
+      must!(keys(&input); eq vec![key("a"), key("b"), key("c")])?;
+      must!(input.get(&key("a")); eq Some(Int::new(9).boxed().as_ref()))
+    }
+
+    "test remove keeps order" || {
+      let mut input = entries(vec![("a", 1), ("b", 2), ("c", 3)]);
+
+      must!(input.remove(&key("a")); eq Some(Int::new(1).boxed()))?;
+      must!(input.remove(&key("a")); eq None)?;
+      must!(keys(&input); eq vec![key("b"), key("c")])?;
+      must!(input.get(&key("c")); eq Some(Int::new(3).boxed().as_ref()))
+    }
+
+    "test equality ignores order" || {
+      let lhs = entries(vec![("a", 1), ("b", 2)]);
+
+      must!(lhs.to_owned(); eq entries(vec![("b", 2), ("a", 1)]))?;
+      must!(lhs.to_owned(); ne entries(vec![("a", 1), ("b", 3)]))?;
+      must!(lhs; ne entries(vec![("a", 1)]))
+    }
+
+    "test hashable keys" || {
+      let tuple: Values = vec![Int::new(1).boxed(), Int::new(2).boxed()];
+      let tuple = super::instruction::array::Array::new(tuple).boxed();
+
+      must!(
+        HashKey::new(tuple.as_ref());
+        eq Ok(HashKey::Tuple(vec![HashKey::Int(1), HashKey::Int(2)]))
+      )?;
+      must!(HashKey::new(&Float::new(1.5)).is_err(); be.truthy)
+    }
+
+    "test duplicate keys in literals" || {
+      let input = r#"val h := { "a": 1, "a": 2 }; h;"#;
+
+      must!(
+        eval(input);
+        eq Err("duplicate key `a` in hash literal".into())
+      )
+    }
+
+    "test keys that cannot be hashed" || {
+      must!(
+        eval("val h := { 1.5: 1 }; h;");
+        eq Err(
+          "`1.5` cannot be used as a hash key, floats are not hashable".into()
+        )
+      )?;
+      must!(
+        eval("val k := 1.5; { k: 1 };");
+        eq Err("`k` cannot be used as a hash key".into())
+      )
+    }
+  });
+}
+
+#[test]
+fn compare_test() {
+  use super::instruction::array::Array;
+  use super::instruction::binary::Binary;
+  use super::instruction::identifier::Identifier;
+  use super::instruction::instance::Instance;
+  use super::primitive::{float::Float, int::Int, str::Str};
+  use super::{Value, Values};
+  use crate::analyzer::evalify;
+  use crate::analyzer::interpreter::Interpreter;
+  use crate::compiler::vmify;
+  use crate::tokenizer::kind::{OperatorKind, TokenKind};
+  use crate::tokenizer::token::Token;
+
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::{Hash, Hasher};
+
+  fn array(items: Vec<i64>) -> Box<dyn Value> {
+    Array::new(
+      items
+        .into_iter()
+        .map(|item| Int::new(item).boxed())
+        .collect(),
+    )
+    .boxed()
+  }
+
+  fn point(fields: Vec<(&str, i64)>) -> Box<dyn Value> {
+    let fields = fields
+      .into_iter()
+      .map(|(name, value)| (name.to_string(), Int::new(value).boxed()))
+      .collect();
+
+    Instance::new(Identifier::new("Point").boxed(), fields).boxed()
+  }
+
+  fn sum(lhs: i64, rhs: i64) -> Box<dyn Value> {
+    let operator = Token::new(TokenKind::Operator(OperatorKind::Plus), "+", 1);
+
+    Binary::new(
+      Int::new(lhs).boxed(),
+      Box::new(operator),
+      Int::new(rhs).boxed(),
+    )
+    .boxed()
+  }
+
+  fn hashed(value: Box<dyn Value>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    value.hash(&mut hasher);
+    hasher.finish()
+  }
+
+  suite!("qoeurc::value::compare", {
+    "test deep equality" || {
+      let nested: Values = vec![Int::new(1).boxed(), array(vec![2, 3])];
+      let nested = Array::new(nested).boxed();
+
+      must!(nested == nested.cloned(); be.truthy)?;
+      must!(array(vec![1, 2]) == array(vec![1, 3]); be.falsy)?;
+      must!(Int::new(1).boxed() == Float::new(1.0).boxed(); be.falsy)
+    }
+
+    "test instances compare field by field" || {
+      let lhs = point(vec![("x", 1), ("y", 2)]);
+
+      must!(lhs == point(vec![("y", 2), ("x", 1)]); be.truthy)?;
+      must!(lhs == point(vec![("x", 1), ("y", 3)]); be.falsy)
+    }
+
+    "test unrelated expressions are not equal" || {
+      must!(sum(1, 2) == sum(1, 2); be.truthy)?;
+      must!(sum(1, 2) == sum(3, 4); be.falsy)
+    }
+
+    "test ordering" || {
+      must!(array(vec![1, 2]) < array(vec![1, 3]); be.truthy)?;
+      must!(array(vec![2]) > array(vec![1, 5]); be.truthy)?;
+      must!(Str::new("ab").boxed() < Str::new("b").boxed(); be.truthy)?;
+      must!(array(vec![1]).partial_cmp(&Int::new(1).boxed()); eq None)
+    }
+
+    "test equal values hash the same" || {
+      let lhs = point(vec![("x", 1), ("y", 2)]);
+      let rhs = point(vec![("y", 2), ("x", 1)]);
+
+      let zero = Float::new(0.0).boxed();
+
+      must!(hashed(lhs); eq hashed(rhs))?;
+      must!(hashed(zero); eq hashed(Float::new(-0.0).boxed()))
+    }
+
+    "test closures are only equal to themselves" || {
+      let eval = |input: &str| {
+        let tree = evalify(input, &mut Interpreter::new());
+        let vm = vmify(input, &mut Interpreter::new());
+
+        (tree.map(|value| value.text()), vm.map(|value| value.text()))
+      };
+      let same = || (Ok("true".to_string()), Ok("true".to_string()));
+      let different = || (Ok("false".to_string()), Ok("false".to_string()));
+
+      must!(
+        eval("val a := |x: int| x; val b := |x: int| x; a == b;");
+        eq different()
+      )?;
+      must!(eval("val a := |x: int| x; val b := a; a == b;"); eq same())?;
+      must!(
+        eval("ƒ make() -> fn { return |x: int| x; } make() == make();");
+        eq different()
+      )
+    }
+  });
 }
@@ -1,6 +1,8 @@
 mod utils;
 
 use qoeurc::analyzer;
+use qoeurc::analyzer::environment::host::BufferHost;
+use qoeurc::analyzer::environment::meter::Limits;
 use qoeurc::analyzer::interpreter::Interpreter;
 use qoeurc::converter;
 use qoeurc::tokenizer::{self, token::Token};
@@ -14,13 +16,26 @@ use wasm_bindgen::prelude::*;
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 pub fn astify(input: &str) -> String {
-  converter::astify(input).unwrap().text()
+  format!("{:?}", converter::astify(input).unwrap())
 }
 
+// what the program printed followed by its value, or its error, there is no
+// terminal in the browser so the output is kept in a buffer until the end,
+// and a runaway program runs out of fuel instead of freezing the tab
 pub fn evalify(input: &str) -> String {
+  let host = BufferHost::new(0);
   let mut interpreter = Interpreter::new();
 
-  analyzer::evalify(input, &mut interpreter).unwrap().text()
+  interpreter
+    .add_host(host.into_host())
+    .add_limits(Limits::new().add_fuel(1_000_000).add_memory(64 << 20));
+
+  let value = match analyzer::evalify(input, &mut interpreter) {
+    Ok(value) => value.text(),
+    Err(error) => error,
+  };
+
+  format!("{}{}{}", host.take_stdout(), host.take_stderr(), value)
 }
 
 pub fn tokenify(input: &str) -> String {
@@ -30,10 +45,14 @@ pub fn tokenify(input: &str) -> String {
   )
 }
 
+// the javascript of the program, or the error found while writing it
 pub fn transformify(input: &str, mode: &str) -> String {
   let interpreter = Interpreter::new();
 
-  transformer::transformify(input, mode, &interpreter).unwrap()
+  match transformer::transformify(input, mode, &interpreter) {
+    Ok(output) => output,
+    Err(error) => error,
+  }
 }
 
 #[wasm_bindgen]